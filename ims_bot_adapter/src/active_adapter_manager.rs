@@ -55,23 +55,11 @@ impl ActiveAdapterManager {
         adapter: SharedBotAdapter,
     ) -> JoinHandle<()> {
         tokio::spawn(async move {
-            loop {
-                let adapter_for_run = Arc::clone(&adapter);
-                match BotAdapter::start(adapter_for_run).await {
-                    Ok(()) => {
-                        warn!(
-                            "[active_adapter_manager] bot adapter '{}' (config_id={}) disconnected, retrying in 2s",
-                            connection_name, connection_id
-                        );
-                    }
-                    Err(err) => {
-                        error!(
-                            "[active_adapter_manager] bot adapter '{}' (config_id={}) exited with error: {}. retrying in 2s",
-                            connection_name, connection_id, err
-                        );
-                    }
-                }
-                tokio::time::sleep(Duration::from_secs(2)).await;
+            if let Err(err) = BotAdapter::run(adapter).await {
+                error!(
+                    "[active_adapter_manager] bot adapter '{}' (config_id={}) stopped reconnecting: {}",
+                    connection_name, connection_id, err
+                );
             }
         })
     }
@@ -299,6 +287,30 @@ impl ConnectionManagerTrait for ActiveAdapterManager {
     }
 }
 
+/// Gracefully shut down every running bot adapter, e.g. when the process
+/// receives SIGINT/SIGTERM, so events already received are still stored and
+/// answered before exit.
+pub async fn shutdown_active_bot_adapters() {
+    let removed = {
+        let mut instances = ActiveAdapterManager::shared().instances.write().await;
+        instances.drain().flat_map(|(_, bucket)| bucket).collect::<Vec<_>>()
+    };
+    if removed.is_empty() {
+        return;
+    }
+
+    info!(
+        "[active_adapter_manager] shutting down {} bot adapter instance(s)",
+        removed.len()
+    );
+    futures_util::future::join_all(removed.iter().map(|item| async move {
+        item.heartbeat_task.abort();
+        BotAdapter::shutdown(&item.adapter).await;
+        item.task.abort();
+    }))
+    .await;
+}
+
 pub async fn initialize_enabled_bot_adapters(_connections: &[ConnectionConfig]) {}
 
 pub async fn sync_enabled_bot_adapters(connections: &[ConnectionConfig]) {
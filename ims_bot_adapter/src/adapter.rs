@@ -4,25 +4,48 @@ use log::{debug, error, info, warn};
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
-use uuid::Uuid;
 
 use super::event;
-use super::models::{MessageEvent, MessageType, Profile, RawMessageEvent};
-use crate::ws_action::ws_send_action_async;
+use super::models::{Event, MessageEvent, MessageType, Profile, RawMessageEvent};
+use crate::catch_up::{catch_up_missed_messages, CatchUpState};
+use crate::login_info::parse_login_info;
+use crate::message_dedup::MessageDeduplicator;
+use crate::sent_message_ids::SentMessageIds;
+use crate::server_info::{fetch_app_name, is_napcat};
+use crate::shutdown::AdapterShutdown;
+use crate::watchdog::EventWatchdog;
+use crate::webhook::{WebhookSink, WEBHOOK_HANDLER_ID};
+use crate::ws_action::{qq_message_list_to_json, ws_send_action_async};
 use storage_handler::{enrich_event_images, enrich_message_images, ImageCacheAdapter, PendingImageUpload};
 use tokio::sync::Mutex as TokioMutex;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use zihuan_core::error::Result;
-use zihuan_core::ims_bot_adapter::models::message::{ForwardNodeMessage, Message};
+use zihuan_core::ims_bot_adapter::models::message::{ForwardNodeMessage, Message, PlainTextMessage, ReplyMessage};
 use zihuan_core::url_utils::extract_host;
+use zihuan_core::utils::backoff::BackoffPolicy;
 use zihuan_graph_engine::message_restore::restore_message_snapshot;
 use zihuan_graph_engine::object_storage::S3Ref;
 
 /// Trait for brain agents that handle event processing
 pub trait BrainAgentTrait: Send + Sync {
-    fn on_event(&self, ims_bot_adapter: &mut BotAdapter, event: &super::models::MessageEvent) -> Result<()>;
+    /// Handle a message addressed to the bot. A returned [`AgentOutput`] is sent
+    /// back to the chat the message came from, quoting it in groups.
+    fn on_event(
+        &self,
+        ims_bot_adapter: &mut BotAdapter,
+        event: &super::models::MessageEvent,
+    ) -> Result<Option<AgentOutput>>;
+    /// Called for notices such as group joins and new friends; ignored by default.
+    fn on_notice(&self, _ims_bot_adapter: &mut BotAdapter, _notice: &super::models::NoticeEvent) -> Result<()> {
+        Ok(())
+    }
+    /// Called for friend requests and group invites; ignored by default.
+    fn on_request(&self, _ims_bot_adapter: &mut BotAdapter, _request: &super::models::RequestEvent) -> Result<()> {
+        Ok(())
+    }
     fn name(&self) -> &'static str;
     fn clone_box(&self) -> AgentBox;
 }
@@ -35,6 +58,25 @@ impl Clone for AgentBox {
     }
 }
 
+/// A response a brain agent sends for an inbound message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentOutput {
+    /// A text message sent to the group or user the event came from.
+    Text(String),
+    /// An emoji reaction on the event's message, by QQ face id (e.g. `"124"`).
+    /// Much cheaper than a reply for simple acknowledgements.
+    Reaction(String),
+}
+
+/// Delay before the first reconnect when `ws_reconnect_interval_secs` is unset.
+pub const DEFAULT_WS_RECONNECT_INTERVAL_SECS: u64 = 2;
+const MAX_WS_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+/// A connection that stayed up at least this long resets the reconnect backoff.
+const STABLE_CONNECTION_DURATION: Duration = Duration::from_secs(60);
+/// Upper bound on how long [`BotAdapter::shutdown`] waits for in-flight events
+/// and the WebSocket close before giving up on them.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Configuration for BotAdapter initialization
 pub struct BotAdapterConfig {
     pub url: String,
@@ -42,6 +84,20 @@ pub struct BotAdapterConfig {
     pub qq_id: String,
     pub brain_agent: Option<AgentBox>,
     pub object_storage: Option<Arc<S3Ref>>,
+    /// Whether the bot server has NapCat's reaction action; `None` asks the
+    /// server with `get_version_info` after each connect.
+    pub supports_reactions: Option<bool>,
+    /// Only dispatch group messages that @ the bot or reply to one of its
+    /// messages to the brain agent. Private messages are always dispatched.
+    pub respond_only_when_mentioned: bool,
+    pub catch_up: Option<Arc<CatchUpState>>,
+    pub event_watchdog: Option<Arc<EventWatchdog>>,
+    pub message_dedup: Option<Arc<MessageDeduplicator>>,
+    pub webhook_sink: Option<Arc<WebhookSink>>,
+    /// Consecutive failed reconnects before [`BotAdapter::run`] gives up; `None` retries forever.
+    pub ws_reconnect_max_attempts: Option<u32>,
+    /// Delay before the first reconnect; later ones double up to a minute.
+    pub ws_reconnect_interval_secs: Option<u64>,
 }
 
 impl BotAdapterConfig {
@@ -52,6 +108,14 @@ impl BotAdapterConfig {
             qq_id: qq_id.into(),
             brain_agent: None,
             object_storage: None,
+            supports_reactions: None,
+            respond_only_when_mentioned: false,
+            catch_up: None,
+            event_watchdog: None,
+            message_dedup: None,
+            webhook_sink: None,
+            ws_reconnect_max_attempts: None,
+            ws_reconnect_interval_secs: None,
         }
     }
 
@@ -64,18 +128,86 @@ impl BotAdapterConfig {
         self.object_storage = object_storage;
         self
     }
+
+    pub fn with_reactions(mut self, supports_reactions: Option<bool>) -> Self {
+        self.supports_reactions = supports_reactions;
+        self
+    }
+
+    pub fn with_respond_only_when_mentioned(mut self, respond_only_when_mentioned: bool) -> Self {
+        self.respond_only_when_mentioned = respond_only_when_mentioned;
+        self
+    }
+
+    /// Replay messages missed while disconnected each time the connection is (re)established.
+    pub fn with_catch_up(mut self, catch_up: Option<Arc<CatchUpState>>) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+
+    /// Alert (and optionally reconnect) when no message event arrives for too long.
+    pub fn with_event_watchdog(mut self, event_watchdog: Option<Arc<EventWatchdog>>) -> Self {
+        self.event_watchdog = event_watchdog;
+        self
+    }
+
+    /// Drop message events whose `message_id` was already processed recently.
+    pub fn with_message_dedup(mut self, message_dedup: Option<Arc<MessageDeduplicator>>) -> Self {
+        self.message_dedup = message_dedup;
+        self
+    }
+
+    /// Forward every processed message event to an external webhook.
+    pub fn with_webhook_sink(mut self, webhook_sink: Option<Arc<WebhookSink>>) -> Self {
+        self.webhook_sink = webhook_sink;
+        self
+    }
+
+    pub fn with_ws_reconnect(mut self, max_attempts: Option<u32>, interval_secs: Option<u64>) -> Self {
+        self.ws_reconnect_max_attempts = max_attempts;
+        self.ws_reconnect_interval_secs = interval_secs;
+        self
+    }
+
+    fn ws_reconnect_policy(&self) -> BackoffPolicy {
+        BackoffPolicy::new(
+            self.ws_reconnect_max_attempts.unwrap_or(u32::MAX),
+            Duration::from_secs(self.ws_reconnect_interval_secs.unwrap_or(DEFAULT_WS_RECONNECT_INTERVAL_SECS)),
+            MAX_WS_RECONNECT_DELAY,
+        )
+    }
 }
 
 /// Pending action response channels keyed by echo ID.
 pub type PendingActions = Arc<TokioMutex<HashMap<String, oneshot::Sender<serde_json::Value>>>>;
 
+/// Bot profile shared outside the adapter lock, so prompt builders can read it
+/// while event handlers hold the adapter.
+pub type SharedBotProfile = Arc<RwLock<Option<Profile>>>;
+
+/// Messages the bot sent that subscribers have not received yet; slow receivers
+/// beyond this lag and skip the oldest.
+const OUTBOUND_EVENT_CAPACITY: usize = 256;
+
 /// BotAdapter connects to the QQ bot server via WebSocket and processes events
 pub struct BotAdapter {
     url: String,
     token: String,
-    bot_profile: Option<Profile>,
+    bot_profile: SharedBotProfile,
     brain_agent: Option<AgentBox>,
-    event_handlers: HashMap<String, event::EventHandler>,
+    supports_reactions: bool,
+    /// Reaction support was not configured and is detected on connect.
+    probe_reactions: bool,
+    respond_only_when_mentioned: bool,
+    sent_message_ids: Arc<SentMessageIds>,
+    catch_up: Option<Arc<CatchUpState>>,
+    event_watchdog: Option<Arc<EventWatchdog>>,
+    message_dedup: Option<Arc<MessageDeduplicator>>,
+    ws_reconnect: BackoffPolicy,
+    shutdown: Arc<AdapterShutdown>,
+    /// Shared with the dispatch loop, which runs handlers without the adapter lock.
+    event_handlers: event::EventHandlerRegistry,
+    outbound_tx: broadcast::Sender<MessageEvent>,
     /// Sender half for outbound WebSocket actions (set once the connection is live).
     pub action_tx: Option<mpsc::UnboundedSender<String>>,
     /// Echo → oneshot channel map for correlating action responses.
@@ -86,7 +218,67 @@ pub struct BotAdapter {
 }
 
 /// Shared handle for BotAdapter that allows mutation inside async tasks
-pub type SharedBotAdapter = Arc<TokioMutex<BotAdapter>>;
+pub type SharedBotAdapter = Arc<BotAdapterCell>;
+
+/// The mutex around a [`BotAdapter`], plus the bot profile and shutdown
+/// state, which are kept beside the mutex so readers never wait for the
+/// adapter lock. Derefs to the mutex, so `adapter.lock()` works as before.
+pub struct BotAdapterCell {
+    adapter: TokioMutex<BotAdapter>,
+    bot_profile: SharedBotProfile,
+    shutdown: Arc<AdapterShutdown>,
+}
+
+impl std::ops::Deref for BotAdapterCell {
+    type Target = TokioMutex<BotAdapter>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.adapter
+    }
+}
+
+impl BotAdapterCell {
+    /// The bot's QQ id, read without the adapter lock.
+    pub fn bot_id(&self) -> String {
+        bot_id_of(&self.bot_profile)
+    }
+
+    /// Copy of the current bot profile, read without the adapter lock.
+    pub fn profile_snapshot(&self) -> Option<Profile> {
+        self.bot_profile.read().unwrap().clone()
+    }
+
+    /// Replace the bot profile, e.g. with the account reported by `get_login_info`.
+    pub fn update_profile(&self, profile: Profile) {
+        *self.bot_profile.write().unwrap() = Some(profile);
+    }
+
+    /// Shutdown state of the wrapped adapter, read without the adapter lock.
+    pub fn shutdown_state(&self) -> Arc<AdapterShutdown> {
+        Arc::clone(&self.shutdown)
+    }
+}
+
+fn bot_id_of(profile: &SharedBotProfile) -> String {
+    profile
+        .read()
+        .unwrap()
+        .as_ref()
+        .expect("BotProfile must be initialized before accessing bot_id")
+        .qq_id
+        .clone()
+}
+
+/// Adapter state [`BotAdapter::process_event`] reads once per event.
+struct EventPathHandles {
+    pending_actions: PendingActions,
+    sent_message_ids: Arc<SentMessageIds>,
+    shutdown: Arc<AdapterShutdown>,
+    message_dedup: Option<Arc<MessageDeduplicator>>,
+    catch_up: Option<Arc<CatchUpState>>,
+    watchdog: Option<Arc<EventWatchdog>>,
+    event_handlers: event::EventHandlerRegistry,
+}
 
 #[derive(Clone)]
 struct BotAdapterImageCacheHandle(SharedBotAdapter);
@@ -136,15 +328,30 @@ impl ImageCacheAdapter for BotAdapterImageCacheHandle {
 
 impl BotAdapter {
     pub async fn new(config: BotAdapterConfig) -> Self {
+        let event_handlers = event::EventHandlerRegistry::default();
+        if let Some(webhook_sink) = &config.webhook_sink {
+            event_handlers.register_with_id(WEBHOOK_HANDLER_ID, webhook_sink.event_handler());
+        }
+        let ws_reconnect = config.ws_reconnect_policy();
         Self {
             url: config.url,
             token: config.token,
-            bot_profile: Some(Profile {
+            bot_profile: Arc::new(RwLock::new(Some(Profile {
                 qq_id: config.qq_id,
                 ..Default::default()
-            }),
+            }))),
             brain_agent: config.brain_agent,
-            event_handlers: HashMap::new(),
+            supports_reactions: config.supports_reactions.unwrap_or(false),
+            probe_reactions: config.supports_reactions.is_none(),
+            respond_only_when_mentioned: config.respond_only_when_mentioned,
+            sent_message_ids: Arc::new(SentMessageIds::default()),
+            catch_up: config.catch_up,
+            event_watchdog: config.event_watchdog,
+            message_dedup: config.message_dedup,
+            ws_reconnect,
+            shutdown: Arc::new(AdapterShutdown::default()),
+            event_handlers,
+            outbound_tx: broadcast::channel(OUTBOUND_EVENT_CAPACITY).0,
             action_tx: None,
             pending_actions: Arc::new(TokioMutex::new(HashMap::new())),
             object_storage: config.object_storage,
@@ -153,9 +360,19 @@ impl BotAdapter {
         }
     }
 
+    /// Shutdown state shared with the connection loop, for tracking tasks
+    /// that [`Self::shutdown`] should wait for.
+    pub fn shutdown_state(&self) -> Arc<AdapterShutdown> {
+        self.shutdown.clone()
+    }
+
     /// Convert this adapter into a shared, mutex-protected handle
     pub fn into_shared(self) -> SharedBotAdapter {
-        Arc::new(TokioMutex::new(self))
+        Arc::new(BotAdapterCell {
+            bot_profile: Arc::clone(&self.bot_profile),
+            shutdown: Arc::clone(&self.shutdown),
+            adapter: TokioMutex::new(self),
+        })
     }
 }
 
@@ -166,21 +383,18 @@ impl BotAdapter {
 pub fn shared_from_handle(handle: &zihuan_core::ims_bot_adapter::BotAdapterHandle) -> SharedBotAdapter {
     handle
         .clone()
-        .downcast::<TokioMutex<BotAdapter>>()
+        .downcast::<BotAdapterCell>()
         .expect("BotAdapterHandle contains unexpected concrete type")
 }
 
 impl BotAdapter {
-    pub fn get_bot_id(&self) -> &str {
-        self.bot_profile
-            .as_ref()
-            .expect("BotProfile must be initialized before accessing bot_id")
-            .qq_id
-            .as_str()
+    pub fn get_bot_id(&self) -> String {
+        bot_id_of(&self.bot_profile)
     }
 
-    pub fn get_bot_profile(&self) -> Option<&Profile> {
-        self.bot_profile.as_ref()
+    /// Copy of the current bot profile.
+    pub fn profile_snapshot(&self) -> Option<Profile> {
+        self.bot_profile.read().unwrap().clone()
     }
 
     /// Derive an HTTP base URL from the WebSocket URL (ws→http, wss→https, path stripped)
@@ -217,30 +431,315 @@ impl BotAdapter {
         self.brain_agent.as_ref()
     }
 
-    pub fn register_event_handler(&mut self, handler: event::EventHandler) -> String {
-        let handler_id = Uuid::new_v4().to_string();
-        self.register_event_handler_with_id(handler_id.clone(), handler);
-        handler_id
+    pub fn set_brain_agent(&mut self, agent: Option<AgentBox>) {
+        self.brain_agent = agent;
+    }
+
+    /// Queue an OneBot action on the live connection without waiting for its response.
+    ///
+    /// Unlike `ws_send_action_async` this needs no adapter lock, so brain agents can
+    /// call it from `on_event` while they hold `&mut BotAdapter`. Message sends and
+    /// reactions share the QQ rate limit with `ws_send_action_async`; past the limit
+    /// they are written once their slot comes up instead of blocking the caller.
+    pub fn enqueue_action(&self, action_name: &str, params: serde_json::Value) -> Result<()> {
+        let action_tx = self.action_tx.as_ref().ok_or_else(|| {
+            zihuan_core::error::Error::ValidationError("Bot adapter WebSocket not connected yet".to_string())
+        })?;
+        let echo = if crate::ws_action::is_message_send_action(action_name) {
+            crate::ws_action::next_queued_send_echo()
+        } else {
+            crate::ws_action::next_echo()
+        };
+        let payload = serde_json::json!({
+            "action": action_name,
+            "params": params,
+            "echo": echo,
+        });
+        let wait = if crate::ws_action::is_rate_limited_action(action_name) {
+            crate::ws_action::qq_message_rate_limiter().reserve()
+        } else {
+            Duration::ZERO
+        };
+        if wait.is_zero() {
+            return action_tx.send(payload.to_string()).map_err(|_| {
+                zihuan_core::error::Error::ValidationError(format!(
+                    "Failed to enqueue WebSocket action '{action_name}'"
+                ))
+            });
+        }
+
+        let action_tx = action_tx.clone();
+        let action_name = action_name.to_string();
+        tokio::spawn(async move {
+            tokio::time::sleep(wait).await;
+            if action_tx.send(payload.to_string()).is_err() {
+                warn!("Dropped rate-limited WebSocket action '{action_name}': connection closed");
+            }
+        });
+        Ok(())
+    }
+
+    pub fn supports_reactions(&self) -> bool {
+        self.supports_reactions
+    }
+
+    /// Ids of messages the bot sent; replies to them count as addressing the bot.
+    pub fn sent_message_ids(&self) -> Arc<SentMessageIds> {
+        self.sent_message_ids.clone()
+    }
+
+    /// Whether the brain agent should handle `event`: always for private
+    /// messages, and for group messages either when mention filtering is off or
+    /// when the message @s the bot or replies to one of the bot's messages.
+    pub fn should_dispatch_to_brain(&self, event: &MessageEvent) -> bool {
+        if !self.respond_only_when_mentioned || !matches!(event.message_type, MessageType::Group) {
+            return true;
+        }
+        event.mentions_bot(&self.get_bot_id())
+            || event.reply_target().is_some_and(|id| self.sent_message_ids.contains(id))
+    }
+
+    pub fn catch_up_state(&self) -> Option<Arc<CatchUpState>> {
+        self.catch_up.clone()
+    }
+
+    pub fn event_watchdog(&self) -> Option<Arc<EventWatchdog>> {
+        self.event_watchdog.clone()
+    }
+
+    /// React to a message with an emoji via NapCat's `set_msg_emoji_like`.
+    ///
+    /// Plain OneBot v11 servers have no reaction action, so this is rejected unless
+    /// the connection was configured with reaction support or the server is NapCat.
+    pub fn react(&self, message_id: i64, emoji_id: &str) -> Result<()> {
+        if !self.supports_reactions {
+            return Err(zihuan_core::error::Error::ValidationError(
+                "Bot server does not support message reactions".to_string(),
+            ));
+        }
+        self.enqueue_action(
+            "set_msg_emoji_like",
+            serde_json::json!({ "message_id": message_id, "emoji_id": emoji_id, "set": true }),
+        )
+    }
+
+    /// Deliver an [`AgentOutput`] in response to `event`. Text output quotes the
+    /// message `reply_to`, usually `event.message_id`, when one is given.
+    pub fn send_agent_output(&self, event: &MessageEvent, output: AgentOutput, reply_to: Option<i64>) -> Result<()> {
+        let text = match output {
+            AgentOutput::Reaction(emoji_id) => return self.react(event.message_id, &emoji_id),
+            AgentOutput::Text(text) => text,
+        };
+        let message = text_message_json(&text, reply_to);
+        match (event.message_type, event.group_id) {
+            (MessageType::Group, Some(group_id)) => self.enqueue_action(
+                "send_group_msg",
+                serde_json::json!({ "group_id": group_id, "message": message }),
+            ),
+            (MessageType::Group, None) => Err(zihuan_core::error::Error::ValidationError(
+                "Group message event has no group_id".to_string(),
+            )),
+            (MessageType::Private, _) => self.enqueue_action(
+                "send_private_msg",
+                serde_json::json!({ "user_id": event.sender.user_id, "message": message }),
+            ),
+        }
+    }
+
+    // Typed OneBot v11 actions. Each waits for the response matched by `echo`
+    // and returns it as-is; check it with `ws_action::response_success`.
+
+    pub async fn send_private_msg(
+        adapter: &SharedBotAdapter,
+        user_id: i64,
+        message: &[Message],
+    ) -> Result<serde_json::Value> {
+        let params = serde_json::json!({ "user_id": user_id, "message": qq_message_list_to_json(message) });
+        ws_send_action_async(adapter, "send_private_msg", params).await
+    }
+
+    pub async fn send_group_msg(
+        adapter: &SharedBotAdapter,
+        group_id: i64,
+        message: &[Message],
+    ) -> Result<serde_json::Value> {
+        let params = serde_json::json!({ "group_id": group_id, "message": qq_message_list_to_json(message) });
+        ws_send_action_async(adapter, "send_group_msg", params).await
+    }
+
+    /// Recall a message.
+    pub async fn delete_msg(adapter: &SharedBotAdapter, message_id: i64) -> Result<serde_json::Value> {
+        ws_send_action_async(adapter, "delete_msg", serde_json::json!({ "message_id": message_id })).await
+    }
+
+    /// Remove `user_id` from the group; `reject_add_request` also blocks them from re-applying.
+    pub async fn set_group_kick(
+        adapter: &SharedBotAdapter,
+        group_id: i64,
+        user_id: i64,
+        reject_add_request: bool,
+    ) -> Result<serde_json::Value> {
+        let params = serde_json::json!({
+            "group_id": group_id,
+            "user_id": user_id,
+            "reject_add_request": reject_add_request,
+        });
+        ws_send_action_async(adapter, "set_group_kick", params).await
+    }
+
+    /// Mute `user_id` in the group for `duration`, rounded down to whole seconds;
+    /// a zero duration lifts the mute.
+    pub async fn set_group_ban(
+        adapter: &SharedBotAdapter,
+        group_id: i64,
+        user_id: i64,
+        duration: Duration,
+    ) -> Result<serde_json::Value> {
+        let params = serde_json::json!({
+            "group_id": group_id,
+            "user_id": user_id,
+            "duration": duration.as_secs(),
+        });
+        ws_send_action_async(adapter, "set_group_ban", params).await
+    }
+
+    pub fn register_event_handler(&self, handler: event::EventHandler) -> String {
+        self.event_handlers.register(handler)
+    }
+
+    pub fn register_event_handler_with_id(&self, handler_id: impl Into<String>, handler: event::EventHandler) {
+        self.event_handlers.register_with_id(handler_id, handler);
+    }
+
+    pub fn unregister_event_handler(&self, handler_id: &str) -> bool {
+        self.event_handlers.unregister(handler_id)
+    }
+
+    /// Handle to the handler map. It stays valid after the adapter lock is
+    /// released, so callers can register handlers or dispatch without it.
+    pub fn event_handlers(&self) -> event::EventHandlerRegistry {
+        self.event_handlers.clone()
     }
 
-    pub fn register_event_handler_with_id(&mut self, handler_id: impl Into<String>, handler: event::EventHandler) {
-        self.event_handlers.insert(handler_id.into(), handler);
+    /// Snapshot of the registered handlers; a single `Arc` clone per call.
+    pub fn get_event_handlers(&self) -> Arc<HashMap<String, event::EventHandler>> {
+        self.event_handlers.snapshot()
     }
 
-    pub fn unregister_event_handler(&mut self, handler_id: &str) -> bool {
-        self.event_handlers.remove(handler_id).is_some()
+    /// Receive a `MessageEvent` (sender = bot) for every message the bot sends,
+    /// so history and analytics see both sides of the conversation.
+    pub fn subscribe_outbound(&self) -> broadcast::Receiver<MessageEvent> {
+        self.outbound_tx.subscribe()
     }
 
-    pub fn get_event_handlers(&self) -> Vec<event::EventHandler> {
-        self.event_handlers.values().cloned().collect()
+    /// Sender for outbound events; cloned so callers can publish without the adapter lock.
+    pub fn outbound_sender(&self) -> broadcast::Sender<MessageEvent> {
+        self.outbound_tx.clone()
+    }
+
+    /// Keep the WebSocket connection up: run [`Self::start`] and reconnect
+    /// whenever the connection fails or closes, waiting an exponentially growing
+    /// delay between attempts. A connection that stayed up for a while resets the
+    /// delay. Returns an error once `ws_reconnect_max_attempts` consecutive
+    /// attempts have failed, and `Ok` once [`Self::shutdown`] was requested.
+    pub async fn run(adapter: SharedBotAdapter) -> Result<()> {
+        let (url, policy, shutdown) = {
+            let guard = adapter.lock().await;
+            (guard.url.clone(), guard.ws_reconnect, guard.shutdown.clone())
+        };
+
+        let mut failures = 0u32;
+        loop {
+            let started_at = Instant::now();
+            let result = Self::start(adapter.clone()).await;
+            if shutdown.is_requested() {
+                info!("Bot adapter for {} shut down", url);
+                return Ok(());
+            }
+            if started_at.elapsed() >= STABLE_CONNECTION_DURATION {
+                failures = 0;
+            }
+            failures = failures.saturating_add(1);
+            let reason = match result {
+                Ok(()) => "connection closed".to_string(),
+                Err(err) => err.to_string(),
+            };
+
+            if !policy.should_retry(failures) {
+                error!(
+                    "Giving up on bot server {} after {} attempt(s) without a stable connection: {}",
+                    url, failures, reason
+                );
+                return Err(zihuan_core::error::Error::StringError(format!(
+                    "bot server {url} unreachable after {failures} attempt(s): {reason}"
+                )));
+            }
+
+            let delay = policy.jittered_delay_for_attempt(failures);
+            warn!(
+                "Bot server {} disconnected ({}), reconnecting in {:.1}s (attempt {})",
+                url,
+                reason,
+                delay.as_secs_f64(),
+                failures
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.requested() => {
+                    info!("Bot adapter for {} shut down", url);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Stop the adapter gracefully: stop reading new events, wait for the
+    /// events already being processed (message persistence, handlers, brain
+    /// replies) to finish, flush queued outbound actions and close the
+    /// WebSocket with a Close frame. Returns once draining is done, or after
+    /// a bounded wait if something is stuck.
+    pub async fn shutdown(adapter: &SharedBotAdapter) {
+        // Request the stop before touching the lock, so a handler stuck
+        // holding the adapter cannot keep the connection loop running.
+        let shutdown = adapter.shutdown_state();
+        shutdown.request();
+
+        // Taking the lock counts against the drain budget, so a handler stuck
+        // holding the adapter cannot stall shutdown indefinitely.
+        let deadline = tokio::time::Instant::now() + SHUTDOWN_DRAIN_TIMEOUT;
+        let Ok(guard) = tokio::time::timeout_at(deadline, adapter.lock()).await else {
+            warn!(
+                "Bot adapter lock still held after {}s; shutting down without draining",
+                SHUTDOWN_DRAIN_TIMEOUT.as_secs()
+            );
+            return;
+        };
+        let (url, catch_up) = (guard.url.clone(), guard.catch_up.clone());
+        drop(guard);
+        info!("Shutting down bot adapter for {}", url);
+
+        if tokio::time::timeout_at(deadline, shutdown.wait_drained()).await.is_err() {
+            warn!(
+                "Bot adapter for {} did not drain within {}s ({} event task(s) still running)",
+                url,
+                SHUTDOWN_DRAIN_TIMEOUT.as_secs(),
+                shutdown.event_tasks_in_flight()
+            );
+        }
+        if let Some(catch_up) = catch_up {
+            catch_up.flush().await;
+        }
     }
 
     /// Start the WebSocket connection and begin processing events using a shared handle
     pub async fn start(adapter: SharedBotAdapter) -> Result<()> {
-        let (url, token) = {
+        let (url, token, shutdown) = {
             let guard = adapter.lock().await;
-            (guard.url.clone(), guard.token.clone())
+            (guard.url.clone(), guard.token.clone(), guard.shutdown.clone())
         };
+        if shutdown.is_requested() {
+            return Ok(());
+        }
 
         info!("Connecting to bot server at {}", url);
 
@@ -258,7 +757,10 @@ impl BotAdapter {
             )
             .body(())?;
 
-        let (ws_stream, _) = connect_async(request).await?;
+        let (ws_stream, _) = tokio::select! {
+            connected = connect_async(request) => connected?,
+            _ = shutdown.requested() => return Ok(()),
+        };
         info!("Connected to the qq bot server successfully.");
 
         let (mut write, mut read) = ws_stream.split();
@@ -269,26 +771,128 @@ impl BotAdapter {
             guard.action_tx = Some(action_tx);
         }
 
-        tokio::spawn(async move {
-            while let Some(msg) = action_rx.recv().await {
+        let (close_tx, mut close_rx) = oneshot::channel::<()>();
+        let connection = shutdown.track_connection();
+        let writer = tokio::spawn(async move {
+            let _connection = connection;
+            loop {
+                tokio::select! {
+                    msg = action_rx.recv() => match msg {
+                        Some(msg) => {
+                            if write.send(WsMessage::Text(msg)).await.is_err() {
+                                return;
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = &mut close_rx => break,
+                }
+            }
+            // Flush actions queued before the close, then closing the sink sends
+            // the Close frame.
+            while let Ok(msg) = action_rx.try_recv() {
                 if write.send(WsMessage::Text(msg)).await.is_err() {
-                    break;
+                    return;
+                }
+            }
+            if let Err(err) = write.close().await {
+                debug!("Failed to close the WebSocket cleanly: {}", err);
+            }
+        });
+
+        // Responses to the history requests arrive on the read loop below, so
+        // catch-up has to run alongside it.
+        if adapter.lock().await.catch_up.is_some() {
+            let adapter_for_catch_up = adapter.clone();
+            tokio::spawn(async move {
+                if let Err(err) = catch_up_missed_messages(adapter_for_catch_up).await {
+                    warn!("Message catch-up after connect failed: {}", err);
                 }
+            });
+        }
+
+        if adapter.lock().await.probe_reactions {
+            let adapter_for_probe = adapter.clone();
+            tokio::spawn(async move {
+                match fetch_app_name(&adapter_for_probe).await {
+                    Ok(app_name) => {
+                        let supports_reactions = is_napcat(&app_name);
+                        debug!("Bot server {:?} supports reactions: {}", app_name, supports_reactions);
+                        adapter_for_probe.lock().await.supports_reactions = supports_reactions;
+                    }
+                    Err(err) => warn!("Failed to detect reaction support: {}", err),
+                }
+            });
+        }
+
+        // Refresh the profile from the logged-in account; the configured qq_id is
+        // only a placeholder until the server reports the real nickname.
+        let adapter_for_profile = adapter.clone();
+        tokio::spawn(async move {
+            if let Err(err) = refresh_bot_profile(&adapter_for_profile).await {
+                warn!("Failed to refresh bot profile after connect: {}", err);
             }
         });
 
-        while let Some(msg_result) = read.next().await {
+        // A fresh connection counts as activity; the watchdog only measures silence
+        // while the socket is up.
+        let watchdog = adapter.lock().await.event_watchdog.clone();
+        if let Some(watchdog) = &watchdog {
+            watchdog.record_event();
+        }
+        let mut watchdog_tick = tokio::time::interval(
+            watchdog
+                .as_ref()
+                .map(|watchdog| watchdog.poll_interval())
+                .unwrap_or(Duration::from_secs(60)),
+        );
+        watchdog_tick.tick().await;
+
+        // After a shutdown request the loop keeps reading until the in-flight
+        // events are done, since their actions still need responses.
+        let mut draining = false;
+        loop {
+            let msg_result = tokio::select! {
+                msg_result = read.next() => match msg_result {
+                    Some(msg_result) => msg_result,
+                    None => break,
+                },
+                _ = shutdown.requested(), if !draining => {
+                    draining = true;
+                    continue;
+                }
+                _ = shutdown.wait_event_tasks(), if draining => break,
+                _ = watchdog_tick.tick(), if watchdog.is_some() => {
+                    let Some(watchdog) = &watchdog else { continue };
+                    if let Some(silence) = watchdog.check() {
+                        error!(
+                            "No message event received from {} for {}s while connected",
+                            url,
+                            silence.as_secs()
+                        );
+                        if watchdog.reconnects() {
+                            warn!("Dropping the silent connection to {} to force a reconnect", url);
+                            break;
+                        }
+                    }
+                    continue;
+                }
+            };
             match msg_result {
                 Ok(WsMessage::Text(text)) => {
                     let adapter_clone = adapter.clone();
+                    let in_flight = shutdown.track_event_task();
                     tokio::spawn(async move {
+                        let _in_flight = in_flight;
                         BotAdapter::process_event(adapter_clone, text).await;
                     });
                 }
                 Ok(WsMessage::Binary(data)) => {
                     if let Ok(text) = String::from_utf8(data) {
                         let adapter_clone = adapter.clone();
+                        let in_flight = shutdown.track_event_task();
                         tokio::spawn(async move {
+                            let _in_flight = in_flight;
                             BotAdapter::process_event(adapter_clone, text).await;
                         });
                     } else {
@@ -319,11 +923,17 @@ impl BotAdapter {
             }
         }
 
+        adapter.lock().await.action_tx = None;
+        let _ = close_tx.send(());
+        if tokio::time::timeout(SHUTDOWN_DRAIN_TIMEOUT, writer).await.is_err() {
+            warn!("Timed out closing the WebSocket connection to {}", url);
+        }
+
         Ok(())
     }
 
     /// Process a single event message
-    async fn process_event(adapter: SharedBotAdapter, message: String) {
+    pub(crate) async fn process_event(adapter: SharedBotAdapter, message: String) {
         debug!("Received message: {}", message);
 
         // Parse the JSON message
@@ -337,12 +947,28 @@ impl BotAdapter {
 
         // Check if this is an action response (has "echo" field).
         // Dispatch it to the waiting oneshot channel and return early.
+        // Everything the event path needs from the adapter, read under one lock.
+        let handles = {
+            let guard = adapter.lock().await;
+            EventPathHandles {
+                pending_actions: guard.pending_actions.clone(),
+                sent_message_ids: guard.sent_message_ids.clone(),
+                shutdown: guard.shutdown.clone(),
+                message_dedup: guard.message_dedup.clone(),
+                catch_up: guard.catch_up.clone(),
+                watchdog: guard.event_watchdog.clone(),
+                event_handlers: guard.event_handlers(),
+            }
+        };
+
         if let Some(echo) = message_json.get("echo").and_then(|v| v.as_str()) {
-            let pending = {
-                let guard = adapter.lock().await;
-                guard.pending_actions.clone()
-            };
-            let mut map = pending.lock().await;
+            if echo.starts_with(crate::ws_action::QUEUED_SEND_ECHO_PREFIX) {
+                if let Some(message_id) = crate::ws_action::response_message_id(&message_json) {
+                    handles.sent_message_ids.record(message_id);
+                }
+                return;
+            }
+            let mut map = handles.pending_actions.lock().await;
             if let Some(tx) = map.remove(echo) {
                 let _ = tx.send(message_json);
                 return;
@@ -351,7 +977,13 @@ impl BotAdapter {
 
         // Check if this is a message event (has message_type field)
         if message_json.get("message_type").is_none() {
-            debug!("Ignoring non-message event");
+            Self::process_non_message_event(adapter, &handles.shutdown, message_json);
+            return;
+        }
+
+        let shutdown = handles.shutdown;
+        if shutdown.is_requested() {
+            debug!("Ignoring message event received while shutting down");
             return;
         }
 
@@ -364,6 +996,13 @@ impl BotAdapter {
             }
         };
 
+        if let Some(message_dedup) = handles.message_dedup {
+            if !message_dedup.is_new(raw_event.message_id) {
+                info!("[adapter] dropping duplicate delivery of message_id={}", raw_event.message_id);
+                return;
+            }
+        }
+
         // Create the MessageEvent (messages are already deserialized in RawMessageEvent)
         let mut event = MessageEvent {
             message_id: raw_event.message_id,
@@ -373,16 +1012,62 @@ impl BotAdapter {
             group_id: raw_event.group_id,
             group_name: raw_event.group_name.clone(),
             is_group_message: matches!(raw_event.message_type, MessageType::Group),
+            time: raw_event.time,
         };
 
+        if let Some(watchdog) = handles.watchdog {
+            watchdog.record_event();
+        }
+        if let Some(catch_up) = handles.catch_up {
+            catch_up.record(&event).await;
+        }
+
         let image_cache_handle = BotAdapterImageCacheHandle(adapter.clone());
         enrich_event_images(&image_cache_handle, &mut event).await;
         hydrate_message_segments(&adapter, &image_cache_handle, event.message_id, &mut event.message_list).await;
 
         // Dispatch to the unified message handler
         let adapter_clone = adapter.clone();
+        let event_handlers = handles.event_handlers;
+        let in_flight = shutdown.track_event_task();
         tokio::spawn(async move {
-            event::process_message(adapter_clone, event).await;
+            let _in_flight = in_flight;
+            event::process_message(adapter_clone, event_handlers, event).await;
+        });
+    }
+
+    /// Route notice and request posts; heartbeats and other meta events are dropped.
+    fn process_non_message_event(
+        adapter: SharedBotAdapter,
+        shutdown: &Arc<AdapterShutdown>,
+        message_json: serde_json::Value,
+    ) {
+        let post_type = message_json.get("post_type").and_then(|v| v.as_str()).map(str::to_string);
+        if !matches!(post_type.as_deref(), Some("notice" | "request")) {
+            debug!("Ignoring non-message event");
+            return;
+        }
+
+        if shutdown.is_requested() {
+            debug!("Ignoring {:?} event received while shutting down", post_type);
+            return;
+        }
+
+        let parsed: Event = match serde_json::from_value(message_json) {
+            Ok(e) => e,
+            Err(e) => {
+                error!("Failed to parse {:?} event: {}", post_type, e);
+                return;
+            }
+        };
+
+        let in_flight = shutdown.track_event_task();
+        tokio::spawn(async move {
+            let _in_flight = in_flight;
+            match parsed {
+                Event::Notice(notice) => event::process_notice(adapter, notice).await,
+                Event::Request(request) => event::process_request(adapter, request).await,
+            }
         });
     }
 }
@@ -403,6 +1088,17 @@ pub(crate) struct ResolvedMessageList {
     pub source_label: String,
 }
 
+/// A plain text message segment list, led by a `reply` segment quoting
+/// `reply_to` when set.
+fn text_message_json(text: &str, reply_to: Option<i64>) -> serde_json::Value {
+    let mut messages = Vec::with_capacity(2);
+    if let Some(id) = reply_to {
+        messages.push(Message::Reply(ReplyMessage { id, message_source: None }));
+    }
+    messages.push(Message::PlainText(PlainTextMessage { text: text.to_string() }));
+    qq_message_list_to_json(&messages)
+}
+
 fn json_value_to_string(value: Option<&serde_json::Value>) -> Option<String> {
     match value? {
         serde_json::Value::String(text) => Some(text.clone()),
@@ -477,6 +1173,13 @@ fn parse_cq_string_to_messages(content: &str) -> Vec<Message> {
                     ),
                 ));
             }
+            "face" => {
+                if let Some(id) = params.get("id") {
+                    messages.push(Message::Face(zihuan_core::ims_bot_adapter::models::message::FaceMessage {
+                        id: id.clone(),
+                    }));
+                }
+            }
             "at" => {
                 messages.push(Message::At(zihuan_core::ims_bot_adapter::models::message::AtTargetMessage {
                     target: params.get("qq").cloned(),
@@ -692,6 +1395,18 @@ async fn hydrate_message_segments(
     }
 }
 
+async fn refresh_bot_profile(adapter: &SharedBotAdapter) -> Result<()> {
+    let response = ws_send_action_async(adapter, "get_login_info", serde_json::json!({})).await?;
+    let info = parse_login_info(&response)?;
+    let mut profile = adapter.profile_snapshot().unwrap_or_default();
+    if !info.user_id.trim().is_empty() {
+        profile.qq_id = info.user_id;
+    }
+    profile.nickname = info.nickname;
+    adapter.update_profile(profile);
+    Ok(())
+}
+
 async fn fetch_forward_content(adapter: &SharedBotAdapter, forward_id: &str) -> Result<Vec<ForwardNodeMessage>> {
     let response =
         ws_send_action_async(adapter, "get_forward_msg", serde_json::json!({ "message_id": forward_id })).await?;
@@ -720,3 +1435,336 @@ async fn fetch_forward_content(adapter: &SharedBotAdapter, forward_id: &str) ->
 
     Ok(nodes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::event_model::Sender;
+
+    fn group_event(message_id: i64) -> MessageEvent {
+        MessageEvent {
+            message_id,
+            message_type: MessageType::Group,
+            sender: Sender {
+                user_id: 2001,
+                nickname: "sender".to_string(),
+                card: String::new(),
+                role: None,
+            },
+            message_list: vec![],
+            group_id: Some(3001),
+            group_name: None,
+            is_group_message: true,
+            time: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn mention_filter_only_dispatches_group_messages_addressed_to_the_bot() {
+        use zihuan_core::ims_bot_adapter::models::message::{AtTargetMessage, ReplyMessage};
+
+        let adapter = BotAdapter::new(
+            BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000").with_respond_only_when_mentioned(true),
+        )
+        .await;
+        adapter.sent_message_ids().record(555);
+        let group_message = |segment: Option<Message>| MessageEvent {
+            message_list: segment.into_iter().collect(),
+            ..group_event(1)
+        };
+
+        assert!(!adapter.should_dispatch_to_brain(&group_message(None)));
+        assert!(
+            adapter.should_dispatch_to_brain(&group_message(Some(Message::At(AtTargetMessage {
+                target: Some("10000".to_string()),
+            }))))
+        );
+        assert!(
+            !adapter.should_dispatch_to_brain(&group_message(Some(Message::At(AtTargetMessage {
+                target: Some("20000".to_string()),
+            }))))
+        );
+        assert!(
+            adapter.should_dispatch_to_brain(&group_message(Some(Message::Reply(ReplyMessage {
+                id: 555,
+                message_source: None,
+            }))))
+        );
+        assert!(
+            !adapter.should_dispatch_to_brain(&group_message(Some(Message::Reply(ReplyMessage {
+                id: 556,
+                message_source: None,
+            }))))
+        );
+
+        let private_message = MessageEvent {
+            message_type: MessageType::Private,
+            group_id: None,
+            is_group_message: false,
+            ..group_event(2)
+        };
+        assert!(adapter.should_dispatch_to_brain(&private_message));
+    }
+
+    #[tokio::test]
+    async fn reaction_output_queues_emoji_like_action() {
+        let mut adapter =
+            BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000").with_reactions(Some(true))).await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        adapter.action_tx = Some(tx);
+
+        adapter
+            .send_agent_output(&group_event(987654), AgentOutput::Reaction("124".to_string()), None)
+            .unwrap();
+
+        let payload: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(payload["action"], "set_msg_emoji_like");
+        assert_eq!(payload["params"]["message_id"], 987654);
+        assert_eq!(payload["params"]["emoji_id"], "124");
+    }
+
+    #[tokio::test]
+    async fn queued_replies_are_recorded_as_sent_by_the_bot() {
+        use zihuan_core::ims_bot_adapter::models::message::ReplyMessage;
+
+        let mut adapter = BotAdapter::new(
+            BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000").with_respond_only_when_mentioned(true),
+        )
+        .await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        adapter.action_tx = Some(tx);
+        let adapter = adapter.into_shared();
+
+        adapter
+            .lock()
+            .await
+            .send_agent_output(&group_event(1), AgentOutput::Text("好的".to_string()), None)
+            .unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        let response = serde_json::json!({
+            "status": "ok",
+            "retcode": 0,
+            "data": { "message_id": 777 },
+            "echo": payload["echo"],
+        });
+        BotAdapter::process_event(adapter.clone(), response.to_string()).await;
+
+        let reply = MessageEvent {
+            message_list: vec![Message::Reply(ReplyMessage { id: 777, message_source: None })],
+            ..group_event(2)
+        };
+        assert!(adapter.lock().await.should_dispatch_to_brain(&reply));
+    }
+
+    /// A connected adapter plus a fake server that answers the next action with
+    /// `response` and hands back the payload it received.
+    async fn adapter_answering_next_action(
+        response: serde_json::Value,
+    ) -> (SharedBotAdapter, tokio::task::JoinHandle<serde_json::Value>) {
+        let mut adapter = BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000")).await;
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        adapter.action_tx = Some(tx);
+        let pending_actions = adapter.pending_actions.clone();
+
+        let server = tokio::spawn(async move {
+            let payload: serde_json::Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+            let echo = payload["echo"].as_str().unwrap().to_string();
+            let responder = pending_actions.lock().await.remove(&echo).unwrap();
+            responder.send(response).unwrap();
+            payload
+        });
+        (adapter.into_shared(), server)
+    }
+
+    #[tokio::test]
+    async fn set_group_ban_sends_the_duration_in_seconds() {
+        let (adapter, server) =
+            adapter_answering_next_action(serde_json::json!({ "status": "failed", "retcode": 102 })).await;
+
+        let response = BotAdapter::set_group_ban(&adapter, 3001, 2001, Duration::from_millis(600_500))
+            .await
+            .unwrap();
+        let payload = server.await.unwrap();
+
+        assert_eq!(payload["action"], "set_group_ban");
+        assert_eq!(payload["params"]["group_id"], 3001);
+        assert_eq!(payload["params"]["user_id"], 2001);
+        assert_eq!(payload["params"]["duration"], 600);
+        assert!(!crate::ws_action::response_success(&response));
+    }
+
+    #[test]
+    fn text_messages_quote_the_reply_target_first() {
+        let message = text_message_json("收到", Some(987654));
+
+        assert_eq!(
+            message,
+            serde_json::json!([
+                { "type": "reply", "data": { "id": 987654 } },
+                { "type": "text", "data": { "text": "收到" } },
+            ])
+        );
+        let segments: Vec<Message> = serde_json::from_value(message).unwrap();
+        assert!(matches!(&segments[0], Message::Reply(reply) if reply.id == 987654));
+    }
+
+    #[tokio::test]
+    async fn text_agent_output_quotes_the_triggering_message() {
+        let mut adapter = BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000")).await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        adapter.action_tx = Some(tx);
+        let event = group_event(987654);
+
+        adapter
+            .send_agent_output(&event, AgentOutput::Text("hi".to_string()), Some(event.message_id))
+            .unwrap();
+
+        let payload: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(payload["action"], "send_group_msg");
+        assert_eq!(payload["params"]["message"][0]["type"], "reply");
+        assert_eq!(payload["params"]["message"][0]["data"]["id"], 987654);
+        assert_eq!(payload["params"]["message"][1]["data"]["text"], "hi");
+    }
+
+    #[tokio::test]
+    async fn reaction_is_rejected_without_capability() {
+        let mut adapter = BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000")).await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        adapter.action_tx = Some(tx);
+
+        assert!(adapter.react(1, "124").is_err());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn profile_update_is_visible_to_reader_without_adapter_lock() {
+        let adapter = BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000"))
+            .await
+            .into_shared();
+        let reader_adapter = adapter.clone();
+        let reader = tokio::spawn(async move {
+            loop {
+                let nickname = reader_adapter.profile_snapshot().map(|profile| profile.nickname);
+                if nickname.as_deref() == Some("紫幻") {
+                    return nickname;
+                }
+                tokio::task::yield_now().await;
+            }
+        });
+
+        // Keep the adapter locked for the whole read, like a long-running handler would.
+        let guard = adapter.lock().await;
+        adapter.update_profile(Profile {
+            qq_id: "10000".to_string(),
+            nickname: "紫幻".to_string(),
+            ..Default::default()
+        });
+        let nickname = tokio::time::timeout(Duration::from_secs(5), reader).await.unwrap().unwrap();
+
+        assert_eq!(nickname.as_deref(), Some("紫幻"));
+        assert_eq!(adapter.bot_id(), "10000");
+        drop(guard);
+    }
+
+    #[tokio::test]
+    async fn run_retries_failed_connections_and_gives_up_after_max_attempts() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let accepted = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let accepted_for_server = accepted.clone();
+        tokio::spawn(async move {
+            // Drop every connection before the WebSocket handshake completes.
+            while let Ok((socket, _)) = listener.accept().await {
+                accepted_for_server.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                drop(socket);
+            }
+        });
+        let adapter = BotAdapter::new(BotAdapterConfig::new(url, "", "10000").with_ws_reconnect(Some(3), Some(0)))
+            .await
+            .into_shared();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), BotAdapter::run(adapter))
+            .await
+            .unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn connect_refreshes_profile_from_login_info() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            while let Some(Ok(frame)) = ws.next().await {
+                let WsMessage::Text(text) = frame else {
+                    continue;
+                };
+                let payload: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if payload["action"] == "get_login_info" {
+                    let response = serde_json::json!({
+                        "status": "ok",
+                        "retcode": 0,
+                        "data": { "user_id": 10000, "nickname": "紫幻" },
+                        "echo": payload["echo"],
+                    });
+                    ws.send(WsMessage::Text(response.to_string())).await.unwrap();
+                }
+            }
+        });
+        let adapter = BotAdapter::new(BotAdapterConfig::new(url, "", "10000")).await.into_shared();
+        tokio::spawn(BotAdapter::run(adapter.clone()));
+
+        let nickname = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match adapter.profile_snapshot().map(|profile| profile.nickname) {
+                    Some(nickname) if !nickname.is_empty() => return nickname,
+                    _ => tokio::time::sleep(Duration::from_millis(10)).await,
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(nickname, "紫幻");
+        assert_eq!(adapter.bot_id(), "10000");
+    }
+
+    #[tokio::test]
+    async fn shutdown_sends_close_frame_and_stops_run() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (connected_tx, connected_rx) = oneshot::channel();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            let _ = connected_tx.send(());
+            while let Some(Ok(frame)) = ws.next().await {
+                if let WsMessage::Close(_) = frame {
+                    return true;
+                }
+            }
+            false
+        });
+        let adapter = BotAdapter::new(BotAdapterConfig::new(url, "", "10000")).await.into_shared();
+        let run = tokio::spawn(BotAdapter::run(adapter.clone()));
+        connected_rx.await.unwrap();
+
+        let in_flight = adapter.shutdown_state().track_event_task();
+        let shutdown = tokio::spawn({
+            let adapter = adapter.clone();
+            async move { BotAdapter::shutdown(&adapter).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!shutdown.is_finished());
+        drop(in_flight);
+
+        tokio::time::timeout(Duration::from_secs(5), shutdown).await.unwrap().unwrap();
+        assert!(server.await.unwrap());
+        let result = tokio::time::timeout(Duration::from_secs(5), run).await.unwrap().unwrap();
+        assert!(result.is_ok());
+        assert!(adapter.lock().await.action_tx.is_none());
+    }
+}
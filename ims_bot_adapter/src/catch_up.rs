@@ -0,0 +1,432 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use log::{info, warn};
+use tokio::sync::Mutex as TokioMutex;
+use zihuan_core::error::Result;
+use zihuan_graph_engine::data_value::RedisConfig;
+use zihuan_graph_engine::message_restore::restore_message_snapshot;
+
+use crate::adapter::{BotAdapter, SharedBotAdapter};
+use crate::models::{MessageEvent, MessageType};
+use crate::server_info::{fetch_app_name, is_napcat};
+use crate::ws_action::ws_send_action_async;
+
+const LOG_PREFIX: &str = "[ims_bot_adapter][catch_up]";
+/// Message ids remembered in memory so catch-up never replays a message that
+/// was already delivered live on the current connection.
+const RECENT_MESSAGE_IDS: usize = 1024;
+/// Minimum time between two cursor writes. Cursors that moved in between are
+/// written by the next event after the interval, by catch-up and on shutdown;
+/// anything replayed twice after a crash is skipped by the message store.
+const CURSOR_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+pub const DEFAULT_CATCH_UP_HISTORY_COUNT: u32 = 20;
+
+/// Durable storage for the last message id seen in each chat.
+#[async_trait]
+pub trait CatchUpCursorStore: Send + Sync {
+    async fn load(&self) -> Result<HashMap<String, i64>>;
+    async fn save(&self, cursors: &HashMap<String, i64>) -> Result<()>;
+}
+
+/// Keeps cursors in process memory, so only reconnects (not restarts) are caught up.
+#[derive(Default)]
+pub struct MemoryCatchUpCursorStore {
+    cursors: TokioMutex<HashMap<String, i64>>,
+}
+
+#[async_trait]
+impl CatchUpCursorStore for MemoryCatchUpCursorStore {
+    async fn load(&self) -> Result<HashMap<String, i64>> {
+        Ok(self.cursors.lock().await.clone())
+    }
+
+    async fn save(&self, cursors: &HashMap<String, i64>) -> Result<()> {
+        *self.cursors.lock().await = cursors.clone();
+        Ok(())
+    }
+}
+
+/// Stores all cursors of one bot as a JSON object under a single Redis key.
+pub struct RedisCatchUpCursorStore {
+    redis_ref: Arc<RedisConfig>,
+    key: String,
+}
+
+impl RedisCatchUpCursorStore {
+    pub fn new(redis_ref: Arc<RedisConfig>, bot_id: &str) -> Self {
+        Self {
+            redis_ref,
+            key: format!("zihuan:catch_up_cursor:{bot_id}"),
+        }
+    }
+}
+
+#[async_trait]
+impl CatchUpCursorStore for RedisCatchUpCursorStore {
+    async fn load(&self) -> Result<HashMap<String, i64>> {
+        match storage_handler::redis::get_value(&self.redis_ref, &self.key).await? {
+            Some(raw) => Ok(serde_json::from_str(&raw)?),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    async fn save(&self, cursors: &HashMap<String, i64>) -> Result<()> {
+        storage_handler::redis::set_value(&self.redis_ref, &self.key, &serde_json::to_string(cursors)?).await
+    }
+}
+
+/// Per-chat cursors as loaded from the store, plus when they were last written
+/// and whether they moved since. `times` holds the send time of the event each
+/// cursor was last moved to; it is not persisted.
+#[derive(Default)]
+struct CursorState {
+    cursors: HashMap<String, i64>,
+    times: HashMap<String, i64>,
+    dirty: bool,
+    saved_at: Option<Instant>,
+}
+
+/// Catch-up settings and the per-chat cursors of one bot adapter.
+pub struct CatchUpState {
+    store: Arc<dyn CatchUpCursorStore>,
+    history_count: u32,
+    cursors: TokioMutex<Option<CursorState>>,
+    recent_ids: TokioMutex<(HashSet<i64>, VecDeque<i64>)>,
+}
+
+impl CatchUpState {
+    pub fn new(store: Arc<dyn CatchUpCursorStore>, history_count: u32) -> Self {
+        Self {
+            store,
+            history_count: history_count.max(1),
+            cursors: TokioMutex::new(None),
+            recent_ids: TokioMutex::new((HashSet::new(), VecDeque::new())),
+        }
+    }
+
+    async fn load(&self) -> CursorState {
+        let cursors = self.store.load().await.unwrap_or_else(|err| {
+            warn!("{LOG_PREFIX} failed to load cursors, starting empty: {err}");
+            HashMap::new()
+        });
+        CursorState {
+            cursors,
+            ..CursorState::default()
+        }
+    }
+
+    async fn cursors(&self) -> HashMap<String, i64> {
+        let mut guard = self.cursors.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.load().await);
+        }
+        guard.as_ref().map(|state| state.cursors.clone()).unwrap_or_default()
+    }
+
+    async fn mark_seen(&self, message_id: i64) {
+        let mut guard = self.recent_ids.lock().await;
+        let (ids, order) = &mut *guard;
+        if !ids.insert(message_id) {
+            return;
+        }
+        order.push_back(message_id);
+        if order.len() > RECENT_MESSAGE_IDS {
+            if let Some(oldest) = order.pop_front() {
+                ids.remove(&oldest);
+            }
+        }
+    }
+
+    /// Moves the cursor of the chat `event` belongs to onto `event`. Message ids
+    /// are not ordered, so the event's send time decides: an event sent before
+    /// the one the cursor is on (a late or replayed event) leaves it in place.
+    /// Writes to the store are throttled to one per [`CURSOR_SAVE_INTERVAL`].
+    pub async fn record(&self, event: &MessageEvent) {
+        self.mark_seen(event.message_id).await;
+        let mut guard = self.cursors.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.load().await);
+        }
+        let Some(state) = guard.as_mut() else {
+            return;
+        };
+        let key = chat_key(event);
+        if let Some(time) = event.time {
+            if state.times.get(&key).is_some_and(|latest| time < *latest) {
+                return;
+            }
+            state.times.insert(key.clone(), time);
+        }
+        if state.cursors.get(&key) == Some(&event.message_id) {
+            return;
+        }
+        state.cursors.insert(key, event.message_id);
+        state.dirty = true;
+        if state
+            .saved_at
+            .map_or(true, |saved_at| saved_at.elapsed() >= CURSOR_SAVE_INTERVAL)
+        {
+            self.save(state).await;
+        }
+    }
+
+    /// Writes cursors that moved since the last write.
+    pub async fn flush(&self) {
+        if let Some(state) = self.cursors.lock().await.as_mut() {
+            if state.dirty {
+                self.save(state).await;
+            }
+        }
+    }
+
+    async fn save(&self, state: &mut CursorState) {
+        state.saved_at = Some(Instant::now());
+        match self.store.save(&state.cursors).await {
+            Ok(()) => state.dirty = false,
+            Err(err) => warn!("{LOG_PREFIX} failed to persist cursors: {err}"),
+        }
+    }
+
+    async fn is_recent(&self, message_id: i64) -> bool {
+        self.recent_ids.lock().await.0.contains(&message_id)
+    }
+}
+
+/// Cursor key of the chat an event belongs to: `group:<id>` or `private:<id>`.
+pub fn chat_key(event: &MessageEvent) -> String {
+    match (event.message_type, event.group_id) {
+        (MessageType::Group, Some(group_id)) => format!("group:{group_id}"),
+        _ => format!("private:{}", event.sender.user_id),
+    }
+}
+
+/// Messages of `history` (oldest first) that come after `last_seen_id`. When the
+/// cursor is no longer in the fetched window, the whole window is returned.
+pub fn messages_after_cursor(history: Vec<serde_json::Value>, last_seen_id: i64) -> Vec<serde_json::Value> {
+    let position = history
+        .iter()
+        .position(|message| message.get("message_id").and_then(|id| id.as_i64()) == Some(last_seen_id));
+    match position {
+        Some(index) => history.into_iter().skip(index + 1).collect(),
+        None => history,
+    }
+}
+
+fn history_sender_id(message: &serde_json::Value) -> Option<String> {
+    match message.pointer("/sender/user_id").or_else(|| message.get("user_id"))? {
+        serde_json::Value::Number(id) => Some(id.to_string()),
+        serde_json::Value::String(id) => Some(id.clone()),
+        _ => None,
+    }
+}
+
+async fn fetch_chat_history(adapter: &SharedBotAdapter, chat: &str, count: u32) -> Result<Vec<serde_json::Value>> {
+    let (action, params) = match chat.split_once(':') {
+        Some(("group", id)) => (
+            "get_group_msg_history",
+            serde_json::json!({ "group_id": id, "message_seq": 0, "count": count }),
+        ),
+        Some(("private", id)) => (
+            "get_friend_msg_history",
+            serde_json::json!({ "user_id": id, "message_seq": 0, "count": count }),
+        ),
+        _ => return Ok(Vec::new()),
+    };
+    let response = ws_send_action_async(adapter, action, params).await?;
+    Ok(response
+        .get("data")
+        .and_then(|data| data.get("messages"))
+        .and_then(|messages| messages.as_array())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Fetches the messages each known chat received since its cursor and feeds them
+/// through the normal event pipeline. Messages already delivered on this process
+/// or already in the message store are skipped. Returns how many were replayed.
+pub async fn catch_up_missed_messages(adapter: SharedBotAdapter) -> Result<usize> {
+    let (state, bot_id) = {
+        let guard = adapter.lock().await;
+        let Some(state) = guard.catch_up_state() else {
+            return Ok(0);
+        };
+        (state, guard.get_bot_id())
+    };
+
+    // The history actions and their `message_seq: 0` for "latest" are NapCat
+    // extensions; other OneBot servers reject or misread them.
+    match fetch_app_name(&adapter).await {
+        Ok(app_name) if is_napcat(&app_name) => {}
+        Ok(app_name) => {
+            info!("{LOG_PREFIX} skipped, bot server {app_name:?} does not provide NapCat message history");
+            return Ok(0);
+        }
+        Err(err) => {
+            warn!("{LOG_PREFIX} skipped, failed to identify the bot server: {err}");
+            return Ok(0);
+        }
+    }
+
+    let mut replayed = 0;
+    for (chat, last_seen_id) in state.cursors().await {
+        let history = match fetch_chat_history(&adapter, &chat, state.history_count).await {
+            Ok(history) => history,
+            Err(err) => {
+                warn!("{LOG_PREFIX} failed to fetch history for {chat}: {err}");
+                continue;
+            }
+        };
+        for message in messages_after_cursor(history, last_seen_id) {
+            let Some(message_id) = message.get("message_id").and_then(|id| id.as_i64()) else {
+                continue;
+            };
+            // History also lists the bot's own replies, which must not be handled as inbound.
+            if history_sender_id(&message).as_deref() == Some(bot_id.as_str()) {
+                continue;
+            }
+            if state.is_recent(message_id).await || matches!(restore_message_snapshot(message_id), Ok(Some(_))) {
+                continue;
+            }
+            BotAdapter::process_event(Arc::clone(&adapter), message.to_string()).await;
+            replayed += 1;
+        }
+    }
+
+    state.flush().await;
+
+    if replayed > 0 {
+        info!("{LOG_PREFIX} replayed {replayed} message(s) missed while disconnected");
+    }
+    Ok(replayed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::adapter::BotAdapterConfig;
+    use crate::event::EventHandler;
+    use crate::models::event_model::Sender;
+
+    fn history_message(message_id: i64, user_id: i64, text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "message_id": message_id,
+            "message_type": "group",
+            "group_id": 3001,
+            "user_id": user_id,
+            "sender": { "user_id": user_id, "nickname": "member", "card": "" },
+            "message": [{ "type": "text", "data": { "text": text } }]
+        })
+    }
+
+    fn group_event(message_id: i64, time: i64) -> MessageEvent {
+        MessageEvent {
+            message_id,
+            message_type: MessageType::Group,
+            sender: Sender {
+                user_id: 2001,
+                nickname: "member".to_string(),
+                card: String::new(),
+                role: None,
+            },
+            message_list: vec![],
+            group_id: Some(3001),
+            group_name: None,
+            is_group_message: true,
+            time: Some(time),
+        }
+    }
+
+    #[tokio::test]
+    async fn cursor_follows_send_time_not_message_id() {
+        let store = Arc::new(MemoryCatchUpCursorStore::default());
+        let state = CatchUpState::new(store.clone(), 20);
+
+        state.record(&group_event(900, 1_000)).await;
+        state.record(&group_event(-5, 1_001)).await;
+        state.record(&group_event(700, 999)).await;
+        state.flush().await;
+
+        assert_eq!(store.load().await.unwrap().get("group:3001"), Some(&-5));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn missed_messages_are_fetched_and_processed_once() {
+        let store = Arc::new(MemoryCatchUpCursorStore::default());
+        store.save(&HashMap::from([("group:3001".to_string(), 100)])).await.unwrap();
+        let adapter = BotAdapter::new(
+            BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000")
+                .with_catch_up(Some(Arc::new(CatchUpState::new(store.clone(), 20)))),
+        )
+        .await
+        .into_shared();
+
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        {
+            let processed = Arc::clone(&processed);
+            let handler: EventHandler = Arc::new(move |event| {
+                processed.lock().unwrap().push(event.message_id);
+                Box::pin(async { Ok(()) })
+            });
+            adapter.lock().await.register_event_handler(handler);
+        }
+
+        // Stand-in for the bot server: answer every history request with the
+        // cursor message, two messages sent during the gap and the bot's own reply.
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel::<String>();
+        let pending_actions = {
+            let mut guard = adapter.lock().await;
+            guard.action_tx = Some(action_tx);
+            guard.pending_actions.clone()
+        };
+        let requested_actions = Arc::new(Mutex::new(Vec::new()));
+        {
+            let requested_actions = Arc::clone(&requested_actions);
+            tokio::spawn(async move {
+                while let Some(payload) = action_rx.recv().await {
+                    let payload: serde_json::Value = serde_json::from_str(&payload).unwrap();
+                    requested_actions
+                        .lock()
+                        .unwrap()
+                        .push(payload["action"].as_str().unwrap_or_default().to_string());
+                    let echo = payload["echo"].as_str().unwrap_or_default().to_string();
+                    let data = if payload["action"] == "get_version_info" {
+                        serde_json::json!({ "app_name": "NapCat.Onebot" })
+                    } else {
+                        serde_json::json!({ "messages": [
+                            history_message(100, 2001, "断线前"),
+                            history_message(101, 2001, "断线期间 1"),
+                            history_message(102, 2002, "断线期间 2"),
+                            history_message(103, 10000, "机器人的回复"),
+                        ] })
+                    };
+                    let response = serde_json::json!({ "status": "ok", "echo": echo, "data": data });
+                    if let Some(tx) = pending_actions.lock().await.remove(&echo) {
+                        let _ = tx.send(response);
+                    }
+                }
+            });
+        }
+
+        assert_eq!(catch_up_missed_messages(adapter.clone()).await.unwrap(), 2);
+        assert_eq!(catch_up_missed_messages(adapter.clone()).await.unwrap(), 0);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let mut processed = processed.lock().unwrap().clone();
+        processed.sort();
+        assert_eq!(processed, vec![101, 102]);
+        assert_eq!(
+            *requested_actions.lock().unwrap(),
+            ["get_version_info", "get_group_msg_history"].repeat(2)
+        );
+        assert_eq!(store.load().await.unwrap().get("group:3001"), Some(&102));
+    }
+}
@@ -0,0 +1,86 @@
+use zihuan_core::error::Result;
+use zihuan_core::ims_bot_adapter::models::message::render_messages_readable;
+
+use crate::adapter::{AgentBox, AgentOutput, BotAdapter, BrainAgentTrait};
+use crate::models::{MessageEvent, MessageType};
+
+/// Diagnostic brain that sends every received message straight back.
+///
+/// Needs no LLM, so it verifies the connect → receive → persist → reply path on
+/// a fresh deployment. Group messages are only echoed when they mention the bot.
+#[derive(Debug, Clone, Default)]
+pub struct EchoBrainAgent {
+    /// Append the parsed message segments as JSON to the echoed text.
+    pub include_structure: bool,
+}
+
+impl EchoBrainAgent {
+    pub fn new(include_structure: bool) -> Self {
+        Self { include_structure }
+    }
+
+    pub fn build_reply(&self, event: &MessageEvent) -> Option<String> {
+        let content = render_messages_readable(&event.message_list);
+        if content.trim().is_empty() && !self.include_structure {
+            return None;
+        }
+        let mut reply = format!("[echo] {}", content.trim());
+        if self.include_structure {
+            let structure = serde_json::to_string(&event.message_list).unwrap_or_else(|err| format!("<{err}>"));
+            reply.push('\n');
+            reply.push_str(&structure);
+        }
+        Some(reply)
+    }
+}
+
+impl BrainAgentTrait for EchoBrainAgent {
+    fn on_event(&self, ims_bot_adapter: &mut BotAdapter, event: &MessageEvent) -> Result<Option<AgentOutput>> {
+        if event.message_type == MessageType::Group {
+            if !event.mentions_bot(&ims_bot_adapter.get_bot_id()) {
+                return Ok(None);
+            }
+        }
+        Ok(self.build_reply(event).map(AgentOutput::Text))
+    }
+
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn clone_box(&self) -> AgentBox {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::BotAdapterConfig;
+    use crate::models::event_model::Sender;
+    use crate::models::message::{Message, PlainTextMessage};
+
+    #[tokio::test]
+    async fn incoming_private_message_is_echoed() {
+        let mut adapter = BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000")).await;
+        let event = MessageEvent {
+            message_id: 42,
+            message_type: MessageType::Private,
+            sender: Sender {
+                user_id: 2001,
+                nickname: "sender".to_string(),
+                card: String::new(),
+                role: None,
+            },
+            message_list: vec![Message::PlainText(PlainTextMessage { text: "你好".to_string() })],
+            group_id: None,
+            group_name: None,
+            is_group_message: false,
+            time: None,
+        };
+
+        let output = EchoBrainAgent::default().on_event(&mut adapter, &event).unwrap();
+
+        assert_eq!(output, Some(AgentOutput::Text("[echo] 你好".to_string())));
+    }
+}
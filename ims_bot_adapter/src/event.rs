@@ -1,14 +1,19 @@
-use log::{error, info};
+use arc_swap::ArcSwap;
+use log::{debug, error, info};
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use uuid::Uuid;
 use zihuan_core::error::Result;
 
-use super::models::{MessageEvent, MessageType};
+use super::models::{MessageEvent, MessageType, NoticeEvent, RequestEvent};
 use crate::adapter::SharedBotAdapter;
 
-/// Process messages (both private and group)
-pub async fn process_message(ims_bot_adapter: SharedBotAdapter, event: MessageEvent) {
+/// Process messages (both private and group). `handlers` is the adapter's
+/// [`EventHandlerRegistry`]; they run before the adapter lock is taken, so a
+/// brain agent holding the lock does not hold them up.
+pub async fn process_message(ims_bot_adapter: SharedBotAdapter, handlers: EventHandlerRegistry, event: MessageEvent) {
     let messages: Vec<String> = event.message_list.iter().map(|m| m.to_string()).collect();
 
     // Log based on message type
@@ -32,33 +37,253 @@ pub async fn process_message(ims_bot_adapter: SharedBotAdapter, event: MessageEv
         }
     }
 
-    let handlers = {
-        let ims_bot_adapter_guard = ims_bot_adapter.lock().await;
-        ims_bot_adapter_guard.get_event_handlers()
-    };
-
-    for handler in handlers {
+    for handler in handlers.snapshot().values() {
         if let Err(err) = (handler)(&event).await {
             error!("[Bot Adapter] Error processing event handler: {}", err);
         }
     }
 
-    let brain_agent = {
+    let (brain_agent, shutdown) = {
         let ims_bot_adapter_guard = ims_bot_adapter.lock().await;
-        ims_bot_adapter_guard.get_brain_agent().cloned()
+        let brain_agent = if ims_bot_adapter_guard.should_dispatch_to_brain(&event) {
+            ims_bot_adapter_guard.get_brain_agent().cloned()
+        } else {
+            debug!(
+                "[Bot Adapter] message {} does not address the bot, skipping brain agent",
+                event.message_id
+            );
+            None
+        };
+        (brain_agent, ims_bot_adapter_guard.shutdown_state())
     };
 
     if let Some(brain) = brain_agent {
         let ims_bot_adapter_clone = ims_bot_adapter.clone();
+        let in_flight = shutdown.track_event_task();
         tokio::spawn(async move {
+            let _in_flight = in_flight;
             let mut ims_bot_adapter_guard = ims_bot_adapter_clone.lock().await;
-            if let Err(e) = brain.on_event(&mut ims_bot_adapter_guard, &event) {
-                error!("[Brain Agent] Error processing event: {}", e);
+            match brain.on_event(&mut ims_bot_adapter_guard, &event) {
+                Ok(Some(output)) => {
+                    let reply_to = (event.message_type == MessageType::Group).then_some(event.message_id);
+                    if let Err(e) = ims_bot_adapter_guard.send_agent_output(&event, output, reply_to) {
+                        error!("[Brain Agent] Error sending output: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => error!("[Brain Agent] Error processing event: {}", e),
             }
         });
     }
 }
 
+/// Process notices (group joins, new friends, ...). Message handlers are not
+/// called; only the brain agent sees them.
+pub async fn process_notice(ims_bot_adapter: SharedBotAdapter, notice: NoticeEvent) {
+    info!(
+        "[Notice] [type: {}/{}] [Group: {}] [User: {}] [Operator: {}]",
+        notice.notice_type,
+        notice.sub_type.as_deref().unwrap_or_default(),
+        notice.group_id.unwrap_or_default(),
+        notice.user_id.unwrap_or_default(),
+        notice.operator_id.unwrap_or_default()
+    );
+
+    let brain_agent = ims_bot_adapter.lock().await.get_brain_agent().cloned();
+    if let Some(brain) = brain_agent {
+        let mut ims_bot_adapter_guard = ims_bot_adapter.lock().await;
+        if let Err(e) = brain.on_notice(&mut ims_bot_adapter_guard, &notice) {
+            error!("[Brain Agent] Error processing notice: {}", e);
+        }
+    }
+}
+
+/// Process friend requests and group join requests / invites. Like notices,
+/// they only reach the brain agent.
+pub async fn process_request(ims_bot_adapter: SharedBotAdapter, request: RequestEvent) {
+    info!(
+        "[Request] [type: {}/{}] [Group: {}] [User: {}] Comment: {:?}",
+        request.request_type,
+        request.sub_type.as_deref().unwrap_or_default(),
+        request.group_id.unwrap_or_default(),
+        request.user_id,
+        request.comment
+    );
+
+    let brain_agent = ims_bot_adapter.lock().await.get_brain_agent().cloned();
+    if let Some(brain) = brain_agent {
+        let mut ims_bot_adapter_guard = ims_bot_adapter.lock().await;
+        if let Err(e) = brain.on_request(&mut ims_bot_adapter_guard, &request) {
+            error!("[Brain Agent] Error processing request: {}", e);
+        }
+    }
+}
+
 /// Event handler type alias
 pub type EventHandler =
     Arc<dyn for<'a> Fn(&'a MessageEvent) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> + Send + Sync>;
+
+/// Message handlers keyed by id. Clones share one map, which lives outside
+/// the adapter mutex; the map is swapped wholesale on (un)registration, so
+/// dispatch reads a snapshot without blocking registration.
+#[derive(Clone, Default)]
+pub struct EventHandlerRegistry {
+    handlers: Arc<ArcSwap<HashMap<String, EventHandler>>>,
+}
+
+impl EventHandlerRegistry {
+    /// Register `handler` under a fresh id and return the id.
+    pub fn register(&self, handler: EventHandler) -> String {
+        let handler_id = Uuid::new_v4().to_string();
+        self.register_with_id(handler_id.clone(), handler);
+        handler_id
+    }
+
+    pub fn register_with_id(&self, handler_id: impl Into<String>, handler: EventHandler) {
+        let handler_id = handler_id.into();
+        self.handlers.rcu(|handlers| {
+            let mut handlers = HashMap::clone(handlers);
+            handlers.insert(handler_id.clone(), handler.clone());
+            handlers
+        });
+    }
+
+    pub fn unregister(&self, handler_id: &str) -> bool {
+        let previous = self.handlers.rcu(|handlers| {
+            let mut handlers = HashMap::clone(handlers);
+            handlers.remove(handler_id);
+            handlers
+        });
+        previous.contains_key(handler_id)
+    }
+
+    pub fn snapshot(&self) -> Arc<HashMap<String, EventHandler>> {
+        self.handlers.load_full()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::{BotAdapter, BotAdapterConfig};
+    use crate::models::event_model::Sender;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn handlers_run_while_another_task_holds_the_adapter_lock() {
+        let adapter = BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000"))
+            .await
+            .into_shared();
+        let handlers = adapter.lock().await.event_handlers();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let (handled_tx, mut handled_rx) = tokio::sync::mpsc::unbounded_channel();
+        for _ in 0..3 {
+            let calls = Arc::clone(&calls);
+            let handled_tx = handled_tx.clone();
+            handlers.register(Arc::new(move |_event| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                handled_tx.send(()).unwrap();
+                Box::pin(async { Ok(()) })
+            }));
+        }
+
+        let event = MessageEvent {
+            message_id: 1,
+            message_type: MessageType::Private,
+            sender: Sender {
+                user_id: 2001,
+                nickname: "friend".to_string(),
+                card: String::new(),
+                role: None,
+            },
+            message_list: vec![],
+            group_id: None,
+            group_name: None,
+            is_group_message: false,
+            time: None,
+        };
+        // Stand-in for a brain agent that keeps the adapter busy.
+        let busy = adapter.lock().await;
+        let dispatch = tokio::spawn(process_message(adapter.clone(), handlers, event));
+        for _ in 0..3 {
+            tokio::time::timeout(std::time::Duration::from_secs(1), handled_rx.recv())
+                .await
+                .expect("handlers should not wait for the adapter lock");
+        }
+        drop(busy);
+        dispatch.await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingBrain {
+        seen: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl crate::adapter::BrainAgentTrait for RecordingBrain {
+        fn on_event(
+            &self,
+            _ims_bot_adapter: &mut BotAdapter,
+            event: &MessageEvent,
+        ) -> Result<Option<crate::adapter::AgentOutput>> {
+            self.seen.lock().unwrap().push(format!("message {}", event.message_id));
+            Ok(None)
+        }
+
+        fn on_notice(&self, _ims_bot_adapter: &mut BotAdapter, notice: &NoticeEvent) -> Result<()> {
+            self.seen.lock().unwrap().push(format!("notice {}", notice.notice_type));
+            Ok(())
+        }
+
+        fn on_request(&self, _ims_bot_adapter: &mut BotAdapter, request: &RequestEvent) -> Result<()> {
+            self.seen.lock().unwrap().push(format!("request {}", request.flag));
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        fn clone_box(&self) -> crate::adapter::AgentBox {
+            Box::new(self.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn notices_and_requests_reach_the_brain_agent() {
+        let brain = RecordingBrain::default();
+        let adapter = BotAdapter::new(
+            BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000").with_brain_agent(Some(Box::new(brain.clone()))),
+        )
+        .await
+        .into_shared();
+
+        process_notice(
+            adapter.clone(),
+            serde_json::from_value(serde_json::json!({
+                "notice_type": "group_increase",
+                "group_id": 30003,
+                "user_id": 20002,
+            }))
+            .unwrap(),
+        )
+        .await;
+        process_request(
+            adapter.clone(),
+            serde_json::from_value(serde_json::json!({
+                "request_type": "friend",
+                "user_id": 20002,
+                "comment": "hi",
+                "flag": "friend-flag",
+            }))
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(
+            *brain.seen.lock().unwrap(),
+            vec!["notice group_increase".to_string(), "request friend-flag".to_string()]
+        );
+    }
+}
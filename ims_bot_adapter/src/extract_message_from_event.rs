@@ -374,11 +374,11 @@ impl Node for ExtractMessageFromEventNode {
         let (bot_id, adapter_object_storage) = if tokio::runtime::Handle::try_current().is_ok() {
             block_in_place(|| {
                 let adapter = ims_bot_adapter_ref.blocking_lock();
-                (adapter.get_bot_id().to_string(), adapter.get_object_storage())
+                (adapter.get_bot_id(), adapter.get_object_storage())
             })
         } else {
             let adapter = ims_bot_adapter_ref.blocking_lock();
-            (adapter.get_bot_id().to_string(), adapter.get_object_storage())
+            (adapter.get_bot_id(), adapter.get_object_storage())
         };
         let object_storage = explicit_s3_ref.or(adapter_object_storage);
 
@@ -78,6 +78,7 @@ mod tests {
             group_id,
             group_name: None,
             is_group_message: message_type == MessageType::Group,
+            time: None,
         }
     }
 
@@ -1,5 +1,7 @@
 pub mod active_adapter_manager;
 pub mod adapter;
+pub mod catch_up;
+pub mod echo_brain;
 pub mod event;
 pub mod extract_group_id_from_event;
 pub mod extract_message_from_event;
@@ -9,8 +11,10 @@ pub mod extract_sender_from_event;
 pub mod extract_sender_id_from_event;
 pub mod ims_bot_adapter_provider;
 pub mod login_info;
+pub mod message_dedup;
 pub mod message_event_type_filter;
 pub mod message_helpers;
+pub mod message_record_from_event;
 pub mod message_sender;
 pub mod models;
 pub mod multimodal_image_url;
@@ -19,9 +23,14 @@ pub mod send_friend_message_batches;
 pub mod send_group_message_batches;
 pub mod send_message;
 pub mod send_qq_message_batches;
+pub mod sent_message_ids;
+pub mod server_info;
+pub mod shutdown;
 pub mod system_config;
 pub mod tools;
 pub mod utils;
+pub mod watchdog;
+pub mod webhook;
 pub mod ws_action;
 
 use zihuan_core::error::Result;
@@ -30,8 +39,8 @@ use zihuan_graph_engine::register_node;
 pub use active_adapter_manager::{
     close_runtime_bot_adapter_instance, ensure_active_bot_adapter, get_active_bot_adapter_handle,
     has_active_bot_adapter, initialize_enabled_bot_adapters, list_active_bot_adapter_connection_ids,
-    list_runtime_bot_adapter_instances, register_active_bot_adapter, stop_active_bot_adapter,
-    sync_enabled_bot_adapters,
+    list_runtime_bot_adapter_instances, register_active_bot_adapter, shutdown_active_bot_adapters,
+    stop_active_bot_adapter, sync_enabled_bot_adapters,
 };
 pub use extract_optional_group_id_from_event::ExtractOptionalGroupIdFromEventNode;
 pub use extract_qq_message_list_from_event::ExtractQQMessageListFromEventNode;
@@ -40,6 +49,7 @@ pub use extract_sender_id_from_event::ExtractSenderIdFromEventNode;
 pub use ims_bot_adapter_provider::ImsBotAdapterProviderNode;
 pub use login_info::{fetch_login_info, fetch_login_info_via_adapter_connection, qq_avatar_url};
 pub use message_event_type_filter::MessageEventTypeFilterNode;
+pub use message_record_from_event::MessageRecordFromEventNode;
 pub use message_sender::MessageSenderNode;
 pub use profile::{
     profile_from_login_info, resolve_active_or_fallback_bot_profile,
@@ -167,6 +177,13 @@ pub fn init_node_registry() -> Result<()> {
         "从消息事件中提取群号；私聊时返回空字符串",
         ExtractOptionalGroupIdFromEventNode
     );
+    register_node!(
+        "message_record_from_event",
+        "事件转换 MessageRecord",
+        "Bot适配器",
+        "将消息事件转换为与消息持久化一致的 MessageRecord（JSON），用于下游存储或统计",
+        MessageRecordFromEventNode
+    );
 
     Ok(())
 }
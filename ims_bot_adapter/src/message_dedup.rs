@@ -0,0 +1,85 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default window in which a redelivered `message_id` is dropped.
+pub const DEFAULT_MESSAGE_DEDUP_TTL_SECS: u64 = 300;
+/// Upper bound on remembered ids, so a flood of messages cannot grow the set
+/// without limit before the TTL expires them.
+const MAX_REMEMBERED_IDS: usize = 4096;
+
+/// Remembers recently processed message ids so an event the bot server
+/// redelivers, e.g. after a reconnect, is not stored or answered twice.
+pub struct MessageDeduplicator {
+    ttl: Duration,
+    seen: Mutex<SeenIds>,
+}
+
+#[derive(Default)]
+struct SeenIds {
+    ids: HashSet<i64>,
+    order: VecDeque<(i64, Instant)>,
+}
+
+impl MessageDeduplicator {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            seen: Mutex::new(SeenIds::default()),
+        }
+    }
+
+    /// `true` the first time `message_id` is seen within the TTL window.
+    pub fn is_new(&self, message_id: i64) -> bool {
+        self.is_new_at(message_id, Instant::now())
+    }
+
+    fn is_new_at(&self, message_id: i64, now: Instant) -> bool {
+        let mut seen = self.seen.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let SeenIds { ids, order } = &mut *seen;
+
+        while let Some(&(oldest, seen_at)) = order.front() {
+            if order.len() < MAX_REMEMBERED_IDS && now.saturating_duration_since(seen_at) < self.ttl {
+                break;
+            }
+            order.pop_front();
+            ids.remove(&oldest);
+        }
+
+        if !ids.insert(message_id) {
+            return false;
+        }
+        order.push_back((message_id, now));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redelivery_is_dropped_until_the_ttl_expires() {
+        let dedup = MessageDeduplicator::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(dedup.is_new_at(1, start));
+        assert!(dedup.is_new_at(2, start));
+        assert!(!dedup.is_new_at(1, start + Duration::from_secs(30)));
+        assert!(dedup.is_new_at(1, start + Duration::from_secs(61)));
+        assert!(!dedup.is_new_at(1, start + Duration::from_secs(62)));
+    }
+
+    #[test]
+    fn oldest_ids_are_forgotten_past_the_capacity() {
+        let dedup = MessageDeduplicator::new(Duration::from_secs(3600));
+        let now = Instant::now();
+
+        for message_id in 0..=MAX_REMEMBERED_IDS as i64 {
+            assert!(dedup.is_new_at(message_id, now));
+        }
+
+        assert!(dedup.is_new_at(0, now));
+        assert!(!dedup.is_new_at(MAX_REMEMBERED_IDS as i64, now));
+    }
+}
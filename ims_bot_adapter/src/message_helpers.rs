@@ -5,13 +5,15 @@
 
 use crate::adapter::SharedBotAdapter;
 use crate::models::event_model::{MessageEvent, MessageType, Sender};
-use crate::models::message::{render_messages_readable, AtTargetMessage, Message, PlainTextMessage};
+use crate::models::message::{render_messages_readable, AtTargetMessage, Message, PlainTextMessage, ReplyMessage};
 use crate::send_qq_message_batches::send_qq_message_batches;
-use crate::ws_action::{response_message_id, response_success, ws_send_action};
+use crate::ws_action::{qq_message_list_to_json, response_message_id, response_success, ws_send_action};
 use log::{info, warn};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tokio::task::block_in_place;
 use zihuan_core::data_refs::RelationalDbConnection;
+use zihuan_core::error::Result;
 use zihuan_graph_engine::data_value::RedisConfig;
 use zihuan_graph_engine::message_persistence::persist_message_event;
 use zihuan_nlp::{PunctuationSegmenter, TextSegmenter};
@@ -27,6 +29,8 @@ pub struct OutboundMessagePersistence {
     pub sender_name: Option<String>,
 }
 
+/// Synthetic event for a message the bot sent, plus the adapter's outbound sender
+/// to publish it on.
 fn build_outbound_event(
     adapter: &SharedBotAdapter,
     message_id: i64,
@@ -35,25 +39,13 @@ fn build_outbound_event(
     group_name: Option<&str>,
     messages: &[Message],
     sender_name_override: Option<&str>,
-) -> Option<MessageEvent> {
-    let (bot_id, sender_name) = if let Ok(handle) = tokio::runtime::Handle::try_current() {
-        block_in_place(|| {
-            let guard = handle.block_on(adapter.lock());
-            let bot_id = guard.get_bot_id().to_string();
-            let profile_name = guard
-                .get_bot_profile()
-                .map(|profile| profile.nickname.clone())
-                .unwrap_or_default();
-            (bot_id, profile_name)
-        })
+) -> Option<(MessageEvent, broadcast::Sender<MessageEvent>)> {
+    let bot_id = adapter.bot_id();
+    let sender_name = adapter.profile_snapshot().map(|profile| profile.nickname).unwrap_or_default();
+    let outbound_tx = if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        block_in_place(|| handle.block_on(adapter.lock()).outbound_sender())
     } else {
-        let guard = adapter.blocking_lock();
-        let bot_id = guard.get_bot_id().to_string();
-        let profile_name = guard
-            .get_bot_profile()
-            .map(|profile| profile.nickname.clone())
-            .unwrap_or_default();
-        (bot_id, profile_name)
+        adapter.blocking_lock().outbound_sender()
     };
 
     let sender_user_id = match bot_id.parse::<i64>() {
@@ -85,7 +77,7 @@ fn build_outbound_event(
         None
     };
 
-    Some(MessageEvent {
+    let event = MessageEvent {
         message_id,
         message_type,
         sender: Sender {
@@ -98,9 +90,13 @@ fn build_outbound_event(
         group_id,
         group_name: group_name.map(ToOwned::to_owned),
         is_group_message: message_type == MessageType::Group,
-    })
+        time: None,
+    };
+    Some((event, outbound_tx))
 }
 
+/// Persists a sent message as a record with the bot as sender and publishes it to
+/// the adapter's outbound subscribers.
 fn persist_outbound_messages(
     adapter: &SharedBotAdapter,
     message_type: MessageType,
@@ -113,7 +109,7 @@ fn persist_outbound_messages(
         return;
     }
 
-    let Some(event) = build_outbound_event(
+    let Some((event, outbound_tx)) = build_outbound_event(
         adapter,
         message_id,
         message_type,
@@ -133,6 +129,8 @@ fn persist_outbound_messages(
             error
         );
     }
+    // No subscribers is the normal case, not an error.
+    let _ = outbound_tx.send(event);
 }
 
 fn split_text_for_qq(content: &str) -> Vec<String> {
@@ -164,18 +162,10 @@ fn progress_notification_batches(content: &str, is_group: bool, mention_target_i
     batches
 }
 
-/// Return the bot's self QQ ID from a shared adapter handle.
-///
-/// Uses `block_in_place` so it is safe to call from inside a Tokio worker thread.
+/// Return the bot's self QQ ID from a shared adapter handle, without taking
+/// the adapter lock.
 pub fn get_bot_id(adapter: &SharedBotAdapter) -> String {
-    if let Ok(handle) = tokio::runtime::Handle::try_current() {
-        block_in_place(|| {
-            let guard = handle.block_on(adapter.lock());
-            guard.get_bot_id().to_string()
-        })
-    } else {
-        adapter.blocking_lock().get_bot_id().to_string()
-    }
+    adapter.bot_id()
 }
 
 /// Send a single plain-text message to a QQ friend.
@@ -195,27 +185,8 @@ pub fn send_friend_text_with_persistence(
     text: &str,
     persistence: &OutboundMessagePersistence,
 ) {
-    let params = serde_json::json!({
-        "user_id": target_id,
-        "message": [{ "type": "text", "data": { "text": text } }]
-    });
-    match ws_send_action(adapter, "send_private_msg", params) {
-        Ok(response) => {
-            if response_success(&response) {
-                let messages = vec![Message::PlainText(PlainTextMessage { text: text.to_string() })];
-                persist_outbound_messages(
-                    adapter,
-                    MessageType::Private,
-                    target_id,
-                    response_message_id(&response).unwrap_or(-1),
-                    &messages,
-                    persistence,
-                );
-            }
-        }
-        Err(e) => {
-            warn!("{LOG_PREFIX} Failed to send friend text to {target_id}: {e}");
-        }
+    if let Err(e) = send_text_message(adapter, MessageType::Private, target_id, text, None, persistence) {
+        warn!("{LOG_PREFIX} Failed to send friend text to {target_id}: {e}");
     }
 }
 
@@ -236,30 +207,47 @@ pub fn send_group_text_with_persistence(
     text: &str,
     persistence: &OutboundMessagePersistence,
 ) {
-    let params = serde_json::json!({
-        "group_id": target_id,
-        "message": [{ "type": "text", "data": { "text": text } }]
-    });
-    match ws_send_action(adapter, "send_group_msg", params) {
-        Ok(response) => {
-            if response_success(&response) {
-                let messages = vec![Message::PlainText(PlainTextMessage { text: text.to_string() })];
-                persist_outbound_messages(
-                    adapter,
-                    MessageType::Group,
-                    target_id,
-                    response_message_id(&response).unwrap_or(-1),
-                    &messages,
-                    persistence,
-                );
-            }
-        }
-        Err(e) => {
-            warn!("{LOG_PREFIX} Failed to send group text to {target_id}: {e}");
-        }
+    if let Err(e) = send_text_message(adapter, MessageType::Group, target_id, text, None, persistence) {
+        warn!("{LOG_PREFIX} Failed to send group text to {target_id}: {e}");
     }
 }
 
+/// Send `text` to a QQ group or friend, quoting `reply_to` when set. Once the
+/// server accepts it, the message is persisted and broadcast like any other
+/// outbound message. Returns the server's response for the caller to check.
+pub fn send_text_message(
+    adapter: &SharedBotAdapter,
+    message_type: MessageType,
+    target_id: &str,
+    text: &str,
+    reply_to: Option<i64>,
+    persistence: &OutboundMessagePersistence,
+) -> Result<serde_json::Value> {
+    let mut messages = Vec::with_capacity(2);
+    if let Some(id) = reply_to {
+        messages.push(Message::Reply(ReplyMessage { id, message_source: None }));
+    }
+    messages.push(Message::PlainText(PlainTextMessage { text: text.to_string() }));
+
+    let (action, target_key) = match message_type {
+        MessageType::Group => ("send_group_msg", "group_id"),
+        MessageType::Private => ("send_private_msg", "user_id"),
+    };
+    let params = serde_json::json!({ target_key: target_id, "message": qq_message_list_to_json(&messages) });
+    let response = ws_send_action(adapter, action, params)?;
+    if response_success(&response) {
+        persist_outbound_messages(
+            adapter,
+            message_type,
+            target_id,
+            response_message_id(&response).unwrap_or(-1),
+            &messages,
+            persistence,
+        );
+    }
+    Ok(response)
+}
+
 /// Send multiple `Vec<Message>` batches to a QQ friend.
 ///
 /// Mirrors `SendFriendMessageBatchesNode` behaviour without requiring node wiring.
@@ -420,3 +408,120 @@ pub fn render_current_message_body(messages: &[Message]) -> Option<String> {
         Some(trimmed.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::{BotAdapter, BotAdapterConfig};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tokio::sync::mpsc;
+    use zihuan_core::data_refs::SqliteConfig;
+    use zihuan_core::database::ddl::SQLITE_TABLES;
+
+    /// An adapter whose stand-in bot server accepts every send, assigns message
+    /// id 4242 and forwards the payloads it received.
+    async fn adapter_with_fake_server() -> (SharedBotAdapter, mpsc::UnboundedReceiver<serde_json::Value>) {
+        let adapter = BotAdapter::new(BotAdapterConfig::new("ws://127.0.0.1:3001", "", "10000"))
+            .await
+            .into_shared();
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel::<String>();
+        let (payload_tx, payload_rx) = mpsc::unbounded_channel();
+        let pending_actions = {
+            let mut guard = adapter.lock().await;
+            guard.action_tx = Some(action_tx);
+            guard.pending_actions.clone()
+        };
+        tokio::spawn(async move {
+            while let Some(payload) = action_rx.recv().await {
+                let payload: serde_json::Value = serde_json::from_str(&payload).unwrap();
+                let echo = payload["echo"].as_str().unwrap_or_default().to_string();
+                let response = serde_json::json!({ "status": "ok", "echo": echo, "data": { "message_id": 4242 } });
+                if let Some(tx) = pending_actions.lock().await.remove(&echo) {
+                    let _ = tx.send(response);
+                }
+                let _ = payload_tx.send(payload);
+            }
+        });
+        (adapter, payload_rx)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn quoted_text_message_returns_the_response_and_is_broadcast() {
+        let (adapter, mut payloads) = adapter_with_fake_server().await;
+        let mut outbound_rx = adapter.lock().await.subscribe_outbound();
+
+        let response = {
+            let adapter = adapter.clone();
+            tokio::task::spawn_blocking(move || {
+                send_text_message(
+                    &adapter,
+                    MessageType::Private,
+                    "2001",
+                    "好的",
+                    Some(987654),
+                    &OutboundMessagePersistence::default(),
+                )
+            })
+            .await
+            .unwrap()
+            .unwrap()
+        };
+
+        assert_eq!(response_message_id(&response), Some(4242));
+        let payload = payloads.recv().await.unwrap();
+        assert_eq!(payload["action"], "send_private_msg");
+        assert_eq!(payload["params"]["user_id"], "2001");
+        assert_eq!(payload["params"]["message"][0]["type"], "reply");
+        assert_eq!(payload["params"]["message"][1]["data"]["text"], "好的");
+        let event = outbound_rx.try_recv().expect("message should be broadcast");
+        assert_eq!(event.message_id, 4242);
+        assert!(matches!(&event.message_list[0], Message::Reply(reply) if reply.id == 987654));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn sent_reply_is_persisted_and_broadcast() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        for (ddl, _) in SQLITE_TABLES {
+            sqlx::query(ddl).execute(&pool).await.unwrap();
+        }
+        let persistence = OutboundMessagePersistence {
+            rdb_pool: Some(RelationalDbConnection::Sqlite(Arc::new(SqliteConfig {
+                path: ":memory:".to_string(),
+                pool: Some(pool.clone()),
+                runtime_handle: None,
+            }))),
+            ..Default::default()
+        };
+
+        let (adapter, _payloads) = adapter_with_fake_server().await;
+        let mut outbound_rx = adapter.lock().await.subscribe_outbound();
+
+        {
+            let adapter = adapter.clone();
+            tokio::task::spawn_blocking(move || {
+                send_group_text_with_persistence(&adapter, "3001", "收到", &persistence)
+            })
+            .await
+            .unwrap();
+        }
+
+        let event = outbound_rx.try_recv().expect("reply should be broadcast");
+        assert_eq!(event.message_id, 4242);
+        assert_eq!(event.sender.user_id, 10000);
+        assert_eq!(event.group_id, Some(3001));
+
+        let (sender_id, group_id, content): (String, Option<String>, String) =
+            sqlx::query_as("SELECT sender_id, group_id, content FROM message_record WHERE message_id = ?")
+                .bind("4242")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(sender_id, "10000");
+        assert_eq!(group_id.as_deref(), Some("3001"));
+        assert_eq!(content, "收到");
+    }
+}
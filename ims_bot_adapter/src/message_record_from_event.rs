@@ -0,0 +1,64 @@
+use storage_handler::MessageRecord;
+use zihuan_core::error::Result;
+use zihuan_core::utils::clock::SystemClock;
+use zihuan_graph_engine::{node_input, node_output, DataType, DataValue, Node, Port};
+
+pub struct MessageRecordFromEventNode {
+    id: String,
+    name: String,
+}
+
+impl MessageRecordFromEventNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+impl Node for MessageRecordFromEventNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("将消息事件转换为与消息持久化一致的 MessageRecord（JSON），用于下游存储或统计")
+    }
+
+    node_input![
+        port! { name = "message_event", ty = crate::models::event_model::MessageEvent, desc = "输入的消息事件" },
+        port! { name = "bot_id", ty = String, desc = "机器人 QQ 号，用于识别机器人自身发送的消息", optional },
+    ];
+
+    node_output![
+        port! { name = "record", ty = Json, desc = "MessageRecord 的 JSON 表示，字段与 message_record 表一致" },
+        port! { name = "content", ty = String, desc = "消息的文本内容" },
+        port! { name = "at_target_list", ty = String, desc = "逗号分隔的 @ 目标列表；没有 @ 时为空" },
+    ];
+
+    fn execute(&mut self, inputs: zihuan_graph_engine::NodeInputFlow) -> Result<zihuan_graph_engine::NodeOutputFlow> {
+        let event = match inputs.get("message_event") {
+            Some(DataValue::MessageEvent(event)) => event,
+            _ => return Err("message_event input is required".into()),
+        };
+        let bot_id = match inputs.get("bot_id") {
+            Some(DataValue::String(bot_id)) => bot_id.trim(),
+            _ => "",
+        };
+
+        let record = MessageRecord::from_event(event, &SystemClock, bot_id)?;
+        let content = record.content.clone();
+        let at_target_list = record.at_target_list.clone().unwrap_or_default();
+
+        zihuan_graph_engine::return_with_node_output![self;
+            "record" => DataValue::Json(serde_json::to_value(&record)?),
+            "content" => DataValue::String(content),
+            "at_target_list" => DataValue::String(at_target_list),
+        ]
+    }
+}
@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use crate::active_adapter_manager::{get_active_bot_adapter_handle, list_active_bot_adapter_connection_ids};
+use crate::adapter::{shared_from_handle, SharedBotAdapter};
+use crate::message_helpers::{send_text_message, OutboundMessagePersistence};
+use crate::models::MessageType;
+use crate::ws_action::response_success;
+use log::warn;
 use zihuan_core::error::Result;
 use zihuan_graph_engine::{node_input, node_output, DataType, DataValue, Node, Port};
 
@@ -30,9 +35,11 @@ impl Node for MessageSenderNode {
     }
 
     node_input![
+        port! { name = "ims_bot_adapter", ty = BotAdapterRef, desc = "Bot adapter reference; defaults to the only active adapter", optional },
         port! { name = "target_id", ty = String, desc = "Target user or group ID" },
         port! { name = "content", ty = String, desc = "Message content to send" },
-        port! { name = "message_type", ty = String, desc = "Type of message to send" },
+        port! { name = "message_type", ty = String, desc = "Message type: \"group\" or \"private\"" },
+        port! { name = "reply_to", ty = Integer, desc = "Optional message ID to quote in the reply", optional },
     ];
 
     node_output![
@@ -43,19 +50,67 @@ impl Node for MessageSenderNode {
     fn execute(&mut self, inputs: zihuan_graph_engine::NodeInputFlow) -> Result<zihuan_graph_engine::NodeOutputFlow> {
         self.validate_inputs(&inputs)?;
 
-        let mut outputs = HashMap::new();
+        let adapter_ref = match inputs.get("ims_bot_adapter") {
+            Some(DataValue::BotAdapterRef(handle)) => shared_from_handle(handle),
+            _ => sole_active_adapter()?,
+        };
+        let target_id = match inputs.get("target_id") {
+            Some(DataValue::String(target_id)) => target_id.clone(),
+            _ => return Err("target_id input is required".into()),
+        };
+        let content = match inputs.get("content") {
+            Some(DataValue::String(content)) => content.clone(),
+            _ => return Err("content input is required".into()),
+        };
+        let message_type = match inputs.get("message_type") {
+            Some(DataValue::String(message_type)) => match message_type.trim() {
+                "group" => MessageType::Group,
+                "private" => MessageType::Private,
+                other => {
+                    return Err(format!("Unsupported message_type '{other}', expected 'group' or 'private'").into())
+                }
+            },
+            _ => return Err("message_type input is required".into()),
+        };
 
-        outputs.insert("success".to_string(), DataValue::Boolean(true));
-        outputs.insert(
-            "response".to_string(),
-            DataValue::Json(serde_json::json!({
-                "status": "sent",
-                "timestamp": "2025-01-28T00:00:00Z"
-            })),
-        );
+        let reply_to = match inputs.get("reply_to") {
+            Some(DataValue::Integer(message_id)) if *message_id > 0 => Some(*message_id),
+            _ => None,
+        };
 
-        let outputs = zihuan_graph_engine::NodeOutputFlow::from(outputs);
-        self.validate_outputs(&outputs)?;
-        Ok(outputs)
+        let response = send_text_message(
+            &adapter_ref,
+            message_type,
+            target_id.trim(),
+            &content,
+            reply_to,
+            &OutboundMessagePersistence::default(),
+        )?;
+
+        let success = response_success(&response);
+        if !success {
+            warn!("[MessageSenderNode] Failed to send {message_type} message to {target_id}: {response}");
+        }
+
+        zihuan_graph_engine::return_with_node_output![self;
+            "success" => DataValue::Boolean(success),
+            "response" => DataValue::Json(response),
+        ]
     }
 }
+
+/// Graphs saved before the adapter port existed leave it unconnected; they keep
+/// working as long as exactly one bot adapter is running.
+fn sole_active_adapter() -> Result<SharedBotAdapter> {
+    let connection_ids = list_active_bot_adapter_connection_ids();
+    let [connection_id] = connection_ids.as_slice() else {
+        return Err(format!(
+            "ims_bot_adapter input is required when {} bot adapters are active",
+            connection_ids.len()
+        )
+        .into());
+    };
+    get_active_bot_adapter_handle(connection_id)
+        .map(|handle| shared_from_handle(&handle))
+        .ok_or_else(|| format!("Bot adapter '{connection_id}' is no longer active").into())
+}
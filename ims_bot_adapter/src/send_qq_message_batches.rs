@@ -120,6 +120,7 @@ pub fn describe_message_segments(messages: &[Message]) -> String {
                     .or(image.original_source())
                     .unwrap_or("unknown")
             ),
+            Message::Face(face) => format!("face:{}", face.id),
             Message::Forward(forward) => format!("forward:{}nodes", forward.content.len()),
         })
         .collect::<Vec<_>>()
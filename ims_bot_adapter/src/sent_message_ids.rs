@@ -0,0 +1,62 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+
+/// Upper bound on remembered ids; the oldest are forgotten first.
+const MAX_REMEMBERED_IDS: usize = 4096;
+
+/// Bounded set of the ids of messages the bot sent itself, so a reply to one
+/// of them can be recognised as addressed to the bot.
+///
+/// Shared behind an `Arc` so action responses can be recorded from the reader
+/// loop without taking the adapter lock.
+#[derive(Default)]
+pub struct SentMessageIds {
+    inner: Mutex<RememberedIds>,
+}
+
+#[derive(Default)]
+struct RememberedIds {
+    ids: HashSet<i64>,
+    order: VecDeque<i64>,
+}
+
+impl SentMessageIds {
+    pub fn record(&self, message_id: i64) {
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let RememberedIds { ids, order } = &mut *inner;
+        if !ids.insert(message_id) {
+            return;
+        }
+        order.push_back(message_id);
+        if order.len() > MAX_REMEMBERED_IDS {
+            if let Some(oldest) = order.pop_front() {
+                ids.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn contains(&self, message_id: i64) -> bool {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .ids
+            .contains(&message_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_ids_are_forgotten_past_the_capacity() {
+        let sent = SentMessageIds::default();
+        for message_id in 0..=MAX_REMEMBERED_IDS as i64 {
+            sent.record(message_id);
+        }
+
+        assert!(!sent.contains(0));
+        assert!(sent.contains(1));
+        assert!(sent.contains(MAX_REMEMBERED_IDS as i64));
+    }
+}
@@ -0,0 +1,39 @@
+use serde_json::Value;
+use zihuan_core::error::{Error, Result};
+
+use crate::adapter::SharedBotAdapter;
+use crate::ws_action::{response_success, ws_send_action_async};
+
+/// The `app_name` the bot server reports through `get_version_info`.
+pub async fn fetch_app_name(adapter: &SharedBotAdapter) -> Result<String> {
+    let response = ws_send_action_async(adapter, "get_version_info", serde_json::json!({})).await?;
+    if !response_success(&response) {
+        return Err(Error::ValidationError(format!(
+            "get_version_info WebSocket action returned failure: {response}"
+        )));
+    }
+    Ok(response
+        .pointer("/data/app_name")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string())
+}
+
+/// Whether `app_name` names NapCat, the only implementation of the extended
+/// actions the adapter relies on, such as message history and emoji reactions.
+pub fn is_napcat(app_name: &str) -> bool {
+    app_name.to_ascii_lowercase().contains("napcat")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_napcat_builds() {
+        assert!(is_napcat("NapCat.Onebot"));
+        assert!(is_napcat("napcat"));
+        assert!(!is_napcat("Lagrange.OneBot"));
+        assert!(!is_napcat(""));
+    }
+}
@@ -0,0 +1,144 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::Notify;
+
+/// Counts running tasks and wakes waiters once the count drops to zero.
+#[derive(Default)]
+struct InFlight {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+impl InFlight {
+    async fn wait_idle(&self) {
+        loop {
+            let idle = self.idle.notified();
+            tokio::pin!(idle);
+            idle.as_mut().enable();
+            if self.count.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+/// Held by a tracked task until it finishes.
+pub struct InFlightGuard {
+    shutdown: Arc<AdapterShutdown>,
+    connection: bool,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let in_flight = if self.connection {
+            &self.shutdown.connections
+        } else {
+            &self.shutdown.event_tasks
+        };
+        if in_flight.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            in_flight.idle.notify_waiters();
+        }
+    }
+}
+
+/// Shutdown state shared by an adapter's connection loop and the event tasks
+/// it spawns, so a shutdown can stop reading new events and wait for the ones
+/// already being handled (message persistence, brain replies) to finish.
+#[derive(Default)]
+pub struct AdapterShutdown {
+    requested: AtomicBool,
+    requested_notify: Notify,
+    event_tasks: InFlight,
+    connections: InFlight,
+}
+
+impl AdapterShutdown {
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+        self.requested_notify.notify_waiters();
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once [`Self::request`] has been called.
+    pub async fn requested(&self) {
+        loop {
+            let requested = self.requested_notify.notified();
+            tokio::pin!(requested);
+            requested.as_mut().enable();
+            if self.is_requested() {
+                return;
+            }
+            requested.await;
+        }
+    }
+
+    /// Track an event-processing task for as long as the guard lives.
+    pub fn track_event_task(self: &Arc<Self>) -> InFlightGuard {
+        self.event_tasks.count.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            shutdown: Arc::clone(self),
+            connection: false,
+        }
+    }
+
+    /// Track an open WebSocket connection until its Close frame is sent.
+    pub(crate) fn track_connection(self: &Arc<Self>) -> InFlightGuard {
+        self.connections.count.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            shutdown: Arc::clone(self),
+            connection: true,
+        }
+    }
+
+    pub fn event_tasks_in_flight(&self) -> usize {
+        self.event_tasks.count.load(Ordering::SeqCst)
+    }
+
+    pub async fn wait_event_tasks(&self) {
+        self.event_tasks.wait_idle().await;
+    }
+
+    /// Wait for every event task to finish and every connection to close.
+    pub async fn wait_drained(&self) {
+        self.event_tasks.wait_idle().await;
+        self.connections.wait_idle().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn drain_waits_for_tracked_tasks_and_connections() {
+        let shutdown = Arc::new(AdapterShutdown::default());
+        let event_task = shutdown.track_event_task();
+        let connection = shutdown.track_connection();
+
+        let drained = tokio::spawn({
+            let shutdown = Arc::clone(&shutdown);
+            async move { shutdown.wait_drained().await }
+        });
+        shutdown.request();
+        shutdown.requested().await;
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!drained.is_finished());
+        drop(event_task);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!drained.is_finished());
+        drop(connection);
+
+        tokio::time::timeout(Duration::from_secs(1), drained)
+            .await
+            .expect("drain should finish once nothing is in flight")
+            .unwrap();
+        assert_eq!(shutdown.event_tasks_in_flight(), 0);
+    }
+}
@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 use zihuan_core::error::{Error, Result};
 use zihuan_graph_engine::object_storage::S3Ref;
 
 use crate::adapter::{BotAdapter, BotAdapterConfig, SharedBotAdapter};
-use storage_handler::{save_connections, ConnectionConfig, ConnectionKind};
+use crate::catch_up::{
+    CatchUpCursorStore, CatchUpState, MemoryCatchUpCursorStore, RedisCatchUpCursorStore, DEFAULT_CATCH_UP_HISTORY_COUNT,
+};
+use crate::message_dedup::{MessageDeduplicator, DEFAULT_MESSAGE_DEDUP_TTL_SECS};
+use crate::watchdog::EventWatchdog;
+use crate::webhook::{WebhookSink, WebhookSinkConfig};
+use storage_handler::{build_redis_ref, load_connections, save_connections, ConnectionConfig, ConnectionKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotAdapterConnection {
@@ -19,6 +26,57 @@ pub struct BotAdapterConnection {
     /// path to the install directory containing napcat.bat / NapCatWinBootMain.exe.
     #[serde(default)]
     pub napcat_install_path: Option<String>,
+    /// The bot server implements NapCat's `set_msg_emoji_like` reaction action.
+    /// Unset detects it from `get_version_info` on connect.
+    #[serde(default)]
+    pub supports_reactions: Option<bool>,
+    /// In groups, only hand messages that @ the bot or reply to one of its
+    /// messages to the brain agent.
+    #[serde(default)]
+    pub respond_only_when_mentioned: bool,
+    /// After each (re)connect, fetch the messages every known chat received while
+    /// disconnected and process them like live events.
+    #[serde(default)]
+    pub catch_up_on_reconnect: bool,
+    /// Redis connection that keeps the per-chat catch-up cursors across restarts.
+    /// Without it cursors live in memory and only reconnects are caught up.
+    #[serde(default)]
+    pub catch_up_redis_connection_id: Option<String>,
+    /// Number of recent messages fetched per chat when catching up.
+    #[serde(default = "default_catch_up_history_count")]
+    pub catch_up_history_count: u32,
+    /// Seconds without any message event before the connection is considered
+    /// silently dead and an error is logged. `0` disables the watchdog.
+    #[serde(default)]
+    pub event_watchdog_secs: u64,
+    /// Reconnect when the event watchdog fires instead of only logging.
+    #[serde(default)]
+    pub event_watchdog_reconnect: bool,
+    /// Seconds during which a redelivered `message_id` is dropped instead of
+    /// being stored and answered again. `0` disables deduplication.
+    #[serde(default = "default_message_dedup_ttl_secs")]
+    pub message_dedup_ttl_secs: u64,
+    /// POST every processed message event as JSON to this URL.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Top-level event fields included in the webhook payload; empty sends the
+    /// whole event.
+    #[serde(default)]
+    pub webhook_fields: Vec<String>,
+    /// Consecutive failed reconnects before the adapter gives up; unset retries forever.
+    #[serde(default)]
+    pub ws_reconnect_max_attempts: Option<u32>,
+    /// Seconds before the first reconnect, doubling on each further failure.
+    #[serde(default)]
+    pub ws_reconnect_interval_secs: Option<u64>,
+}
+
+fn default_catch_up_history_count() -> u32 {
+    DEFAULT_CATCH_UP_HISTORY_COUNT
+}
+
+fn default_message_dedup_ttl_secs() -> u64 {
+    DEFAULT_MESSAGE_DEDUP_TTL_SECS
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,8 +146,58 @@ pub async fn build_ims_bot_adapter(
             connection.bot_server_token.clone().unwrap_or_default(),
             connection.qq_id.clone().unwrap_or_default(),
         )
-        .with_object_storage(object_storage),
+        .with_object_storage(object_storage)
+        .with_reactions(connection.supports_reactions)
+        .with_respond_only_when_mentioned(connection.respond_only_when_mentioned)
+        .with_catch_up(build_catch_up_state(connection))
+        .with_event_watchdog(build_event_watchdog(connection))
+        .with_message_dedup(build_message_dedup(connection))
+        .with_webhook_sink(build_webhook_sink(connection))
+        .with_ws_reconnect(connection.ws_reconnect_max_attempts, connection.ws_reconnect_interval_secs),
     )
     .await
     .into_shared()
 }
+
+fn build_event_watchdog(connection: &BotAdapterConnection) -> Option<Arc<EventWatchdog>> {
+    (connection.event_watchdog_secs > 0).then(|| {
+        Arc::new(
+            EventWatchdog::new(Duration::from_secs(connection.event_watchdog_secs))
+                .with_reconnect(connection.event_watchdog_reconnect),
+        )
+    })
+}
+
+fn build_message_dedup(connection: &BotAdapterConnection) -> Option<Arc<MessageDeduplicator>> {
+    (connection.message_dedup_ttl_secs > 0)
+        .then(|| Arc::new(MessageDeduplicator::new(Duration::from_secs(connection.message_dedup_ttl_secs))))
+}
+
+fn build_webhook_sink(connection: &BotAdapterConnection) -> Option<Arc<WebhookSink>> {
+    let url = connection.webhook_url.as_deref().map(str::trim).filter(|url| !url.is_empty())?;
+    WebhookSink::spawn(WebhookSinkConfig::new(url).with_fields(connection.webhook_fields.clone()))
+        .map_err(|err| log::warn!("[ims_bot_adapter] webhook sink disabled, failed to start: {err}"))
+        .ok()
+}
+
+fn build_catch_up_state(connection: &BotAdapterConnection) -> Option<Arc<CatchUpState>> {
+    if !connection.catch_up_on_reconnect {
+        return None;
+    }
+    let redis_ref = connection.catch_up_redis_connection_id.as_deref().and_then(|connection_id| {
+        load_connections()
+            .and_then(|connections| build_redis_ref(Some(connection_id), &connections))
+            .unwrap_or_else(|err| {
+                log::warn!("[ims_bot_adapter] catch-up cursors fall back to memory, redis unavailable: {err}");
+                None
+            })
+    });
+    let store: Arc<dyn CatchUpCursorStore> = match redis_ref {
+        Some(redis_ref) => Arc::new(RedisCatchUpCursorStore::new(
+            redis_ref,
+            connection.qq_id.as_deref().unwrap_or_default(),
+        )),
+        None => Arc::new(MemoryCatchUpCursorStore::default()),
+    };
+    Some(Arc::new(CatchUpState::new(store, connection.catch_up_history_count)))
+}
@@ -13,7 +13,7 @@ macro_rules! sender_display_name {
 }
 pub use crate::sender_display_name;
 
-/// Checks whether any message in the list carries actual content (text, image, or
+/// Checks whether any message in the list carries actual content (text, image, face, or
 /// recursively meaningful reply/forward content). A depth limit of 8 prevents
 /// infinite recursion from deeply nested or cyclic structures.
 pub fn messages_have_effective_content(messages: &[Message], depth: usize) -> bool {
@@ -28,7 +28,7 @@ pub fn messages_have_effective_content(messages: &[Message], depth: usize) -> bo
                     return true;
                 }
             }
-            Message::Image(_) => return true,
+            Message::Image(_) | Message::Face(_) => return true,
             Message::Forward(forward) => {
                 if forward
                     .content
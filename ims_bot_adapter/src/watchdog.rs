@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use zihuan_core::utils::clock::{Clock, SystemClock};
+
+pub type WatchdogAlert = Arc<dyn Fn(Duration) + Send + Sync>;
+
+/// Dead-man's switch for the inbound event stream.
+///
+/// Ping/pong keeps a half-open connection looking alive while no events are
+/// delivered any more. The watchdog trips once no message event has been seen
+/// for `threshold`, and re-arms on the next event.
+pub struct EventWatchdog {
+    threshold: Duration,
+    reconnect: bool,
+    clock: Arc<dyn Clock>,
+    alert: Option<WatchdogAlert>,
+    last_event_at: Mutex<NaiveDateTime>,
+    tripped: AtomicBool,
+}
+
+impl EventWatchdog {
+    pub fn new(threshold: Duration) -> Self {
+        Self::with_clock(threshold, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(threshold: Duration, clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
+        Self {
+            threshold,
+            reconnect: false,
+            clock,
+            alert: None,
+            last_event_at: Mutex::new(now),
+            tripped: AtomicBool::new(false),
+        }
+    }
+
+    /// Drop and re-establish the connection when the watchdog trips.
+    pub fn with_reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Called with the silence duration when the watchdog trips.
+    pub fn with_alert(mut self, alert: WatchdogAlert) -> Self {
+        self.alert = Some(alert);
+        self
+    }
+
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    pub fn reconnects(&self) -> bool {
+        self.reconnect
+    }
+
+    /// Resets the silence timer; called for every received message event and on connect.
+    pub fn record_event(&self) {
+        *self.last_event_at.lock().unwrap() = self.clock.now();
+        self.tripped.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns the silence duration the first time it exceeds the threshold, then
+    /// `None` until the next event re-arms the watchdog. Runs the alert callback
+    /// when it trips.
+    pub fn check(&self) -> Option<Duration> {
+        let silence = (self.clock.now() - *self.last_event_at.lock().unwrap())
+            .to_std()
+            .unwrap_or_default();
+        if silence < self.threshold || self.tripped.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        if let Some(alert) = &self.alert {
+            alert(silence);
+        }
+        Some(silence)
+    }
+
+    /// How often the connection loop should call [`check`](Self::check).
+    pub fn poll_interval(&self) -> Duration {
+        (self.threshold / 4).clamp(Duration::from_secs(1), Duration::from_secs(30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    struct ManualClock(Mutex<NaiveDateTime>);
+
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            let mut now = self.0.lock().unwrap();
+            *now += chrono::Duration::from_std(by).unwrap();
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> NaiveDateTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    #[test]
+    fn fires_once_after_the_silence_threshold_and_rearms_on_event() {
+        let clock = Arc::new(ManualClock(Mutex::new(
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )));
+        let alerts = Arc::new(AtomicUsize::new(0));
+        let watchdog = {
+            let alerts = Arc::clone(&alerts);
+            EventWatchdog::with_clock(Duration::from_secs(300), clock.clone()).with_alert(Arc::new(move |_| {
+                alerts.fetch_add(1, Ordering::SeqCst);
+            }))
+        };
+
+        clock.advance(Duration::from_secs(299));
+        assert_eq!(watchdog.check(), None);
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(watchdog.check(), Some(Duration::from_secs(301)));
+        assert_eq!(watchdog.check(), None);
+        assert_eq!(alerts.load(Ordering::SeqCst), 1);
+
+        watchdog.record_event();
+        clock.advance(Duration::from_secs(300));
+        assert_eq!(watchdog.check(), Some(Duration::from_secs(300)));
+        assert_eq!(alerts.load(Ordering::SeqCst), 2);
+    }
+}
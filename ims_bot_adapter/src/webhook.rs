@@ -0,0 +1,207 @@
+use log::{debug, warn};
+use serde_json::{Map, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use zihuan_core::error::{Error, Result};
+use zihuan_core::utils::backoff::BackoffPolicy;
+
+use crate::event::EventHandler;
+use crate::models::MessageEvent;
+
+/// Event handler id the sink is registered under on the adapter.
+pub const WEBHOOK_HANDLER_ID: &str = "webhook_sink";
+pub const DEFAULT_WEBHOOK_QUEUE_CAPACITY: usize = 256;
+const DEFAULT_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone)]
+pub struct WebhookSinkConfig {
+    pub url: String,
+    /// Top-level `MessageEvent` fields to send (e.g. `message_id`, `sender`).
+    /// Empty sends the whole event.
+    pub fields: Vec<String>,
+    /// Events waiting for delivery; once full, new events are dropped instead of
+    /// stalling the event pipeline.
+    pub queue_capacity: usize,
+    pub retry: BackoffPolicy,
+    pub timeout: Duration,
+}
+
+impl WebhookSinkConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            fields: Vec::new(),
+            queue_capacity: DEFAULT_WEBHOOK_QUEUE_CAPACITY,
+            retry: BackoffPolicy::default(),
+            timeout: DEFAULT_WEBHOOK_TIMEOUT,
+        }
+    }
+
+    pub fn with_fields(mut self, fields: Vec<String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    pub fn with_queue_capacity(mut self, queue_capacity: usize) -> Self {
+        self.queue_capacity = queue_capacity.max(1);
+        self
+    }
+
+    pub fn with_retry(mut self, retry: BackoffPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+}
+
+/// POSTs each processed `MessageEvent` as JSON to an external URL.
+///
+/// Events go through a bounded queue drained by a background task, so a slow
+/// or failing webhook never blocks event handling.
+pub struct WebhookSink {
+    queue: mpsc::Sender<Value>,
+    fields: Vec<String>,
+    dropped: AtomicU64,
+}
+
+impl WebhookSink {
+    /// Start the delivery task. Must be called inside a Tokio runtime.
+    pub fn spawn(config: WebhookSinkConfig) -> Result<Arc<Self>> {
+        let client = reqwest::Client::builder().timeout(config.timeout).build()?;
+        let (queue, mut pending) = mpsc::channel::<Value>(config.queue_capacity.max(1));
+        let url = config.url;
+        let retry = config.retry;
+        tokio::spawn(async move {
+            while let Some(payload) = pending.recv().await {
+                if let Err(err) = deliver(&client, &url, &payload, &retry).await {
+                    warn!("[webhook] giving up on event delivery to {url}: {err}");
+                }
+            }
+        });
+
+        Ok(Arc::new(Self {
+            queue,
+            fields: config.fields,
+            dropped: AtomicU64::new(0),
+        }))
+    }
+
+    /// JSON body sent for `event`, limited to the configured fields.
+    pub fn payload(&self, event: &MessageEvent) -> Result<Value> {
+        let value = serde_json::to_value(event)?;
+        if self.fields.is_empty() {
+            return Ok(value);
+        }
+        let Value::Object(object) = value else {
+            return Ok(value);
+        };
+        let selected: Map<String, Value> = object
+            .into_iter()
+            .filter(|(key, _)| self.fields.iter().any(|field| field == key))
+            .collect();
+        Ok(Value::Object(selected))
+    }
+
+    /// Queue `event` for delivery. Returns `false` if it was dropped because the
+    /// queue is full or the delivery task has stopped.
+    pub fn enqueue(&self, event: &MessageEvent) -> bool {
+        let payload = match self.payload(event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                warn!("[webhook] failed to serialize event {}: {err}", event.message_id);
+                return false;
+            }
+        };
+        if self.queue.try_send(payload).is_err() {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            warn!(
+                "[webhook] queue full, dropped event {} ({} dropped so far)",
+                event.message_id, dropped
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Events dropped because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Handler that queues every processed event for delivery.
+    pub fn event_handler(self: &Arc<Self>) -> EventHandler {
+        let sink = Arc::clone(self);
+        Arc::new(move |event| {
+            sink.enqueue(event);
+            Box::pin(async { Ok(()) })
+        })
+    }
+}
+
+async fn deliver(client: &reqwest::Client, url: &str, payload: &Value, retry: &BackoffPolicy) -> Result<()> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let result = match client.post(url).json(payload).send().await {
+            Ok(response) => response.error_for_status().map(|_| ()).map_err(Error::from),
+            Err(err) => Err(Error::from(err)),
+        };
+        match result {
+            Ok(()) => return Ok(()),
+            Err(err) if err.is_retryable() && retry.should_retry(attempt) => {
+                let delay = retry.delay_for_attempt(attempt);
+                debug!("[webhook] delivery attempt {attempt} failed: {err}; retrying in {delay:?}");
+                tokio::time::sleep(delay).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::event_model::{MessageType, Sender};
+    use zihuan_core::test_util::{MockHttpServer, MockReply};
+
+    fn event() -> MessageEvent {
+        MessageEvent {
+            message_id: 777,
+            message_type: MessageType::Group,
+            sender: Sender {
+                user_id: 2001,
+                nickname: "member".to_string(),
+                card: String::new(),
+                role: None,
+            },
+            message_list: vec![],
+            group_id: Some(3001),
+            group_name: None,
+            is_group_message: true,
+            time: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn event_is_delivered_and_retried_after_server_error() {
+        let mut server = MockHttpServer::start("/hook", vec![MockReply::new(500, ""), MockReply::new(200, "")]).await;
+        let sink = WebhookSink::spawn(
+            WebhookSinkConfig::new(server.url())
+                .with_fields(vec!["message_id".to_string(), "group_id".to_string()])
+                .with_retry(BackoffPolicy::new(3, Duration::from_millis(10), Duration::from_millis(10))),
+        )
+        .unwrap();
+
+        (sink.event_handler())(&event()).await.unwrap();
+
+        let expected = serde_json::json!({ "message_id": 777, "group_id": 3001 });
+        for _ in 0..2 {
+            let request = tokio::time::timeout(Duration::from_secs(5), server.next_request())
+                .await
+                .expect("webhook should be called");
+            assert_eq!(request.body_json(), expected);
+        }
+        assert_eq!(sink.dropped(), 0);
+    }
+}
@@ -6,15 +6,18 @@ use serde_json::Value;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
 use tokio::sync::oneshot;
 use tokio::task::block_in_place;
 use uuid::Uuid;
 use zihuan_core::error::{Error, Result};
 use zihuan_core::ims_bot_adapter::models::message::{ImageMessage, Message};
+use zihuan_core::system_config::{load_section, GlobalSettingsSection};
 use zihuan_core::url_utils::{
     content_type_from_url, image_content_type_from_bytes, image_extension_for_content_type,
     supported_image_content_type,
 };
+use zihuan_core::utils::rate_limiter::RateLimiter;
 use zihuan_graph_engine::object_storage::S3Ref;
 
 /// Global counter for generating unique echo IDs.
@@ -29,10 +32,44 @@ struct OutboundImagePayload {
     source: &'static str,
 }
 
+/// Process-wide limit on outgoing QQ messages, configured by
+/// `global_settings.qq_messages_per_second` and `qq_message_burst`. Edits take
+/// effect on restart.
+pub fn qq_message_rate_limiter() -> &'static RateLimiter {
+    static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+    LIMITER.get_or_init(|| match load_section::<GlobalSettingsSection>() {
+        Ok(settings) => RateLimiter::new(settings.qq_messages_per_second, settings.qq_message_burst),
+        Err(err) => {
+            warn!("{LOG_PREFIX} failed to load global settings, not rate limiting QQ messages: {err}");
+            RateLimiter::unlimited()
+        }
+    })
+}
+
+/// `send_msg`, `send_group_msg`, `send_private_forward_msg` and the like.
+pub(crate) fn is_message_send_action(action_name: &str) -> bool {
+    action_name.starts_with("send_") && action_name.ends_with("_msg")
+}
+
+/// Actions that count against [`qq_message_rate_limiter`]: message sends and
+/// emoji reactions, which QQ throttles alike. Other actions such as lookups are
+/// not rate limited.
+pub(crate) fn is_rate_limited_action(action_name: &str) -> bool {
+    is_message_send_action(action_name) || action_name == "set_msg_emoji_like"
+}
+
 pub fn next_echo() -> String {
     format!("zhn_echo_{}", ECHO_COUNTER.fetch_add(1, Ordering::Relaxed))
 }
 
+/// Echo prefix of message sends queued without a waiting caller; the reader
+/// loop records the message ids from their responses.
+pub(crate) const QUEUED_SEND_ECHO_PREFIX: &str = "zhn_queued_send_";
+
+pub(crate) fn next_queued_send_echo() -> String {
+    format!("{QUEUED_SEND_ECHO_PREFIX}{}", ECHO_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
 pub fn json_i64(value: Option<&Value>) -> Option<i64> {
     match value? {
         Value::Number(number) => number.as_i64(),
@@ -526,7 +563,13 @@ pub async fn ws_send_action_async(
     action_name: &str,
     params: serde_json::Value,
 ) -> Result<serde_json::Value> {
-    ws_send_action_with_timeout_async(adapter_ref, action_name, params, std::time::Duration::from_secs(30)).await
+    ws_send_action_with_timeout_async(
+        adapter_ref,
+        action_name,
+        params,
+        zihuan_core::system_config::timeout_settings().http(),
+    )
+    .await
 }
 
 pub async fn ws_send_action_with_timeout_async(
@@ -535,6 +578,10 @@ pub async fn ws_send_action_with_timeout_async(
     params: serde_json::Value,
     response_timeout: std::time::Duration,
 ) -> Result<serde_json::Value> {
+    if is_rate_limited_action(action_name) {
+        qq_message_rate_limiter().acquire_async().await;
+    }
+
     let echo = next_echo();
     let payload = serde_json::json!({
         "action": action_name,
@@ -582,6 +629,12 @@ pub async fn ws_send_action_with_timeout_async(
         }
     };
 
+    if is_message_send_action(&action_name) {
+        if let Some(message_id) = response_message_id(&response) {
+            adapter_ref.lock().await.sent_message_ids().record(message_id);
+        }
+    }
+
     Ok(response)
 }
 
@@ -0,0 +1,313 @@
+use crate::llm_message::convert::{
+    build_anthropic_messages_request_body, parse_anthropic_messages_response, parse_anthropic_messages_sse_response,
+};
+use crate::request_retry::{default_retry_policy, error_reply, send_with_retry, send_with_retry_async, RequestError};
+use log::debug;
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::time::Duration;
+use zihuan_core::error::Error;
+use zihuan_core::llm::llm_base::LLMBase;
+use zihuan_core::llm::{InferenceParam, LLMMessage};
+use zihuan_core::utils::backoff::BackoffPolicy;
+use zihuan_core::utils::string_utils;
+
+pub const DEFAULT_ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Chat model served through Anthropic's Messages API.
+///
+/// System messages go to the top-level `system` field, the reply's content
+/// blocks are read back into text, reasoning and tool calls, and requests are
+/// authenticated with `x-api-key` plus `anthropic-version`. With `stream` set
+/// the reply is requested as server-sent events and assembled before returning.
+#[derive(Debug, Clone)]
+pub struct AnthropicAPI {
+    model_name: String,
+    api_endpoint: String,
+    api_key: Option<String>,
+    anthropic_version: String,
+    stream: bool,
+    supports_multimodal_input: bool,
+    pub timeout: Duration,
+    retry: BackoffPolicy,
+}
+
+impl AnthropicAPI {
+    pub fn new(
+        model_name: String,
+        api_endpoint: String,
+        api_key: Option<String>,
+        stream: bool,
+        supports_multimodal_input: bool,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            model_name,
+            api_endpoint,
+            api_key,
+            anthropic_version: DEFAULT_ANTHROPIC_VERSION.to_string(),
+            stream,
+            supports_multimodal_input,
+            timeout,
+            retry: default_retry_policy(),
+        }
+    }
+
+    pub fn with_anthropic_version(mut self, anthropic_version: impl Into<String>) -> Self {
+        self.anthropic_version = anthropic_version.into();
+        self
+    }
+
+    /// Retries after the first attempt, keeping the current base delay.
+    pub fn with_retry_count(mut self, retry_count: u32) -> Self {
+        self.retry.max_attempts = retry_count.saturating_add(1);
+        self
+    }
+
+    fn request_body(&self, param: &InferenceParam) -> Value {
+        let mut body = build_anthropic_messages_request_body(&self.model_name, param);
+        if self.stream {
+            body["stream"] = Value::Bool(true);
+        }
+        body
+    }
+
+    fn parse_reply(&self, status: StatusCode, response_text: &str) -> Result<LLMMessage, RequestError> {
+        if !status.is_success() {
+            return Err(RequestError::from_status(
+                status,
+                format!(
+                    "model={} endpoint={} status={} body={}",
+                    self.model_name,
+                    self.api_endpoint,
+                    status,
+                    string_utils::shorten_text(response_text, 800)
+                ),
+            ));
+        }
+
+        let message = if self.stream {
+            parse_anthropic_messages_sse_response(response_text)
+        } else {
+            serde_json::from_str::<Value>(response_text)
+                .ok()
+                .as_ref()
+                .and_then(parse_anthropic_messages_response)
+        };
+        message.ok_or_else(|| RequestError::NonRetryable {
+            status: None,
+            message: format!(
+                "model={} endpoint={} invalid_response body={}",
+                self.model_name,
+                self.api_endpoint,
+                string_utils::shorten_text(response_text, 800)
+            ),
+        })
+    }
+
+    fn send_error(&self, error: &reqwest::Error) -> RequestError {
+        RequestError::Retryable {
+            status: error.status().map(|status| status.as_u16()),
+            message: format!("model={} endpoint={} error={}", self.model_name, self.api_endpoint, error),
+        }
+    }
+
+    fn send_blocking(&self, client: &reqwest::blocking::Client, body: &Value) -> Result<LLMMessage, RequestError> {
+        let mut request = client
+            .post(&self.api_endpoint)
+            .header("anthropic-version", &self.anthropic_version)
+            .json(body);
+        if let Some(api_key) = &self.api_key {
+            request = request.header("x-api-key", api_key);
+        }
+        let response = request.send().map_err(|e| self.send_error(&e))?;
+        let status = response.status();
+        let response_text = response.text().unwrap_or_else(|_| "Failed to read response".to_string());
+        self.parse_reply(status, &response_text)
+    }
+
+    async fn send_async(&self, client: &reqwest::Client, body: &Value) -> Result<LLMMessage, RequestError> {
+        let mut request = client
+            .post(&self.api_endpoint)
+            .header("anthropic-version", &self.anthropic_version)
+            .json(body);
+        if let Some(api_key) = &self.api_key {
+            request = request.header("x-api-key", api_key);
+        }
+        let response = request.send().await.map_err(|e| self.send_error(&e))?;
+        let status = response.status();
+        let response_text = response.text().await.unwrap_or_else(|_| "Failed to read response".to_string());
+        self.parse_reply(status, &response_text)
+    }
+
+    /// Async counterpart of [`LLMBase::inference`].
+    pub async fn inference_async(&self, param: &InferenceParam<'_>) -> LLMMessage {
+        self.try_inference_async(param).await.unwrap_or_else(error_reply)
+    }
+
+    /// Like [`AnthropicAPI::inference_async`], but a failed request is returned
+    /// as [`Error::LlmApi`] instead of an `"Error: ..."` reply.
+    pub async fn try_inference_async(&self, param: &InferenceParam<'_>) -> Result<LLMMessage, Error> {
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .expect("Failed to create async HTTP client");
+        let body = self.request_body(param);
+
+        send_with_retry_async("Anthropic API", &self.retry, |attempt, max_attempts| {
+            debug!(
+                "[AnthropicAPI] sending request model={} attempt={}/{}",
+                self.model_name, attempt, max_attempts
+            );
+            self.send_async(&client, &body)
+        })
+        .await
+    }
+}
+
+impl LLMBase for AnthropicAPI {
+    fn get_model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    fn api_style(&self) -> Option<&str> {
+        Some("anthropic_messages")
+    }
+
+    fn supports_multimodal_input(&self) -> bool {
+        self.supports_multimodal_input
+    }
+
+    fn inference(&self, param: &InferenceParam) -> LLMMessage {
+        self.try_inference(param).unwrap_or_else(error_reply)
+    }
+
+    fn try_inference(&self, param: &InferenceParam) -> Result<LLMMessage, Error> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .expect("Failed to create HTTP client");
+        let body = self.request_body(param);
+
+        send_with_retry("Anthropic API", &self.retry, |attempt, max_attempts| {
+            debug!(
+                "[AnthropicAPI] sending request model={} attempt={}/{}",
+                self.model_name, attempt, max_attempts
+            );
+            self.send_blocking(&client, &body)
+        })
+    }
+
+    fn inference_async<'a>(
+        &'a self,
+        param: &'a InferenceParam<'a>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = LLMMessage> + Send + 'a>> {
+        Box::pin(async move { self.inference_async(param).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zihuan_core::test_util::{MockHttpServer, MockReply};
+
+    const MESSAGES_REPLY: &str = r#"{"type":"message","role":"assistant","content":[{"type":"text","text":"你好"}],"stop_reason":"end_turn","usage":{"input_tokens":3,"output_tokens":2}}"#;
+
+    fn api(endpoint: String) -> AnthropicAPI {
+        AnthropicAPI::new(
+            "claude-test".to_string(),
+            endpoint,
+            Some("test-key".to_string()),
+            false,
+            false,
+            Duration::from_secs(5),
+        )
+    }
+
+    fn param(messages: &Vec<LLMMessage>) -> InferenceParam<'_> {
+        InferenceParam::new(messages)
+    }
+
+    #[tokio::test]
+    async fn request_uses_anthropic_headers_and_reply_blocks_are_parsed() {
+        let mut server = MockHttpServer::start("/v1/messages", vec![MockReply::new(200, MESSAGES_REPLY)]).await;
+        let messages = vec![LLMMessage::system("简短回答"), LLMMessage::user("hi")];
+
+        let reply = api(server.url()).with_retry_count(0).inference_async(&param(&messages)).await;
+
+        assert_eq!(reply.content_text_owned().as_deref(), Some("你好"));
+        let request = server.next_request().await;
+        assert_eq!(request.header("x-api-key"), Some("test-key"));
+        assert_eq!(request.header("anthropic-version"), Some(DEFAULT_ANTHROPIC_VERSION));
+        assert_eq!(request.header("authorization"), None);
+        assert_eq!(request.body_json().get("stream"), None);
+    }
+
+    #[tokio::test]
+    async fn overloaded_status_is_retried() {
+        let server = MockHttpServer::start(
+            "/v1/messages",
+            vec![
+                MockReply::new(529, r#"{"type":"error","error":{"type":"overloaded_error"}}"#),
+                MockReply::new(200, MESSAGES_REPLY),
+            ],
+        )
+        .await;
+        let messages = vec![LLMMessage::user("hi")];
+        let mut api = api(server.url()).with_retry_count(1);
+        api.retry.initial_delay = Duration::from_millis(10);
+
+        let reply = api.inference_async(&param(&messages)).await;
+
+        assert_eq!(reply.content_text_owned().as_deref(), Some("你好"));
+    }
+
+    #[tokio::test]
+    async fn try_inference_reports_client_errors_without_retrying() {
+        let server = MockHttpServer::start(
+            "/v1/messages",
+            vec![MockReply::new(400, "{}"), MockReply::new(200, MESSAGES_REPLY)],
+        )
+        .await;
+        let messages = vec![LLMMessage::user("hi")];
+
+        match api(server.url()).try_inference_async(&param(&messages)).await {
+            Err(Error::LlmApi { status, message }) => {
+                assert_eq!(status, Some(400));
+                assert_eq!(message, "LLM API request failed after 1 attempt(s)");
+            }
+            other => panic!("expected an LLM API error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_flag_requests_and_assembles_server_sent_events() {
+        let mut server = MockHttpServer::start(
+            "/v1/messages",
+            vec![MockReply::new(
+                200,
+                concat!(
+                    "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":3}}}\n\n",
+                    "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+                    "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"你好\"}}\n\n",
+                    "data: {\"type\":\"message_stop\"}\n\n",
+                ),
+            )],
+        )
+        .await;
+        let messages = vec![LLMMessage::user("hi")];
+        let api = AnthropicAPI::new(
+            "claude-test".to_string(),
+            server.url(),
+            None,
+            true,
+            false,
+            Duration::from_secs(5),
+        );
+
+        let reply = api.inference_async(&param(&messages)).await;
+
+        assert_eq!(reply.content_text_owned().as_deref(), Some("你好"));
+        assert_eq!(server.next_request().await.body_json()["stream"], Value::Bool(true));
+    }
+}
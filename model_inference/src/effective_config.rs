@@ -0,0 +1,197 @@
+use storage_handler::{ConnectionConfig, ConnectionKind};
+
+use crate::system_config::{AgentConfig, AgentType, LlmRefConfig, ModelRefSpec};
+
+const SECRET_SET: &str = "<redacted>";
+const SECRET_UNSET: &str = "<unset>";
+
+fn secret_state(value: Option<&str>) -> &'static str {
+    match value {
+        Some(value) if !value.trim().is_empty() => SECRET_SET,
+        _ => SECRET_UNSET,
+    }
+}
+
+/// Host (and port) of a URL, with scheme, credentials and path stripped.
+fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    authority.rsplit_once('@').map(|(_, host)| host).unwrap_or(authority)
+}
+
+/// Userinfo password embedded in a URL such as `redis://:secret@host`.
+fn url_password(url: &str) -> Option<&str> {
+    let rest = url.split_once("://")?.1;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let (userinfo, _) = authority.rsplit_once('@')?;
+    userinfo.split_once(':').map(|(_, password)| password)
+}
+
+fn connection_line(connection: &ConnectionConfig) -> String {
+    let details = match &connection.kind {
+        ConnectionKind::Mysql(mysql) => format!(
+            "kind=mysql host={} password={}",
+            url_host(&mysql.url),
+            secret_state(url_password(&mysql.url))
+        ),
+        ConnectionKind::Redis(redis) => format!(
+            "kind=redis host={} password={}",
+            url_host(&redis.url),
+            secret_state(redis.password.as_deref().or_else(|| url_password(&redis.url)))
+        ),
+        ConnectionKind::Sqlite(sqlite) => format!("kind=sqlite path={}", sqlite.path),
+        ConnectionKind::Postgres(postgres) => format!(
+            "kind=postgres host={} password={}",
+            url_host(&postgres.url),
+            secret_state(url_password(&postgres.url))
+        ),
+        ConnectionKind::Weaviate(weaviate) => format!(
+            "kind=weaviate host={} api_key={} password={}",
+            url_host(&weaviate.base_url),
+            secret_state(weaviate.api_key.as_deref()),
+            secret_state(weaviate.password.as_deref())
+        ),
+        ConnectionKind::Elasticsearch(elasticsearch) => format!(
+            "kind=elasticsearch host={} api_key={} password={}",
+            url_host(&elasticsearch.base_url),
+            secret_state(elasticsearch.api_key.as_deref()),
+            secret_state(elasticsearch.password.as_deref())
+        ),
+        ConnectionKind::Rustfs(rustfs) => format!(
+            "kind=rustfs host={} bucket={} secret_key={}",
+            url_host(&rustfs.endpoint),
+            rustfs.bucket,
+            secret_state(Some(&rustfs.secret_key))
+        ),
+        ConnectionKind::BotAdapter(raw) => format!(
+            "kind=bot_adapter host={} token={}",
+            url_host(raw.get("bot_server_url").and_then(|value| value.as_str()).unwrap_or_default()),
+            secret_state(raw.get("bot_server_token").and_then(|value| value.as_str()))
+        ),
+        ConnectionKind::WebSearchEngine(engine) => format!(
+            "kind=web_search_engine provider={} api_token={}",
+            engine.provider,
+            secret_state(engine.api_token.as_deref())
+        ),
+        ConnectionKind::WeatherApi(weather) => format!(
+            "kind=weather_api provider={} host={} api_key={}",
+            weather.provider,
+            weather.base_url.as_deref().map(url_host).unwrap_or("default"),
+            secret_state(weather.api_key.as_deref())
+        ),
+        ConnectionKind::Tokenizer(tokenizer) => format!("kind=tokenizer model={}", tokenizer.model_name),
+    };
+    format!("connection '{}' enabled={} {}", connection.name, connection.enabled, details)
+}
+
+fn llm_ref_line(llm_ref: &LlmRefConfig) -> String {
+    let details = match &llm_ref.model {
+        ModelRefSpec::ChatLlm { llm } => format!(
+            "model={} endpoint={} api_key={}",
+            llm.model_name,
+            url_host(&llm.api_endpoint),
+            secret_state(llm.api_key.as_deref())
+        ),
+        ModelRefSpec::TextEmbeddingLocal { model_name } => format!("embedding_model={model_name}"),
+    };
+    format!("llm_ref '{}' enabled={} {}", llm_ref.name, llm_ref.enabled, details)
+}
+
+fn agent_line(agent: &AgentConfig) -> String {
+    let details = match &agent.agent_type {
+        AgentType::QqChat(config) => format!(
+            "type=qq_chat brain={:?} bot_connection={} rdb={} persona_chars={}",
+            config.brain,
+            config.ims_bot_adapter_connection_id,
+            config.resolved_rdb_id().is_some(),
+            config
+                .system_prompt
+                .as_deref()
+                .map(|prompt| prompt.chars().count())
+                .unwrap_or(0)
+        ),
+        AgentType::HttpStream(config) => format!(
+            "type=http_stream bind={} api_key={}",
+            config.bind,
+            secret_state(config.api_key.as_deref())
+        ),
+        AgentType::Workspace(_) => "type=workspace".to_string(),
+    };
+    format!(
+        "agent '{}' enabled={} auto_start={} tools={} {}",
+        agent.name,
+        agent.enabled,
+        agent.auto_start,
+        agent.tools.iter().filter(|tool| tool.enabled).count(),
+        details
+    )
+}
+
+/// One line per loaded config entry describing what the process will actually use.
+///
+/// Secrets are never printed: only whether they are set, as `<redacted>` or
+/// `<unset>`. URLs are reduced to their host so embedded credentials are dropped.
+pub fn effective_config_lines(
+    connections: &[ConnectionConfig],
+    llm_refs: &[LlmRefConfig],
+    agents: &[AgentConfig],
+) -> Vec<String> {
+    connections
+        .iter()
+        .map(connection_line)
+        .chain(llm_refs.iter().map(llm_ref_line))
+        .chain(agents.iter().map(agent_line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_shows_redacted_placeholders_instead_of_secrets() {
+        let connections: Vec<ConnectionConfig> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "redis",
+                "name": "Redis",
+                "enabled": true,
+                "kind": { "type": "redis", "url": "redis://:hunter2@10.0.0.5:6379/0" },
+                "updated_at": ""
+            },
+            {
+                "id": "bot",
+                "name": "NapCat",
+                "enabled": true,
+                "kind": {
+                    "type": "bot_adapter",
+                    "bot_server_url": "ws://127.0.0.1:3001",
+                    "bot_server_token": "napcat-token"
+                },
+                "updated_at": ""
+            }
+        ]))
+        .expect("deserialize connections");
+        let llm_refs: Vec<LlmRefConfig> = serde_json::from_value(serde_json::json!([{
+            "name": "main",
+            "enabled": true,
+            "model": {
+                "type": "chat_llm",
+                "llm": {
+                    "model_name": "deepseek-chat",
+                    "api_endpoint": "https://api.deepseek.com/v1/chat/completions",
+                    "api_key": "sk-secret"
+                }
+            }
+        }]))
+        .expect("deserialize llm refs");
+
+        let dump = effective_config_lines(&connections, &llm_refs, &[]).join("\n");
+
+        for secret in ["hunter2", "napcat-token", "sk-secret"] {
+            assert!(!dump.contains(secret), "dump leaked {secret}: {dump}");
+        }
+        assert!(dump.contains("host=10.0.0.5:6379 password=<redacted>"));
+        assert!(dump.contains("host=127.0.0.1:3001 token=<redacted>"));
+        assert!(dump.contains("endpoint=api.deepseek.com api_key=<redacted>"));
+    }
+}
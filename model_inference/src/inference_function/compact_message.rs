@@ -81,10 +81,7 @@ pub fn compact_context_messages(
         LLMMessage::user(build_compaction_prompt(&prefix_messages)),
     ];
 
-    let response = llm.inference(&InferenceParam {
-        messages: &prompt_messages,
-        tools: None,
-    });
+    let response = llm.inference(&InferenceParam::new(&prompt_messages));
 
     let Some(summary_text) = response
         .content_text_owned()
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use zihuan_core::error::Result;
+use zihuan_core::llm::llm_base::LLMBase;
+use zihuan_core::llm::tooling::{FunctionTool, StaticFunctionToolSpec};
+use zihuan_core::llm::{InferenceParam, LLMMessage};
+use zihuan_graph_engine::util::json_schema_validate::JsonSchemaValidator;
+
+pub const SUBMIT_RESULT_TOOL_NAME: &str = "submit_result";
+/// Tool arguments must be an object, so other schemas are submitted under this key.
+const WRAPPED_VALUE_KEY: &str = "value";
+
+const EXTRACT_SYSTEM_PROMPT: &str = "你负责从用户给出的文本中抽取结构化数据。只依据文本内容填写字段，不要编造文本中没有的信息。必须调用 submit_result 工具提交结果；如果无法调用工具，则只输出一个符合 JSON Schema 的 JSON 值，不要输出任何解释。";
+
+#[derive(Debug, Clone)]
+pub struct ExtractionResult {
+    /// Last JSON value the model produced, even when it failed validation.
+    pub value: Option<Value>,
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub attempts: usize,
+}
+
+/// Asks `llm` to turn `text` into JSON conforming to `schema`.
+///
+/// The model is required to call a single `submit_result` tool whose parameters
+/// are the schema, or an object wrapping it under `value` when its root is not an
+/// object. A model that answers in plain text anyway has the reply content parsed
+/// as JSON. Output that does not parse or validate is retried once with the
+/// errors fed back to the model.
+pub fn extract_structured(
+    llm: &Arc<dyn LLMBase>,
+    text: &str,
+    schema: &Value,
+    instruction: Option<&str>,
+) -> Result<ExtractionResult> {
+    // Reject a broken schema before spending a model call on it.
+    let validator = JsonSchemaValidator::new(schema)?;
+
+    let wrapped = schema.get("type").and_then(Value::as_str) != Some("object");
+    let parameters = if wrapped {
+        serde_json::json!({
+            "type": "object",
+            "properties": { WRAPPED_VALUE_KEY: schema },
+            "required": [WRAPPED_VALUE_KEY],
+        })
+    } else {
+        schema.clone()
+    };
+    let tools: Vec<Arc<dyn FunctionTool>> = vec![Arc::new(StaticFunctionToolSpec {
+        name: SUBMIT_RESULT_TOOL_NAME,
+        description: "提交从文本中抽取出的结构化结果",
+        parameters,
+    })];
+
+    let mut system_prompt = format!(
+        "{EXTRACT_SYSTEM_PROMPT}\n\nJSON Schema:\n{}",
+        serde_json::to_string_pretty(schema)?
+    );
+    if let Some(instruction) = instruction.map(str::trim).filter(|instruction| !instruction.is_empty()) {
+        system_prompt.push_str("\n\n额外要求：");
+        system_prompt.push_str(instruction);
+    }
+    let mut messages = vec![LLMMessage::system(system_prompt), LLMMessage::user(text)];
+
+    let mut result = ExtractionResult {
+        value: None,
+        valid: false,
+        errors: Vec::new(),
+        attempts: 0,
+    };
+    while result.attempts < 2 {
+        result.attempts += 1;
+        let response = llm.inference(
+            &InferenceParam::new(&messages)
+                .with_tools(Some(&tools))
+                .with_required_tool(SUBMIT_RESULT_TOOL_NAME),
+        );
+
+        let (raw, value) = match response_json(&response, wrapped) {
+            Ok(value) => (value.to_string(), Some(value)),
+            Err(raw) => (raw, None),
+        };
+        result.errors = match &value {
+            Some(value) => validator.errors(value),
+            None => vec!["/: 输出不是合法的 JSON".to_string()],
+        };
+        result.valid = result.errors.is_empty();
+        result.value = value;
+        if result.valid {
+            break;
+        }
+
+        messages.push(LLMMessage::assistant_text(raw));
+        messages.push(LLMMessage::user(format!(
+            "上面的输出不符合 JSON Schema，错误如下：\n{}\n请修正后重新提交完整结果。",
+            result.errors.join("\n")
+        )));
+    }
+    Ok(result)
+}
+
+/// JSON carried by `response`: the `submit_result` arguments (unwrapped from
+/// `value` when `wrapped`) if the tool was called, otherwise the reply text
+/// (optionally wrapped in a code fence). The raw text is returned as the error
+/// when it does not parse.
+fn response_json(response: &LLMMessage, wrapped: bool) -> std::result::Result<Value, String> {
+    if let Some(call) = response
+        .tool_calls
+        .iter()
+        .find(|call| call.function.name == SUBMIT_RESULT_TOOL_NAME)
+    {
+        let arguments = match &call.function.arguments {
+            Value::String(arguments) => serde_json::from_str(arguments).map_err(|_| arguments.clone())?,
+            arguments => arguments.clone(),
+        };
+        if !wrapped {
+            return Ok(arguments);
+        }
+        return match arguments {
+            Value::Object(mut fields) => fields
+                .remove(WRAPPED_VALUE_KEY)
+                .ok_or_else(|| Value::Object(fields).to_string()),
+            arguments => Err(arguments.to_string()),
+        };
+    }
+
+    let text = response.content_text_owned().unwrap_or_default();
+    let trimmed = text.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|value| value.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(trimmed);
+    serde_json::from_str(unfenced).map_err(|_| text)
+}
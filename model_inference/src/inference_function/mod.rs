@@ -1 +1,2 @@
 pub mod compact_message;
+pub mod extract_structured;
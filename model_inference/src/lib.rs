@@ -1,11 +1,15 @@
 pub mod agent_config_support;
+pub mod anthropic_api;
+pub mod effective_config;
 pub mod inference_function;
 pub mod linalg;
 pub mod llm_api;
+pub mod llm_concurrency;
 pub mod llm_message;
 pub mod message_content_utils;
 pub mod nn;
 pub mod nodes;
+mod request_retry;
 pub mod system_config;
 
 use zihuan_core::error::Result;
@@ -15,6 +19,8 @@ pub fn init_node_registry() -> Result<()> {
 
     use nodes::batch_text_embedding_node::BatchTextEmbeddingNode;
     use nodes::context_compact_node::ContextCompactNode;
+    use nodes::extract_node::ExtractNode;
+    use nodes::gated_llm_node::GatedLLMNode;
     use nodes::llm_infer_node::LLMInferNode;
     use nodes::llm_node::LlmNode;
     use nodes::load_local_text_embedder_node::LoadLocalTextEmbedderNode;
@@ -31,6 +37,13 @@ pub fn init_node_registry() -> Result<()> {
         "使用LLModel引用对消息列表进行一次推理",
         LLMInferNode
     );
+    register_node!(
+        "gated_llm_infer",
+        "条件LLM推理",
+        "AI",
+        "仅在 gate 为 true 时调用 LLM 推理，否则跳过并输出空回复",
+        GatedLLMNode
+    );
     register_node!(
         "context_compact",
         "上下文压缩",
@@ -38,6 +51,13 @@ pub fn init_node_registry() -> Result<()> {
         "压缩 LLMMessage 历史，仅保留摘要对和最近 2 条非 tool 消息",
         ContextCompactNode
     );
+    register_node!(
+        "llm_extract",
+        "结构化抽取",
+        "AI",
+        "让 LLM 按 JSON Schema 从自由文本中抽取结构化数据并校验",
+        ExtractNode
+    );
     register_node!(
         "load_text_embedder",
         "加载文本Embedder(API)",
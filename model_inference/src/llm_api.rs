@@ -8,28 +8,29 @@ use crate::llm_message::convert::{
     parse_responses_message_compat_sse_response, parse_responses_message_compat_sse_stream_response,
     parse_responses_response, parse_responses_sse_response, parse_responses_sse_stream_response,
 };
+use crate::request_retry::{
+    default_retry_policy, error_reply, send_with_retry, send_with_retry_async, RequestError, MAX_RETRY_DELAY,
+    USER_VISIBLE_REQUEST_ERROR,
+};
 use crate::system_config::{LlmApiStyle, ReasoningEffort, ThinkingType};
+use futures_util::stream::{self, Stream};
 use log::{debug, error, warn};
 use reqwest::blocking::Client;
 use reqwest::StatusCode;
 use serde_json::Value;
 use std::error::Error as _;
 use std::fmt::Write as _;
-use std::thread;
+use std::future::Future;
+use std::pin::Pin;
 use std::time::Duration;
 use tokio::sync::mpsc;
+use zihuan_core::error::Error;
 use zihuan_core::llm::llm_base::{LLMBase, StreamingLLMBase};
-use zihuan_core::llm::{InferenceParam, LLMMessage, StreamToken};
+use zihuan_core::llm::{InferenceParam, LLMMessage, MessagePart, StreamToken};
+use zihuan_core::utils::backoff::BackoffPolicy;
 use zihuan_core::utils::string_utils;
 
-const DEFAULT_RETRY_COUNT: u32 = 2;
-const RETRY_DELAY_MS: u64 = 1_000;
-const USER_VISIBLE_REQUEST_ERROR: &str = "Error: LLM API request failed";
-
-enum RequestError {
-    Retryable { message: String },
-    NonRetryable { message: String },
-}
+const DEFAULT_AUTH_HEADER: &str = "Authorization";
 
 #[derive(Debug, Clone)]
 struct RequestContext {
@@ -38,6 +39,16 @@ struct RequestContext {
     has_multimodal_input: bool,
 }
 
+impl RequestContext {
+    fn new(param: &InferenceParam) -> Self {
+        Self {
+            message_count: param.messages.len(),
+            tool_count: param.tools.as_ref().map(|tools| tools.len()).unwrap_or(0),
+            has_multimodal_input: has_multimodal_messages(param.messages),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LLMAPI {
     model_name: String,
@@ -50,7 +61,9 @@ pub struct LLMAPI {
     thinking_type: Option<ThinkingType>,
     reasoning_effort: Option<ReasoningEffort>,
     pub timeout: Duration,
-    retry_count: u32,
+    retry: BackoffPolicy,
+    auth_header_name: String,
+    extra_headers: Vec<(String, String)>,
 }
 
 impl LLMAPI {
@@ -129,7 +142,9 @@ impl LLMAPI {
             thinking_type,
             reasoning_effort,
             timeout,
-            retry_count: DEFAULT_RETRY_COUNT,
+            retry: default_retry_policy(),
+            auth_header_name: DEFAULT_AUTH_HEADER.to_string(),
+            extra_headers: Vec::new(),
         }
     }
 
@@ -138,11 +153,43 @@ impl LLMAPI {
         self
     }
 
+    /// Retries after the first attempt, keeping the current base delay.
     pub fn with_retry_count(mut self, retry_count: u32) -> Self {
-        self.retry_count = retry_count;
+        self.retry.max_attempts = retry_count.saturating_add(1);
+        self
+    }
+
+    /// Makes up to `max_attempts` requests in total, retrying rate limits,
+    /// gateway/server errors and network failures with exponential backoff
+    /// starting at `base_delay`, plus jitter.
+    pub fn with_retry(mut self, max_attempts: u32, base_delay: Duration) -> Self {
+        self.retry = Self::retry_policy(max_attempts, base_delay);
+        self
+    }
+
+    /// Adds headers sent with every request after the auth header, e.g.
+    /// `api-version` for Azure OpenAI or `HTTP-Referer` for OpenRouter.
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.extra_headers.extend(headers);
+        self
+    }
+
+    /// Sends the `OpenAI-Organization` header.
+    pub fn with_organization(self, organization: impl Into<String>) -> Self {
+        self.with_headers(vec![("OpenAI-Organization".to_string(), organization.into())])
+    }
+
+    /// Sends the API key in `name` instead of `Authorization`. Any other
+    /// header carries the bare key, as Azure OpenAI expects for `api-key`.
+    pub fn with_auth_header(mut self, name: impl Into<String>) -> Self {
+        self.auth_header_name = name.into();
         self
     }
 
+    fn retry_policy(max_attempts: u32, base_delay: Duration) -> BackoffPolicy {
+        BackoffPolicy::new(max_attempts.max(1), base_delay, MAX_RETRY_DELAY.max(base_delay))
+    }
+
     pub fn system_message(content: &str) -> LLMMessage {
         LLMMessage::system(content)
     }
@@ -151,8 +198,12 @@ impl LLMAPI {
         LLMMessage::user(content)
     }
 
-    fn should_retry_status(status: StatusCode) -> bool {
-        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    /// A user turn with `text` followed by one image part per URL (http(s) or
+    /// `data:` URL); sent as an OpenAI `content` array.
+    pub fn user_message_with_images(text: &str, image_urls: &[&str]) -> LLMMessage {
+        let mut parts = vec![MessagePart::text(text)];
+        parts.extend(image_urls.iter().map(|url| MessagePart::image_url_string(*url)));
+        LLMMessage::user_with_parts(parts)
     }
 
     fn endpoint_label(&self) -> &str {
@@ -231,6 +282,7 @@ impl LLMAPI {
             LlmApiStyle::OpenAiResponses => "open_ai_responses",
             LlmApiStyle::OpenAiResponsesMessageCompat => "open_ai_responses_message_compat",
             LlmApiStyle::OpenAiResponsesImageUrlObjectCompat => "open_ai_responses_image_url_object_compat",
+            LlmApiStyle::AnthropicMessages => "anthropic_messages",
         }
     }
 
@@ -247,6 +299,118 @@ impl LLMAPI {
         )
     }
 
+    fn authorization_header(&self) -> Option<String> {
+        self.api_key.as_ref().map(|api_key| {
+            if !self.auth_header_name.eq_ignore_ascii_case(DEFAULT_AUTH_HEADER) {
+                api_key.strip_prefix("Bearer ").unwrap_or(api_key).to_string()
+            } else if api_key.starts_with("Bearer ") {
+                api_key.to_string()
+            } else {
+                format!("Bearer {}", api_key)
+            }
+        })
+    }
+
+    /// The auth header, when a key is set, followed by the extra headers.
+    fn request_headers(&self) -> Vec<(&str, String)> {
+        let auth = self.authorization_header().map(|value| (self.auth_header_name.as_str(), value));
+        auth.into_iter()
+            .chain(self.extra_headers.iter().map(|(name, value)| (name.as_str(), value.clone())))
+            .collect()
+    }
+
+    fn build_request_body(&self, param: &InferenceParam, stream: bool) -> Value {
+        let mut request_body = if self.uses_responses_api() {
+            match self.api_style {
+                LlmApiStyle::OpenAiResponses => {
+                    build_responses_request_body(&self.model_name, param, stream, self.include_reasoning_content)
+                }
+                LlmApiStyle::OpenAiResponsesMessageCompat => build_responses_message_compat_request_body(
+                    &self.model_name,
+                    param,
+                    stream,
+                    self.include_reasoning_content,
+                ),
+                LlmApiStyle::OpenAiResponsesImageUrlObjectCompat => {
+                    build_responses_image_url_object_compat_request_body(
+                        &self.model_name,
+                        param,
+                        stream,
+                        self.include_reasoning_content,
+                    )
+                }
+                _ => unreachable!("non-responses style reached responses request builder"),
+            }
+        } else if matches!(self.api_style, LlmApiStyle::OpenAiChatCompletionsTencentMultimodalCompat) {
+            build_tencent_multimodal_chat_completions_request_body(
+                &self.model_name,
+                param,
+                stream,
+                self.include_reasoning_content,
+                self.thinking_type.as_ref(),
+                self.reasoning_effort.as_ref(),
+            )
+        } else {
+            build_chat_completions_request_body(
+                &self.model_name,
+                param,
+                stream,
+                self.include_reasoning_content,
+                self.thinking_type.as_ref(),
+                self.reasoning_effort.as_ref(),
+            )
+        };
+        self.apply_sampling_params(&mut request_body, param);
+        request_body
+    }
+
+    /// Adds the sampling overrides set on `param`; unset ones are left out so
+    /// the server defaults apply.
+    fn apply_sampling_params(&self, request_body: &mut Value, param: &InferenceParam) {
+        if let Some(temperature) = param.temperature {
+            request_body["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(top_p) = param.top_p {
+            request_body["top_p"] = serde_json::json!(top_p);
+        }
+        if let Some(max_tokens) = param.max_tokens {
+            let key = if self.uses_responses_api() {
+                "max_output_tokens"
+            } else {
+                "max_tokens"
+            };
+            request_body[key] = serde_json::json!(max_tokens);
+        }
+        if let Some(stop) = param.stop.as_ref().filter(|stop| !stop.is_empty()) {
+            if self.uses_responses_api() {
+                warn!(
+                    "[LLMAPI] The Responses API has no stop sequences; ignoring stop for model {}",
+                    self.model_name
+                );
+            } else {
+                request_body["stop"] = serde_json::json!(stop);
+            }
+        }
+    }
+
+    fn send_error(
+        &self,
+        error: &reqwest::Error,
+        request_context: &RequestContext,
+        attempt: u32,
+        max_attempts: u32,
+    ) -> RequestError {
+        RequestError::Retryable {
+            status: error.status().map(|status| status.as_u16()),
+            message: format!(
+                "{} detail={} message={}",
+                self.format_request_context(request_context, Some((attempt, max_attempts)),),
+                Self::describe_reqwest_error(error),
+                error
+            ),
+        }
+    }
+
     fn send_request(
         &self,
         client: &Client,
@@ -256,41 +420,65 @@ impl LLMAPI {
         max_attempts: u32,
     ) -> Result<LLMMessage, RequestError> {
         let mut request = client.post(&self.api_endpoint).json(request_body);
-
-        if let Some(ref api_key) = self.api_key {
-            let auth_header = if api_key.starts_with("Bearer ") {
-                api_key.to_string()
-            } else {
-                format!("Bearer {}", api_key)
-            };
-            request = request.header("Authorization", auth_header);
+        for (name, value) in self.request_headers() {
+            request = request.header(name, value);
         }
 
-        let response = request.send().map_err(|e| {
-            let err_detail = format!(
-                "{} detail={} message={}",
-                self.format_request_context(request_context, Some((attempt, max_attempts)),),
-                Self::describe_reqwest_error(&e),
-                e
-            );
-            RequestError::Retryable { message: err_detail }
-        })?;
+        let response = request
+            .send()
+            .map_err(|e| self.send_error(&e, request_context, attempt, max_attempts))?;
         let status = response.status();
         let response_text = response.text().unwrap_or_else(|_| "Failed to read response".to_string());
+        let message = self.parse_response(status, &response_text, request_context, attempt, max_attempts)?;
+        self.log_success(&message, request_context, attempt, max_attempts);
+        Ok(message)
+    }
+
+    async fn send_request_async(
+        &self,
+        client: &reqwest::Client,
+        request_body: &Value,
+        request_context: &RequestContext,
+        attempt: u32,
+        max_attempts: u32,
+    ) -> Result<LLMMessage, RequestError> {
+        let mut request = client.post(&self.api_endpoint).json(request_body);
+        for (name, value) in self.request_headers() {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| self.send_error(&e, request_context, attempt, max_attempts))?;
+        let status = response.status();
+        let response_text = response.text().await.unwrap_or_else(|_| "Failed to read response".to_string());
+        let message = self.parse_response(status, &response_text, request_context, attempt, max_attempts)?;
+        self.log_success(&message, request_context, attempt, max_attempts);
+        Ok(message)
+    }
 
+    fn parse_response(
+        &self,
+        status: StatusCode,
+        response_text: &str,
+        request_context: &RequestContext,
+        attempt: u32,
+        max_attempts: u32,
+    ) -> Result<LLMMessage, RequestError> {
         if self.stream {
             if let Some(message) = match self.uses_responses_api() {
                 true => match self.api_style {
-                    LlmApiStyle::OpenAiResponses => parse_responses_sse_response(&response_text),
+                    LlmApiStyle::OpenAiResponses => parse_responses_sse_response(response_text),
                     LlmApiStyle::OpenAiResponsesMessageCompat => {
-                        parse_responses_message_compat_sse_response(&response_text)
+                        parse_responses_message_compat_sse_response(response_text)
                     }
                     LlmApiStyle::OpenAiResponsesImageUrlObjectCompat => {
-                        parse_responses_image_url_object_compat_sse_response(&response_text)
+                        parse_responses_image_url_object_compat_sse_response(response_text)
                     }
                     _ => unreachable!("non-responses style reached responses sse parser"),
                 },
-                _ => parse_chat_completions_sse_response(&response_text),
+                _ => parse_chat_completions_sse_response(response_text),
             } {
                 if matches!(self.api_style, LlmApiStyle::OpenAiResponsesMessageCompat)
                     && message.parts.is_empty()
@@ -315,21 +503,18 @@ impl LLMAPI {
                 "{} status={} body={}",
                 self.format_request_context(request_context, Some((attempt, max_attempts)),),
                 status,
-                string_utils::shorten_text(&response_text, 800)
+                string_utils::shorten_text(response_text, 800)
             );
-            return if Self::should_retry_status(status) {
-                Err(RequestError::Retryable { message: err_msg })
-            } else {
-                Err(RequestError::NonRetryable { message: err_msg })
-            };
+            return Err(RequestError::from_status(status, err_msg));
         }
 
-        let api_resp = serde_json::from_str::<Value>(&response_text).map_err(|e| RequestError::NonRetryable {
+        let api_resp = serde_json::from_str::<Value>(response_text).map_err(|e| RequestError::NonRetryable {
+            status: None,
             message: format!(
                 "{} parse_error={} body={}",
                 self.format_request_context(request_context, Some((attempt, max_attempts)),),
                 e,
-                string_utils::shorten_text(&response_text, 800)
+                string_utils::shorten_text(response_text, 800)
             ),
         })?;
 
@@ -364,14 +549,64 @@ impl LLMAPI {
         parsed_message
             .map(|message| self.tag_response_api_style(message))
             .ok_or_else(|| RequestError::NonRetryable {
+                status: None,
                 message: format!(
                     "{} invalid_response choices_present={} body={}",
                     self.format_request_context(request_context, Some((attempt, max_attempts)),),
                     api_resp.get("choices").is_some() || api_resp.get("output").is_some(),
-                    string_utils::shorten_text(&response_text, 800)
+                    string_utils::shorten_text(response_text, 800)
                 ),
             })
     }
+
+    fn log_success(&self, message: &LLMMessage, request_context: &RequestContext, attempt: u32, max_attempts: u32) {
+        if let Some(usage) = message.usage.as_ref() {
+            self.log_usage(request_context, usage);
+        }
+        debug!(
+            "Successfully parsed API response: {}",
+            self.format_request_context(request_context, Some((attempt, max_attempts)),)
+        );
+    }
+
+    fn local_style_error() -> Error {
+        error!("Local Candle styles should be routed through the local runtime, not LLMAPI");
+        Error::LlmApi {
+            status: None,
+            message: USER_VISIBLE_REQUEST_ERROR.to_string(),
+        }
+    }
+
+    /// Async counterpart of [`LLMBase::inference`]: same request, retries and
+    /// error reply, but awaits the HTTP call instead of blocking the thread.
+    pub async fn inference_async(&self, param: &InferenceParam<'_>) -> LLMMessage {
+        self.try_inference_async(param).await.unwrap_or_else(error_reply)
+    }
+
+    /// Like [`LLMAPI::inference_async`], but a failed request is returned as
+    /// [`Error::LlmApi`] instead of an `"Error: ..."` reply.
+    pub async fn try_inference_async(&self, param: &InferenceParam<'_>) -> Result<LLMMessage, Error> {
+        if matches!(self.api_style, LlmApiStyle::CandleGguf | LlmApiStyle::CandleHf) {
+            return Err(Self::local_style_error());
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .expect("Failed to create async HTTP client");
+
+        let request_context = RequestContext::new(param);
+        let request_body = self.build_request_body(param, self.stream);
+
+        send_with_retry_async("LLM API", &self.retry, |attempt, max_attempts| {
+            debug!(
+                "Sending LLM API request: {}",
+                self.format_request_context(&request_context, Some((attempt, max_attempts)),)
+            );
+            self.send_request_async(&client, &request_body, &request_context, attempt, max_attempts)
+        })
+        .await
+    }
 }
 
 impl LLMBase for LLMAPI {
@@ -392,9 +627,12 @@ impl LLMBase for LLMAPI {
     }
 
     fn inference(&self, param: &InferenceParam) -> LLMMessage {
+        self.try_inference(param).unwrap_or_else(error_reply)
+    }
+
+    fn try_inference(&self, param: &InferenceParam) -> Result<LLMMessage, Error> {
         if matches!(self.api_style, LlmApiStyle::CandleGguf | LlmApiStyle::CandleHf) {
-            error!("Local Candle styles should be routed through the local runtime, not LLMAPI");
-            return LLMMessage::assistant_text(USER_VISIBLE_REQUEST_ERROR);
+            return Err(Self::local_style_error());
         }
 
         let client = Client::builder()
@@ -402,105 +640,73 @@ impl LLMBase for LLMAPI {
             .build()
             .expect("Failed to create HTTP client");
 
-        let request_context = RequestContext {
-            message_count: param.messages.len(),
-            tool_count: param.tools.as_ref().map(|tools| tools.len()).unwrap_or(0),
-            has_multimodal_input: has_multimodal_messages(param.messages),
-        };
-        let request_body = if self.uses_responses_api() {
-            match self.api_style {
-                LlmApiStyle::OpenAiResponses => {
-                    build_responses_request_body(&self.model_name, param, self.stream, self.include_reasoning_content)
-                }
-                LlmApiStyle::OpenAiResponsesMessageCompat => build_responses_message_compat_request_body(
-                    &self.model_name,
-                    param,
-                    self.stream,
-                    self.include_reasoning_content,
-                ),
-                LlmApiStyle::OpenAiResponsesImageUrlObjectCompat => {
-                    build_responses_image_url_object_compat_request_body(
-                        &self.model_name,
-                        param,
-                        self.stream,
-                        self.include_reasoning_content,
-                    )
-                }
-                _ => unreachable!("non-responses style reached responses request builder"),
-            }
-        } else if matches!(self.api_style, LlmApiStyle::OpenAiChatCompletionsTencentMultimodalCompat) {
-            build_tencent_multimodal_chat_completions_request_body(
-                &self.model_name,
-                param,
-                self.stream,
-                self.include_reasoning_content,
-                self.thinking_type.as_ref(),
-                self.reasoning_effort.as_ref(),
-            )
-        } else {
-            build_chat_completions_request_body(
-                &self.model_name,
-                param,
-                self.stream,
-                self.include_reasoning_content,
-                self.thinking_type.as_ref(),
-                self.reasoning_effort.as_ref(),
-            )
-        };
-        let max_attempts = self.retry_count.saturating_add(1);
-        let mut last_error = None;
+        let request_context = RequestContext::new(param);
+        let request_body = self.build_request_body(param, self.stream);
 
-        for attempt in 1..=max_attempts {
+        send_with_retry("LLM API", &self.retry, |attempt, max_attempts| {
             debug!(
                 "Sending LLM API request: {}",
                 self.format_request_context(&request_context, Some((attempt, max_attempts)),)
             );
+            self.send_request(&client, &request_body, &request_context, attempt, max_attempts)
+        })
+    }
+
+    fn inference_async<'a>(
+        &'a self,
+        param: &'a InferenceParam<'a>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = LLMMessage> + Send + 'a>> {
+        Box::pin(async move { self.inference_async(param).await })
+    }
+}
 
-            match self.send_request(&client, &request_body, &request_context, attempt, max_attempts) {
-                Ok(msg) => {
-                    if let Some(usage) = msg.usage.as_ref() {
-                        self.log_usage(&request_context, usage);
+/// Item of [`LLMAPI::inference_stream`].
+#[derive(Debug, Clone)]
+pub enum InferenceStreamItem {
+    /// Incremental assistant text, in generation order.
+    Text(String),
+    /// The complete reply, including tool calls assembled from their deltas.
+    /// Always the last item of a successful stream.
+    Done(LLMMessage),
+}
+
+type StreamingInference<'a> = Pin<Box<dyn Future<Output = Result<LLMMessage, Error>> + Send + 'a>>;
+
+/// Drives a streaming request while handing out the content tokens it emits.
+struct InferenceStreamState<'a> {
+    inference: Option<StreamingInference<'a>>,
+    token_rx: mpsc::UnboundedReceiver<StreamToken>,
+    result: Option<Result<LLMMessage, Error>>,
+}
+
+impl InferenceStreamState<'_> {
+    async fn next_item(&mut self) -> Option<Result<InferenceStreamItem, Error>> {
+        loop {
+            if let Some(inference) = self.inference.as_mut() {
+                tokio::select! {
+                    biased;
+                    Some(token) = self.token_rx.recv() => {
+                        if let StreamToken::Content(text) = token {
+                            return Some(Ok(InferenceStreamItem::Text(text)));
+                        }
                     }
-                    debug!(
-                        "Successfully parsed API response: {}",
-                        self.format_request_context(&request_context, Some((attempt, max_attempts)),)
-                    );
-                    return msg;
-                }
-                Err(RequestError::Retryable { message }) => {
-                    last_error = Some(message.clone());
-
-                    if attempt < max_attempts {
-                        warn!(
-                            "LLM API request failed on attempt {}/{} and will retry: {}",
-                            attempt, max_attempts, message
-                        );
-                        thread::sleep(Duration::from_millis(RETRY_DELAY_MS));
-                    } else {
-                        error!("LLM API request failed on attempt {}/{}: {}", attempt, max_attempts, message);
+                    result = inference => {
+                        self.inference = None;
+                        self.result = Some(result);
                     }
                 }
-                Err(RequestError::NonRetryable { message }) => {
-                    error!(
-                        "LLM API request failed on attempt {}/{} without retry: {}",
-                        attempt, max_attempts, message
-                    );
-                    last_error = Some(message);
-                    break;
-                }
+                continue;
             }
-        }
 
-        if let Some(err_msg) = last_error {
-            error!(
-                "Returning sanitized LLM API error to caller; detailed error kept in logs: {}",
-                err_msg
-            );
-        } else {
-            error!("Returning sanitized LLM API error to caller without detailed context");
+            // Tokens emitted in the same poll that finished the request.
+            match self.token_rx.try_recv() {
+                Ok(StreamToken::Content(text)) => return Some(Ok(InferenceStreamItem::Text(text))),
+                Ok(_) => continue,
+                Err(_) => {
+                    return Some(self.result.take()?.map(InferenceStreamItem::Done));
+                }
+            }
         }
-
-        LLMMessage::assistant_text(USER_VISIBLE_REQUEST_ERROR)
     }
 }
 
@@ -510,90 +716,94 @@ impl LLMAPI {
         param: &InferenceParam<'_>,
         token_tx: mpsc::UnboundedSender<StreamToken>,
     ) -> LLMMessage {
+        self.send_streaming_request(param, token_tx).await.unwrap_or_else(error_reply)
+    }
+
+    /// Streams the reply as it is generated: a [`InferenceStreamItem::Text`] per
+    /// content delta, then one [`InferenceStreamItem::Done`]. A failed request
+    /// ends the stream with an error instead.
+    pub fn inference_stream<'a>(
+        &'a self,
+        param: &'a InferenceParam<'a>,
+    ) -> impl Stream<Item = Result<InferenceStreamItem, Error>> + Send + 'a {
+        let (token_tx, token_rx) = mpsc::unbounded_channel();
+        let state = InferenceStreamState {
+            inference: Some(Box::pin(self.send_streaming_request(param, token_tx))),
+            token_rx,
+            result: None,
+        };
+        stream::unfold(state, |mut state| async move {
+            let item = state.next_item().await?;
+            Some((item, state))
+        })
+    }
+
+    async fn send_streaming_request(
+        &self,
+        param: &InferenceParam<'_>,
+        token_tx: mpsc::UnboundedSender<StreamToken>,
+    ) -> Result<LLMMessage, Error> {
         if matches!(self.api_style, LlmApiStyle::CandleGguf | LlmApiStyle::CandleHf) {
-            error!("Local Candle styles should be routed through the local runtime, not LLMAPI");
-            return LLMMessage::assistant_text(USER_VISIBLE_REQUEST_ERROR);
+            return Err(Self::local_style_error());
         }
 
-        let request_context = RequestContext {
-            message_count: param.messages.len(),
-            tool_count: param.tools.as_ref().map(|tools| tools.len()).unwrap_or(0),
-            has_multimodal_input: has_multimodal_messages(param.messages),
-        };
-        let request_body = if self.uses_responses_api() {
-            match self.api_style {
-                LlmApiStyle::OpenAiResponses => {
-                    build_responses_request_body(&self.model_name, param, true, self.include_reasoning_content)
-                }
-                LlmApiStyle::OpenAiResponsesMessageCompat => build_responses_message_compat_request_body(
-                    &self.model_name,
-                    param,
-                    true,
-                    self.include_reasoning_content,
-                ),
-                LlmApiStyle::OpenAiResponsesImageUrlObjectCompat => {
-                    build_responses_image_url_object_compat_request_body(
-                        &self.model_name,
-                        param,
-                        true,
-                        self.include_reasoning_content,
-                    )
-                }
-                _ => unreachable!("non-responses style reached responses request builder"),
-            }
-        } else if matches!(self.api_style, LlmApiStyle::OpenAiChatCompletionsTencentMultimodalCompat) {
-            build_tencent_multimodal_chat_completions_request_body(
-                &self.model_name,
-                param,
-                true,
-                self.include_reasoning_content,
-                self.thinking_type.as_ref(),
-                self.reasoning_effort.as_ref(),
-            )
-        } else {
-            build_chat_completions_request_body(
-                &self.model_name,
-                param,
-                true,
-                self.include_reasoning_content,
-                self.thinking_type.as_ref(),
-                self.reasoning_effort.as_ref(),
-            )
-        };
+        let request_context = RequestContext::new(param);
+        let request_body = self.build_request_body(param, true);
 
         let client = reqwest::Client::builder()
             .timeout(self.timeout)
             .build()
             .expect("Failed to create async HTTP client");
 
-        let mut request = client.post(&self.api_endpoint).json(&request_body);
-        if let Some(ref api_key) = self.api_key {
-            let auth_header = if api_key.starts_with("Bearer ") {
-                api_key.to_string()
-            } else {
-                format!("Bearer {}", api_key)
-            };
-            request = request.header("Authorization", auth_header);
-        }
+        // The helper holds an LLM slot per attempt, so a successful attempt
+        // keeps it until the whole stream has been read.
+        send_with_retry_async("Streaming LLM API", &self.retry, |attempt, max_attempts| {
+            debug!(
+                "Sending streaming LLM API request: {}",
+                self.format_request_context(&request_context, Some((attempt, max_attempts)),)
+            );
+            self.send_streaming_attempt(
+                &client,
+                &request_body,
+                &request_context,
+                token_tx.clone(),
+                attempt,
+                max_attempts,
+            )
+        })
+        .await
+    }
 
-        let response = match request.send().await {
-            Ok(r) => r,
-            Err(e) => {
-                error!("Streaming LLM API request failed: {e}");
-                return LLMMessage::assistant_text(USER_VISIBLE_REQUEST_ERROR);
-            }
-        };
+    async fn send_streaming_attempt(
+        &self,
+        client: &reqwest::Client,
+        request_body: &Value,
+        request_context: &RequestContext,
+        token_tx: mpsc::UnboundedSender<StreamToken>,
+        attempt: u32,
+        max_attempts: u32,
+    ) -> Result<LLMMessage, RequestError> {
+        let mut request = client.post(&self.api_endpoint).json(request_body);
+        for (name, value) in self.request_headers() {
+            request = request.header(name, value);
+        }
 
+        let response = request
+            .send()
+            .await
+            .map_err(|e| self.send_error(&e, request_context, attempt, max_attempts))?;
         let status = response.status();
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
-            error!(
-                "Streaming LLM API request failed: {} status={} body={}",
-                self.format_request_context(&request_context, None),
+            return Err(RequestError::from_status(
                 status,
-                string_utils::shorten_text(&body, 800)
-            );
-            return LLMMessage::assistant_text(USER_VISIBLE_REQUEST_ERROR);
+                format!(
+                    "{} status={} body={}",
+                    self.format_request_context(request_context, Some((attempt, max_attempts)),),
+                    status,
+                    string_utils::shorten_text(&body, 800)
+                ),
+            ));
         }
 
         let message = match self.uses_responses_api() {
@@ -611,9 +821,9 @@ impl LLMAPI {
         };
         let message = self.tag_response_api_style(message);
         if let Some(usage) = message.usage.as_ref() {
-            self.log_usage(&request_context, usage);
+            self.log_usage(request_context, usage);
         }
-        message
+        Ok(message)
     }
 }
 
@@ -626,3 +836,268 @@ impl StreamingLLMBase for LLMAPI {
         Box::pin(async move { self.inference_streaming(param, token_tx).await })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+    use zihuan_core::test_util::{MockHttpServer, MockReply};
+
+    const CHAT_REPLY: &str =
+        r#"{"choices":[{"index":0,"message":{"role":"assistant","content":"你好"},"finish_reason":"stop"}]}"#;
+
+    async fn mock_endpoint(replies: Vec<MockReply>) -> String {
+        MockHttpServer::start("/v1/chat/completions", replies).await.url()
+    }
+
+    fn api(endpoint: String, timeout: Duration) -> LLMAPI {
+        LLMAPI::new(
+            "test-model".to_string(),
+            endpoint,
+            Some("test-key".to_string()),
+            LlmApiStyle::OpenAiChatCompletions,
+            false,
+            false,
+            false,
+            None,
+            None,
+            timeout,
+        )
+        .with_retry_count(0)
+    }
+
+    #[tokio::test]
+    async fn inference_async_parses_chat_completion() {
+        let endpoint = mock_endpoint(vec![MockReply::new(200, CHAT_REPLY)]).await;
+        let messages = vec![LLMMessage::user("hi")];
+
+        let reply = api(endpoint, Duration::from_secs(5))
+            .inference_async(&InferenceParam::new(&messages))
+            .await;
+
+        assert_eq!(reply.content_text_owned().as_deref(), Some("你好"));
+    }
+
+    #[tokio::test]
+    async fn inference_async_times_out_with_the_usual_error_reply() {
+        let endpoint = mock_endpoint(vec![MockReply::Hang]).await;
+        let messages = vec![LLMMessage::user("hi")];
+
+        let reply = tokio::time::timeout(
+            Duration::from_secs(5),
+            api(endpoint, Duration::from_millis(200)).inference_async(&InferenceParam::new(&messages)),
+        )
+        .await
+        .expect("request timeout should end the inference");
+
+        assert_eq!(
+            reply.content_text_owned().as_deref(),
+            Some("Error: LLM API request failed after 1 attempt(s)")
+        );
+    }
+
+    #[tokio::test]
+    async fn inference_stream_yields_text_deltas_then_the_assembled_reply() {
+        let endpoint = mock_endpoint(vec![MockReply::new(
+            200,
+            concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"你\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"好\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"type\":\"function\",\"function\":{\"name\":\"search\",\"arguments\":\"{\\\"q\\\":\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"rust\\\"}\"}}]}}]}\n\n",
+            "data: [DONE]\n\n",
+            ),
+        )])
+        .await;
+        let messages = vec![LLMMessage::user("hi")];
+        let param = InferenceParam::new(&messages);
+        let llm = api(endpoint, Duration::from_secs(5));
+
+        let items: Vec<_> = llm.inference_stream(&param).collect().await;
+
+        let mut text = Vec::new();
+        let mut done = None;
+        for item in items {
+            match item.expect("stream item should not be an error") {
+                InferenceStreamItem::Text(piece) => text.push(piece),
+                InferenceStreamItem::Done(message) => done = Some(message),
+            }
+        }
+        assert_eq!(text, vec!["你", "好"]);
+        let done = done.expect("stream should end with the assembled reply");
+        assert_eq!(done.content_text_owned().as_deref(), Some("你好"));
+        assert_eq!(done.tool_calls.len(), 1);
+        assert_eq!(done.tool_calls[0].function.name, "search");
+        assert_eq!(done.tool_calls[0].function.arguments, json!({ "q": "rust" }));
+    }
+
+    #[tokio::test]
+    async fn inference_stream_ends_with_an_error_when_the_request_fails() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/v1/chat/completions", listener.local_addr().unwrap());
+        drop(listener);
+        let messages = vec![LLMMessage::user("hi")];
+        let param = InferenceParam::new(&messages);
+        let llm = api(endpoint, Duration::from_secs(5));
+
+        let items: Vec<_> = llm.inference_stream(&param).collect().await;
+
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(Error::LlmApi { status: None, .. })));
+    }
+
+    #[tokio::test]
+    async fn inference_stream_error_keeps_the_http_status() {
+        let endpoint = mock_endpoint(vec![MockReply::new(401, "{}")]).await;
+        let messages = vec![LLMMessage::user("hi")];
+        let param = InferenceParam::new(&messages);
+        let llm = api(endpoint, Duration::from_secs(5));
+
+        let items: Vec<_> = llm.inference_stream(&param).collect().await;
+
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(Error::LlmApi { status: Some(401), .. })));
+    }
+
+    #[tokio::test]
+    async fn inference_stream_retries_a_rate_limited_request() {
+        let endpoint = mock_endpoint(vec![
+            MockReply::new(429, "{}"),
+            MockReply::new(
+                200,
+                "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"你好\"}}]}\n\ndata: [DONE]\n\n",
+            ),
+        ])
+        .await;
+        let messages = vec![LLMMessage::user("hi")];
+        let param = InferenceParam::new(&messages);
+        let llm = api(endpoint, Duration::from_secs(5)).with_retry(3, Duration::from_millis(10));
+
+        let items: Vec<_> = llm.inference_stream(&param).collect().await;
+
+        match items.last() {
+            Some(Ok(InferenceStreamItem::Done(message))) => {
+                assert_eq!(message.content_text_owned().as_deref(), Some("你好"));
+            }
+            other => panic!("expected the assembled reply, got {other:?}"),
+        }
+    }
+
+    async fn infer(llm: &LLMAPI) -> String {
+        let messages = vec![LLMMessage::user("hi")];
+        llm.inference_async(&InferenceParam::new(&messages))
+            .await
+            .content_text_owned()
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried_until_the_request_succeeds() {
+        let endpoint = mock_endpoint(vec![
+            MockReply::new(429, "{}"),
+            MockReply::new(503, "{}"),
+            MockReply::new(200, CHAT_REPLY),
+        ])
+        .await;
+        let llm = api(endpoint, Duration::from_secs(5)).with_retry(3, Duration::from_millis(10));
+
+        assert_eq!(infer(&llm).await, "你好");
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_report_the_attempt_count() {
+        let endpoint = mock_endpoint(vec![MockReply::new(502, "{}")]).await;
+        let llm = api(endpoint, Duration::from_secs(5)).with_retry(3, Duration::from_millis(10));
+
+        assert_eq!(infer(&llm).await, "Error: LLM API request failed after 3 attempt(s)");
+    }
+
+    #[tokio::test]
+    async fn client_errors_fail_fast() {
+        let endpoint = mock_endpoint(vec![MockReply::new(401, "{}"), MockReply::new(200, CHAT_REPLY)]).await;
+        let llm = api(endpoint, Duration::from_secs(5)).with_retry(3, Duration::from_millis(10));
+
+        assert_eq!(infer(&llm).await, "Error: LLM API request failed after 1 attempt(s)");
+    }
+
+    #[tokio::test]
+    async fn try_inference_returns_the_last_status_as_an_error() {
+        let endpoint = mock_endpoint(vec![MockReply::new(503, "{}"), MockReply::new(401, "{}")]).await;
+        let llm = api(endpoint, Duration::from_secs(5)).with_retry(3, Duration::from_millis(10));
+        let messages = vec![LLMMessage::user("hi")];
+        let param = InferenceParam::new(&messages);
+
+        match llm.try_inference_async(&param).await {
+            Err(Error::LlmApi { status, message }) => {
+                assert_eq!(status, Some(401));
+                assert_eq!(message, "LLM API request failed after 2 attempt(s)");
+            }
+            other => panic!("expected an LLM API error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn auth_header_can_be_renamed_and_extra_headers_follow_it() {
+        let default = api("http://localhost/v1/chat/completions".to_string(), Duration::from_secs(5));
+        assert_eq!(
+            default.request_headers(),
+            vec![("Authorization", "Bearer test-key".to_string())]
+        );
+
+        let azure = default
+            .with_auth_header("api-key")
+            .with_headers(vec![("api-version".to_string(), "2024-06-01".to_string())])
+            .with_organization("org-1");
+        assert_eq!(
+            azure.request_headers(),
+            vec![
+                ("api-key", "test-key".to_string()),
+                ("api-version", "2024-06-01".to_string()),
+                ("OpenAI-Organization", "org-1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn sampling_params_are_sent_only_when_set() {
+        let messages = vec![LLMMessage::user("hi")];
+        let mut param = InferenceParam::new(&messages);
+        let llm = api("http://127.0.0.1:1/v1/chat/completions".to_string(), Duration::from_secs(5));
+
+        let body = llm.build_request_body(&param, false);
+        for key in ["temperature", "top_p", "max_tokens", "stop"] {
+            assert!(body.get(key).is_none(), "{key} should be left to the server default");
+        }
+
+        param.temperature = Some(0.5);
+        param.top_p = Some(0.9);
+        param.max_tokens = Some(256);
+        param.stop = Some(vec!["\n\n".to_string()]);
+        let body = llm.build_request_body(&param, false);
+        assert_eq!(body["temperature"], json!(0.5));
+        assert_eq!(body["top_p"], json!(0.9f32));
+        assert_eq!(body["max_tokens"], json!(256));
+        assert_eq!(body["stop"], json!(["\n\n"]));
+    }
+
+    #[test]
+    fn image_parts_are_sent_as_a_content_array() {
+        let messages = vec![
+            LLMAPI::user_message("hi"),
+            LLMAPI::user_message_with_images("这是什么？", &["https://example.com/cat.png"]),
+        ];
+        let param = InferenceParam::new(&messages);
+        let llm = api("http://127.0.0.1:1/v1/chat/completions".to_string(), Duration::from_secs(5));
+
+        let body = llm.build_request_body(&param, false);
+
+        assert_eq!(body["messages"][0]["content"], json!("hi"));
+        let content = body["messages"][1]["content"].as_array().unwrap();
+        assert_eq!(content[0]["type"], "text");
+        assert_eq!(content[0]["text"], "这是什么？");
+        assert_eq!(content[1]["type"], "image_url");
+        assert_eq!(content[1]["image_url"]["url"], "https://example.com/cat.png");
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+use zihuan_core::runtime::block_async;
+use zihuan_core::system_config::{load_section, GlobalSettingsSection};
+use zihuan_core::utils::rate_limiter::RateLimiter;
+
+/// Caps how many LLM requests run at once. Callers past the limit wait, blocking
+/// or async, until a running request drops its permit.
+pub struct LlmConcurrencyLimiter {
+    /// `None` when unlimited.
+    semaphore: Option<Semaphore>,
+    limit: usize,
+    in_flight: AtomicUsize,
+}
+
+/// Held for the duration of one request; releases its slot on drop.
+pub struct LlmPermit<'a> {
+    _permit: Option<SemaphorePermit<'a>>,
+    in_flight: &'a AtomicUsize,
+}
+
+impl Drop for LlmPermit<'_> {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl LlmConcurrencyLimiter {
+    /// `0` means unlimited.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            semaphore: (limit != 0).then(|| Semaphore::new(limit)),
+            limit,
+            in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Requests currently holding a permit.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    fn permit<'a>(&'a self, permit: Option<SemaphorePermit<'a>>) -> LlmPermit<'a> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        LlmPermit {
+            _permit: permit,
+            in_flight: &self.in_flight,
+        }
+    }
+
+    /// Block the current thread until a slot is free.
+    pub fn acquire(&self) -> LlmPermit<'_> {
+        let permit = self.semaphore.as_ref().map(|semaphore| {
+            semaphore
+                .try_acquire()
+                .or_else(|_| block_async(semaphore.acquire()))
+                .expect("LLM concurrency semaphore is never closed")
+        });
+        self.permit(permit)
+    }
+
+    /// Wait asynchronously until a slot is free.
+    pub async fn acquire_async(&self) -> LlmPermit<'_> {
+        let permit = match &self.semaphore {
+            Some(semaphore) => Some(semaphore.acquire().await.expect("LLM concurrency semaphore is never closed")),
+            None => None,
+        };
+        self.permit(permit)
+    }
+}
+
+/// Process-wide limiter shared by every `LLMAPI`, sized by
+/// `global_settings.max_concurrent_llm`. Edits take effect on restart.
+pub fn llm_concurrency() -> &'static LlmConcurrencyLimiter {
+    static LIMITER: OnceLock<LlmConcurrencyLimiter> = OnceLock::new();
+    LIMITER.get_or_init(|| {
+        let limit = load_section::<GlobalSettingsSection>()
+            .map(|settings| settings.max_concurrent_llm)
+            .unwrap_or_else(|err| {
+                log::warn!("[llm_concurrency] failed to load global settings, not limiting LLM requests: {err}");
+                0
+            });
+        LlmConcurrencyLimiter::new(limit)
+    })
+}
+
+/// Process-wide token bucket shared by every LLM client, configured by
+/// `global_settings.llm_requests_per_second` and `llm_burst`. Edits take effect
+/// on restart.
+pub fn llm_rate_limiter() -> &'static RateLimiter {
+    static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+    LIMITER.get_or_init(|| match load_section::<GlobalSettingsSection>() {
+        Ok(settings) => RateLimiter::new(settings.llm_requests_per_second, settings.llm_burst),
+        Err(err) => {
+            log::warn!("[llm_concurrency] failed to load global settings, not rate limiting LLM requests: {err}");
+            RateLimiter::unlimited()
+        }
+    })
+}
+
+/// Block until the rate limit lets a request start and a concurrency slot is
+/// free.
+pub fn acquire_llm_slot() -> LlmPermit<'static> {
+    llm_rate_limiter().acquire();
+    llm_concurrency().acquire()
+}
+
+/// Async [`acquire_llm_slot`].
+pub async fn acquire_llm_slot_async() -> LlmPermit<'static> {
+    llm_rate_limiter().acquire_async().await;
+    llm_concurrency().acquire_async().await
+}
+
+/// LLM requests currently in flight across the process.
+pub fn llm_in_flight() -> usize {
+    llm_concurrency().in_flight()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn third_request_waits_for_a_free_slot() {
+        let limiter = Arc::new(LlmConcurrencyLimiter::new(2));
+        let first = limiter.acquire();
+        let _second = limiter.acquire();
+        assert_eq!(limiter.in_flight(), 2);
+
+        let (acquired_tx, acquired_rx) = mpsc::channel();
+        let waiter = {
+            let limiter = Arc::clone(&limiter);
+            thread::spawn(move || {
+                let _third = limiter.acquire();
+                acquired_tx.send(()).unwrap();
+            })
+        };
+
+        assert!(acquired_rx.recv_timeout(Duration::from_millis(200)).is_err());
+        assert_eq!(limiter.in_flight(), 2);
+
+        drop(first);
+        acquired_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("third request should start once a slot frees up");
+        waiter.join().unwrap();
+        assert_eq!(limiter.in_flight(), 1);
+    }
+}
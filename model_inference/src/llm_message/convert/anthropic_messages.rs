@@ -0,0 +1,318 @@
+use serde_json::{json, Value};
+use zihuan_core::llm::tooling::{ToolCalls, ToolCallsFuncSpec};
+use zihuan_core::llm::{InferenceParam, LLMMessage, LLMMessageConvertStyle, MessagePart, MessageRole, TokenUsage};
+
+/// The Messages API requires `max_tokens`; used when the call sets none.
+pub const DEFAULT_ANTHROPIC_MAX_TOKENS: u32 = 4096;
+
+fn system_prompt(messages: &[LLMMessage]) -> Option<String> {
+    let system = messages
+        .iter()
+        .filter(|message| matches!(message.role, MessageRole::System))
+        .filter_map(|message| message.content_text_owned())
+        .filter(|text| !text.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (!system.is_empty()).then_some(system)
+}
+
+pub fn build_anthropic_messages_request_body(model_name: &str, param: &InferenceParam<'_>) -> Value {
+    let mut request_body = json!({
+        "model": model_name,
+        "messages": LLMMessage::convert_list(param.messages, LLMMessageConvertStyle::AnthropicMessages, false),
+        "max_tokens": param.max_tokens.unwrap_or(DEFAULT_ANTHROPIC_MAX_TOKENS),
+    });
+
+    if let Some(system) = system_prompt(param.messages) {
+        request_body["system"] = json!(system);
+    }
+    if let Some(temperature) = param.temperature {
+        request_body["temperature"] = json!(temperature);
+    }
+    if let Some(top_p) = param.top_p {
+        request_body["top_p"] = json!(top_p);
+    }
+    if let Some(stop) = param.stop.as_ref().filter(|stop| !stop.is_empty()) {
+        request_body["stop_sequences"] = json!(stop);
+    }
+
+    if let Some(tools) = param.active_tools() {
+        let tool_list = tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "name": tool.name(),
+                    "description": tool.description(),
+                    "input_schema": tool.parameters(),
+                })
+            })
+            .collect::<Vec<_>>();
+        request_body["tools"] = json!(tool_list);
+        request_body["tool_choice"] = match param.required_tool {
+            Some(name) => json!({ "type": "tool", "name": name }),
+            None => json!({ "type": "auto" }),
+        };
+    }
+
+    request_body
+}
+
+/// Anthropic reports uncached input separately from cache reads and writes;
+/// `prompt_tokens` is their sum so it matches the OpenAI meaning.
+fn parse_token_usage(value: Option<&Value>) -> Option<TokenUsage> {
+    let value = value?;
+    let count = |key: &str| value.get(key).and_then(Value::as_u64).map(|v| v as usize);
+
+    let input_tokens = count("input_tokens");
+    let cache_read = count("cache_read_input_tokens");
+    let cache_creation = count("cache_creation_input_tokens");
+    let completion_tokens = count("output_tokens");
+    let prompt_tokens =
+        input_tokens.map(|input| input + cache_read.unwrap_or_default() + cache_creation.unwrap_or_default());
+
+    Some(TokenUsage {
+        prompt_tokens,
+        cached_prompt_tokens: cache_read,
+        prompt_cache_miss_tokens: input_tokens.map(|input| input + cache_creation.unwrap_or_default()),
+        completion_tokens,
+        total_tokens: prompt_tokens
+            .zip(completion_tokens)
+            .map(|(prompt, completion)| prompt + completion),
+    })
+}
+
+/// Reads the `content` block array of a Messages API reply: `text` blocks
+/// become the reply text, `thinking` blocks the reasoning content and
+/// `tool_use` blocks tool calls.
+pub fn parse_anthropic_messages_response(api_resp: &Value) -> Option<LLMMessage> {
+    let blocks = api_resp.get("content")?.as_array()?;
+
+    let mut text = String::new();
+    let mut thinking = String::new();
+    let mut tool_calls = Vec::new();
+    for block in blocks {
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => text.push_str(block.get("text").and_then(Value::as_str).unwrap_or_default()),
+            Some("thinking") => thinking.push_str(block.get("thinking").and_then(Value::as_str).unwrap_or_default()),
+            Some("tool_use") => tool_calls.push(ToolCalls {
+                id: block.get("id").and_then(Value::as_str).unwrap_or_default().to_string(),
+                type_name: "function".to_string(),
+                function: ToolCallsFuncSpec {
+                    name: block.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+                    arguments: block.get("input").cloned().unwrap_or_else(|| json!({})),
+                },
+            }),
+            _ => {}
+        }
+    }
+
+    Some(LLMMessage {
+        role: MessageRole::Assistant,
+        parts: if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![MessagePart::text(text)]
+        },
+        reasoning_content: (!thinking.is_empty()).then_some(thinking),
+        tool_calls,
+        tool_call_id: None,
+        name: None,
+        usage: parse_token_usage(api_resp.get("usage")),
+    })
+}
+
+/// Rebuilds a Messages API reply from its `stream: true` events and reads it
+/// like [`parse_anthropic_messages_response`]. `None` when the body holds no
+/// `message_start` event.
+pub fn parse_anthropic_messages_sse_response(response_text: &str) -> Option<LLMMessage> {
+    let mut started = false;
+    let mut blocks: Vec<Value> = Vec::new();
+    let mut partial_inputs: Vec<String> = Vec::new();
+    let mut usage = json!({});
+
+    for line in response_text.lines() {
+        let Some(payload) = line.trim().strip_prefix("data:") else {
+            continue;
+        };
+        let Ok(event) = serde_json::from_str::<Value>(payload.trim()) else {
+            continue;
+        };
+        let index = event.get("index").and_then(Value::as_u64).unwrap_or_default() as usize;
+
+        match event.get("type").and_then(Value::as_str) {
+            Some("message_start") => {
+                started = true;
+                if let Some(start_usage) = event.pointer("/message/usage").and_then(Value::as_object) {
+                    usage.as_object_mut()?.extend(start_usage.clone());
+                }
+            }
+            Some("content_block_start") => {
+                if blocks.len() <= index {
+                    blocks.resize(index + 1, json!({}));
+                    partial_inputs.resize(index + 1, String::new());
+                }
+                blocks[index] = event.get("content_block").cloned().unwrap_or_else(|| json!({}));
+            }
+            Some("content_block_delta") => {
+                let (Some(block), Some(delta)) = (blocks.get_mut(index), event.get("delta")) else {
+                    continue;
+                };
+                let piece = |key: &str| delta.get(key).and_then(Value::as_str).unwrap_or_default();
+                match delta.get("type").and_then(Value::as_str) {
+                    Some("text_delta") => append_str(block, "text", piece("text")),
+                    Some("thinking_delta") => append_str(block, "thinking", piece("thinking")),
+                    Some("input_json_delta") => partial_inputs[index].push_str(piece("partial_json")),
+                    _ => {}
+                }
+            }
+            Some("message_delta") => {
+                if let Some(delta_usage) = event.get("usage").and_then(Value::as_object) {
+                    usage.as_object_mut()?.extend(delta_usage.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !started {
+        return None;
+    }
+    for (block, input) in blocks.iter_mut().zip(&partial_inputs) {
+        if !input.is_empty() {
+            block["input"] = serde_json::from_str(input).unwrap_or_else(|_| json!({}));
+        }
+    }
+    parse_anthropic_messages_response(&json!({ "content": blocks, "usage": usage }))
+}
+
+fn append_str(block: &mut Value, key: &str, piece: &str) {
+    let text = block.get(key).and_then(Value::as_str).unwrap_or_default();
+    block[key] = json!(format!("{text}{piece}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use zihuan_core::llm::tooling::{FunctionTool, StaticFunctionToolSpec};
+
+    use super::*;
+
+    #[test]
+    fn system_messages_move_to_the_top_level_and_tool_turns_become_blocks() {
+        let mut assistant = LLMMessage::assistant_text("我查一下");
+        assistant.tool_calls = vec![ToolCalls {
+            id: "toolu_1".to_string(),
+            type_name: "function".to_string(),
+            function: ToolCallsFuncSpec {
+                name: "get_weather".to_string(),
+                arguments: json!("{\"city\":\"上海\"}"),
+            },
+        }];
+        let mut tool_result = LLMMessage::user("多云");
+        tool_result.role = MessageRole::Tool;
+        tool_result.tool_call_id = Some("toolu_1".to_string());
+        let messages = vec![
+            LLMMessage::system("你是天气助手"),
+            LLMMessage::user("上海天气？"),
+            assistant,
+            tool_result,
+        ];
+        let tools: Vec<Arc<dyn FunctionTool>> = vec![Arc::new(StaticFunctionToolSpec {
+            name: "get_weather",
+            description: "查询天气",
+            parameters: json!({ "type": "object", "properties": { "city": { "type": "string" } } }),
+        })];
+        let param = InferenceParam::new(&messages)
+            .with_tools(Some(&tools))
+            .with_stop(Some(vec!["END".to_string()]));
+
+        let body = build_anthropic_messages_request_body("claude-test", &param);
+
+        assert_eq!(body["system"], json!("你是天气助手"));
+        assert_eq!(body["max_tokens"], json!(DEFAULT_ANTHROPIC_MAX_TOKENS));
+        assert_eq!(body["stop_sequences"], json!(["END"]));
+        assert_eq!(body["tools"][0]["input_schema"]["properties"]["city"]["type"], json!("string"));
+        assert_eq!(
+            body["messages"],
+            json!([
+                { "role": "user", "content": [{ "type": "text", "text": "上海天气？" }] },
+                { "role": "assistant", "content": [
+                    { "type": "text", "text": "我查一下" },
+                    { "type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": { "city": "上海" } },
+                ] },
+                { "role": "user", "content": [{ "type": "tool_result", "tool_use_id": "toolu_1", "content": "多云" }] },
+            ])
+        );
+    }
+
+    #[test]
+    fn empty_assistant_turns_are_left_out() {
+        let messages = vec![
+            LLMMessage::user("在吗"),
+            LLMMessage::assistant_text(""),
+            LLMMessage::user("在吗？"),
+        ];
+
+        let body = build_anthropic_messages_request_body("claude-test", &InferenceParam::new(&messages));
+
+        let roles: Vec<_> = body["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|message| message["role"].clone())
+            .collect();
+        assert_eq!(roles, vec![json!("user"), json!("user")]);
+    }
+
+    #[test]
+    fn content_blocks_are_parsed_into_text_reasoning_and_tool_calls() {
+        let response = json!({
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                { "type": "thinking", "thinking": "需要查天气" },
+                { "type": "text", "text": "稍等" },
+                { "type": "tool_use", "id": "toolu_2", "name": "get_weather", "input": { "city": "北京" } },
+            ],
+            "stop_reason": "tool_use",
+            "usage": { "input_tokens": 10, "cache_read_input_tokens": 90, "output_tokens": 5 },
+        });
+
+        let message = parse_anthropic_messages_response(&response).unwrap();
+
+        assert_eq!(message.content_text_owned().as_deref(), Some("稍等"));
+        assert_eq!(message.reasoning_content.as_deref(), Some("需要查天气"));
+        assert_eq!(message.tool_calls.len(), 1);
+        assert_eq!(message.tool_calls[0].id, "toolu_2");
+        assert_eq!(message.tool_calls[0].function.arguments, json!({ "city": "北京" }));
+        let usage = message.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, Some(100));
+        assert_eq!(usage.cached_prompt_tokens, Some(90));
+        assert_eq!(usage.total_tokens, Some(105));
+    }
+
+    #[test]
+    fn streamed_events_are_assembled_into_one_reply() {
+        let body = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"content\":[],\"usage\":{\"input_tokens\":7,\"output_tokens\":1}}}\n\n",
+            "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"你\"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"好\"}}\n\n",
+            "data: {\"type\":\"content_block_start\",\"index\":1,\"content_block\":{\"type\":\"tool_use\",\"id\":\"toolu_3\",\"name\":\"search\",\"input\":{}}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"{\\\"q\\\":\"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"\\\"rust\\\"}\"}}\n\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"tool_use\"},\"usage\":{\"output_tokens\":9}}\n\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+
+        let message = parse_anthropic_messages_sse_response(body).unwrap();
+
+        assert_eq!(message.content_text_owned().as_deref(), Some("你好"));
+        assert_eq!(message.tool_calls.len(), 1);
+        assert_eq!(message.tool_calls[0].function.arguments, json!({ "q": "rust" }));
+        assert_eq!(message.usage.unwrap().total_tokens, Some(16));
+        assert!(parse_anthropic_messages_sse_response("{\"type\":\"message\"}").is_none());
+    }
+}
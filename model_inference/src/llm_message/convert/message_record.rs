@@ -37,6 +37,7 @@ pub fn message_record_to_llm_message(record: &MessageRecord) -> LLMMessage {
         reasoning_content: None,
         tool_calls: Vec::new(),
         tool_call_id: None,
+        name: None,
         usage: None,
     }
 }
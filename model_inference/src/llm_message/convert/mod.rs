@@ -1,3 +1,4 @@
+pub mod anthropic_messages;
 pub mod ims_message;
 pub mod message_record;
 pub mod openai_chat_completions;
@@ -8,6 +9,10 @@ pub mod openai_responses_message_compat;
 
 use zihuan_core::llm::{LLMMessage, MessagePart};
 
+pub use anthropic_messages::{
+    build_anthropic_messages_request_body, parse_anthropic_messages_response, parse_anthropic_messages_sse_response,
+    DEFAULT_ANTHROPIC_MAX_TOKENS,
+};
 pub use ims_message::{event_to_llm_message, qq_messages_to_llm_message};
 pub use message_record::{llm_message_to_message_record, message_record_to_llm_message};
 pub use openai_chat_completions::{
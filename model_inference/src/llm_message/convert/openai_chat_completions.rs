@@ -135,12 +135,14 @@ pub fn build_chat_completions_request_body(
     }
 
     if let Some(tool_list) = param
-        .tools
-        .as_ref()
+        .active_tools()
         .map(|ts| ts.iter().map(|tool| tool.get_json()).collect::<Vec<_>>())
     {
         request_body["tools"] = serde_json::json!(tool_list);
-        request_body["tool_choice"] = serde_json::json!("auto");
+        request_body["tool_choice"] = match param.required_tool {
+            Some(name) => serde_json::json!({ "type": "function", "function": { "name": name } }),
+            None => serde_json::json!("auto"),
+        };
     }
 
     request_body
@@ -161,6 +163,7 @@ pub fn parse_chat_completions_response(api_resp: &Value) -> Option<LLMMessage> {
         reasoning_content: msg.get("reasoning_content").and_then(|v| v.as_str()).map(|s| s.to_string()),
         tool_calls: msg.get("tool_calls").map(parse_tool_calls).unwrap_or_default(),
         tool_call_id: msg.get("tool_call_id").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        name: None,
         usage: parse_token_usage(api_resp.get("usage")),
     })
 }
@@ -296,6 +299,7 @@ pub fn parse_chat_completions_sse_response(response_text: &str) -> Option<LLMMes
         },
         tool_calls,
         tool_call_id: None,
+        name: None,
         usage,
     })
 }
@@ -453,6 +457,65 @@ pub async fn parse_chat_completions_sse_stream_response(
         },
         tool_calls,
         tool_call_id: None,
+        name: None,
         usage,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+    use zihuan_core::llm::tooling::{FunctionTool, StaticFunctionToolSpec};
+
+    use super::*;
+
+    fn request_body(tools: Option<&Vec<Arc<dyn FunctionTool>>>, disable_tools: bool) -> Value {
+        let messages = vec![LLMMessage::user("你好")];
+        let param = InferenceParam {
+            disable_tools,
+            ..InferenceParam::new(&messages).with_tools(tools)
+        };
+        build_chat_completions_request_body("test-model", &param, false, false, None, None)
+    }
+
+    #[test]
+    fn empty_or_disabled_tools_omit_tool_fields() {
+        let tools: Vec<Arc<dyn FunctionTool>> = vec![Arc::new(StaticFunctionToolSpec {
+            name: "get_weather",
+            description: "查询天气",
+            parameters: json!({ "type": "object", "properties": {} }),
+        })];
+        let no_tools: Vec<Arc<dyn FunctionTool>> = Vec::new();
+
+        for body in [request_body(Some(&no_tools), false), request_body(Some(&tools), true)] {
+            assert!(body.get("tools").is_none(), "unexpected tools in {body}");
+            assert!(body.get("tool_choice").is_none(), "unexpected tool_choice in {body}");
+        }
+
+        let body = request_body(Some(&tools), false);
+        assert_eq!(body["tools"].as_array().map(Vec::len), Some(1));
+        assert_eq!(body["tool_choice"], json!("auto"));
+    }
+
+    #[test]
+    fn required_tool_is_sent_as_a_named_tool_choice() {
+        let tools: Vec<Arc<dyn FunctionTool>> = vec![Arc::new(StaticFunctionToolSpec {
+            name: "submit_result",
+            description: "提交结果",
+            parameters: json!({ "type": "object", "properties": {} }),
+        })];
+        let messages = vec![LLMMessage::user("你好")];
+        let param = InferenceParam::new(&messages)
+            .with_tools(Some(&tools))
+            .with_required_tool("submit_result");
+
+        let body = build_chat_completions_request_body("test-model", &param, false, false, None, None);
+
+        assert_eq!(
+            body["tool_choice"],
+            json!({ "type": "function", "function": { "name": "submit_result" } })
+        );
+    }
+}
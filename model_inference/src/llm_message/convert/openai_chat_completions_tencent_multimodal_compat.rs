@@ -33,12 +33,14 @@ pub fn build_tencent_multimodal_chat_completions_request_body(
     }
 
     if let Some(tool_list) = param
-        .tools
-        .as_ref()
+        .active_tools()
         .map(|ts| ts.iter().map(|tool| tool.get_json()).collect::<Vec<_>>())
     {
         request_body["tools"] = serde_json::json!(tool_list);
-        request_body["tool_choice"] = serde_json::json!("auto");
+        request_body["tool_choice"] = match param.required_tool {
+            Some(name) => serde_json::json!({ "type": "function", "function": { "name": name } }),
+            None => serde_json::json!("auto"),
+        };
     }
 
     request_body
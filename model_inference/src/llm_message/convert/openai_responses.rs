@@ -169,7 +169,7 @@ pub(crate) fn build_responses_request_body_for_style(
         "stream": stream,
     });
 
-    if let Some(tool_list) = param.tools.as_ref().map(|ts| {
+    if let Some(tool_list) = param.active_tools().map(|ts| {
         ts.iter()
             .map(|tool| {
                 json!({
@@ -183,7 +183,10 @@ pub(crate) fn build_responses_request_body_for_style(
             .collect::<Vec<_>>()
     }) {
         request_body["tools"] = json!(tool_list);
-        request_body["tool_choice"] = json!("auto");
+        request_body["tool_choice"] = match param.required_tool {
+            Some(name) => json!({ "type": "function", "name": name }),
+            None => json!("auto"),
+        };
     }
 
     request_body
@@ -291,6 +294,7 @@ pub fn parse_responses_response(api_resp: &Value) -> Option<LLMMessage> {
         },
         tool_calls,
         tool_call_id: None,
+        name: None,
         usage,
     })
 }
@@ -409,6 +413,7 @@ pub fn parse_responses_sse_response(response_text: &str) -> Option<LLMMessage> {
             reasoning_content: None,
             tool_calls,
             tool_call_id: None,
+            name: None,
             usage,
         })
     }
@@ -553,6 +558,7 @@ pub async fn parse_responses_sse_stream_response(
         reasoning_content: None,
         tool_calls: collect_responses_stream_tool_calls(streamed_tool_calls),
         tool_call_id: None,
+        name: None,
         usage,
     }
 }
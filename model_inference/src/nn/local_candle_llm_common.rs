@@ -37,6 +37,7 @@ impl ParsedLocalResponse {
             reasoning_content: self.reasoning_content,
             tool_calls: self.tool_calls,
             tool_call_id: None,
+            name: None,
             usage: None,
         }
     }
@@ -184,7 +185,7 @@ pub fn render_local_prompt(messages: &[LLMMessage], tools: Option<&Vec<Arc<dyn F
 
 pub fn prepare_prompt(param: &InferenceParam, supports_multimodal_input: bool) -> Result<(String, usize)> {
     let downgraded = downgrade_messages_for_model(param.messages.clone(), supports_multimodal_input);
-    let prompt = render_local_prompt(&downgraded, param.tools);
+    let prompt = render_local_prompt(&downgraded, param.active_tools());
     let prompt_chars = prompt.chars().count();
     if prompt_chars == 0 {
         return Err(Error::ValidationError("local llm prompt must not be empty".to_string()));
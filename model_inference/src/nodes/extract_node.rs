@@ -0,0 +1,200 @@
+use crate::inference_function::extract_structured::extract_structured;
+use zihuan_core::error::{Error, Result};
+use zihuan_graph_engine::{node_input, node_output, DataType, DataValue, Node, Port};
+
+pub struct ExtractNode {
+    id: String,
+    name: String,
+}
+
+impl ExtractNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+impl Node for ExtractNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> Option<&str> {
+        Some("让 LLM 按 JSON Schema 从自由文本中抽取结构化数据并校验，不符合时带着错误重试一次")
+    }
+
+    node_input![
+        port! { name = "llm_model", ty = LLModel, desc = "LLM模型引用，由LlmNode提供" },
+        port! { name = "text", ty = String, desc = "待抽取的自由文本" },
+        port! { name = "schema", ty = Json, desc = "目标结构的 JSON Schema" },
+        port! { name = "instruction", ty = String, desc = "可选：额外的抽取要求", optional },
+    ];
+
+    node_output![
+        port! { name = "data", ty = Json, desc = "抽取出的 JSON 数据；模型未输出合法 JSON 时为 null" },
+        port! { name = "valid", ty = Boolean, desc = "data 是否符合 schema" },
+        port! { name = "errors", ty = Vec(String), desc = "最后一次输出的校验错误，通过时为空列表" },
+    ];
+
+    fn execute(&mut self, inputs: zihuan_graph_engine::NodeInputFlow) -> Result<zihuan_graph_engine::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::LLModel(model)) = inputs.get("llm_model") else {
+            return Err(Error::ValidationError("Missing required input: llm_model".to_string()));
+        };
+        let Some(DataValue::String(text)) = inputs.get("text") else {
+            return Err(Error::ValidationError("Missing required input: text".to_string()));
+        };
+        let Some(DataValue::Json(schema)) = inputs.get("schema") else {
+            return Err(Error::ValidationError("Missing required input: schema".to_string()));
+        };
+        let instruction = match inputs.get("instruction") {
+            Some(DataValue::String(instruction)) => Some(instruction.as_str()),
+            _ => None,
+        };
+
+        let result = extract_structured(model, text, schema, instruction)?;
+
+        zihuan_graph_engine::return_with_node_output![self;
+            "data" => DataValue::Json(result.value.unwrap_or(serde_json::Value::Null)),
+            "valid" => DataValue::Boolean(result.valid),
+            "errors" => DataValue::Vec(
+                Box::new(DataType::String),
+                result.errors.into_iter().map(DataValue::String).collect(),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+    use zihuan_core::llm::llm_base::LLMBase;
+    use zihuan_core::llm::tooling::{ToolCalls, ToolCallsFuncSpec};
+    use zihuan_core::llm::{InferenceParam, LLMMessage};
+
+    use super::*;
+    use crate::inference_function::extract_structured::SUBMIT_RESULT_TOOL_NAME;
+
+    /// Replies with the queued messages in order and records every conversation.
+    #[derive(Debug)]
+    struct ScriptedLlm {
+        replies: Mutex<Vec<LLMMessage>>,
+        conversations: Mutex<Vec<Vec<LLMMessage>>>,
+        required_tools: Mutex<Vec<Option<String>>>,
+    }
+
+    impl ScriptedLlm {
+        fn new(replies: Vec<LLMMessage>) -> Arc<Self> {
+            Arc::new(Self {
+                replies: Mutex::new(replies),
+                conversations: Mutex::new(Vec::new()),
+                required_tools: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    impl LLMBase for ScriptedLlm {
+        fn get_model_name(&self) -> &str {
+            "scripted"
+        }
+
+        fn inference(&self, param: &InferenceParam) -> LLMMessage {
+            self.conversations.lock().unwrap().push(param.messages.to_vec());
+            self.required_tools
+                .lock()
+                .unwrap()
+                .push(param.required_tool.map(str::to_string));
+            self.replies.lock().unwrap().remove(0)
+        }
+    }
+
+    fn submit(arguments: serde_json::Value) -> LLMMessage {
+        let mut message = LLMMessage::assistant_text("");
+        message.tool_calls = vec![ToolCalls {
+            id: "call-1".to_string(),
+            type_name: "function".to_string(),
+            function: ToolCallsFuncSpec {
+                name: SUBMIT_RESULT_TOOL_NAME.to_string(),
+                arguments,
+            },
+        }];
+        message
+    }
+
+    fn order_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "item": { "type": "string" },
+                "quantity": { "type": "integer", "minimum": 1 }
+            },
+            "required": ["item", "quantity"]
+        })
+    }
+
+    fn run(llm: Arc<ScriptedLlm>) -> zihuan_graph_engine::NodeOutputFlow {
+        run_with_schema(llm, order_schema())
+    }
+
+    fn run_with_schema(llm: Arc<ScriptedLlm>, schema: serde_json::Value) -> zihuan_graph_engine::NodeOutputFlow {
+        ExtractNode::new("extract", "extract")
+            .execute(zihuan_graph_engine::NodeInputFlow::from(HashMap::from([
+                ("llm_model".to_string(), DataValue::LLModel(llm)),
+                ("text".to_string(), DataValue::String("来两杯拿铁".to_string())),
+                ("schema".to_string(), DataValue::Json(schema)),
+            ])))
+            .unwrap()
+    }
+
+    #[test]
+    fn valid_output_is_returned_without_retry() {
+        let llm = ScriptedLlm::new(vec![submit(json!({ "item": "拿铁", "quantity": 2 }))]);
+
+        let outputs = run(llm.clone());
+
+        assert!(matches!(outputs.get("valid"), Some(DataValue::Boolean(true))));
+        assert!(
+            matches!(outputs.get("data"), Some(DataValue::Json(data)) if *data == json!({ "item": "拿铁", "quantity": 2 }))
+        );
+        assert_eq!(llm.conversations.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn invalid_output_is_retried_once_with_the_errors() {
+        let llm = ScriptedLlm::new(vec![
+            submit(json!({ "item": "拿铁", "quantity": 0 })),
+            LLMMessage::assistant_text("```json\n{ \"item\": \"拿铁\", \"quantity\": -1 }\n```"),
+        ]);
+
+        let outputs = run(llm.clone());
+
+        assert!(matches!(outputs.get("valid"), Some(DataValue::Boolean(false))));
+        assert!(matches!(outputs.get("errors"), Some(DataValue::Vec(_, errors)) if errors.len() == 1));
+        let conversations = llm.conversations.lock().unwrap();
+        assert_eq!(conversations.len(), 2);
+        let feedback = conversations[1].last().and_then(LLMMessage::content_text_owned).unwrap();
+        assert!(feedback.contains("/quantity: 0 is less than the minimum of 1"));
+    }
+
+    #[test]
+    fn submit_result_is_required_and_non_object_schemas_are_unwrapped() {
+        let llm = ScriptedLlm::new(vec![submit(json!({ "value": ["拿铁", "摩卡"] }))]);
+
+        let outputs = run_with_schema(llm.clone(), json!({ "type": "array", "items": { "type": "string" } }));
+
+        assert!(matches!(outputs.get("valid"), Some(DataValue::Boolean(true))));
+        assert!(matches!(outputs.get("data"), Some(DataValue::Json(data)) if *data == json!(["拿铁", "摩卡"])));
+        assert_eq!(
+            *llm.required_tools.lock().unwrap(),
+            vec![Some(SUBMIT_RESULT_TOOL_NAME.to_string())]
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use super::llm_infer_node::infer_messages;
+use zihuan_core::error::{Error, Result};
+use zihuan_graph_engine::{node_input, node_output, DataType, DataValue, Node, Port};
+
+pub struct GatedLLMNode {
+    id: String,
+    name: String,
+}
+
+impl GatedLLMNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+impl Node for GatedLLMNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> Option<&str> {
+        Some("gate 为 true 时才调用 LLM 推理；为 false 时跳过调用并输出空回复，用于节省 token")
+    }
+
+    node_input![
+        port! { name = "llm_model", ty = LLModel, desc = "LLM模型引用，由LlmNode提供" },
+        port! { name = "messages",  ty = Vec(LLMMessage), desc = "输入消息列表，包含系统消息和用户消息" },
+        port! { name = "gate", ty = Boolean, desc = "是否调用 LLM，false 时直接跳过" },
+    ];
+
+    node_output![
+        port! { name = "response", ty = Vec(LLMMessage), desc = "LLM返回的消息列表，跳过时为空列表" },
+        port! { name = "skipped", ty = Boolean, desc = "本次是否跳过了 LLM 调用" },
+    ];
+
+    fn execute(&mut self, inputs: zihuan_graph_engine::NodeInputFlow) -> Result<zihuan_graph_engine::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::Boolean(gate)) = inputs.get("gate") else {
+            return Err(Error::ValidationError("Missing required input: gate".to_string()));
+        };
+
+        let response = if *gate {
+            vec![DataValue::LLMMessage(infer_messages(&inputs)?)]
+        } else {
+            Vec::new()
+        };
+
+        zihuan_graph_engine::return_with_node_output![self;
+            "response" => DataValue::Vec(Box::new(DataType::LLMMessage), response),
+            "skipped" => DataValue::Boolean(!*gate),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use zihuan_core::llm::llm_base::LLMBase;
+    use zihuan_core::llm::{InferenceParam, LLMMessage};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CountingLlm {
+        calls: AtomicUsize,
+    }
+
+    impl LLMBase for CountingLlm {
+        fn get_model_name(&self) -> &str {
+            "counting"
+        }
+
+        fn inference(&self, _param: &InferenceParam) -> LLMMessage {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            LLMMessage::assistant_text("需要回复")
+        }
+    }
+
+    fn run(llm: Arc<CountingLlm>, gate: bool) -> zihuan_graph_engine::NodeOutputFlow {
+        GatedLLMNode::new("gated", "gated")
+            .execute(zihuan_graph_engine::NodeInputFlow::from(HashMap::from([
+                ("llm_model".to_string(), DataValue::LLModel(llm)),
+                (
+                    "messages".to_string(),
+                    DataValue::Vec(
+                        Box::new(DataType::LLMMessage),
+                        vec![DataValue::LLMMessage(LLMMessage::user("在吗"))],
+                    ),
+                ),
+                ("gate".to_string(), DataValue::Boolean(gate)),
+            ])))
+            .unwrap()
+    }
+
+    #[test]
+    fn closed_gate_skips_the_model_call() {
+        let llm = Arc::new(CountingLlm::default());
+
+        let outputs = run(llm.clone(), false);
+
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 0);
+        assert!(matches!(outputs.get("skipped"), Some(DataValue::Boolean(true))));
+        assert!(matches!(outputs.get("response"), Some(DataValue::Vec(_, items)) if items.is_empty()));
+
+        let outputs = run(llm.clone(), true);
+
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 1);
+        assert!(matches!(outputs.get("skipped"), Some(DataValue::Boolean(false))));
+        assert!(matches!(outputs.get("response"), Some(DataValue::Vec(_, items)) if items.len() == 1));
+    }
+}
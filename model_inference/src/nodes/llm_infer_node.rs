@@ -16,6 +16,51 @@ impl LLMInferNode {
     }
 }
 
+/// Runs one inference over the `llm_model` and `messages` inputs, capped by the
+/// optional `max_tokens` input. Shared by the nodes that call the model with a
+/// plain message list.
+pub(crate) fn infer_messages(inputs: &zihuan_graph_engine::NodeInputFlow) -> Result<LLMMessage> {
+    let model = match inputs.get("llm_model") {
+        Some(DataValue::LLModel(m)) => m.clone(),
+        _ => {
+            return Err(zihuan_core::error::Error::ValidationError(
+                "Missing required input: llm_model".to_string(),
+            ));
+        }
+    };
+
+    let messages: Vec<LLMMessage> = match inputs.get("messages") {
+        Some(DataValue::Vec(_, items)) => items
+            .iter()
+            .filter_map(|item| {
+                if let DataValue::LLMMessage(m) = item {
+                    Some(m.clone())
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        _ => {
+            return Err(zihuan_core::error::Error::ValidationError(
+                "Missing required input: messages".to_string(),
+            ));
+        }
+    };
+
+    let max_tokens = match inputs.get("max_tokens") {
+        Some(DataValue::Integer(value)) if *value > 0 => Some(u32::try_from(*value).unwrap_or(u32::MAX)),
+        Some(DataValue::Integer(value)) => {
+            return Err(zihuan_core::error::Error::ValidationError(format!(
+                "max_tokens must be greater than 0, got {value}"
+            )));
+        }
+        _ => None,
+    };
+
+    let param = InferenceParam::new(&messages).with_max_tokens(max_tokens);
+    Ok(model.inference(&param))
+}
+
 impl Node for LLMInferNode {
     fn id(&self) -> &str {
         &self.id
@@ -30,47 +75,22 @@ impl Node for LLMInferNode {
     node_input![
         port! { name = "llm_model", ty = LLModel, desc = "LLM模型引用，由LlmNode提供" },
         port! { name = "messages",  ty = Vec(LLMMessage), desc = "输入消息列表，包含系统消息和用户消息" },
+        port! { name = "max_tokens", ty = Integer, desc = "本次回复最多生成的 token 数，可选，默认使用服务端设置", optional },
     ];
 
-    node_output![port! { name = "response", ty = Vec(LLMMessage), desc = "LLM返回的消息列表" },];
+    node_output![
+        port! { name = "response", ty = Vec(LLMMessage), desc = "LLM返回的消息列表" },
+        port! { name = "token_usage", ty = Json, desc = "本次调用的 token 用量，接口未返回时为 null" },
+    ];
 
     fn execute(&mut self, inputs: zihuan_graph_engine::NodeInputFlow) -> Result<zihuan_graph_engine::NodeOutputFlow> {
         self.validate_inputs(&inputs)?;
 
-        let model = match inputs.get("llm_model") {
-            Some(DataValue::LLModel(m)) => m.clone(),
-            _ => {
-                return Err(zihuan_core::error::Error::ValidationError(
-                    "Missing required input: llm_model".to_string(),
-                ));
-            }
-        };
-
-        let messages: Vec<LLMMessage> = match inputs.get("messages") {
-            Some(DataValue::Vec(_, items)) => items
-                .iter()
-                .filter_map(|item| {
-                    if let DataValue::LLMMessage(m) = item {
-                        Some(m.clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            _ => {
-                return Err(zihuan_core::error::Error::ValidationError(
-                    "Missing required input: messages".to_string(),
-                ));
-            }
-        };
-
-        let param = InferenceParam {
-            messages: &messages,
-            tools: None,
-        };
-        let response_message = model.inference(&param);
+        let response_message = infer_messages(&inputs)?;
+        let token_usage = serde_json::to_value(&response_message.usage)?;
 
         zihuan_graph_engine::return_with_node_output![self;
+            "token_usage" => DataValue::Json(token_usage),
             "response" => DataValue::Vec(
                 Box::new(DataType::LLMMessage),
                 vec![DataValue::LLMMessage(response_message)],
@@ -78,3 +98,82 @@ impl Node for LLMInferNode {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use zihuan_core::llm::llm_base::LLMBase;
+    use zihuan_core::llm::TokenUsage;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingLlm {
+        max_tokens: Mutex<Option<Option<u32>>>,
+    }
+
+    impl LLMBase for RecordingLlm {
+        fn get_model_name(&self) -> &str {
+            "recording"
+        }
+
+        fn inference(&self, param: &InferenceParam) -> LLMMessage {
+            *self.max_tokens.lock().unwrap() = Some(param.max_tokens);
+            let mut reply = LLMMessage::assistant_text("好的");
+            reply.usage = Some(TokenUsage {
+                prompt_tokens: Some(12),
+                completion_tokens: Some(3),
+                total_tokens: Some(15),
+                ..TokenUsage::default()
+            });
+            reply
+        }
+    }
+
+    fn run(llm: Arc<RecordingLlm>, max_tokens: Option<i64>) -> zihuan_graph_engine::NodeOutputFlow {
+        let mut inputs = HashMap::from([
+            ("llm_model".to_string(), DataValue::LLModel(llm)),
+            (
+                "messages".to_string(),
+                DataValue::Vec(
+                    Box::new(DataType::LLMMessage),
+                    vec![DataValue::LLMMessage(LLMMessage::user("在吗"))],
+                ),
+            ),
+        ]);
+        if let Some(max_tokens) = max_tokens {
+            inputs.insert("max_tokens".to_string(), DataValue::Integer(max_tokens));
+        }
+        LLMInferNode::new("infer", "infer")
+            .execute(zihuan_graph_engine::NodeInputFlow::from(inputs))
+            .unwrap()
+    }
+
+    #[test]
+    fn max_tokens_input_is_forwarded_to_the_model() {
+        let llm = Arc::new(RecordingLlm::default());
+
+        run(llm.clone(), Some(64));
+        assert_eq!(*llm.max_tokens.lock().unwrap(), Some(Some(64)));
+
+        run(llm.clone(), None);
+        assert_eq!(*llm.max_tokens.lock().unwrap(), Some(None));
+    }
+
+    #[test]
+    fn token_usage_reported_by_the_model_is_output() {
+        let outputs = run(Arc::new(RecordingLlm::default()), None);
+
+        match outputs.get("token_usage") {
+            Some(DataValue::Json(usage)) => {
+                assert_eq!(
+                    usage,
+                    &serde_json::json!({"prompt_tokens": 12, "completion_tokens": 3, "total_tokens": 15})
+                );
+            }
+            other => panic!("unexpected token_usage: {other:?}"),
+        }
+    }
+}
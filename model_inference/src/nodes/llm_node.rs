@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::anthropic_api::AnthropicAPI;
 use crate::llm_api::LLMAPI;
 use crate::nn::local_candle_llm_gguf::build_local_candle_gguf_llm;
 use crate::nn::local_candle_llm_hf::build_local_candle_hf_llm;
@@ -9,6 +10,10 @@ use zihuan_core::llm::llm_base::LLMBase;
 use zihuan_graph_engine::{node_output, DataType, DataValue, Node, NodeConfigField, NodeConfigWidget, Port};
 
 pub fn build_llm(config: LlmServiceConfig) -> Result<Arc<dyn LLMBase>> {
+    let timeout = match config.timeout_secs {
+        Some(secs) => std::time::Duration::from_secs(secs),
+        None => zihuan_core::system_config::timeout_settings().llm(),
+    };
     match config.api_style {
         LlmApiStyle::OpenAiChatCompletions
         | LlmApiStyle::OpenAiChatCompletionsTencentMultimodalCompat
@@ -25,7 +30,24 @@ pub fn build_llm(config: LlmServiceConfig) -> Result<Arc<dyn LLMBase>> {
                 config.include_reasoning_content,
                 config.thinking_type,
                 config.reasoning_effort,
-                std::time::Duration::from_secs(config.timeout_secs),
+                timeout,
+            )
+            .with_retry_count(config.retry_count)
+            .with_headers(config.extra_headers.into_iter().collect());
+            let api = match config.auth_header {
+                Some(name) => api.with_auth_header(name),
+                None => api,
+            };
+            Ok(Arc::new(api))
+        }
+        LlmApiStyle::AnthropicMessages => {
+            let api = AnthropicAPI::new(
+                config.model_name,
+                config.api_endpoint,
+                config.api_key,
+                config.stream,
+                config.supports_multimodal_input,
+                timeout,
             )
             .with_retry_count(config.retry_count);
             Ok(Arc::new(api))
@@ -1,5 +1,7 @@
 pub mod batch_text_embedding_node;
 pub mod context_compact_node;
+pub mod extract_node;
+pub mod gated_llm_node;
 pub mod llm_infer_node;
 pub mod llm_node;
 pub mod load_local_text_embedder_node;
@@ -25,16 +25,18 @@ impl Node for TextEmbeddingNode {
     }
 
     fn description(&self) -> Option<&str> {
-        Some("使用 EmbeddingModel 将文本编码为向量，输出 Vector")
+        Some("使用 EmbeddingModel 将文本编码为向量；传入 texts 时批量编码，输出 Vec<Vector>")
     }
 
     node_input![
         port! { name = "embedding_model", ty = EmbeddingModel, desc = "embedding 模型引用" },
-        port! { name = "text", ty = String, desc = "待编码的文本" },
+        port! { name = "text", ty = String, desc = "待编码的文本", optional },
+        port! { name = "texts", ty = Vec(String), desc = "可选：批量编码的文本列表", optional },
     ];
 
     node_output![
-        port! { name = "embedding", ty = Vector, desc = "文本向量" },
+        port! { name = "embedding", ty = Vector, desc = "text 的向量" },
+        port! { name = "embeddings", ty = Vec(Vector), desc = "texts 的向量，顺序与 texts 一致" },
         port! { name = "dimension", ty = Integer, desc = "向量维度" },
     ];
 
@@ -44,24 +46,137 @@ impl Node for TextEmbeddingNode {
         let embedding_model = match inputs.get("embedding_model") {
             Some(DataValue::EmbeddingModel(value)) => value.clone(),
             _ => {
-                return Err(Error::ValidationError("Missing required input: embedding_model".to_string()));
+                return Err(Error::ValidationError(
+                    "Missing required input: embedding_model (connect a text embedder loader node)".to_string(),
+                ));
             }
         };
 
         let text = match inputs.get("text") {
-            Some(DataValue::String(value)) if !value.trim().is_empty() => value.trim().to_string(),
-            _ => {
-                return Err(Error::ValidationError("Missing required input: text".to_string()));
-            }
+            Some(DataValue::String(value)) if !value.trim().is_empty() => Some(value.trim().to_string()),
+            _ => None,
         };
+        let texts = match inputs.get("texts") {
+            Some(DataValue::Vec(_, items)) => Some(
+                items
+                    .iter()
+                    .map(|item| match item {
+                        DataValue::String(value) => Ok(value.trim().to_string()),
+                        other => Err(Error::ValidationError(format!(
+                            "texts expects String items, got {}",
+                            other.data_type()
+                        ))),
+                    })
+                    .collect::<Result<Vec<String>>>()?,
+            ),
+            _ => None,
+        };
+        if text.is_none() && texts.is_none() {
+            return Err(Error::ValidationError("Missing required input: text or texts".to_string()));
+        }
+
+        let mut outputs = zihuan_graph_engine::NodeOutputFlow::new();
+        let mut dimension = None;
+        if let Some(text) = text {
+            let embedding = embedding_model.inference(&text)?;
+            dimension = Some(embedding.len());
+            outputs.insert("embedding", DataValue::Vector(embedding));
+        }
+        if let Some(texts) = texts {
+            let embeddings = embedding_model.batch_inference(&texts)?;
+            dimension = dimension.or_else(|| embeddings.first().map(Vec::len));
+            outputs.insert(
+                "embeddings",
+                DataValue::Vec(
+                    Box::new(DataType::Vector),
+                    embeddings.into_iter().map(DataValue::Vector).collect(),
+                ),
+            );
+        }
+        outputs.insert("dimension", DataValue::Integer(dimension.unwrap_or(0) as i64));
+
+        self.validate_outputs(&outputs)?;
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use zihuan_core::llm::embedding_base::EmbeddingBase;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct FixedEmbedder {
+        available: bool,
+    }
+
+    impl EmbeddingBase for FixedEmbedder {
+        fn get_model_name(&self) -> &str {
+            "fixed"
+        }
+
+        fn inference(&self, _text: &str) -> Result<Vec<f32>> {
+            if !self.available {
+                return Err(Error::StringError("connection refused".to_string()));
+            }
+            Ok(vec![0.1, 0.2, 0.3, 0.4])
+        }
+
+        fn batch_inference(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            texts.iter().map(|text| self.inference(text)).collect()
+        }
+    }
+
+    fn run_with(available: bool, name: &str, value: DataValue) -> Result<zihuan_graph_engine::NodeOutputFlow> {
+        let mut node = TextEmbeddingNode::new("embed", "embed");
+        node.execute(zihuan_graph_engine::NodeInputFlow::from(HashMap::from([
+            (
+                "embedding_model".to_string(),
+                DataValue::EmbeddingModel(Arc::new(FixedEmbedder { available })),
+            ),
+            (name.to_string(), value),
+        ])))
+    }
+
+    fn run(available: bool) -> Result<zihuan_graph_engine::NodeOutputFlow> {
+        run_with(available, "text", DataValue::String("你好".to_string()))
+    }
+
+    #[test]
+    fn outputs_vector_and_dimension_from_embedder() {
+        let outputs = run(true).expect("embedding should succeed");
+        match outputs.get("embedding") {
+            Some(DataValue::Vector(vector)) => assert_eq!(vector, &vec![0.1, 0.2, 0.3, 0.4]),
+            other => panic!("unexpected embedding output: {other:?}"),
+        }
+        assert!(matches!(outputs.get("dimension"), Some(DataValue::Integer(4))));
+    }
 
-        let embedding = embedding_model.inference(&text)?;
-        let dimension = embedding.len() as i64;
-        let vector = DataValue::Vector(embedding);
+    #[test]
+    fn texts_are_embedded_in_one_batch() {
+        let texts = DataValue::Vec(
+            Box::new(DataType::String),
+            vec![DataValue::String("a".to_string()), DataValue::String("b".to_string())],
+        );
+        let outputs = run_with(true, "texts", texts).expect("batch embedding should succeed");
+        match outputs.get("embeddings") {
+            Some(DataValue::Vec(_, items)) => assert_eq!(items.len(), 2),
+            other => panic!("unexpected embeddings output: {other:?}"),
+        }
+        assert!(outputs.get("embedding").is_none());
+        assert!(matches!(outputs.get("dimension"), Some(DataValue::Integer(4))));
+    }
 
-        zihuan_graph_engine::return_with_node_output![self;
-            "embedding" => vector,
-            "dimension" => DataValue::Integer(dimension),
-        ]
+    #[test]
+    fn inference_error_is_propagated() {
+        let error = run(false).expect_err("embedding should fail");
+        assert!(
+            matches!(&error, Error::StringError(message) if message == "connection refused"),
+            "{error}"
+        );
     }
 }
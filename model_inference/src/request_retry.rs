@@ -0,0 +1,176 @@
+use crate::llm_concurrency::{acquire_llm_slot, acquire_llm_slot_async};
+use log::{error, warn};
+use reqwest::StatusCode;
+use std::future::Future;
+use std::thread;
+use std::time::Duration;
+use zihuan_core::error::Error;
+use zihuan_core::llm::LLMMessage;
+use zihuan_core::utils::backoff::{is_retryable_http_status, BackoffPolicy};
+
+pub(crate) const DEFAULT_RETRY_COUNT: u32 = 2;
+pub(crate) const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+pub(crate) const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+/// The only failure text callers see; details stay in the logs.
+pub(crate) const USER_VISIBLE_REQUEST_ERROR: &str = "LLM API request failed";
+
+/// A failed attempt; the message is the detail kept in the logs.
+pub(crate) enum RequestError {
+    Retryable { status: Option<u16>, message: String },
+    NonRetryable { status: Option<u16>, message: String },
+}
+
+impl RequestError {
+    /// Failure for an HTTP error status, retryable when the status is transient.
+    pub(crate) fn from_status(status: StatusCode, message: String) -> Self {
+        let status_code = Some(status.as_u16());
+        if is_retryable_http_status(status.as_u16()) {
+            RequestError::Retryable { status: status_code, message }
+        } else {
+            RequestError::NonRetryable { status: status_code, message }
+        }
+    }
+
+    fn status(&self) -> Option<u16> {
+        match self {
+            RequestError::Retryable { status, .. } | RequestError::NonRetryable { status, .. } => *status,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            RequestError::Retryable { message, .. } | RequestError::NonRetryable { message, .. } => message,
+        }
+    }
+}
+
+pub(crate) fn default_retry_policy() -> BackoffPolicy {
+    BackoffPolicy::new(DEFAULT_RETRY_COUNT + 1, DEFAULT_RETRY_BASE_DELAY, MAX_RETRY_DELAY)
+}
+
+/// The in-band reply [`zihuan_core::llm::llm_base::LLMBase::inference`] returns
+/// in place of an error.
+pub(crate) fn error_reply(error: Error) -> LLMMessage {
+    match error {
+        Error::LlmApi { message, .. } => LLMMessage::assistant_text(format!("Error: {message}")),
+        other => LLMMessage::assistant_text(format!("Error: {other}")),
+    }
+}
+
+/// Logs a failed attempt and records it in `last_error`. Returns whether
+/// another attempt should follow.
+fn log_failure(
+    label: &str,
+    retry: &BackoffPolicy,
+    error: RequestError,
+    attempt: u32,
+    max_attempts: u32,
+    last_error: &mut Option<RequestError>,
+) -> bool {
+    let retry = match &error {
+        RequestError::Retryable { message, .. } => {
+            let retry = retry.should_retry(attempt);
+            if retry {
+                warn!(
+                    "{label} request failed on attempt {}/{} and will retry: {}",
+                    attempt, max_attempts, message
+                );
+            } else {
+                error!("{label} request failed on attempt {}/{}: {}", attempt, max_attempts, message);
+            }
+            retry
+        }
+        RequestError::NonRetryable { message, .. } => {
+            error!(
+                "{label} request failed on attempt {}/{} without retry: {}",
+                attempt, max_attempts, message
+            );
+            false
+        }
+    };
+    *last_error = Some(error);
+    retry
+}
+
+/// The sanitized error returned once every attempt has failed; it names the
+/// number of attempts made and keeps the last HTTP status.
+fn request_failed_error(label: &str, last_error: Option<RequestError>, attempts: u32) -> Error {
+    if let Some(err) = last_error.as_ref() {
+        error!(
+            "Returning sanitized {label} error to caller; detailed error kept in logs: {}",
+            err.message()
+        );
+    } else {
+        error!("Returning sanitized {label} error to caller without detailed context");
+    }
+
+    Error::LlmApi {
+        status: last_error.and_then(|err| err.status()),
+        message: format!("{USER_VISIBLE_REQUEST_ERROR} after {attempts} attempt(s)"),
+    }
+}
+
+/// Runs `send(attempt, max_attempts)` under `retry`, holding an LLM slot only
+/// for the request itself and not for the back-off between attempts.
+pub(crate) fn send_with_retry(
+    label: &str,
+    retry: &BackoffPolicy,
+    mut send: impl FnMut(u32, u32) -> Result<LLMMessage, RequestError>,
+) -> Result<LLMMessage, Error> {
+    let max_attempts = retry.max_attempts.max(1);
+    let mut last_error = None;
+    let mut attempts = 0;
+
+    for attempt in 1..=max_attempts {
+        attempts = attempt;
+        let result = {
+            let _permit = acquire_llm_slot();
+            send(attempt, max_attempts)
+        };
+        match result {
+            Ok(message) => return Ok(message),
+            Err(err) => {
+                if !log_failure(label, retry, err, attempt, max_attempts, &mut last_error) {
+                    break;
+                }
+                thread::sleep(retry.jittered_delay_for_attempt(attempt));
+            }
+        }
+    }
+
+    Err(request_failed_error(label, last_error, attempts))
+}
+
+/// Async counterpart of [`send_with_retry`].
+pub(crate) async fn send_with_retry_async<F, Fut>(
+    label: &str,
+    retry: &BackoffPolicy,
+    mut send: F,
+) -> Result<LLMMessage, Error>
+where
+    F: FnMut(u32, u32) -> Fut,
+    Fut: Future<Output = Result<LLMMessage, RequestError>>,
+{
+    let max_attempts = retry.max_attempts.max(1);
+    let mut last_error = None;
+    let mut attempts = 0;
+
+    for attempt in 1..=max_attempts {
+        attempts = attempt;
+        let result = {
+            let _permit = acquire_llm_slot_async().await;
+            send(attempt, max_attempts).await
+        };
+        match result {
+            Ok(message) => return Ok(message),
+            Err(err) => {
+                if !log_failure(label, retry, err, attempt, max_attempts, &mut last_error) {
+                    break;
+                }
+                tokio::time::sleep(retry.jittered_delay_for_attempt(attempt)).await;
+            }
+        }
+    }
+
+    Err(request_failed_error(label, last_error, attempts))
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
@@ -84,6 +86,7 @@ pub enum LlmApiStyle {
     OpenAiResponses,
     OpenAiResponsesMessageCompat,
     OpenAiResponsesImageUrlObjectCompat,
+    AnthropicMessages,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -151,10 +154,19 @@ pub struct LlmServiceConfig {
     pub thinking_type: Option<ThinkingType>,
     #[serde(default)]
     pub reasoning_effort: Option<ReasoningEffort>,
-    #[serde(default = "default_timeout_secs")]
-    pub timeout_secs: u64,
+    /// Request timeout; `None` uses `timeouts.llm_secs` from the system config.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
     #[serde(default = "default_retry_count")]
     pub retry_count: u32,
+    /// Header carrying `api_key` for OpenAI-style APIs; `None` keeps
+    /// `Authorization: Bearer <key>`. Azure OpenAI uses `api-key`.
+    #[serde(default)]
+    pub auth_header: Option<String>,
+    /// Extra headers sent with every OpenAI-style request, e.g. `api-version`,
+    /// `HTTP-Referer` or `OpenAI-Organization`.
+    #[serde(default)]
+    pub extra_headers: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -261,10 +273,6 @@ fn default_http_bind() -> String {
     "127.0.0.1:18080".to_string()
 }
 
-fn default_timeout_secs() -> u64 {
-    30
-}
-
 fn default_retry_count() -> u32 {
     2
 }
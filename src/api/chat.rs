@@ -107,6 +107,7 @@ impl From<DashboardChatMessage> for LLMMessage {
             reasoning_content: None,
             tool_calls: msg.tool_calls,
             tool_call_id: msg.tool_call_id,
+            name: None,
             usage: None,
         }
     }
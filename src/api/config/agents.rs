@@ -841,6 +841,14 @@ fn validate_agent_connection_schemas(agent_type: &AgentType, connections: &[Conn
     match agent_type {
         AgentType::QqChat(config) => {
             validate_rdb_connection(connections, config.resolved_rdb_id())?;
+            validate_message_history_connection(
+                connections,
+                config
+                    .message_history_connection_id
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty()),
+            )?;
             validate_weaviate_connection_schema(
                 connections,
                 config.weaviate_image_connection_id.as_deref(),
@@ -1020,6 +1028,29 @@ fn validate_rdb_connection(connections: &[ConnectionConfig], connection_id: Opti
     Ok(())
 }
 
+fn validate_message_history_connection(
+    connections: &[ConnectionConfig],
+    connection_id: Option<&str>,
+) -> Result<(), String> {
+    let Some(connection_id) = connection_id else {
+        return Ok(());
+    };
+    let connection = connections
+        .iter()
+        .find(|item| item.id == connection_id || item.config_id == connection_id)
+        .ok_or_else(|| format!("message_history_connection_id '{}' not found", connection_id))?;
+    if !matches!(
+        connection.kind,
+        ConnectionKind::Mysql(_) | ConnectionKind::Sqlite(_) | ConnectionKind::Postgres(_)
+    ) {
+        return Err(format!(
+            "message_history_connection_id '{}' is not a MySQL, SQLite or PostgreSQL connection",
+            connection.name
+        ));
+    }
+    Ok(())
+}
+
 fn validate_weaviate_connection_schema(
     connections: &[ConnectionConfig],
     connection_id: Option<&str>,
@@ -18,6 +18,7 @@ use storage_handler::{
     ConnectionConfig, ConnectionKind,
 };
 use zihuan_core::weaviate::{WeaviateEnsureCollectionResult, WeaviateRef};
+use zihuan_graph_engine::message_restore::{message_index_warmup_status, MessageIndexWarmupStatus};
 
 use super::{now_rfc3339, ok_response, render_bad_request, render_internal_error, render_not_found};
 
@@ -77,6 +78,12 @@ fn validate_connection_basics(kind: &ConnectionKind) -> Result<(), String> {
             Ok(())
         }
         ConnectionKind::WebSearchEngine(_) => Ok(()),
+        ConnectionKind::WeatherApi(weather) => {
+            if weather.provider.trim().is_empty() {
+                return Err("weather_api.provider must not be empty".to_string());
+            }
+            Ok(())
+        }
         ConnectionKind::Tokenizer(tokenizer) => {
             if tokenizer.model_name.trim().is_empty() {
                 return Err("tokenizer.model_name must not be empty".to_string());
@@ -89,6 +96,15 @@ fn validate_connection_basics(kind: &ConnectionKind) -> Result<(), String> {
             }
             Ok(())
         }
+        ConnectionKind::Postgres(postgres) => {
+            if postgres.url.trim().is_empty() {
+                return Err("postgres.url must not be empty".to_string());
+            }
+            if postgres.max_connections == 0 {
+                return Err("postgres.max_connections must be greater than 0".to_string());
+            }
+            Ok(())
+        }
         _ => Ok(()),
     }
 }
@@ -156,6 +172,16 @@ pub struct ActiveBotAdapterInfo {
     pub config_id: String,
     pub name: String,
     pub ws_url: String,
+    /// Startup message cache warm-up of the agent bound to this adapter; absent
+    /// when no warm-up ran.
+    pub message_index_warmup: Option<MessageIndexWarmupStatus>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ActiveBotAdaptersResponse {
+    pub items: Vec<ActiveBotAdapterInfo>,
+    /// LLM requests in flight process-wide, shared by every adapter's agents.
+    pub llm_in_flight: usize,
 }
 
 #[handler]
@@ -176,10 +202,14 @@ pub async fn list_active_bot_adapters(_req: &mut Request, res: &mut Response, _d
                         config_id: connection.canonical_config_id().to_string(),
                         name: connection.name.clone(),
                         ws_url: parsed.bot_server_url,
+                        message_index_warmup: message_index_warmup_status(&connection.id),
                     })
                 })
                 .collect();
-            res.render(Json(items));
+            res.render(Json(ActiveBotAdaptersResponse {
+                items,
+                llm_in_flight: model_inference::llm_concurrency::llm_in_flight(),
+            }));
         }
         Err(err) => render_internal_error(res, err),
     }
@@ -68,6 +68,11 @@ pub async fn execute_graph(req: &mut Request, res: &mut Response, depot: &mut De
         )
     };
 
+    if let Err(e) = graph_def.validate() {
+        res.status_code(StatusCode::UNPROCESSABLE_ENTITY);
+        res.render(Json(serde_json::json!({"error": e.to_string()})));
+        return;
+    }
     if let Err(msg) = validate_duplicate_port_names(&graph_def) {
         res.status_code(StatusCode::BAD_REQUEST);
         res.render(Json(serde_json::json!({"error": msg})));
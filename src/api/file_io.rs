@@ -159,7 +159,7 @@ pub async fn list_workflows_detailed(_req: &mut Request, res: &mut Response, _de
             });
 
             let (display_name, description, version, inputs, outputs) =
-                zihuan_graph_engine::load_graph_definition_from_json(&p)
+                zihuan_graph_engine::read_graph_definition_from_json(&p)
                     .ok()
                     .map(|graph| {
                         (
@@ -390,7 +390,7 @@ pub async fn open_file(req: &mut Request, res: &mut Response, depot: &mut Depot)
         }
     };
 
-    let result = zihuan_graph_engine::load_graph_definition_from_json(&body.path);
+    let result = zihuan_graph_engine::read_graph_definition_from_json(&body.path);
     match result {
         Ok(mut graph) => {
             zihuan_graph_engine::graph_boundary::sync_root_graph_io(&mut graph);
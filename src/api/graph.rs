@@ -10,7 +10,7 @@ use zihuan_graph_engine::graph_boundary::{
 };
 use zihuan_graph_engine::graph_io::{
     refresh_node_dynamic_ports, GraphMetadata, GraphPosition, GraphSize, NodeDefinition, NodeGraphDefinition,
-    PortBinding,
+    PortBinding, ValidationIssue,
 };
 
 use super::state::{AppState, GraphSession, GraphTabInfo};
@@ -92,9 +92,14 @@ pub async fn put_graph(req: &mut Request, res: &mut Response, depot: &mut Depot)
                 let graph_outputs = body.graph_outputs.clone();
                 sync_root_graph_io_signature(&mut body, &graph_inputs, &graph_outputs);
             }
+            let mut cycle_nodes: Vec<String> = zihuan_graph_engine::graph_io::find_cycles(&body)
+                .into_iter()
+                .flatten()
+                .collect();
+            cycle_nodes.sort();
             s.graph = body;
             s.dirty = true;
-            res.render(Json(serde_json::json!({"ok": true})));
+            res.render(Json(serde_json::json!({"ok": true, "cycle_nodes": cycle_nodes})));
         }
         None => {
             res.status_code(StatusCode::NOT_FOUND);
@@ -320,6 +325,35 @@ pub async fn update_node(req: &mut Request, res: &mut Response, depot: &mut Depo
     res.render(Json(serde_json::json!({"ok": true})));
 }
 
+/// Text preview of a node before the graph runs, through the preview renderer
+/// registered for its node type.
+#[handler]
+pub async fn get_node_preview(req: &mut Request, res: &mut Response, depot: &mut Depot) {
+    let state = depot.obtain::<Arc<AppState>>().unwrap();
+    let graph_id = req.param::<String>("id").unwrap_or_default();
+    let node_id = req.param::<String>("node_id").unwrap_or_default();
+
+    let sessions = state.sessions.read().unwrap();
+    let Some(session) = sessions.get(&graph_id) else {
+        res.status_code(StatusCode::NOT_FOUND);
+        res.render(Json(serde_json::json!({"error": "Graph not found"})));
+        return;
+    };
+    let Some(node) = session.graph.nodes.iter().find(|n| n.id == node_id) else {
+        res.status_code(StatusCode::NOT_FOUND);
+        res.render(Json(serde_json::json!({"error": "Node not found"})));
+        return;
+    };
+
+    let preview = zihuan_graph_engine::preview_renderer::render_preview(
+        &node.node_type,
+        &node.id,
+        &session.graph,
+        &node.inline_values,
+    );
+    res.render(Json(serde_json::json!({"preview": preview})));
+}
+
 #[handler]
 pub async fn delete_node(req: &mut Request, res: &mut Response, depot: &mut Depot) {
     let state = depot.obtain::<Arc<AppState>>().unwrap();
@@ -394,19 +428,26 @@ pub async fn add_edge(req: &mut Request, res: &mut Response, depot: &mut Depot)
     };
 
     // Prevent duplicate edges to same target port
-    session
-        .graph
-        .edges
-        .retain(|e| !(e.to_node_id == body.target_node && e.to_port == body.target_port));
+    let (replaced, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut session.graph.edges)
+        .into_iter()
+        .partition(|e| e.to_node_id == body.target_node && e.to_port == body.target_port);
+    session.graph.edges = kept;
 
+    let (source_node, target_node) = (body.source_node.clone(), body.target_node.clone());
     session.graph.edges.push(zihuan_graph_engine::graph_io::EdgeDefinition {
         from_node_id: body.source_node,
         from_port: body.source_port,
         to_node_id: body.target_node,
         to_port: body.target_port,
     });
+
+    // Nodes on the cycle this edge closes, so the UI can warn about it.
+    let cycle_nodes: Vec<String> = zihuan_graph_engine::graph_io::find_cycles(&session.graph)
+        .into_iter()
+        .find(|component| component.contains(&source_node) && component.contains(&target_node))
+        .unwrap_or_default();
     session.dirty = true;
-    res.render(Json(serde_json::json!({"ok": true})));
+    res.render(Json(serde_json::json!({"ok": true, "cycle_nodes": cycle_nodes})));
 }
 
 #[derive(Deserialize)]
@@ -474,16 +515,25 @@ pub async fn validate_graph(req: &mut Request, res: &mut Response, depot: &mut D
     let issues = zihuan_graph_engine::graph_io::validate_graph_definition(&session.graph);
     let cycle_nodes = zihuan_graph_engine::graph_io::find_cycle_node_ids(&session.graph);
 
-    let has_errors = issues.iter().any(|i| i.severity == "error") || !cycle_nodes.is_empty();
+    let has_errors = issues.iter().any(ValidationIssue::is_error) || !cycle_nodes.is_empty();
     let issues_json: Vec<serde_json::Value> = issues
         .iter()
         .map(|i| serde_json::json!({"severity": i.severity, "message": i.message}))
         .collect();
     let cycle_vec: Vec<&String> = cycle_nodes.iter().collect();
+    // Lint needs a buildable graph; definitions with hard errors are already reported above.
+    let lint_warnings = if has_errors {
+        Vec::new()
+    } else {
+        zihuan_graph_engine::build_node_graph_from_definition(&session.graph)
+            .map(|graph| graph.lint())
+            .unwrap_or_default()
+    };
     res.render(Json(serde_json::json!({
         "issues": issues_json,
         "cycle_nodes": cycle_vec,
         "has_errors": has_errors,
+        "lint_warnings": lint_warnings,
     })));
 }
 
@@ -152,6 +152,7 @@ pub fn build_router(state: Arc<AppState>, broadcast: WsBroadcast, canonical_loca
                                 .put(graph::update_node)
                                 .delete(graph::delete_node),
                         )
+                        .push(Router::with_path("nodes/<node_id>/preview").get(graph::get_node_preview))
                         .push(
                             Router::with_path("edges")
                                 .post(graph::add_edge)
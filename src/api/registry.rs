@@ -3,6 +3,8 @@ use salvo::writing::Json;
 use serde::Serialize;
 
 use zihuan_graph_engine::registry::NODE_REGISTRY;
+use zihuan_graph_engine::type_compatibility::TYPE_COMPATIBILITY_REGISTRY;
+use zihuan_graph_engine::DataType;
 
 #[derive(Serialize)]
 pub struct PortInfo {
@@ -41,6 +43,9 @@ pub struct NodeTypeInfo {
 pub struct RegistryResponse {
     pub types: Vec<NodeTypeInfo>,
     pub categories: Vec<String>,
+    /// Type pairs registered as compatible on top of the built-in rules, in
+    /// both orders, so the editor accepts the same links as the engine.
+    pub type_compatibility: Vec<(DataType, DataType)>,
 }
 
 #[handler]
@@ -113,7 +118,11 @@ pub async fn get_registry(_req: &mut Request, res: &mut Response, _depot: &mut D
         cats
     };
 
-    res.render(Json(RegistryResponse { types, categories }));
+    res.render(Json(RegistryResponse {
+        types,
+        categories,
+        type_compatibility: TYPE_COMPATIBILITY_REGISTRY.pairs(),
+    }));
 }
 
 #[handler]
@@ -75,6 +75,21 @@ async fn main() {
             error!("Failed to load connections for auto start: {e}");
             Vec::new()
         });
+        let llm_refs = crate::system_config::load_llm_refs().unwrap_or_else(|e| {
+            error!("Failed to load llm refs for startup summary: {e}");
+            Vec::new()
+        });
+        info!(
+            "Zihuan Next v{} effective configuration ({} connections, {} llm refs, {} agents):",
+            env!("CARGO_PKG_VERSION"),
+            connections.len(),
+            llm_refs.len(),
+            agents.len()
+        );
+        for line in model_inference::effective_config::effective_config_lines(&connections, &llm_refs, &agents) {
+            info!("  {line}");
+        }
+
         for agent in agents.into_iter().filter(|a| a.enabled && a.auto_start) {
             if let Err(err) = api::config::agents::start_agent_runtime(
                 Arc::clone(&state),
@@ -110,7 +125,46 @@ async fn main() {
         .try_bind()
         .await
         .expect("Failed to bind TCP listener");
-    salvo::Server::new(acceptor).serve(service).await;
+    let server = salvo::Server::new(acceptor);
+    let server_handle = server.handle();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        info!("Shutdown signal received, draining bot adapters");
+        ims_bot_adapter::shutdown_active_bot_adapters().await;
+        server_handle.stop_graceful(None);
+    });
+    server.serve(service).await;
+    info!("Web server stopped");
+}
+
+/// Resolves on Ctrl-C, or on SIGTERM where supported.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl-C: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                error!("Failed to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
 
 async fn startup_recover_orphan_tasks(state: &api::state::AppState) {
@@ -316,6 +316,7 @@ pub(crate) fn json_to_data_value(json: &Value, target_type: &DataType) -> Option
                 reasoning_content: None,
                 tool_calls: Vec::new(),
                 tool_call_id: None,
+                name: None,
                 usage: None,
             }))
         }
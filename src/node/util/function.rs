@@ -96,15 +96,16 @@ impl FunctionNode {
             let value = result_node_values.get(&port.name).ok_or_else(|| {
                 self.wrap_error(format!("函数输出 '{}' 未在子图中提供", port.name))
             })?;
-            if !port.data_type.is_compatible_with(&value.data_type()) {
-                return Err(self.wrap_error(format!(
+            // Same conversions a link into a port of the declared type allows.
+            let value = value.coerce_to(&port.data_type).ok_or_else(|| {
+                self.wrap_error(format!(
                     "函数输出 '{}' 类型不匹配：声明为 {}，实际为 {}",
                     port.name,
                     port.data_type,
                     value.data_type()
-                )));
-            }
-            outputs.insert(port.name.clone(), value.clone());
+                ))
+            })?;
+            outputs.insert(port.name.clone(), value);
         }
 
         Ok(outputs)
@@ -67,6 +67,7 @@ impl Node for StringToLLMMessageNode {
                 reasoning_content: None,
                 tool_calls: Vec::new(),
                 tool_call_id: None,
+                name: None,
                 usage: None,
             }),
         );
@@ -108,6 +108,18 @@ pub async fn create_qq_bot_stack(
                 bot_server_token: ims_config.token.clone(),
                 qq_id: ims_config.qq_id.clone(),
                 napcat_install_path: napcat_native_path.map(|s| s.to_string()),
+                supports_reactions: None,
+                respond_only_when_mentioned: false,
+                catch_up_on_reconnect: false,
+                catch_up_redis_connection_id: None,
+                catch_up_history_count: ims_bot_adapter::catch_up::DEFAULT_CATCH_UP_HISTORY_COUNT,
+                event_watchdog_secs: 0,
+                event_watchdog_reconnect: false,
+                message_dedup_ttl_secs: ims_bot_adapter::message_dedup::DEFAULT_MESSAGE_DEDUP_TTL_SECS,
+                webhook_url: None,
+                webhook_fields: Vec::new(),
+                ws_reconnect_max_attempts: None,
+                ws_reconnect_interval_secs: None,
             })
             .unwrap_or(serde_json::Value::Null),
         ),
@@ -177,8 +189,10 @@ fn build_llm_ref(config: &LlmSetupConfig, id: &str, name: &str) -> LlmRefConfig
                 include_reasoning_content: false,
                 thinking_type: None,
                 reasoning_effort: None,
-                timeout_secs: 120,
+                timeout_secs: Some(120),
                 retry_count: 2,
+                auth_header: None,
+                extra_headers: Default::default(),
             },
         }
     };
@@ -256,8 +270,12 @@ fn build_qq_chat_agent_service() -> AgentConfig {
         "web_search",
         "get_agent_public_info",
         "get_function_list",
+        "get_current_time",
+        "get_weather",
+        "calculate",
         "get_recent_group_messages",
         "get_recent_user_messages",
+        "search_chat_messages",
         "search_similar_images",
         "image_understand",
         "list_available_memory_keys",
@@ -286,6 +304,8 @@ fn build_qq_chat_agent_service() -> AgentConfig {
             embedding_model_ref_id: Some("setup-default-embedding".to_string()),
             tokenizer_connection_id: None,
             web_search_engine_connection_id: "setup-default-web-search".to_string(),
+            weather_api_connection_id: None,
+            message_history_connection_id: None,
             rdb_id: Some("setup-default-sqlite".to_string()),
             embedding: None,
             mysql_connection_id: None,
@@ -297,6 +317,7 @@ fn build_qq_chat_agent_service() -> AgentConfig {
             max_message_length: 500,
             compact_context_length: 0,
             max_steer_count: 4,
+            max_tool_result_chars: 0,
             default_tools_enabled: default_tools,
             tool_session_call_limits: HashMap::new(),
             tool_session_limit_message: None,
@@ -305,6 +326,18 @@ fn build_qq_chat_agent_service() -> AgentConfig {
             message_rate_limit_users: vec![],
             emotion_dimensions: vec![],
             event_handler_threads: None,
+            message_cache_warmup_limit: 1000,
+            message_cache_warmup_strict: false,
+            llm_profile_overrides: vec![],
+            sender_name_precedence: Default::default(),
+            sender_anonymization_key: None,
+            brain: Default::default(),
+            echo_include_structure: false,
+            redis_cache_codec: Default::default(),
+            no_tool_fallback: Default::default(),
+            empty_reply_fallback: Default::default(),
+            sticky_secs: 0,
+            redis_message_ttl_secs: 0,
         }),
         enabled: true,
         auto_start: false,
@@ -345,6 +378,7 @@ fn parse_api_style(value: &str) -> model_inference::system_config::LlmApiStyle {
         "open_ai_chat_completions_tencent_multimodal_compat" => {
             model_inference::system_config::LlmApiStyle::OpenAiChatCompletionsTencentMultimodalCompat
         }
+        "anthropic_messages" => model_inference::system_config::LlmApiStyle::AnthropicMessages,
         _ => model_inference::system_config::LlmApiStyle::OpenAiChatCompletions,
     }
 }
@@ -7,6 +7,7 @@ use chrono::Utc;
 use log::info;
 use once_cell::sync::Lazy;
 use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+use sqlx::postgres::{PgPool, PgPoolOptions};
 use sqlx::sqlite::SqlitePoolOptions;
 use tokio::sync::RwLock;
 use uuid::Uuid;
@@ -34,6 +35,7 @@ const DEFAULT_WEAVIATE_TIMEOUT_SECS: u64 = 30;
 pub enum StorageRuntimeHandle {
     MySql(Arc<MySqlConfig>),
     Sqlite(Arc<SqliteConfig>),
+    Postgres(PgPool),
     S3(Arc<S3Ref>),
     Weaviate(Arc<WeaviateRef>),
 }
@@ -42,6 +44,7 @@ pub enum StorageRuntimeHandle {
 enum StorageRuntimePayload {
     MySql(Arc<MySqlConfig>),
     Sqlite(Arc<SqliteConfig>),
+    Postgres(PgPool),
     S3(Arc<S3Ref>),
     Weaviate(Arc<WeaviateRef>),
 }
@@ -51,6 +54,7 @@ impl StorageRuntimePayload {
         match self {
             Self::MySql(value) => StorageRuntimeHandle::MySql(value.clone()),
             Self::Sqlite(value) => StorageRuntimeHandle::Sqlite(value.clone()),
+            Self::Postgres(value) => StorageRuntimeHandle::Postgres(value.clone()),
             Self::S3(value) => StorageRuntimeHandle::S3(value.clone()),
             Self::Weaviate(value) => StorageRuntimeHandle::Weaviate(value.clone()),
         }
@@ -59,7 +63,7 @@ impl StorageRuntimePayload {
     fn has_external_mysql_refs(&self) -> bool {
         match self {
             Self::MySql(value) => Arc::strong_count(value) > 1,
-            Self::Sqlite(_) | Self::S3(_) | Self::Weaviate(_) => false,
+            Self::Sqlite(_) | Self::Postgres(_) | Self::S3(_) | Self::Weaviate(_) => false,
         }
     }
 }
@@ -109,6 +113,16 @@ impl RuntimeStorageConnectionManager {
         }
     }
 
+    pub async fn get_or_create_postgres_pool(&self, config_id: &str) -> Result<PgPool> {
+        match self.get_or_create(config_id).await? {
+            StorageRuntimeHandle::Postgres(value) => Ok(value),
+            _ => Err(Error::ValidationError(format!(
+                "config '{}' is not a postgres runtime connection",
+                config_id
+            ))),
+        }
+    }
+
     pub async fn get_or_create_s3_ref(&self, config_id: &str) -> Result<Arc<S3Ref>> {
         match self.get_or_create(config_id).await? {
             StorageRuntimeHandle::S3(value) => Ok(value),
@@ -210,6 +224,37 @@ impl RuntimeStorageConnectionManager {
                 });
                 (StorageRuntimePayload::MySql(config), "mysql".to_string(), runtime)
             }
+            ConnectionKind::Postgres(postgres) => {
+                info!(
+                    "[storage_instance_manager] creating postgres pool config_id={} max_connections={}",
+                    connection.canonical_config_id(),
+                    postgres.max_connections
+                );
+                let pool = PgPoolOptions::new()
+                    .max_connections(postgres.max_connections)
+                    .min_connections(1)
+                    .acquire_timeout(Duration::from_secs(DEFAULT_MYSQL_ACQUIRE_TIMEOUT_SECS))
+                    .connect(&postgres.url)
+                    .await
+                    .map_err(|err| {
+                        Error::Database(sqlx::Error::Configuration(Box::new(std::io::Error::other(format!(
+                            "failed to create postgres pool for connection '{}' (config_id={}, url={}): {}",
+                            connection.name,
+                            connection.canonical_config_id(),
+                            mask_url_credentials(&postgres.url),
+                            err
+                        )))))
+                    })?;
+                if let Err(e) = ensure_tables_for_connection(&connection.kind).await {
+                    log::warn!(
+                        "[storage_instance_manager] ensure tables failed for postgres connection '{}' (id={}): {}",
+                        connection.name,
+                        connection.id,
+                        e
+                    );
+                }
+                (StorageRuntimePayload::Postgres(pool), "postgres".to_string(), None)
+            }
             ConnectionKind::Rustfs(rustfs) => {
                 let s3_ref = zihuan_core::runtime::block_async(build_s3_direct_ref(
                     &rustfs.endpoint,
@@ -464,8 +509,10 @@ fn kind_name(kind: &ConnectionKind) -> &'static str {
         ConnectionKind::Rustfs(_) => "rustfs",
         ConnectionKind::BotAdapter(_) => "bot_adapter",
         ConnectionKind::WebSearchEngine(_) => "web_search_engine",
+        ConnectionKind::WeatherApi(_) => "weather_api",
         ConnectionKind::Tokenizer(_) => "tokenizer",
         ConnectionKind::Sqlite(_) => "sqlite",
+        ConnectionKind::Postgres(_) => "postgres",
     }
 }
 
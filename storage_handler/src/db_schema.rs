@@ -1,5 +1,6 @@
 use log::info;
 use sqlx::mysql::MySqlConnection;
+use sqlx::postgres::PgConnection;
 use sqlx::sqlite::SqliteConnection;
 use sqlx::Connection;
 use zihuan_core::database;
@@ -10,7 +11,7 @@ use crate::ConnectionKind;
 /// Open a temporary connection to the database described by `kind`,
 /// ensure all required tables and indexes exist, then close the connection.
 ///
-/// For MySQL and SQLite connections, this is called when a connection is
+/// For MySQL, SQLite and PostgreSQL connections, this is called when a connection is
 /// created or updated via the REST API, and at startup for all existing
 /// connections. Non-database connection kinds are silently ignored.
 pub async fn ensure_tables_for_connection(kind: &ConnectionKind) -> Result<()> {
@@ -52,6 +53,26 @@ pub async fn ensure_tables_for_connection(kind: &ConnectionKind) -> Result<()> {
             })?;
             info!("[db_schema] SQLite tables ensured successfully");
         }
+        ConnectionKind::Postgres(postgres) => {
+            info!("[db_schema] ensuring PostgreSQL tables for url={}", mask_url(&postgres.url));
+            let mut conn = PgConnection::connect(&postgres.url).await.map_err(|e| {
+                zihuan_core::error::Error::Database(sqlx::Error::Configuration(Box::new(std::io::Error::other(
+                    format!(
+                        "failed to connect to PostgreSQL for schema setup (url={}): {}",
+                        mask_url(&postgres.url),
+                        e
+                    ),
+                ))))
+            })?;
+            database::ensure_tables_postgres(&mut conn).await?;
+            conn.close().await.map_err(|e| {
+                zihuan_core::error::Error::Database(sqlx::Error::Protocol(format!(
+                    "failed to close PostgreSQL schema connection: {}",
+                    e
+                )))
+            })?;
+            info!("[db_schema] PostgreSQL tables ensured successfully");
+        }
         _ => {}
     }
     Ok(())
@@ -5,7 +5,7 @@ mod connection_manager;
 mod db_schema;
 mod elasticsearch;
 mod image_weaviate_persistence;
-mod message_record;
+mod message_store;
 pub mod mysql;
 pub mod object_storage;
 mod qq_message_list_weaviate_persistence;
@@ -45,7 +45,10 @@ pub use elasticsearch::{
     create_elasticsearch_memory_record, ensure_elasticsearch_index, list_elasticsearch_memory_keys,
     search_elasticsearch_memory, upsert_elasticsearch_image, ElasticsearchIndexSchema, ElasticsearchRef,
 };
-pub use message_record::MessageRecord;
+pub use message_store::{
+    HistoryQuery, InMemoryMessageBackend, MessageBackend, MessageRetention, MessageStore, MySqlMessageBackend,
+    PostgresMessageBackend, SqliteMessageBackend,
+};
 pub use mysql::MySqlNode;
 pub use object_storage::{
     enrich_event_images, enrich_message_images, save_image_to_object_storage, upload_remote_image_to_s3,
@@ -54,7 +57,7 @@ pub use object_storage::{
 pub use rdb::{build_relational_db_connection_for_connection, build_relational_db_connection_for_kind};
 pub use redis::RedisNode;
 pub use resource_resolver::{
-    build_elasticsearch_ref, build_rdb_ref, build_redis_ref, build_s3_ref, build_weaviate_ref,
+    build_elasticsearch_ref, build_rdb_ref, build_redis_ref, build_s3_ref, build_weather_ref, build_weaviate_ref,
     build_web_search_engine_ref, find_connection, resolve_connection_data_value,
 };
 pub use rustfs::RustfsNode;
@@ -67,6 +70,7 @@ pub use weaviate_persistence::{
 };
 pub use weaviate_schema::{collection_config_for_schema, ensure_collection_schema, validate_collection_schema};
 pub use zihuan_core::weaviate::WeaviateCollectionSchema;
+pub use zihuan_graph_engine::message_record::MessageRecord;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -92,8 +96,10 @@ pub enum ConnectionKind {
     Rustfs(RustfsConnection),
     BotAdapter(serde_json::Value),
     WebSearchEngine(WebSearchEngineConnection),
+    WeatherApi(WeatherApiConnection),
     Tokenizer(TokenizerConnection),
     Sqlite(SqliteConnection),
+    Postgres(PostgresConnection),
 }
 
 pub const DEFAULT_MYSQL_MAX_CONNECTIONS: u32 = 32;
@@ -166,6 +172,20 @@ pub struct WebSearchEngineConnection {
     pub timeout_secs: u64,
 }
 
+/// A weather service. `provider` picks the API flavour (currently only
+/// `openweathermap`); `base_url` overrides the provider's default endpoint,
+/// e.g. for a proxy or a compatible self-hosted service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherApiConnection {
+    pub provider: String,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default = "default_weather_api_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenizerConnection {
     pub model_name: String,
@@ -176,10 +196,23 @@ pub struct SqliteConnection {
     pub path: String,
 }
 
+/// A PostgreSQL database. Only the message history ([`MessageStore`]) can use
+/// it; the other relational features need MySQL or SQLite.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostgresConnection {
+    pub url: String,
+    #[serde(default = "default_mysql_max_connections")]
+    pub max_connections: u32,
+}
+
 fn default_web_search_engine_timeout_secs() -> u64 {
     30
 }
 
+fn default_weather_api_timeout_secs() -> u64 {
+    15
+}
+
 fn default_mysql_max_connections() -> u32 {
     DEFAULT_MYSQL_MAX_CONNECTIONS
 }
@@ -233,8 +266,10 @@ impl ConfigRecord for ConnectionConfig {
             ConnectionKind::Rustfs(_) => ConfigKind::ConnectionRustfs,
             ConnectionKind::BotAdapter(_) => ConfigKind::ConnectionBotAdapter,
             ConnectionKind::WebSearchEngine(_) => ConfigKind::ConnectionWebSearchEngine,
+            ConnectionKind::WeatherApi(_) => ConfigKind::ConnectionWeatherApi,
             ConnectionKind::Tokenizer(_) => ConfigKind::ConnectionTokenizer,
             ConnectionKind::Sqlite(_) => ConfigKind::ConnectionSqlite,
+            ConnectionKind::Postgres(_) => ConfigKind::ConnectionPostgres,
         }
     }
 
@@ -261,6 +296,14 @@ impl ConfigRecord for ConnectionConfig {
                 return Err(zihuan_core::string_error!("sqlite.path must not be empty"));
             }
         }
+        if let ConnectionKind::Postgres(postgres) = &self.kind {
+            if postgres.url.trim().is_empty() {
+                return Err(zihuan_core::string_error!("postgres.url must not be empty"));
+            }
+            if postgres.max_connections == 0 {
+                return Err(zihuan_core::string_error!("postgres.max_connections must be greater than 0"));
+            }
+        }
         if let ConnectionKind::Elasticsearch(elasticsearch) = &self.kind {
             let reference = crate::ElasticsearchRef::new(elasticsearch.clone())?;
             let _ = reference;
@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use zihuan_core::error::Result;
+
+use zihuan_graph_engine::message_record::MessageRecord;
+
+use super::{HistoryQuery, MessageBackend};
+
+/// Bounds for an [`InMemoryMessageBackend`]. Both limits are per message, not
+/// per stored chunk; `None` leaves that limit off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageRetention {
+    /// Most messages kept; inserting past it evicts the least recently used
+    /// message, where both writes and history reads count as a use.
+    pub max_messages: Option<usize>,
+    /// How long a message is kept after its last write. Pass the same value to
+    /// `message_persistence::register_redis_message_ttl` so Redis snapshots
+    /// expire alongside.
+    pub ttl: Option<Duration>,
+}
+
+/// Process-local [`MessageBackend`] for tests and dev runs without a
+/// database. Rows live in a `HashMap` keyed by `message_id` and are lost on
+/// drop; [`MessageRetention`] keeps a long-running store bounded. History
+/// lookups follow the SQL backends, including the case-insensitive keyword
+/// search.
+#[derive(Default)]
+pub struct InMemoryMessageBackend {
+    state: Mutex<InMemoryRows>,
+    retention: MessageRetention,
+}
+
+#[derive(Default)]
+struct InMemoryRows {
+    next_id: u64,
+    /// Logical clock for `last_used` and `written`, bumped on every write and
+    /// read.
+    next_use: u64,
+    by_message: HashMap<String, StoredMessage>,
+    /// `message_id` by `last_used`, least recently used first.
+    by_use: BTreeMap<u64, String>,
+    /// `message_id` by `written`, so also by `written_at`, oldest first.
+    by_write: BTreeMap<u64, String>,
+}
+
+struct StoredMessage {
+    /// Rows with their insertion id, in insertion order.
+    rows: Vec<(u64, MessageRecord)>,
+    written_at: Instant,
+    written: u64,
+    last_used: u64,
+}
+
+impl InMemoryMessageBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_retention(retention: MessageRetention) -> Self {
+        Self { retention, ..Self::default() }
+    }
+}
+
+impl InMemoryRows {
+    fn tick(&mut self) -> u64 {
+        self.next_use += 1;
+        self.next_use
+    }
+
+    fn touch(&mut self, message_id: &str, used: u64) {
+        let Some(message) = self.by_message.get_mut(message_id) else {
+            return;
+        };
+        self.by_use.remove(&message.last_used);
+        message.last_used = used;
+        self.by_use.insert(used, message_id.to_string());
+    }
+
+    fn remove(&mut self, message_id: &str) {
+        if let Some(message) = self.by_message.remove(message_id) {
+            self.by_use.remove(&message.last_used);
+            self.by_write.remove(&message.written);
+        }
+    }
+
+    fn drop_expired(&mut self, ttl: Option<Duration>, now: Instant) {
+        let Some(ttl) = ttl else {
+            return;
+        };
+        while let Some((_, message_id)) = self.by_write.first_key_value() {
+            let expired = self
+                .by_message
+                .get(message_id)
+                .map_or(true, |message| now.duration_since(message.written_at) >= ttl);
+            if !expired {
+                break;
+            }
+            let message_id = message_id.clone();
+            self.remove(&message_id);
+        }
+    }
+
+    fn evict_over(&mut self, max_messages: Option<usize>) {
+        let Some(max_messages) = max_messages else {
+            return;
+        };
+        while self.by_message.len() > max_messages {
+            let Some((_, least_recent)) = self.by_use.pop_first() else {
+                break;
+            };
+            self.remove(&least_recent);
+        }
+    }
+}
+
+fn row_matches(query: &HistoryQuery<'_>, row: &MessageRecord) -> bool {
+    let in_group = |group_id: &str| row.group_id.as_deref() == Some(group_id);
+    match *query {
+        HistoryQuery::Group(group_id) => in_group(group_id),
+        HistoryQuery::Sender(sender_id) => row.sender_id == sender_id,
+        HistoryQuery::Search { keyword, group_id } => {
+            row.content.to_lowercase().contains(&keyword.to_lowercase()) && group_id.map_or(true, in_group)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl MessageBackend for InMemoryMessageBackend {
+    async fn insert_rows(&self, rows: &[MessageRecord]) -> Result<()> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.drop_expired(self.retention.ttl, now);
+        let used = state.tick();
+        for row in rows {
+            state.next_id += 1;
+            let id = state.next_id;
+            let message = state.by_message.entry(row.message_id.clone()).or_insert_with(|| StoredMessage {
+                rows: Vec::new(),
+                written_at: now,
+                written: used,
+                last_used: used,
+            });
+            message.rows.push((id, row.clone()));
+            let previous_write = std::mem::replace(&mut message.written, used);
+            message.written_at = now;
+            state.by_write.remove(&previous_write);
+            state.by_write.insert(used, row.message_id.clone());
+            state.touch(&row.message_id, used);
+        }
+        state.evict_over(self.retention.max_messages);
+        Ok(())
+    }
+
+    async fn fetch_history(&self, query: &HistoryQuery<'_>, limit: i64) -> Result<Vec<MessageRecord>> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.drop_expired(self.retention.ttl, now);
+        // Same ranking as the SQL derived table: latest send time, then
+        // latest id, each taken over the matching rows of a message.
+        let mut latest: Vec<_> = state
+            .by_message
+            .iter()
+            .filter_map(|(message_id, message)| {
+                let matching = message.rows.iter().filter(|(_, row)| row_matches(query, row));
+                let latest_send_time = matching.clone().map(|(_, row)| row.send_time).max()?;
+                let latest_id = matching.map(|(id, _)| *id).max()?;
+                Some(((latest_send_time, latest_id), message_id.clone()))
+            })
+            .collect();
+        latest.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+        let mut records = Vec::new();
+        for (_, message_id) in latest.into_iter().take(limit) {
+            records.extend(state.by_message[&message_id].rows.iter().map(|(_, row)| row.clone()));
+            let used = state.tick();
+            state.touch(&message_id, used);
+        }
+        Ok(records)
+    }
+}
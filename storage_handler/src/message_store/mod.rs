@@ -0,0 +1,385 @@
+mod memory;
+
+use std::sync::Arc;
+
+use log::info;
+use sqlx::mysql::MySqlPoolOptions;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::sqlite::SqlitePoolOptions;
+use zihuan_core::data_refs::RelationalDbConnection;
+use zihuan_core::database;
+use zihuan_core::error::{Error, Result};
+use zihuan_graph_engine::message_backend::message_backend_for_connection;
+pub use zihuan_graph_engine::message_backend::{
+    HistoryQuery, MessageBackend, MySqlMessageBackend, PostgresMessageBackend, SqliteMessageBackend,
+};
+use zihuan_graph_engine::message_rdb_chunking::message_record_rows;
+use zihuan_graph_engine::message_record::MessageRecord;
+
+pub use memory::{InMemoryMessageBackend, MessageRetention};
+
+use crate::connection_manager::RuntimeStorageConnectionManager;
+use crate::{build_relational_db_connection_for_connection, find_connection, ConnectionConfig, ConnectionKind};
+
+/// Read and write access to the persisted `message_record` history, e.g. for
+/// agents summarizing recent group activity.
+///
+/// [`MessageStore::in_memory`] needs no database at all, for tests and quick
+/// dev runs; the other constructors connect to MySQL, SQLite or PostgreSQL.
+pub struct MessageStore {
+    backend: Arc<dyn MessageBackend>,
+}
+
+impl MessageStore {
+    pub fn new(connection: RelationalDbConnection) -> Result<Self> {
+        Ok(Self::with_backend(message_backend_for_connection(&connection)?))
+    }
+
+    pub fn with_backend(backend: Arc<dyn MessageBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// The backend the store reads and writes through, e.g. for message
+    /// persistence to write inbound messages to the same database.
+    pub fn backend(&self) -> &dyn MessageBackend {
+        self.backend.as_ref()
+    }
+
+    /// A store backed by an [`InMemoryMessageBackend`]: no external services,
+    /// and nothing outlives the store.
+    pub fn in_memory() -> Self {
+        Self::with_backend(Arc::new(InMemoryMessageBackend::new()))
+    }
+
+    /// Like [`MessageStore::in_memory`], bounded by `retention` so a
+    /// long-running process does not grow without limit.
+    pub fn in_memory_with_retention(retention: MessageRetention) -> Self {
+        Self::with_backend(Arc::new(InMemoryMessageBackend::with_retention(retention)))
+    }
+
+    /// A store on the configured connection `connection_id`: MySQL, SQLite or
+    /// PostgreSQL. The pool is shared with other users of the connection.
+    pub async fn from_connection_id(connection_id: &str, connections: &[ConnectionConfig]) -> Result<Self> {
+        if let ConnectionKind::Postgres(_) = find_connection(connections, connection_id)?.kind {
+            let pool = RuntimeStorageConnectionManager::shared()
+                .get_or_create_postgres_pool(connection_id)
+                .await?;
+            return Ok(Self::with_backend(Arc::new(PostgresMessageBackend::new(pool))));
+        }
+        let connection = build_relational_db_connection_for_connection(connection_id, connections).await?;
+        Self::new(connection)
+    }
+
+    /// Connect to the database at `url`, with a pool of its own, and make sure
+    /// its tables exist. The backend follows the URL scheme: `mysql://`,
+    /// `postgres://` (or `postgresql://`) and `sqlite:` are supported.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let scheme = url.split(':').next().unwrap_or_default().to_ascii_lowercase();
+        info!("[message_store] connecting {scheme} message store");
+        let backend: Arc<dyn MessageBackend> = match scheme.as_str() {
+            "mysql" => {
+                let pool = MySqlPoolOptions::new().connect(url).await?;
+                database::ensure_tables_mysql(&mut *pool.acquire().await?).await?;
+                Arc::new(MySqlMessageBackend::new(pool))
+            }
+            "postgres" | "postgresql" => {
+                let pool = PgPoolOptions::new().connect(url).await?;
+                database::ensure_tables_postgres(&mut *pool.acquire().await?).await?;
+                Arc::new(PostgresMessageBackend::new(pool))
+            }
+            "sqlite" => {
+                let pool = SqlitePoolOptions::new().connect(url).await?;
+                database::ensure_tables_sqlite(&mut *pool.acquire().await?).await?;
+                Arc::new(SqliteMessageBackend::new(pool))
+            }
+            _ => {
+                return Err(Error::ValidationError(format!(
+                    "unsupported message store url scheme '{scheme}', expected mysql, postgres or sqlite"
+                )))
+            }
+        };
+        Ok(Self::with_backend(backend))
+    }
+
+    /// Persist `record` the way message persistence does, as the rows of
+    /// [`message_record_rows`].
+    pub async fn store_message(&self, record: &MessageRecord) -> Result<()> {
+        self.backend.insert_rows(&message_record_rows(record)).await
+    }
+
+    /// The latest `limit` messages sent in `group_id`, newest first.
+    pub async fn get_messages_by_group(&self, group_id: &str, limit: usize) -> Result<Vec<MessageRecord>> {
+        self.query_messages(HistoryQuery::Group(group_id), limit).await
+    }
+
+    /// The latest `limit` messages sent by `sender_id` in any chat, newest first.
+    pub async fn get_messages_by_sender(&self, sender_id: &str, limit: usize) -> Result<Vec<MessageRecord>> {
+        self.query_messages(HistoryQuery::Sender(sender_id), limit).await
+    }
+
+    /// The latest `limit` messages whose content contains `keyword`, optionally
+    /// only in `group_id`, newest first. The match is a case-insensitive
+    /// substring match: `%` and `_` in `keyword` have no wildcard meaning. A
+    /// keyword split across two stored chunks of a long message is not found.
+    pub async fn search_messages(
+        &self,
+        keyword: &str,
+        group_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<MessageRecord>> {
+        let keyword = keyword.trim();
+        if keyword.is_empty() {
+            return Err(Error::ValidationError("search keyword must not be empty".to_string()));
+        }
+        self.query_messages(HistoryQuery::Search { keyword, group_id }, limit).await
+    }
+
+    async fn query_messages(&self, query: HistoryQuery<'_>, limit: usize) -> Result<Vec<MessageRecord>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = self.backend.fetch_history(&query, limit).await?;
+        Ok(merge_content_chunks(rows))
+    }
+}
+
+/// Joins consecutive rows of one message back into a single record. The first
+/// chunk carries the mention, media and raw message columns.
+fn merge_content_chunks(rows: Vec<MessageRecord>) -> Vec<MessageRecord> {
+    let mut records: Vec<MessageRecord> = Vec::new();
+    for row in rows {
+        if let Some(last) = records.last_mut().filter(|last| last.message_id == row.message_id) {
+            last.content.push_str(&row.content);
+            continue;
+        }
+        records.push(row);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use zihuan_core::data_refs::SqliteConfig;
+    use zihuan_core::database::ddl::SQLITE_TABLES;
+    use zihuan_graph_engine::message_rdb_chunking::CONTENT_MAX_CHARS;
+
+    use super::*;
+
+    async fn store_with(rows: &[(&str, &str, Option<&str>, &str, &str)]) -> MessageStore {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        for (ddl, _) in SQLITE_TABLES {
+            sqlx::query(ddl).execute(&pool).await.unwrap();
+        }
+        for (message_id, sender_id, group_id, send_time, content) in rows {
+            sqlx::query(
+                "INSERT INTO message_record (message_id, sender_id, sender_name, send_time, group_id, content)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(message_id)
+            .bind(sender_id)
+            .bind(format!("用户{sender_id}"))
+            .bind(send_time)
+            .bind(group_id)
+            .bind(content)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        MessageStore::new(RelationalDbConnection::Sqlite(Arc::new(SqliteConfig {
+            path: ":memory:".to_string(),
+            pool: Some(pool),
+            runtime_handle: None,
+        })))
+        .unwrap()
+    }
+
+    fn contents(records: &[MessageRecord]) -> Vec<&str> {
+        records.iter().map(|record| record.content.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn group_history_is_newest_first_with_chunks_merged() {
+        let store = store_with(&[
+            ("1", "100", Some("9"), "2024-05-01 10:00:00", "早上好"),
+            ("2", "200", Some("9"), "2024-05-01 10:01:00", "很长的消息"),
+            ("2", "200", Some("9"), "2024-05-01 10:01:00", "的第二段"),
+            ("3", "100", Some("8"), "2024-05-01 10:02:00", "别的群"),
+            ("4", "100", Some("9"), "2024-05-01 10:03:00", "最新一条"),
+        ])
+        .await;
+
+        let records = store.get_messages_by_group("9", 2).await.unwrap();
+
+        assert_eq!(contents(&records), vec!["最新一条", "很长的消息的第二段"]);
+        assert_eq!(records[1].sender_id, "200");
+        assert_eq!(records[1].group_id.as_deref(), Some("9"));
+        assert_eq!(
+            records[0].send_time,
+            NaiveDateTime::parse_from_str("2024-05-01 10:03:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn sender_history_spans_groups_and_private_chats() {
+        let store = store_with(&[
+            ("1", "100", Some("9"), "2024-05-01 10:00:00", "群里"),
+            ("2", "200", Some("9"), "2024-05-01 10:01:00", "别人"),
+            ("3", "100", None, "2024-05-01 10:02:00", "私聊"),
+        ])
+        .await;
+
+        let records = store.get_messages_by_sender("100", 10).await.unwrap();
+
+        assert_eq!(contents(&records), vec!["私聊", "群里"]);
+        assert!(store.get_messages_by_sender("100", 0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_matches_keywords_literally_and_can_be_scoped_to_a_group() {
+        let store = store_with(&[
+            ("1", "100", Some("9"), "2024-05-01 10:00:00", "周末去爬山吗"),
+            ("2", "200", Some("8"), "2024-05-01 10:01:00", "爬山的照片发群里了"),
+            ("3", "100", Some("9"), "2024-05-01 10:02:00", "完成度 100% 了"),
+            ("4", "300", Some("9"), "2024-05-01 10:03:00", "完成度 1000"),
+            ("5", "300", Some("9"), "2024-05-01 10:04:00", "' OR 1=1 --"),
+        ])
+        .await;
+
+        let all = store.search_messages("爬山", None, 10).await.unwrap();
+        assert_eq!(contents(&all), vec!["爬山的照片发群里了", "周末去爬山吗"]);
+
+        let in_group = store.search_messages("爬山", Some("9"), 10).await.unwrap();
+        assert_eq!(contents(&in_group), vec!["周末去爬山吗"]);
+
+        let percent = store.search_messages("100%", None, 10).await.unwrap();
+        assert_eq!(contents(&percent), vec!["完成度 100% 了"]);
+
+        assert!(store.search_messages("' OR '1'='1", None, 10).await.unwrap().is_empty());
+        assert!(store.search_messages("  ", None, 10).await.is_err());
+    }
+
+    fn at(time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    /// Stores messages through `store` and reads them back. Ids are derived
+    /// from a fresh `group_id`, so it can run against a shared database.
+    async fn assert_round_trip(store: &MessageStore) {
+        let group_id = uuid::Uuid::new_v4().simple().to_string();
+        let message = |suffix: &str, time: &str, content: String| MessageRecord {
+            message_id: format!("{group_id}-{suffix}"),
+            sender_id: format!("{group_id}-sender"),
+            sender_name: "小明".to_string(),
+            send_time: at(time),
+            group_id: Some(group_id.clone()),
+            group_name: Some("测试群".to_string()),
+            content,
+            at_target_list: Some("10001".to_string()),
+            media_json: None,
+            raw_message_json: Some("[]".to_string()),
+        };
+        let long_content = "长".repeat(CONTENT_MAX_CHARS + 10);
+        for record in [
+            message("1", "2024-05-01 10:00:00", "完成度 100% 了 OK".to_string()),
+            message("2", "2024-05-01 10:01:00", long_content.clone()),
+            message("3", "2024-05-01 10:02:00", "完成度 1000".to_string()),
+        ] {
+            store.store_message(&record).await.unwrap();
+        }
+
+        let records = store.get_messages_by_group(&group_id, 2).await.unwrap();
+        assert_eq!(contents(&records), vec!["完成度 1000", long_content.as_str()]);
+        assert_eq!(records[1].at_target_list.as_deref(), Some("10001"));
+        assert_eq!(records[1].send_time, at("2024-05-01 10:01:00"));
+
+        let by_sender = store.get_messages_by_sender(&format!("{group_id}-sender"), 10).await.unwrap();
+        assert_eq!(by_sender.len(), 3);
+
+        let percent = store.search_messages("100%", Some(&group_id), 10).await.unwrap();
+        assert_eq!(contents(&percent), vec!["完成度 100% 了 OK"]);
+
+        let any_case = store.search_messages("ok", Some(&group_id), 10).await.unwrap();
+        assert_eq!(contents(&any_case), vec!["完成度 100% 了 OK"]);
+    }
+
+    #[tokio::test]
+    async fn stored_messages_round_trip_on_sqlite() {
+        assert_round_trip(&store_with(&[]).await).await;
+    }
+
+    #[tokio::test]
+    async fn stored_messages_round_trip_in_memory() {
+        assert_round_trip(&MessageStore::in_memory()).await;
+    }
+
+    #[tokio::test]
+    async fn stored_messages_round_trip_on_mysql() {
+        let Ok(url) = std::env::var("ZIHUAN_TEST_MYSQL_URL") else {
+            eprintln!("ZIHUAN_TEST_MYSQL_URL not set; skipping");
+            return;
+        };
+        assert_round_trip(&MessageStore::connect(&url).await.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn stored_messages_round_trip_on_postgres() {
+        let Ok(url) = std::env::var("ZIHUAN_TEST_POSTGRES_URL") else {
+            eprintln!("ZIHUAN_TEST_POSTGRES_URL not set; skipping");
+            return;
+        };
+        assert_round_trip(&MessageStore::connect(&url).await.unwrap()).await;
+    }
+
+    async fn store_texts(store: &MessageStore, group_id: &str, texts: &[&str]) {
+        for (index, text) in texts.iter().enumerate() {
+            let record = MessageRecord {
+                message_id: format!("{group_id}-{index}"),
+                sender_id: "100".to_string(),
+                sender_name: "小明".to_string(),
+                send_time: at(&format!("2024-05-01 10:{index:02}:00")),
+                group_id: Some(group_id.to_string()),
+                group_name: None,
+                content: text.to_string(),
+                at_target_list: None,
+                media_json: None,
+                raw_message_json: None,
+            };
+            store.store_message(&record).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn in_memory_store_evicts_least_recently_used_messages_past_the_cap() {
+        let store = MessageStore::in_memory_with_retention(MessageRetention {
+            max_messages: Some(2),
+            ttl: None,
+        });
+        store_texts(&store, "9", &["一", "二"]).await;
+        // Reading "一" makes "二" the least recently used message.
+        store.search_messages("一", None, 10).await.unwrap();
+        store_texts(&store, "8", &["三"]).await;
+
+        let all = store.get_messages_by_sender("100", 10).await.unwrap();
+        assert_eq!(contents(&all), vec!["三", "一"]);
+    }
+
+    #[tokio::test]
+    async fn in_memory_store_drops_messages_after_the_ttl() {
+        let store = MessageStore::in_memory_with_retention(MessageRetention {
+            max_messages: None,
+            ttl: Some(std::time::Duration::from_millis(50)),
+        });
+        store_texts(&store, "9", &["一"]).await;
+        assert_eq!(store.get_messages_by_group("9", 10).await.unwrap().len(), 1);
+
+        tokio::time::sleep(std::time::Duration::from_millis(80)).await;
+
+        assert!(store.get_messages_by_group("9", 10).await.unwrap().is_empty());
+    }
+}
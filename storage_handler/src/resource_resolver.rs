@@ -4,6 +4,7 @@ use std::time::Duration;
 use zihuan_core::data_refs::RelationalDbConnection;
 use zihuan_core::error::{Error, Result};
 use zihuan_core::rag::{BraveSearch, TavilySearch, WebSearchEngine, WebSearchEngineRef};
+use zihuan_core::weather::{OpenWeatherMap, WeatherProvider, WeatherRef};
 use zihuan_core::weaviate::WeaviateRef;
 use zihuan_graph_engine::data_value::RedisConfig;
 use zihuan_graph_engine::object_storage::S3Ref;
@@ -177,6 +178,37 @@ pub fn build_web_search_engine_ref(
     Ok(Some(Arc::new(WebSearchEngineRef::new(engine_ref))))
 }
 
+pub fn build_weather_ref(
+    connection_id: Option<&str>,
+    connections: &[ConnectionConfig],
+) -> Result<Option<Arc<WeatherRef>>> {
+    let Some(connection_id) = connection_id else {
+        return Ok(None);
+    };
+    let connection = find_connection(connections, connection_id)?;
+    let ConnectionKind::WeatherApi(weather) = &connection.kind else {
+        return Err(Error::ValidationError(format!(
+            "connection '{}' is not a weather api connection",
+            connection.name
+        )));
+    };
+    let api_key = weather
+        .api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| Error::ValidationError("weather_api.api_key must not be empty".to_string()))?;
+    let provider = match weather.provider.as_str() {
+        "openweathermap" => Arc::new(OpenWeatherMap::new(
+            weather.base_url.as_deref(),
+            api_key.to_string(),
+            Duration::from_secs(weather.timeout_secs),
+        )) as Arc<dyn WeatherProvider>,
+        other => return Err(Error::ValidationError(format!("unsupported weather api provider: {}", other))),
+    };
+    Ok(Some(Arc::new(WeatherRef::new(provider))))
+}
+
 pub async fn resolve_connection_data_value(
     data_type: &zihuan_graph_engine::DataType,
     connection_id: &str,
@@ -8,7 +8,7 @@ use uuid::Uuid;
 
 use zihuan_core::error::{Error, Result};
 use zihuan_core::ims_bot_adapter::models::event_model::MessageEvent;
-use zihuan_core::ims_bot_adapter::models::message::{collect_media_records, Message, PersistedMedia};
+use zihuan_core::ims_bot_adapter::models::message::{at_target_ids, collect_media_records, Message, PersistedMedia};
 use zihuan_core::llm::embedding_base::EmbeddingBase;
 use zihuan_core::weaviate::WeaviateRef;
 
@@ -58,13 +58,7 @@ pub fn upsert_qq_message_list(
         return Err(Error::ValidationError("qq_message_list content must not be empty".to_string()));
     }
 
-    let at_targets: Vec<String> = messages
-        .iter()
-        .filter_map(|message| match message {
-            Message::At(at) => Some(at.target_id()),
-            _ => None,
-        })
-        .collect();
+    let at_targets = at_target_ids(messages);
     let at_target_list = (!at_targets.is_empty()).then(|| at_targets.join(","));
     let media_json = {
         let records = collect_media_records(messages);
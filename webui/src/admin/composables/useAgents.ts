@@ -202,11 +202,22 @@ const rustfsConnections = computed(() =>
 const webSearchEngineConnections = computed(() =>
   connections.value.filter((item) => item.kind.type === "web_search_engine"),
 );
+const weatherApiConnections = computed(() =>
+  connections.value.filter((item) => item.kind.type === "weather_api"),
+);
 const taskDbConnections = computed(() =>
   connections.value.filter(
     (item) => item.kind.type === "mysql" || item.kind.type === "sqlite",
   ),
 );
+const messageHistoryConnections = computed(() =>
+  connections.value.filter(
+    (item) =>
+      item.kind.type === "mysql" ||
+      item.kind.type === "sqlite" ||
+      item.kind.type === "postgres",
+  ),
+);
 const tokenizerConnections = computed(() =>
   connections.value.filter((item) => item.kind.type === "tokenizer"),
 );
@@ -996,7 +1007,9 @@ onMounted(() => {
     botConnections,
     rustfsConnections,
     webSearchEngineConnections,
+    weatherApiConnections,
     taskDbConnections,
+    messageHistoryConnections,
     tokenizerConnections,
     imageWeaviateConnections,
     memoryWeaviateConnections,
@@ -36,8 +36,10 @@ export function useConnections() {
     { value: "rustfs", label: "RustFS", hint: "对象存储" },
     { value: "bot_adapter", label: "Bot Adapter", hint: "Bot 服务接入" },
     { value: "web_search_engine", label: "Web Search Engine", hint: "网页搜索引擎配置" },
+    { value: "weather_api", label: "Weather API", hint: "天气查询服务" },
     { value: "tokenizer", label: "Tokenizer", hint: "分词模型" },
     { value: "sqlite", label: "SQLite", hint: "SQLite 数据库" },
+    { value: "postgres", label: "PostgreSQL", hint: "消息历史数据库" },
   ];
 
   const connections = ref<ConnectionConfig[]>([]);
@@ -155,6 +157,10 @@ export function useConnections() {
         return;
       }
     }
+    if (form.type === "weather_api" && !form.weather_api_key.trim()) {
+      alert("请填写 Weather API Key");
+      return;
+    }
     if (form.type === "tokenizer" && !form.tokenizer_model_name.trim()) {
       alert("请选择 Tokenizer 模型");
       return;
@@ -293,6 +299,13 @@ export function useConnections() {
           { label: "API Token", value: String(kind.api_token ?? "") ? "已配置" : "未设置" },
           { label: "Timeout", value: String(kind.timeout_secs ?? 30) },
         ];
+      case "weather_api":
+        return [
+          ...base,
+          { label: "Provider", value: String(kind.provider ?? "openweathermap") },
+          { label: "Base URL", value: String(kind.base_url ?? "") || "默认" },
+          { label: "API Key", value: String(kind.api_key ?? "") ? "已配置" : "未设置" },
+        ];
       case "tokenizer":
         return [
           ...base,
@@ -17,8 +17,10 @@ export type ConnectionType =
   | "bot_adapter"
   | "ims_bot_adapter"
   | "web_search_engine"
+  | "weather_api"
   | "tokenizer"
-  | "sqlite";
+  | "sqlite"
+  | "postgres";
 export type WeaviateCollectionSchema = "image_semantic" | "agent_memory";
 export type ServiceTypeName = "qq_chat" | "http_stream" | "workspace";
 
@@ -33,7 +35,8 @@ export type LlmApiStyle =
   | "open_ai_chat_completions_tencent_multimodal_compat"
   | "open_ai_responses"
   | "open_ai_responses_message_compat"
-  | "open_ai_responses_image_url_object_compat";
+  | "open_ai_responses_image_url_object_compat"
+  | "anthropic_messages";
 export type ToolImplementation = "node_graph" | "python_script";
 export type ToolTargetType = "workflow_set" | "file_path" | "inline_graph";
 export type PythonToolMode = "uv_project" | "project_venv" | "custom_executable";
@@ -87,8 +90,14 @@ export interface ConnectionFormState {
   web_search_engine_provider: string;
   web_search_engine_api_token: string;
   web_search_engine_timeout_secs: number;
+  weather_api_provider: string;
+  weather_api_base_url: string;
+  weather_api_key: string;
+  weather_api_timeout_secs: number;
   tokenizer_model_name: string;
   sqlite_path: string;
+  postgres_url: string;
+  postgres_max_connections: number;
 }
 
 export interface LlmFormState {
@@ -140,7 +149,9 @@ export interface ServiceFormState {
   embedding_model_ref_id: string;
   tokenizer_connection_id: string;
   web_search_engine_connection_id: string;
+  weather_api_connection_id: string;
   rdb_id: string;
+  message_history_connection_id: string;
   weaviate_image_connection_id: string;
   weaviate_memory_connection_id: string;
   elasticsearch_image_connection_id: string;
@@ -148,6 +159,7 @@ export interface ServiceFormState {
   max_message_length: number;
   compact_context_length: number;
   max_steer_count: number;
+  max_tool_result_chars: number;
   emotion_dimensions: QqChatEmotionDimensionFormItem[];
   default_tools_enabled: Record<string, boolean>;
   tool_session_call_limits: Record<string, number>;
@@ -219,6 +231,21 @@ export const QQ_CHAT_DEFAULT_TOOLS: DefaultToolOption[] = [
     label: "get_function_list",
     description: "获取功能列表",
   },
+  {
+    id: "get_current_time",
+    label: "get_current_time",
+    description: "查询当前时间，可指定 UTC 偏移",
+  },
+  {
+    id: "get_weather",
+    label: "get_weather",
+    description: "通过天气 API 连接查询城市当前天气，未配置天气连接时不注册",
+  },
+  {
+    id: "calculate",
+    label: "calculate",
+    description: "计算算术表达式",
+  },
   {
     id: "get_recent_group_messages",
     label: "get_recent_group_messages",
@@ -229,6 +256,11 @@ export const QQ_CHAT_DEFAULT_TOOLS: DefaultToolOption[] = [
     label: "get_recent_user_messages",
     description: "查询用户近期消息",
   },
+  {
+    id: "search_chat_messages",
+    label: "search_chat_messages",
+    description: "按关键词搜索历史聊天消息",
+  },
   {
     id: "search_similar_images",
     label: "search_similar_images",
@@ -391,8 +423,14 @@ export function defaultConnectionForm(): ConnectionFormState {
     web_search_engine_provider: "tavily",
     web_search_engine_api_token: "",
     web_search_engine_timeout_secs: 30,
+    weather_api_provider: "openweathermap",
+    weather_api_base_url: "",
+    weather_api_key: "",
+    weather_api_timeout_secs: 15,
     tokenizer_model_name: "",
     sqlite_path: "",
+    postgres_url: "",
+    postgres_max_connections: DEFAULT_MYSQL_MAX_CONNECTIONS,
   };
 }
 
@@ -451,7 +489,9 @@ export function defaultServiceForm(): ServiceFormState {
     embedding_model_ref_id: "",
     tokenizer_connection_id: "",
     web_search_engine_connection_id: "",
+    weather_api_connection_id: "",
     rdb_id: "",
+    message_history_connection_id: "",
     weaviate_image_connection_id: "",
     weaviate_memory_connection_id: "",
     elasticsearch_image_connection_id: "",
@@ -459,6 +499,7 @@ export function defaultServiceForm(): ServiceFormState {
     max_message_length: 500,
     compact_context_length: 0,
     max_steer_count: 4,
+    max_tool_result_chars: 0,
     emotion_dimensions: defaultQqChatEmotionDimensions(),
     default_tools_enabled: defaultQqChatDefaultToolsEnabled(),
     tool_session_call_limits: {},
@@ -616,12 +657,28 @@ export function connectionFormFromConfig(
         connection.kind.timeout_secs ?? 30,
       );
       break;
+    case "weather_api":
+      form.weather_api_provider = String(
+        connection.kind.provider ?? "openweathermap",
+      );
+      form.weather_api_base_url = String(connection.kind.base_url ?? "");
+      form.weather_api_key = String(connection.kind.api_key ?? "");
+      form.weather_api_timeout_secs = Number(
+        connection.kind.timeout_secs ?? 15,
+      );
+      break;
     case "tokenizer":
       form.tokenizer_model_name = String(connection.kind.model_name ?? "");
       break;
     case "sqlite":
       form.sqlite_path = String(connection.kind.path ?? "");
       break;
+    case "postgres":
+      form.postgres_url = String(connection.kind.url ?? "");
+      form.postgres_max_connections = Number(
+        connection.kind.max_connections ?? DEFAULT_MYSQL_MAX_CONNECTIONS,
+      );
+      break;
   }
   return form;
 }
@@ -743,6 +800,15 @@ export function buildConnectionPayload(form: ConnectionFormState): {
         timeout_secs: form.web_search_engine_timeout_secs,
       };
       break;
+    case "weather_api":
+      payload.kind = {
+        type: "weather_api",
+        provider: form.weather_api_provider,
+        base_url: form.weather_api_base_url.trim() || null,
+        api_key: form.weather_api_key.trim() || null,
+        timeout_secs: form.weather_api_timeout_secs,
+      };
+      break;
     case "tokenizer":
       payload.kind = {
         type: "tokenizer",
@@ -755,6 +821,13 @@ export function buildConnectionPayload(form: ConnectionFormState): {
         path: form.sqlite_path.trim(),
       };
       break;
+    case "postgres":
+      payload.kind = {
+        type: "postgres",
+        url: form.postgres_url.trim(),
+        max_connections: form.postgres_max_connections,
+      };
+      break;
   }
   return payload;
 }
@@ -789,6 +862,8 @@ export function llmFormFromConfig(config: LlmConfig): LlmFormState {
       reasoning_effort: config.model.llm.reasoning_effort ?? null,
       timeout_secs: config.model.llm.timeout_secs,
       retry_count: config.model.llm.retry_count,
+      auth_header: config.model.llm.auth_header ?? null,
+      extra_headers: config.model.llm.extra_headers ?? {},
     },
   };
 }
@@ -888,12 +963,18 @@ export function serviceFormFromConfig(
     form.web_search_engine_connection_id = String(
       agentType.web_search_engine_connection_id ?? "",
     );
+    form.weather_api_connection_id = String(
+      agentType.weather_api_connection_id ?? "",
+    );
     form.rdb_id = String(
       agentType.rdb_id ??
         agentType.mysql_connection_id ??
         agentType.task_db_connection_id ??
         "",
     );
+    form.message_history_connection_id = String(
+      agentType.message_history_connection_id ?? "",
+    );
     form.weaviate_image_connection_id = String(
       agentType.weaviate_image_connection_id ?? "",
     );
@@ -905,6 +986,7 @@ export function serviceFormFromConfig(
     form.max_message_length = Number(agentType.max_message_length ?? 500);
     form.compact_context_length = Number(agentType.compact_context_length ?? 0);
     form.max_steer_count = Number(agentType.max_steer_count ?? 4);
+    form.max_tool_result_chars = Number(agentType.max_tool_result_chars ?? 0);
     form.emotion_dimensions = normalizeQqChatEmotionDimensions(
       agentType.emotion_dimensions,
     );
@@ -1114,8 +1196,11 @@ export function buildServicePayload(form: ServiceFormState): {
         embedding_model_ref_id: form.embedding_model_ref_id || null,
         tokenizer_connection_id: form.tokenizer_connection_id || null,
         web_search_engine_connection_id: form.web_search_engine_connection_id,
+        weather_api_connection_id: form.weather_api_connection_id || null,
         embedding: null,
         rdb_id: form.rdb_id || null,
+        message_history_connection_id:
+          form.message_history_connection_id || null,
         weaviate_image_connection_id: form.weaviate_image_connection_id || null,
         weaviate_memory_connection_id:
           form.weaviate_memory_connection_id || null,
@@ -1124,6 +1209,7 @@ export function buildServicePayload(form: ServiceFormState): {
         max_message_length: form.max_message_length,
         compact_context_length: form.compact_context_length,
         max_steer_count: form.max_steer_count,
+        max_tool_result_chars: form.max_tool_result_chars,
         emotion_dimensions: normalizeQqChatEmotionDimensions(
           form.emotion_dimensions,
         ),
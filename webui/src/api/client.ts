@@ -55,7 +55,11 @@ export async function request<T>(
 
 // Registry
 export const registry = {
-  getTypes(): Promise<{ types: NodeTypeInfo[]; categories: string[] }> {
+  getTypes(): Promise<{
+    types: NodeTypeInfo[];
+    categories: string[];
+    type_compatibility: [DataTypeMetaData, DataTypeMetaData][];
+  }> {
     return request("GET", "/registry/types");
   },
   getCategories(): Promise<string[]> {
@@ -74,7 +78,7 @@ export const graphs = {
   get(id: string): Promise<NodeGraphDefinition> {
     return request("GET", `/graphs/${id}`);
   },
-  put(id: string, graph: NodeGraphDefinition): Promise<{ ok: boolean }> {
+  put(id: string, graph: NodeGraphDefinition): Promise<{ ok: boolean; cycle_nodes?: string[] }> {
     return request("PUT", `/graphs/${id}`, graph);
   },
   delete(id: string): Promise<{ ok: boolean }> {
@@ -108,6 +112,9 @@ export const graphs = {
   deleteNode(graphId: string, nodeId: string): Promise<{ ok: boolean }> {
     return request("DELETE", `/graphs/${graphId}/nodes/${nodeId}`);
   },
+  nodePreview(graphId: string, nodeId: string): Promise<{ preview: string }> {
+    return request("GET", `/graphs/${graphId}/nodes/${nodeId}/preview`);
+  },
   addEdge(
     graphId: string,
     edge: {
@@ -116,7 +123,7 @@ export const graphs = {
       target_node: string;
       target_port: string;
     }
-  ): Promise<{ ok: boolean }> {
+  ): Promise<{ ok: boolean; cycle_nodes?: string[] }> {
     return request("POST", `/graphs/${graphId}/edges`, edge);
   },
   deleteEdge(
@@ -306,11 +313,24 @@ export interface ConnectionMutationResponse extends ConnectionConfig {
   collection_created: boolean;
 }
 
+export interface MessageIndexWarmupStatus {
+  succeeded: boolean;
+  loaded_count: number;
+  attempts: number;
+  last_error: string | null;
+}
+
 export interface ActiveBotAdapterInfo {
   connection_id: string;
   config_id: string;
   name: string;
   ws_url: string;
+  message_index_warmup: MessageIndexWarmupStatus | null;
+}
+
+export interface ActiveBotAdaptersResponse {
+  items: ActiveBotAdapterInfo[];
+  llm_in_flight: number;
 }
 
 export interface RuntimeConnectionInstanceSummary {
@@ -336,14 +356,17 @@ export interface LlmServiceConfig {
     | "open_ai_chat_completions_tencent_multimodal_compat"
     | "open_ai_responses"
     | "open_ai_responses_message_compat"
-    | "open_ai_responses_image_url_object_compat";
+    | "open_ai_responses_image_url_object_compat"
+    | "anthropic_messages";
   stream: boolean;
   supports_multimodal_input: boolean;
   include_reasoning_content: boolean;
   thinking_type?: "enabled" | "disabled" | null;
   reasoning_effort?: "low" | "medium" | "high" | "max" | null;
-  timeout_secs: number;
+  timeout_secs?: number | null;
   retry_count: number;
+  auth_header?: string | null;
+  extra_headers?: Record<string, string>;
 }
 
 export interface LocalLlmModelInfo {
@@ -521,7 +544,7 @@ export const system = {
     list(): Promise<ConnectionConfig[]> {
       return request("GET", "/system/connections");
     },
-    listActiveBotAdapters(): Promise<ActiveBotAdapterInfo[]> {
+    listActiveBotAdapters(): Promise<ActiveBotAdaptersResponse> {
       return request("GET", "/system/connections/active-bot-adapters");
     },
     listRuntimeInstances(params?: {
@@ -137,6 +137,7 @@ export interface NodeGraphDefinition {
   variables: GraphVariable[];
   metadata: GraphMetadata;
   accepts_agent_events?: boolean;
+  execution_mode?: "sequential" | "parallel";
 }
 
 export interface GraphTabInfo {
@@ -153,10 +154,18 @@ export interface ValidationIssue {
   message: string;
 }
 
+export interface LintWarning {
+  kind: "unused_output" | "unreachable_node" | "lossy_coercion";
+  node_id: string;
+  port: string | null;
+  message: string;
+}
+
 export interface ValidationResult {
   issues: ValidationIssue[];
   cycle_nodes: string[];
   has_errors: boolean;
+  lint_warnings: LintWarning[];
 }
 
 export interface TaskEntry {
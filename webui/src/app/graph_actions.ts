@@ -128,8 +128,12 @@ export class GraphActions {
       }
       const result = await graphs.validate(sid);
       if (result.has_errors) {
-        const msgs = result.issues.map((issue) => `[${issue.severity}] ${issue.message}`).join("\n");
-        showErrorDialog(`验证失败:\n\n${msgs}`);
+        const msgs = result.issues.map((issue) => `[${issue.severity}] ${issue.message}`);
+        if (result.cycle_nodes.length > 0) msgs.push(`[error] 存在依赖环: ${result.cycle_nodes.join(", ")}`);
+        showErrorDialog(`验证失败:\n\n${msgs.join("\n")}`);
+      } else if (result.lint_warnings.length > 0) {
+        const msgs = result.lint_warnings.map((warning) => `[${warning.kind}] ${warning.message}`).join("\n");
+        showErrorDialog(`验证通过，但存在以下警告:\n\n${msgs}`, "警告");
       }
     } catch (e) {
       showErrorDialog(`验证失败: ${(e as Error).message}`);
@@ -29,6 +29,8 @@ export class ZihuanCanvas implements CanvasFacade {
   onGraphDirty?: () => void;
   onHistoryChange?: () => void;
   onAddNodeRequest?: (graphX: number, graphY: number) => void;
+  onConnectionRejected?: (message: string) => void;
+  onCycleDetected?: (nodeIds: string[]) => void;
 
   private readonly graphOps: CanvasGraphOps;
   private readonly interactions: CanvasInteractions;
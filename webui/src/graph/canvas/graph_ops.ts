@@ -623,7 +623,8 @@ export class CanvasGraphOps {
     const updatedGraph = { ...graph, edges: edgeDefs };
     this.canvas.state.graph = updatedGraph;
     const pending = graphs.put(sessionId, updatedGraph)
-      .then(() => {
+      .then((result) => {
+        if (result.cycle_nodes?.length) this.canvas.onCycleDetected?.(result.cycle_nodes);
         this.canvas.history.push(updatedGraph);
         this.canvas.onHistoryChange?.();
       })
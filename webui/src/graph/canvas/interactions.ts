@@ -3,6 +3,7 @@ import { graphs } from "../../api/client";
 import type { EdgeDefinition, NodeDefinition, NodeGraphDefinition, NodeTypeInfo } from "../../api/types";
 import { portTypeString, getNodeTypeInfo } from "../registry";
 import type { ConnectPortChoice, PortSelectOption } from "../../ui/dialogs/types";
+import { acceptsLinkFrom } from "../../ui/dialogs/data_types";
 import {
   showAddNodeDialog,
   showConnectPortDialog,
@@ -28,6 +29,7 @@ export class CanvasInteractions {
   bind(): void {
     (LiteGraph as any).release_link_on_empty_shows_menu = true;
     this.bindClipboardShortcuts();
+    this.bindConnectionRejectionNotice();
 
     const dispatchConnectionDrop = (
       sourceNode: any,
@@ -107,6 +109,48 @@ export class CanvasInteractions {
     }, { capture: true });
   }
 
+  /** LiteGraph silently drops a link released on an incompatible port; say why. */
+  private bindConnectionRejectionNotice(): void {
+    const lCanvas = this.canvas.lCanvas as any;
+    const originalProcessMouseUp = lCanvas.processMouseUp.bind(lCanvas);
+    const processMouseUp = (e: MouseEvent) => {
+      const rejection = this.incompatibleLinkDropMessage(e);
+      const result = originalProcessMouseUp(e);
+      if (rejection) this.canvas.onConnectionRejected?.(rejection);
+      return result;
+    };
+    lCanvas.processMouseUp = processMouseUp;
+    // The pointer-up listener is registered from the callback bound when the canvas was created.
+    if (lCanvas._mouseup_callback) lCanvas._mouseup_callback = processMouseUp;
+  }
+
+  private incompatibleLinkDropMessage(e: MouseEvent): string | null {
+    const lCanvas = this.canvas.lCanvas as any;
+    const sourceNode = lCanvas.connecting_node;
+    if (!sourceNode) return null;
+
+    const [gx, gy] = lCanvas.convertEventToCanvasOffset(e) as [number, number];
+    const targetNode = this.canvas.lGraph.getNodeOnPos(gx, gy) as any;
+    if (!targetNode || targetNode === sourceNode) return null;
+    const found = targetNode.getSlotInPosition(gx, gy) as { input?: any; output?: any } | null;
+
+    let from: { node: any; slot: any };
+    let to: { node: any; slot: any };
+    if (lCanvas.connecting_output && found?.input) {
+      from = { node: sourceNode, slot: lCanvas.connecting_output };
+      to = { node: targetNode, slot: found.input };
+    } else if (lCanvas.connecting_input && found?.output) {
+      from = { node: targetNode, slot: found.output };
+      to = { node: sourceNode, slot: lCanvas.connecting_input };
+    } else {
+      return null;
+    }
+    if ((LiteGraph as any).isValidConnection(from.slot.type, to.slot.type)) return null;
+
+    return `无法连接：${from.node.title}.${from.slot.name} (${from.slot.type}) 与 `
+      + `${to.node.title}.${to.slot.name} (${to.slot.type}) 类型不兼容`;
+  }
+
   private bindClipboardShortcuts(): void {
     const lCanvas = this.canvas.lCanvas as any;
     lCanvas.copyToClipboard = () => {
@@ -223,8 +267,8 @@ export class CanvasInteractions {
         if (port.hidden) continue;
         const pt = portTypeString(port.data_type);
         const compatible = isFromOutput
-          ? wantInput && isCompatibleTypes(sourceType, pt)
-          : !wantInput && isCompatibleTypes(sourceType, pt);
+          ? wantInput && (isCompatibleTypes(sourceType, pt) || acceptsLinkFrom(pt, sourceType))
+          : !wantInput && (isCompatibleTypes(sourceType, pt) || acceptsLinkFrom(sourceType, pt));
         if (compatible) candidatePorts.push({ portName: port.name, dataType: pt, isInput: wantInput });
       }
     };
@@ -1,5 +1,6 @@
 import { LGraphCanvas, LiteGraph } from "litegraph.js";
 import { computeLinkGeometry, pointOnLinkGeometry, traceLinkPath } from "../link_layout";
+import { acceptsLinkFrom, isValidConnectionType } from "../../ui/dialogs/data_types";
 import { getLiteGraphColors } from "../../ui/theme";
 import { truncateText } from "./rendering";
 
@@ -11,15 +12,10 @@ export function installLiteGraphPatches(): void {
 
   const originalIsValidConnection = (LiteGraph as any).isValidConnection.bind(LiteGraph);
   (LiteGraph as any).isValidConnection = function (typeA: unknown, typeB: unknown): boolean {
-    if (
-      typeA === "Any" || typeB === "Any" ||
-      (typeof typeA === "string" && typeA.toLowerCase() === "any") ||
-      (typeof typeB === "string" && typeB.toLowerCase() === "any")
-    ) return true;
     if (typeof typeA === "string" && typeof typeB === "string") {
-      const isVecAny = (t: string) => /^Vec<Any>$/i.test(t);
-      const isVec = (t: string) => /^Vec<.+>/i.test(t);
-      if ((isVecAny(typeA) && isVec(typeB)) || (isVecAny(typeB) && isVec(typeA))) return true;
+      if (isValidConnectionType(typeA, typeB)) return true;
+      // typeA is the output slot, typeB the input slot.
+      if (acceptsLinkFrom(typeB, typeA)) return true;
     }
     return originalIsValidConnection(typeA, typeB);
   };
@@ -1,14 +1,9 @@
 import type { NodeDefinition, NodeGraphDefinition } from "../../api/types";
 import { portTypeString } from "../registry";
+import { isValidConnectionType } from "../../ui/dialogs/data_types";
 
 export function isCompatibleTypes(a: string, b: string): boolean {
-  if (!a || !b || a === "*" || b === "*") return true;
-  const lower = (s: string) => s.toLowerCase();
-  if (lower(a) === "any" || lower(b) === "any") return true;
-  const isVecAny = (t: string) => /^Vec<Any>$/i.test(t);
-  const isVec = (t: string) => /^Vec<.+>/i.test(t);
-  if ((isVecAny(a) && isVec(b)) || (isVecAny(b) && isVec(a))) return true;
-  return a === b;
+  return isValidConnectionType(a, b);
 }
 
 export function visibleInputPorts(ports: NodeDefinition["input_ports"]): NodeDefinition["input_ports"] {
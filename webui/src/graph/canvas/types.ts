@@ -34,6 +34,8 @@ export interface CanvasFacade {
   onGraphDirty?: () => void;
   onHistoryChange?: () => void;
   onAddNodeRequest?: (graphX: number, graphY: number) => void;
+  onConnectionRejected?: (message: string) => void;
+  onCycleDetected?: (nodeIds: string[]) => void;
   sessionId: string | null;
   rootSessionId: string | null;
   isInSubgraph: boolean;
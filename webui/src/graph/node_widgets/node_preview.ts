@@ -0,0 +1,91 @@
+// Draws the server-rendered preview of a node (see GET /graphs/:id/nodes/:node_id/preview)
+// inside the node card, so inputs can be checked before the graph runs.
+
+import { graphs } from "../../api/client";
+import type { NodeDefinition } from "../../api/types";
+
+const PADDING = 8;
+const LINE_HEIGHT = 16;
+const SLOT_HEIGHT = 20;
+const MAX_LINES = 12;
+
+export function setupNodePreviewWidgets(
+  lNode: any,
+  nodeDef: NodeDefinition,
+  getSessionId: () => string | null
+): void {
+  let preview = "";
+
+  const refresh = () => {
+    const sid = getSessionId();
+    if (!sid) return;
+    graphs
+      .nodePreview(sid, nodeDef.id)
+      .then((result) => {
+        preview = result.preview;
+        lNode.setDirtyCanvas?.(true, false);
+      })
+      .catch((e) => console.error("[Canvas] node preview failed:", e));
+  };
+  refresh();
+
+  const prev = lNode.onDrawForeground;
+  lNode.onDrawForeground = function (this: any, ctx: CanvasRenderingContext2D) {
+    if (typeof prev === "function") prev.call(this, ctx);
+
+    const nodeWidth: number = this.size?.[0] ?? 240;
+    const maxTextWidth = Math.max(20, nodeWidth - PADDING * 2);
+    const slotCount = Math.max(this.inputs?.length ?? 0, this.outputs?.length ?? 0);
+    const startY = slotCount * SLOT_HEIGHT + PADDING;
+
+    ctx.save();
+    ctx.font = "12px monospace";
+    ctx.textAlign = "left";
+    ctx.textBaseline = "top";
+
+    const lines: string[] = [];
+    for (const para of (preview || "(无预览 — 单击刷新)").split("\n")) {
+      let current = "";
+      for (const ch of para) {
+        if (ctx.measureText(current + ch).width > maxTextWidth && current.length > 0) {
+          lines.push(current);
+          current = ch;
+        } else {
+          current += ch;
+        }
+      }
+      lines.push(current);
+    }
+    if (lines.length > MAX_LINES) {
+      lines.length = MAX_LINES;
+      lines[MAX_LINES - 1] = "…";
+    }
+
+    const boxH = lines.length * LINE_HEIGHT + PADDING;
+    ctx.fillStyle = "rgba(0,0,0,0.25)";
+    ctx.strokeStyle = "rgba(255,255,255,0.08)";
+    ctx.lineWidth = 1;
+    ctx.beginPath();
+    ctx.rect(PADDING / 2, startY, nodeWidth - PADDING, boxH);
+    ctx.fill();
+    ctx.stroke();
+
+    ctx.fillStyle = preview ? "#d4e0f0" : "#777";
+    let curY = startY + PADDING / 2;
+    for (const line of lines) {
+      ctx.fillText(line, PADDING, curY);
+      curY += LINE_HEIGHT;
+    }
+    ctx.restore();
+
+    const desired = startY + boxH + PADDING;
+    if (this.size && this.size[1] < desired) {
+      this.size[1] = desired;
+    }
+  };
+
+  lNode.onMouseDown = function (this: any, _e: MouseEvent, pos: [number, number]) {
+    const slotCount = Math.max(this.inputs?.length ?? 0, this.outputs?.length ?? 0);
+    if (pos[1] >= slotCount * SLOT_HEIGHT + PADDING) refresh();
+  };
+}
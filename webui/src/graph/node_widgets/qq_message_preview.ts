@@ -309,6 +309,18 @@ function drawPreview(
           BUBBLE_BG_REPLY,
         );
         break;
+      case "face":
+        blockHeight = drawTextBubble(
+          ctx,
+          `[Face ${msg.data.id ?? "?"}]`,
+          "",
+          x,
+          y,
+          maxWidth,
+          "#aaa",
+          BUBBLE_BG_TEXT,
+        );
+        break;
       case "forward": {
         const count = msg.data.content?.length ?? 0;
         blockHeight = drawTextBubble(
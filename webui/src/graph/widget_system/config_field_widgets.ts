@@ -199,7 +199,7 @@ function setupActiveBotAdapterSelectWidget(
     void (async () => {
       const sid = getSessionId();
       if (!sid) return;
-      const connections = await system.connections.listActiveBotAdapters();
+      const { items: connections } = await system.connections.listActiveBotAdapters();
       const selected = await showActiveBotAdapterPicker(
         connections,
         initialValue || widget._selectedConnectionId || "",
@@ -189,7 +189,7 @@ export function loadActiveBotAdapterOptions(
 ): void {
   system.connections
     .listActiveBotAdapters()
-    .then((connections) => {
+    .then(({ items: connections }) => {
       const values: Record<string, string> = {
         [CONNECTION_PLACEHOLDER_VALUE]: "请选择连接...",
       };
@@ -6,6 +6,7 @@ import { setupFormatStringWidgets } from "./node_widgets/format_string";
 import { setupFunctionWidgets } from "./node_widgets/function_node";
 import { setupJsonExtractWidgets } from "./node_widgets/json_extract";
 import { setupLLMMessageListWidgets } from "./node_widgets/llm_message_list_data";
+import { setupNodePreviewWidgets } from "./node_widgets/node_preview";
 import { setupQQMessageListWidgets } from "./node_widgets/qq_message_list_data";
 import { setupQQMessagePreviewWidgets } from "./node_widgets/qq_message_preview";
 import { setupStringDataWidgets } from "./node_widgets/string_data";
@@ -50,6 +51,9 @@ export function setupNodeWidgets(
     case "qq_message_list_data":
       setupQQMessageListWidgets(lNode, nodeDef, getSessionId, onRefresh);
       break;
+    case "preview_message_list":
+      setupNodePreviewWidgets(lNode, nodeDef, getSessionId);
+      break;
     case "qq_message_preview":
       setupQQMessagePreviewWidgets(lNode, nodeDef);
       break;
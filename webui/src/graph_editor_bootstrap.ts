@@ -4,6 +4,7 @@ import { registry, graphs, workflows, fileIO } from "./api/client";
 import { ws } from "./api/ws";
 import type { NodeTypeInfo, TaskEntry } from "./api/types";
 import { registerNodeTypes } from "./graph/registry";
+import { setRegisteredCompatibleTypes } from "./ui/dialogs/data_types";
 import { ZihuanCanvas } from "./graph/canvas";
 import { installPreviewWsHandler } from "./graph/node_widgets/qq_message_preview";
 import {
@@ -38,6 +39,7 @@ export async function bootstrapGraphEditor() {
   try {
     const reg = await registry.getTypes();
     nodeTypes = reg.types;
+    setRegisteredCompatibleTypes(reg.type_compatibility ?? []);
     registerNodeTypes(nodeTypes);
   } catch (e) {
     console.error("Failed to load registry:", e);
@@ -166,6 +168,8 @@ export async function bootstrapGraphEditor() {
   };
 
   const addLog = createLogToastOverlay(canvasContainer);
+  canvas.onConnectionRejected = (message) => addLog("warn", message);
+  canvas.onCycleDetected = (nodeIds) => addLog("warn", `连线形成依赖环: ${nodeIds.join(", ")}`);
   registerTaskRuntimeHandlers(ws, {
     onTaskLifecycleChanged: () => {
       taskStore.refresh().catch(console.error);
@@ -4,7 +4,7 @@ export function ensureDialogStyles(): void {
 	// Styles are injected via CSS import (dialog.css)
 }
 
-export function showErrorDialog(message: string): void {
+export function showErrorDialog(message: string, titleText = "错误"): void {
 	ensureDialogStyles();
 	const overlay = document.createElement("div");
 	overlay.className = "zh-overlay";
@@ -14,7 +14,7 @@ export function showErrorDialog(message: string): void {
 	dialog.style.maxWidth = "520px";
 	const title = document.createElement("h3");
 	title.style.color = "var(--accent, #e94560)";
-	title.textContent = "错误";
+	title.textContent = titleText;
 	dialog.appendChild(title);
 	const msg = document.createElement("p");
 	msg.style.cssText = "margin:0 0 16px;font-size:13px;white-space:pre-wrap;word-break:break-all;line-height:1.5;";
@@ -1,6 +1,6 @@
 import type { NodeDefinition } from "../../api/types";
 import { ensureDialogStyles, openOverlay } from "./base";
-import { isValidConnectionType } from "./data_types";
+import { acceptsLinkFrom, isValidConnectionType } from "./data_types";
 import type { ConnectPortChoice, PortSelectOption } from "./types";
 import "./connection_dialogs.css";
 
@@ -107,7 +107,9 @@ export function showConnectPortDialog(
             : Object.keys(p.data_type as object).length > 0
               ? `${Object.keys(p.data_type as object)[0]}<${Object.values(p.data_type as object)[0] as string}>`
               : "*";
-          if (!isValidConnectionType(sourceType, pt)) return false;
+          const linkable = isValidConnectionType(sourceType, pt)
+            || (isFromOutput ? acceptsLinkFrom(pt, sourceType) : acceptsLinkFrom(sourceType, pt));
+          if (!linkable) return false;
           if (q) {
             const portName = p.name.toLowerCase();
             const nodeNameLower = nodeName.toLowerCase();
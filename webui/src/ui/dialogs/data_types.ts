@@ -134,14 +134,57 @@ export function dataTypeSelect(
   return sel;
 }
 
-/** Check whether two DataType strings are wire-compatible. */
+/** Pairs from the backend `TypeCompatibilityRegistry`, keyed by `compatiblePairKey`. */
+const registeredCompatiblePairs = new Set<string>();
+
+function compatiblePairKey(a: string, b: string): string {
+  return `${a}\u0000${b}`;
+}
+
+/**
+ * Replace the extra compatible type pairs with those reported by
+ * `/api/registry/types`. The backend lists each pair in both orders.
+ */
+export function setRegisteredCompatibleTypes(pairs: [DataTypeMetaData, DataTypeMetaData][]): void {
+  registeredCompatiblePairs.clear();
+  for (const [a, b] of pairs) {
+    registeredCompatiblePairs.add(compatiblePairKey(normalizeDataType(a), normalizeDataType(b)));
+  }
+}
+
+/** Check whether two DataType strings are wire-compatible. Mirrors Rust `DataType::is_compatible_with`. */
 export function isValidConnectionType(a: string, b: string): boolean {
   if (!a || !b) return true;
   if (a === "*" || b === "*") return true;
   const lower = (s: string) => s.toLowerCase();
   if (lower(a) === "any" || lower(b) === "any") return true;
-  const isVecAny = (t: string) => /^Vec<Any>$/i.test(t);
-  const isVec = (t: string) => /^Vec<.+>/i.test(t);
-  if ((isVecAny(a) && isVec(b)) || (isVecAny(b) && isVec(a))) return true;
-  return a === b;
+  const aInner = vecElementType(a);
+  const bInner = vecElementType(b);
+  if (aInner !== null && bInner !== null && isValidConnectionType(aInner, bInner)) return true;
+  return a === b || registeredCompatiblePairs.has(compatiblePairKey(a, b));
+}
+
+function vecElementType(t: string): string | null {
+  return t.match(/^Vec<(.+)>$/)?.[1] ?? null;
+}
+
+/** Mirrors Rust `DataType::can_coerce_to`. */
+function coercesTo(from: string, to: string): boolean {
+  if (isValidConnectionType(from, to) || to === "Json") return true;
+  if (from === "Integer" && to === "Float") return true;
+  const fromInner = vecElementType(from);
+  const toInner = vecElementType(to);
+  return fromInner !== null && toInner !== null && coercesTo(fromInner, toInner);
+}
+
+/**
+ * Directional relaxations on top of `isValidConnectionType`, mirroring Rust
+ * `DataType::accepts_link_from`: a Json input accepts any output, and a
+ * Vec<T> input accepts Vec<U> when U coerces to T.
+ */
+export function acceptsLinkFrom(inputType: string, outputType: string): boolean {
+  if (inputType === "Json") return true;
+  const inputInner = vecElementType(inputType);
+  const outputInner = vecElementType(outputType);
+  return inputInner !== null && outputInner !== null && coercesTo(outputInner, inputInner);
 }
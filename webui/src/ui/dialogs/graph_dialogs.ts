@@ -224,6 +224,19 @@ export async function openGraphIODialog(
   agentEventWrap.appendChild(agentEventLabel);
   dialog.appendChild(agentEventWrap);
 
+  const parallelWrap = document.createElement("label");
+  parallelWrap.style.cssText = "display:flex;align-items:center;gap:8px;margin:10px 0;cursor:pointer;";
+  const parallelCb = document.createElement("input");
+  parallelCb.type = "checkbox";
+  parallelCb.checked = graph.execution_mode === "parallel";
+  parallelCb.style.cssText = "flex-shrink:0;width:16px;height:16px;margin:0;";
+  const parallelLabel = document.createElement("span");
+  parallelLabel.textContent = "并行执行互不依赖的节点";
+  parallelLabel.style.cssText = "font-size:13px;line-height:1.4;";
+  parallelWrap.appendChild(parallelCb);
+  parallelWrap.appendChild(parallelLabel);
+  dialog.appendChild(parallelWrap);
+
   const inputsSection = document.createElement("div");
   const inputsLabel = document.createElement("div");
   inputsLabel.className = "zh-section-label";
@@ -274,6 +287,7 @@ export async function openGraphIODialog(
         graph_inputs: readInputs(),
         graph_outputs: readOutputs(),
         accepts_agent_events: agentEventCb.checked,
+        execution_mode: parallelCb.checked ? "parallel" : "sequential",
       });
       await onSaved();
       close();
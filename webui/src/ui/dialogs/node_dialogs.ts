@@ -137,6 +137,7 @@ export function showAddNodeDialog(nodeTypes: NodeTypeInfo[]): Promise<string | n
 
     let activeCategory = "全部";
     let searchText = "";
+    const collapsedCategories = new Set<string>();
     let currentDetailTypeId: string | null = null;
 
     const updateActiveItem = () => {
@@ -292,6 +293,45 @@ export function showAddNodeDialog(nodeTypes: NodeTypeInfo[]): Promise<string | n
       });
     }
 
+    function renderItem(nt: NodeTypeInfo) {
+      const item = document.createElement("div");
+      item.className = "zh-an-item";
+      item.dataset.typeId = nt.type_id;
+
+      const top = document.createElement("div");
+      top.className = "zh-an-item-top";
+
+      const name = document.createElement("span");
+      name.className = "zh-an-name";
+      name.textContent = nt.display_name;
+      top.appendChild(name);
+
+      const badge = document.createElement("span");
+      badge.className = "zh-an-badge";
+      badge.textContent = nt.category;
+      top.appendChild(badge);
+
+      item.appendChild(top);
+
+      if (nt.description) {
+        const desc = document.createElement("div");
+        desc.className = "zh-an-desc";
+        desc.textContent = nt.description;
+        item.appendChild(desc);
+      }
+
+      if (supportsHoverPreview) {
+        item.addEventListener("mouseenter", () => renderDetail(nt));
+      }
+      item.addEventListener("focus", () => renderDetail(nt));
+      item.addEventListener("click", () => {
+        document.body.removeChild(overlay);
+        resolve(nt.type_id);
+      });
+
+      listEl.appendChild(item);
+    }
+
     function renderList() {
       listEl.innerHTML = "";
       const filtered = applyFilter();
@@ -302,43 +342,31 @@ export function showAddNodeDialog(nodeTypes: NodeTypeInfo[]): Promise<string | n
         listEl.appendChild(empty);
         return;
       }
-      for (const nt of filtered) {
-        const item = document.createElement("div");
-        item.className = "zh-an-item";
-        item.dataset.typeId = nt.type_id;
-
-        const top = document.createElement("div");
-        top.className = "zh-an-item-top";
-
-        const name = document.createElement("span");
-        name.className = "zh-an-name";
-        name.textContent = nt.display_name;
-        top.appendChild(name);
-
-        const badge = document.createElement("span");
-        badge.className = "zh-an-badge";
-        badge.textContent = nt.category;
-        top.appendChild(badge);
-
-        item.appendChild(top);
-
-        if (nt.description) {
-          const desc = document.createElement("div");
-          desc.className = "zh-an-desc";
-          desc.textContent = nt.description;
-          item.appendChild(desc);
-        }
+      if (activeCategory !== "全部") {
+        filtered.forEach(renderItem);
+        updateActiveItem();
+        return;
+      }
 
-        if (supportsHoverPreview) {
-          item.addEventListener("mouseenter", () => renderDetail(nt));
-        }
-        item.addEventListener("focus", () => renderDetail(nt));
-        item.addEventListener("click", () => {
-          document.body.removeChild(overlay);
-          resolve(nt.type_id);
+      // "全部" groups the list by category. A search expands every group so
+      // no match stays hidden behind a collapsed header.
+      const groups = new Map<string, NodeTypeInfo[]>();
+      for (const nt of filtered) {
+        if (!groups.has(nt.category)) groups.set(nt.category, []);
+        groups.get(nt.category)!.push(nt);
+      }
+      for (const [category, types] of groups) {
+        const collapsed = !searchText && collapsedCategories.has(category);
+        const header = document.createElement("div");
+        header.className = "zh-an-group-header" + (collapsed ? " collapsed" : "");
+        header.textContent = `${category} (${types.length})`;
+        header.addEventListener("click", () => {
+          if (collapsedCategories.has(category)) collapsedCategories.delete(category);
+          else collapsedCategories.add(category);
+          renderList();
         });
-
-        listEl.appendChild(item);
+        listEl.appendChild(header);
+        if (!collapsed) types.forEach(renderItem);
       }
       updateActiveItem();
     }
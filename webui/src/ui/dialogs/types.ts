@@ -51,11 +51,11 @@ export interface BrainToolDefinition {
 }
 
 export interface QQMessageItem {
-  type: "text" | "at" | "reply" | "image" | "forward";
+  type: "text" | "at" | "reply" | "image" | "face" | "forward";
   data: {
     text?: string;
     target?: string;
-    id?: number;
+    id?: number | string;
     url?: string;
     path?: string;
     file?: string;
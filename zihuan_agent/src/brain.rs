@@ -1,15 +1,18 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use log::{info, warn};
 use model_inference::message_content_utils::{is_transport_error, sanitize_messages_for_inference};
 use serde_json::Value;
 use tokio::sync::mpsc;
 
+pub use zihuan_core::agent_config::qq_chat::{EmptyReplyFallback, NoToolFallback, EMPTY_REPLY_CANNED_MESSAGE};
+use zihuan_core::error::Error;
 use zihuan_core::llm::llm_base::LLMBase;
-use zihuan_core::llm::tooling::FunctionTool;
-use zihuan_core::llm::tooling::ToolCalls;
+use zihuan_core::llm::tooling::{normalized_tool_arguments, FunctionTool};
+use zihuan_core::llm::tooling::{ToolCalls, ToolCallsFuncSpec, ToolRegistry};
 use zihuan_core::llm::{InferenceParam, LLMMessage, MessagePart, MessageRole, StreamToken};
 use zihuan_core::task_context::{
     scope_task_id, scope_task_runtime, AgentTaskRequest, AgentTaskResult, AgentTaskRuntime, AgentTaskStatus,
@@ -18,7 +21,14 @@ pub use zihuan_core::tool_runtime::ToolRunDuration;
 use zihuan_core::workspace::AskUserRequest;
 
 pub const MAX_TOOL_ITERATIONS: usize = 25;
+/// How often one tool may run with identical arguments in a single run before
+/// further repeats are refused and the model has to answer without tools.
+pub const MAX_IDENTICAL_TOOL_CALLS: usize = 3;
+pub const TOOL_RESULT_TRUNCATED_MARKER: &str = "...(truncated)";
 const LOG_PREVIEW_CHARS: usize = 600;
+/// Final content emitted for [`NoToolFallback::Silent`] and [`EmptyReplyFallback::Silent`];
+/// callers treat it as "do not reply".
+pub const NO_REPLY_DIRECTIVE: &str = "[no_reply]";
 
 thread_local! {
     static TOOL_PROGRESS_SCOPE_STACK: RefCell<Vec<ToolProgressScopeState>> = const { RefCell::new(Vec::new()) };
@@ -40,6 +50,16 @@ fn truncate_for_log(text: &str, max_chars: usize) -> String {
     format!("{truncated}...(truncated,total_chars={total_chars})")
 }
 
+/// Cuts an oversized tool result to `max_chars` and appends
+/// [`TOOL_RESULT_TRUNCATED_MARKER`] so the model knows the output is incomplete.
+pub fn truncate_tool_result(result: &str, max_chars: usize) -> Option<String> {
+    if result.chars().count() <= max_chars {
+        return None;
+    }
+    let truncated: String = result.chars().take(max_chars).collect();
+    Some(format!("{truncated}{TOOL_RESULT_TRUNCATED_MARKER}"))
+}
+
 fn format_cache_hit_rate(cached_prompt_tokens: Option<usize>, prompt_tokens: Option<usize>) -> String {
     match (cached_prompt_tokens, prompt_tokens) {
         (Some(cached), Some(prompt)) if prompt > 0 => {
@@ -71,6 +91,106 @@ impl Drop for ToolProgressScopeGuard {
     }
 }
 
+fn current_tool_progress_consumed() -> bool {
+    TOOL_PROGRESS_SCOPE_STACK.with(|stack| stack.borrow().last().map(|scope| scope.consumed).unwrap_or(false))
+}
+
+fn mark_tool_progress_consumed() {
+    TOOL_PROGRESS_SCOPE_STACK.with(|stack| {
+        if let Some(scope) = stack.borrow_mut().last_mut() {
+            scope.consumed = true;
+        }
+    });
+}
+
+/// Work item running one tool call on a blocking-pool thread. The caller's tool
+/// progress scope is carried over and the consumed flag is returned with the output.
+fn tool_call_job(
+    tool: Arc<dyn BrainTool>,
+    call_content: &str,
+    arguments: &Value,
+) -> impl FnOnce() -> (ToolExecutionOutput, bool) + Send + 'static {
+    let call_content = call_content.to_string();
+    let arguments = arguments.clone();
+    let progress_consumed = current_tool_progress_consumed();
+    move || {
+        let _scope = ToolProgressScopeGuard::enter(&call_content);
+        if progress_consumed {
+            mark_tool_progress_consumed();
+        }
+        let output = tool.execute_with_outcome(&call_content, &arguments);
+        (output, current_tool_progress_consumed())
+    }
+}
+
+fn tool_timeout_error(tool_name: &str, timeout: Duration) -> Error {
+    Error::ToolTimeout {
+        tool_name: tool_name.to_string(),
+        secs: timeout.as_secs(),
+    }
+}
+
+fn finish_tool_call((output, progress_consumed): (ToolExecutionOutput, bool)) -> ToolExecutionOutput {
+    if progress_consumed {
+        mark_tool_progress_consumed();
+    }
+    output
+}
+
+/// Run `tool` on the tokio blocking pool and give up waiting after `timeout`.
+///
+/// Sync tools cannot be interrupted once started, so a timed-out call still
+/// finishes in the background, but it holds a slot of the runtime's bounded
+/// blocking pool instead of a thread of its own. A call that is still queued
+/// when the wait ends is cancelled.
+pub async fn run_tool_with_timeout(
+    tool: Arc<dyn BrainTool>,
+    tool_name: &str,
+    call_content: &str,
+    arguments: &Value,
+    timeout: Duration,
+) -> zihuan_core::error::Result<ToolExecutionOutput> {
+    let task = tokio::task::spawn_blocking(tool_call_job(tool, call_content, arguments));
+    let abort_handle = task.abort_handle();
+    let Ok(joined) = tokio::time::timeout(timeout, task).await else {
+        abort_handle.abort();
+        return Err(tool_timeout_error(tool_name, timeout));
+    };
+    let result = joined.map_err(|err| Error::StringError(format!("tool '{tool_name}' panicked: {err}")))?;
+    Ok(finish_tool_call(result))
+}
+
+/// Blocking counterpart of [`run_tool_with_timeout`] for the sync [`Brain::run`]
+/// loop. Without a tokio runtime there is no bounded pool to run on, so the tool
+/// runs inline and is not limited.
+fn run_tool_with_timeout_blocking(
+    tool: Arc<dyn BrainTool>,
+    tool_name: &str,
+    call_content: &str,
+    arguments: &Value,
+    timeout: Duration,
+) -> zihuan_core::error::Result<ToolExecutionOutput> {
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        warn!("[Brain] no tokio runtime, running tool '{tool_name}' without its timeout");
+        return Ok(tool.execute_with_outcome(call_content, arguments));
+    };
+    let (tx, rx) = std::sync::mpsc::channel();
+    let job = tool_call_job(tool, call_content, arguments);
+    let task = runtime.spawn_blocking(move || {
+        let _ = tx.send(job());
+    });
+    match rx.recv_timeout(timeout) {
+        Ok(result) => Ok(finish_tool_call(result)),
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+            task.abort();
+            Err(tool_timeout_error(tool_name, timeout))
+        }
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+            Err(Error::StringError(format!("tool '{tool_name}' panicked")))
+        }
+    }
+}
+
 pub fn consume_tool_progress_notification(call_content: &str) -> bool {
     let trimmed = call_content.trim();
     if trimmed.is_empty() {
@@ -205,32 +325,78 @@ impl ToolExecutionOutput {
 /// then call [`Brain::run`] with the initial conversation messages.
 pub struct Brain {
     llm: Arc<dyn LLMBase>,
-    tools: Vec<Arc<dyn BrainTool>>,
+    tools: ToolRegistry<dyn BrainTool>,
     observer: Option<Arc<dyn BrainObserver>>,
     iteration_hook: Option<Arc<dyn BrainIterationHook>>,
     long_task_context: Option<LongTaskContext>,
+    max_tool_result_chars: Option<usize>,
+    tool_timeout: Option<Duration>,
+    turn_budget: Option<Duration>,
+    no_tool_fallback: NoToolFallback,
+    empty_reply_fallback: EmptyReplyFallback,
+}
+
+/// Outcome of [`Brain::dispatch_tool_call`].
+enum ToolDispatch {
+    /// The call already ran or was rejected.
+    Done(ToolExecutionOutput),
+    /// The call still has to run, limited to this timeout.
+    Timed(Duration),
+}
+
+/// Turns a failed timed tool call into an error result the model can read.
+fn tool_call_output_or_error(result: zihuan_core::error::Result<ToolExecutionOutput>) -> ToolExecutionOutput {
+    match result {
+        Ok(output) => output,
+        Err(err) => {
+            warn!("[Brain] {err}");
+            ToolExecutionOutput::text(serde_json::json!({ "error": err.to_string() }).to_string())
+        }
+    }
+}
+
+/// A reply with neither tool calls nor visible text.
+fn is_empty_reply(response: &LLMMessage) -> bool {
+    response.tool_calls.is_empty() && response.content_text_owned().map_or(true, |content| content.trim().is_empty())
+}
+
+/// How a response without tool calls is handled, see [`NoToolFallback`].
+enum NoToolResolution {
+    /// Ends the run with this final assistant message.
+    Final(LLMMessage),
+    /// Continues the loop with this message, which now carries a tool call.
+    Dispatch(LLMMessage),
 }
 
 impl Brain {
     pub fn new(llm: Arc<dyn LLMBase>) -> Self {
         Self {
             llm,
-            tools: Vec::new(),
+            tools: ToolRegistry::new(),
             observer: None,
             iteration_hook: None,
             long_task_context: None,
+            max_tool_result_chars: None,
+            tool_timeout: None,
+            turn_budget: None,
+            no_tool_fallback: NoToolFallback::default(),
+            empty_reply_fallback: EmptyReplyFallback::default(),
         }
     }
 
     /// Register a tool, consuming and returning `self` for builder-style chaining.
     pub fn with_tool(mut self, tool: impl BrainTool) -> Self {
-        self.tools.push(Arc::new(tool));
+        self.add_tool(tool);
         self
     }
 
-    /// Register a tool in-place.
+    /// Register a tool in-place. A tool with the same name replaces the earlier one.
     pub fn add_tool(&mut self, tool: impl BrainTool) {
-        self.tools.push(Arc::new(tool));
+        let tool: Arc<dyn BrainTool> = Arc::new(tool);
+        let name = tool.spec().name().to_string();
+        if self.tools.insert(name.clone(), tool).is_some() {
+            warn!("[Brain] tool '{name}' registered twice, keeping the latest");
+        }
     }
 
     /// Attach a long-task execution context.
@@ -256,6 +422,169 @@ impl Brain {
         self.iteration_hook = Some(hook);
     }
 
+    /// Limit how many chars of each tool result are appended to the conversation.
+    /// `None`, the default, feeds results back unchanged.
+    pub fn with_max_tool_result_chars(mut self, max_chars: Option<usize>) -> Self {
+        self.max_tool_result_chars = max_chars;
+        self
+    }
+
+    pub fn set_max_tool_result_chars(&mut self, max_chars: Option<usize>) {
+        self.max_tool_result_chars = max_chars;
+    }
+
+    /// Abandon short-running tool calls that take longer than `timeout`; the model
+    /// receives an error result instead. Long-running tools are not limited.
+    pub fn with_tool_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.tool_timeout = timeout;
+        self
+    }
+
+    pub fn set_tool_timeout(&mut self, timeout: Option<Duration>) {
+        self.tool_timeout = timeout;
+    }
+
+    /// Once a run has used up `budget`, the next inference is the last one and is
+    /// made without tools, like when [`MAX_TOOL_ITERATIONS`] is reached.
+    pub fn set_turn_budget(&mut self, budget: Option<Duration>) {
+        self.turn_budget = budget;
+    }
+
+    /// Apply the `tool_secs` and `agent_secs` limits from the `timeouts` config.
+    pub fn apply_timeout_settings(&mut self, settings: &zihuan_core::system_config::TimeoutSettings) {
+        self.tool_timeout = Some(settings.tool());
+        self.turn_budget = Some(settings.agent());
+    }
+
+    /// Behaviour when the model answers a turn without calling any tool.
+    pub fn with_no_tool_fallback(mut self, fallback: NoToolFallback) -> Self {
+        self.no_tool_fallback = fallback;
+        self
+    }
+
+    pub fn set_no_tool_fallback(&mut self, fallback: NoToolFallback) {
+        self.no_tool_fallback = fallback;
+    }
+
+    /// Behaviour when the model's final reply is empty or whitespace-only.
+    /// Defaults to one regeneration, then silence.
+    pub fn with_empty_reply_fallback(mut self, fallback: EmptyReplyFallback) -> Self {
+        self.empty_reply_fallback = fallback;
+        self
+    }
+
+    pub fn set_empty_reply_fallback(&mut self, fallback: EmptyReplyFallback) {
+        self.empty_reply_fallback = fallback;
+    }
+
+    /// Whether `response` should be discarded and the inference repeated once.
+    fn should_regenerate(&self, response: &LLMMessage) -> bool {
+        if self.empty_reply_fallback != EmptyReplyFallback::Regenerate || !is_empty_reply(response) {
+            return false;
+        }
+        warn!("[Brain] model returned an empty reply, regenerating once");
+        true
+    }
+
+    /// Replaces an empty final `response` per [`EmptyReplyFallback`]. After a
+    /// regeneration that came back empty again the turn stays silent.
+    fn fill_empty_reply(&self, mut response: LLMMessage) -> LLMMessage {
+        if !is_empty_reply(&response) {
+            return response;
+        }
+        let content = match self.empty_reply_fallback {
+            EmptyReplyFallback::Canned => EMPTY_REPLY_CANNED_MESSAGE,
+            EmptyReplyFallback::Regenerate | EmptyReplyFallback::Silent => {
+                info!("[Brain] model returned an empty reply, staying silent per empty_reply_fallback");
+                NO_REPLY_DIRECTIVE
+            }
+        };
+        response.parts = vec![MessagePart::text(content)];
+        response
+    }
+
+    /// Applies [`NoToolFallback`] to a tool-less `response`. The fallback only
+    /// kicks in when the run has tools and none was called yet; once a tool ran,
+    /// plain content is the model's final answer.
+    fn resolve_no_tool_response(
+        &self,
+        mut response: LLMMessage,
+        output: &[LLMMessage],
+        iteration: usize,
+        is_last_iteration: bool,
+    ) -> NoToolResolution {
+        let called_tool = output.iter().any(|message| matches!(message.role, MessageRole::Tool));
+        if self.tools.is_empty() || called_tool {
+            return NoToolResolution::Final(response);
+        }
+
+        match &self.no_tool_fallback {
+            NoToolFallback::SendContent => NoToolResolution::Final(response),
+            NoToolFallback::Silent => {
+                info!("[Brain] model called no tool, staying silent per no_tool_fallback");
+                response.parts = vec![MessagePart::text(NO_REPLY_DIRECTIVE)];
+                NoToolResolution::Final(response)
+            }
+            NoToolFallback::DefaultAgent(agent) => {
+                if is_last_iteration {
+                    return NoToolResolution::Final(response);
+                }
+                if !self.tools.contains(agent) {
+                    warn!("[Brain] no_tool_fallback agent '{agent}' is not registered, sending content instead");
+                    return NoToolResolution::Final(response);
+                }
+                info!("[Brain] model called no tool, dispatching to default agent '{agent}'");
+                let content = response.content_text_owned().unwrap_or_default();
+                response.tool_calls = vec![ToolCalls {
+                    id: format!("no_tool_fallback_{}", iteration + 1),
+                    type_name: "function".to_string(),
+                    function: ToolCallsFuncSpec {
+                        name: agent.clone(),
+                        arguments: serde_json::json!({ "content": content }),
+                    },
+                }];
+                NoToolResolution::Dispatch(response)
+            }
+        }
+    }
+
+    fn is_final_iteration(&self, iteration: usize, started_at: Instant) -> bool {
+        if iteration == MAX_TOOL_ITERATIONS - 1 {
+            return true;
+        }
+        match self.turn_budget {
+            Some(budget) if iteration > 0 && started_at.elapsed() >= budget => {
+                warn!(
+                    "[Brain] turn budget of {}s used up after {} iteration(s), answering without tools",
+                    budget.as_secs(),
+                    iteration
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Build the tool message for `result`, truncating it to the configured limit.
+    fn tool_result_message(&self, tool_name: &str, call_id: &str, result: &str) -> LLMMessage {
+        let truncated = self
+            .max_tool_result_chars
+            .and_then(|max_chars| truncate_tool_result(result, max_chars));
+        match truncated {
+            Some(content) => {
+                warn!(
+                    "[Brain] tool call id={} name={} result truncated from {} to {} chars before feeding back",
+                    call_id,
+                    tool_name,
+                    result.chars().count(),
+                    self.max_tool_result_chars.unwrap_or_default()
+                );
+                LLMMessage::tool_result(call_id.to_string(), content)
+            }
+            None => LLMMessage::tool_result(call_id.to_string(), result.to_string()),
+        }
+    }
+
     /// Execute a single tool call, creating a tracked task entry when the tool's
     /// run duration is `Long` and a [`LongTaskContext`] is available.
     fn execute_tool_call(
@@ -265,6 +594,48 @@ impl Brain {
         arguments: &Value,
         tool_name: &str,
     ) -> ToolExecutionOutput {
+        let arguments = &normalized_tool_arguments(arguments);
+        let timeout = match self.dispatch_tool_call(tool, call_content, arguments, tool_name) {
+            ToolDispatch::Done(output) => return output,
+            ToolDispatch::Timed(timeout) => timeout,
+        };
+        let result = run_tool_with_timeout_blocking(Arc::clone(tool), tool_name, call_content, arguments, timeout);
+        tool_call_output_or_error(result)
+    }
+
+    /// Async counterpart of [`Brain::execute_tool_call`] for the streaming loop,
+    /// waiting on timed tool calls without blocking a runtime worker.
+    async fn execute_tool_call_async(
+        &self,
+        tool: &Arc<dyn BrainTool>,
+        call_content: &str,
+        arguments: &Value,
+        tool_name: &str,
+    ) -> ToolExecutionOutput {
+        let arguments = &normalized_tool_arguments(arguments);
+        let timeout = match self.dispatch_tool_call(tool, call_content, arguments, tool_name) {
+            ToolDispatch::Done(output) => return output,
+            ToolDispatch::Timed(timeout) => timeout,
+        };
+        let result = run_tool_with_timeout(Arc::clone(tool), tool_name, call_content, arguments, timeout).await;
+        tool_call_output_or_error(result)
+    }
+
+    /// Handles every tool call that does not need a timeout: invalid arguments,
+    /// tracked long tasks and runs without [`Brain::set_tool_timeout`].
+    fn dispatch_tool_call(
+        &self,
+        tool: &Arc<dyn BrainTool>,
+        call_content: &str,
+        arguments: &Value,
+        tool_name: &str,
+    ) -> ToolDispatch {
+        // Reject arguments that do not match the schema before the tool runs,
+        // and let the model correct the call from the error.
+        if let Err(message) = tool.spec().validate_arguments(arguments) {
+            warn!("[Brain] tool '{}' called with invalid arguments: {}", tool_name, message);
+            return ToolDispatch::Done(ToolExecutionOutput::text(serde_json::json!({ "error": message }).to_string()));
+        }
         if tool.run_duration() == ToolRunDuration::Long {
             if let Some(long_ctx) = &self.long_task_context {
                 let task_name = format!("工具: {tool_name}");
@@ -291,10 +662,13 @@ impl Brain {
                 });
                 long_ctx.notifier.on_complete(&task_id, &task_name, &result.result);
                 info!("[Brain] tool '{}' completed as long task_id={}", tool_name, task_id);
-                return result;
+                return ToolDispatch::Done(result);
             }
         }
-        tool.execute_with_outcome(call_content, arguments)
+        match self.tool_timeout.filter(|_| tool.run_duration() != ToolRunDuration::Long) {
+            Some(timeout) => ToolDispatch::Timed(timeout),
+            None => ToolDispatch::Done(tool.execute_with_outcome(call_content, arguments)),
+        }
     }
 
     fn log_llm_usage(&self, response: &LLMMessage) {
@@ -353,25 +727,26 @@ impl Brain {
         let tool_specs: Vec<Arc<dyn FunctionTool>> = self.tools.iter().map(|t| t.spec()).collect();
         let mut conversation = sanitize_messages_for_inference(messages);
         let mut output: Vec<LLMMessage> = Vec::new();
+        let started_at = Instant::now();
+        let mut identical_calls: HashMap<String, usize> = HashMap::new();
+        let mut repeating_tool_calls = false;
         for iteration in 0..MAX_TOOL_ITERATIONS {
             if iteration > 0 {
                 self.append_iteration_messages(iteration + 1, &mut conversation);
             }
-            let is_last_iteration = iteration == MAX_TOOL_ITERATIONS - 1;
+            let is_last_iteration = repeating_tool_calls || self.is_final_iteration(iteration, started_at);
 
             if is_last_iteration {
                 let counts = count_tool_calls(&conversation);
                 append_tool_summary_to_system(&mut conversation, &counts);
             }
 
-            let response = self.llm.inference(&InferenceParam {
-                messages: &conversation,
-                tools: if is_last_iteration || tool_specs.is_empty() {
-                    None
-                } else {
-                    Some(&tool_specs)
-                },
-            });
+            let tools_param = (!is_last_iteration && !tool_specs.is_empty()).then_some(&tool_specs);
+            let param = InferenceParam::new(&conversation).with_tools(tools_param);
+            let mut response = self.llm.inference(&param);
+            if self.should_regenerate(&response) {
+                response = self.llm.inference(&param);
+            }
 
             if let Some(content) = response.content_text() {
                 if is_transport_error(content) {
@@ -387,13 +762,25 @@ impl Brain {
 
             self.log_llm_usage(&response);
 
-            if response.tool_calls.is_empty() {
-                if let Some(observer) = self.observer.as_ref() {
-                    observer.on_final_assistant(&response, &BrainStopReason::Done);
+            let response = if response.tool_calls.is_empty() {
+                let resolution = if is_empty_reply(&response) {
+                    NoToolResolution::Final(self.fill_empty_reply(response))
+                } else {
+                    self.resolve_no_tool_response(response, &output, iteration, is_last_iteration)
+                };
+                match resolution {
+                    NoToolResolution::Final(response) => {
+                        if let Some(observer) = self.observer.as_ref() {
+                            observer.on_final_assistant(&response, &BrainStopReason::Done);
+                        }
+                        output.push(response);
+                        return (output, BrainStopReason::Done);
+                    }
+                    NoToolResolution::Dispatch(response) => response,
                 }
-                output.push(response);
-                return (output, BrainStopReason::Done);
-            }
+            } else {
+                response
+            };
 
             if is_last_iteration {
                 if let Some(observer) = self.observer.as_ref() {
@@ -441,8 +828,11 @@ impl Brain {
                 if let Some(observer) = self.observer.as_ref() {
                     observer.on_tool_start(&tc.function.name, &tc.id, &tc.function.arguments);
                 }
-                let matching_tool = self.tools.iter().find(|t| t.spec().name() == tc.function.name);
-                let result = if let Some(tool) = matching_tool {
+                let matching_tool = self.tools.get(&tc.function.name);
+                let result = if let Some(refusal) = refuse_identical_call(&mut identical_calls, tc) {
+                    repeating_tool_calls = true;
+                    refusal
+                } else if let Some(tool) = matching_tool {
                     self.execute_tool_call(tool, &tool_call_content, &tc.function.arguments, &tc.function.name)
                 } else {
                     warn!(
@@ -463,7 +853,7 @@ impl Brain {
                 if let Some(observer) = self.observer.as_ref() {
                     observer.on_tool_finish(&tc.function.name, &tc.id, &result.result);
                 }
-                let msg = LLMMessage::tool_result(tc.id.clone(), result.result.clone());
+                let msg = self.tool_result_message(&tc.function.name, &tc.id, &result.result);
                 conversation.push(msg.clone());
                 output.push(msg);
                 if let Some(request) = result.ask_user {
@@ -479,6 +869,17 @@ impl Brain {
         (output, BrainStopReason::MaxIterationsReached)
     }
 
+    async fn infer_streaming(
+        &self,
+        param: &InferenceParam<'_>,
+        token_tx: &mpsc::UnboundedSender<StreamToken>,
+    ) -> LLMMessage {
+        match self.llm.as_streaming() {
+            Some(streaming) => streaming.inference_streaming(param, token_tx.clone()).await,
+            None => self.llm.inference_async(param).await,
+        }
+    }
+
     pub async fn run_streaming(
         &self,
         messages: Vec<LLMMessage>,
@@ -488,13 +889,15 @@ impl Brain {
         let mut conversation = sanitize_messages_for_inference(messages);
         let mut output: Vec<LLMMessage> = Vec::new();
 
-        let streaming_llm = self.llm.as_streaming();
+        let started_at = Instant::now();
+        let mut identical_calls: HashMap<String, usize> = HashMap::new();
+        let mut repeating_tool_calls = false;
 
         for iteration in 0..MAX_TOOL_ITERATIONS {
             if iteration > 0 {
                 self.append_iteration_messages(iteration + 1, &mut conversation);
             }
-            let is_last_iteration = iteration == MAX_TOOL_ITERATIONS - 1;
+            let is_last_iteration = repeating_tool_calls || self.is_final_iteration(iteration, started_at);
 
             if is_last_iteration {
                 let counts = count_tool_calls(&conversation);
@@ -507,22 +910,11 @@ impl Brain {
                 Some(&tool_specs)
             };
 
-            let response = if let Some(streaming) = streaming_llm {
-                streaming
-                    .inference_streaming(
-                        &InferenceParam {
-                            messages: &conversation,
-                            tools: tools_param,
-                        },
-                        token_tx.clone(),
-                    )
-                    .await
-            } else {
-                self.llm.inference(&InferenceParam {
-                    messages: &conversation,
-                    tools: tools_param,
-                })
-            };
+            let param = InferenceParam::new(&conversation).with_tools(tools_param);
+            let mut response = self.infer_streaming(&param, &token_tx).await;
+            if self.should_regenerate(&response) {
+                response = self.infer_streaming(&param, &token_tx).await;
+            }
 
             if let Some(content) = response.content_text() {
                 if is_transport_error(content) {
@@ -538,20 +930,32 @@ impl Brain {
 
             self.log_llm_usage(&response);
 
-            if response.tool_calls.is_empty() {
-                let response_preview = response.content_text_owned().unwrap_or_default();
-                if !response_preview.is_empty() {
-                    info!(
-                        "[Brain] final assistant response: {}",
-                        truncate_for_log(&response_preview, LOG_PREVIEW_CHARS)
-                    );
-                }
-                if let Some(observer) = self.observer.as_ref() {
-                    observer.on_final_assistant(&response, &BrainStopReason::Done);
+            let response = if response.tool_calls.is_empty() {
+                let resolution = if is_empty_reply(&response) {
+                    NoToolResolution::Final(self.fill_empty_reply(response))
+                } else {
+                    self.resolve_no_tool_response(response, &output, iteration, is_last_iteration)
+                };
+                match resolution {
+                    NoToolResolution::Final(response) => {
+                        let response_preview = response.content_text_owned().unwrap_or_default();
+                        if !response_preview.is_empty() {
+                            info!(
+                                "[Brain] final assistant response: {}",
+                                truncate_for_log(&response_preview, LOG_PREVIEW_CHARS)
+                            );
+                        }
+                        if let Some(observer) = self.observer.as_ref() {
+                            observer.on_final_assistant(&response, &BrainStopReason::Done);
+                        }
+                        output.push(response);
+                        return (output, BrainStopReason::Done);
+                    }
+                    NoToolResolution::Dispatch(response) => response,
                 }
-                output.push(response);
-                return (output, BrainStopReason::Done);
-            }
+            } else {
+                response
+            };
 
             if is_last_iteration {
                 if let Some(observer) = self.observer.as_ref() {
@@ -599,9 +1003,13 @@ impl Brain {
                 if let Some(observer) = self.observer.as_ref() {
                     observer.on_tool_start(&tc.function.name, &tc.id, &tc.function.arguments);
                 }
-                let matching_tool = self.tools.iter().find(|t| t.spec().name() == tc.function.name);
-                let result = if let Some(tool) = matching_tool {
-                    self.execute_tool_call(tool, &tool_call_content, &tc.function.arguments, &tc.function.name)
+                let matching_tool = self.tools.get(&tc.function.name);
+                let result = if let Some(refusal) = refuse_identical_call(&mut identical_calls, tc) {
+                    repeating_tool_calls = true;
+                    refusal
+                } else if let Some(tool) = matching_tool {
+                    self.execute_tool_call_async(tool, &tool_call_content, &tc.function.arguments, &tc.function.name)
+                        .await
                 } else {
                     warn!(
                         "[Brain] Tool '{}' not found for call id={} arguments={}",
@@ -621,7 +1029,7 @@ impl Brain {
                 if let Some(observer) = self.observer.as_ref() {
                     observer.on_tool_finish(&tc.function.name, &tc.id, &result.result);
                 }
-                let msg = LLMMessage::tool_result(tc.id.clone(), result.result.clone());
+                let msg = self.tool_result_message(&tc.function.name, &tc.id, &result.result);
                 conversation.push(msg.clone());
                 output.push(msg);
                 if let Some(request) = result.ask_user {
@@ -656,6 +1064,31 @@ impl Brain {
     }
 }
 
+/// Records `tc` and, once the same tool has run with the same arguments
+/// [`MAX_IDENTICAL_TOOL_CALLS`] times, returns the error fed back instead of running it again.
+fn refuse_identical_call(identical_calls: &mut HashMap<String, usize>, tc: &ToolCalls) -> Option<ToolExecutionOutput> {
+    let signature = format!("{}:{}", tc.function.name, tc.function.arguments);
+    let count = identical_calls.entry(signature).or_insert(0);
+    *count += 1;
+    if *count <= MAX_IDENTICAL_TOOL_CALLS {
+        return None;
+    }
+    warn!(
+        "[Brain] tool '{}' called with identical arguments {} times, refusing call id={}",
+        tc.function.name, count, tc.id
+    );
+    Some(ToolExecutionOutput::text(
+        serde_json::json!({
+            "error": format!(
+                "工具 '{}' 已使用相同参数调用 {} 次，结果不会变化。请基于已获取的信息直接作答。",
+                tc.function.name,
+                *count - 1
+            )
+        })
+        .to_string(),
+    ))
+}
+
 /// Count tool calls already present in `messages` by tool name.
 fn count_tool_calls(messages: &[LLMMessage]) -> HashMap<String, usize> {
     let mut counts = HashMap::new();
@@ -702,10 +1135,16 @@ fn append_tool_summary_to_system(messages: &mut Vec<LLMMessage>, counts: &HashMa
 mod tests {
     use std::fmt;
     use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     use serde_json::json;
 
-    use super::{Brain, BrainIterationHook, BrainTool};
+    use super::{
+        run_tool_with_timeout, Brain, BrainIterationHook, BrainStopReason, BrainTool, EmptyReplyFallback,
+        NoToolFallback, EMPTY_REPLY_CANNED_MESSAGE, MAX_IDENTICAL_TOOL_CALLS, NO_REPLY_DIRECTIVE,
+        TOOL_RESULT_TRUNCATED_MARKER,
+    };
+    use zihuan_core::error::Error;
     use zihuan_core::llm::llm_base::LLMBase;
     use zihuan_core::llm::tooling::{FunctionTool, ToolCalls, ToolCallsFuncSpec};
     use zihuan_core::llm::{InferenceParam, LLMMessage, MessagePart, MessageRole};
@@ -745,6 +1184,7 @@ mod tests {
                         },
                     }],
                     tool_call_id: None,
+                    name: None,
                     usage: None,
                 }
             } else {
@@ -797,6 +1237,40 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct NoArgumentTool;
+
+    impl BrainTool for NoArgumentTool {
+        fn spec(&self) -> Arc<dyn FunctionTool> {
+            Arc::new(NoArgumentToolSpec)
+        }
+
+        fn execute(&self, _call_content: &str, arguments: &serde_json::Value) -> String {
+            json!({ "arguments": arguments }).to_string()
+        }
+    }
+
+    #[derive(Debug)]
+    struct NoArgumentToolSpec;
+
+    impl FunctionTool for NoArgumentToolSpec {
+        fn name(&self) -> &str {
+            "now"
+        }
+
+        fn description(&self) -> &str {
+            "now"
+        }
+
+        fn parameters(&self) -> serde_json::Value {
+            json!({ "type": "object", "properties": {} })
+        }
+
+        fn call(&self, _arguments: serde_json::Value) -> zihuan_core::error::Result<serde_json::Value> {
+            Ok(json!({}))
+        }
+    }
+
     #[derive(Debug)]
     struct InjectUserHook;
 
@@ -866,4 +1340,292 @@ mod tests {
             .collect();
         assert_eq!(merged_messages.len(), 1);
     }
+
+    #[derive(Debug)]
+    struct HugeResultTool;
+
+    impl BrainTool for HugeResultTool {
+        fn spec(&self) -> Arc<dyn FunctionTool> {
+            Arc::new(EchoToolSpec)
+        }
+
+        fn execute(&self, _call_content: &str, _arguments: &serde_json::Value) -> String {
+            "x".repeat(10_000)
+        }
+    }
+
+    #[test]
+    fn oversized_tool_result_is_truncated_before_next_inference() {
+        let state = Arc::new(Mutex::new(RecordingLlmState::default()));
+        let llm = Arc::new(RecordingLlm { state: Arc::clone(&state) });
+
+        let brain = Brain::new(llm).with_tool(HugeResultTool).with_max_tool_result_chars(Some(100));
+
+        let (output, _stop_reason) = brain.run(vec![LLMMessage::user("原始问题")]);
+
+        let expected = format!("{}{}", "x".repeat(100), TOOL_RESULT_TRUNCATED_MARKER);
+        let state = state.lock().unwrap();
+        let tool_message = state.conversations[1]
+            .iter()
+            .find(|message| matches!(message.role, MessageRole::Tool))
+            .expect("follow-up inference should include the tool result");
+        assert_eq!(tool_message.content_text(), Some(expected.as_str()));
+        assert!(output
+            .iter()
+            .filter(|message| matches!(message.role, MessageRole::Tool))
+            .all(|message| message.content_text() == Some(expected.as_str())));
+    }
+
+    #[test]
+    fn tool_results_are_kept_whole_by_default() {
+        let state = Arc::new(Mutex::new(RecordingLlmState::default()));
+        let llm = Arc::new(RecordingLlm { state: Arc::clone(&state) });
+
+        let brain = Brain::new(llm).with_tool(HugeResultTool);
+
+        brain.run(vec![LLMMessage::user("原始问题")]);
+
+        let expected = "x".repeat(10_000);
+        let state = state.lock().unwrap();
+        let tool_message = state.conversations[1]
+            .iter()
+            .find(|message| matches!(message.role, MessageRole::Tool))
+            .expect("follow-up inference should include the tool result");
+        assert_eq!(tool_message.content_text(), Some(expected.as_str()));
+    }
+
+    #[derive(Debug)]
+    struct SlowTool;
+
+    impl BrainTool for SlowTool {
+        fn spec(&self) -> Arc<dyn FunctionTool> {
+            Arc::new(EchoToolSpec)
+        }
+
+        fn execute(&self, _call_content: &str, _arguments: &serde_json::Value) -> String {
+            std::thread::sleep(Duration::from_secs(2));
+            "finished too late".to_string()
+        }
+    }
+
+    #[test]
+    fn tool_exceeding_timeout_yields_tool_timeout_error() {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+        let result = runtime.block_on(run_tool_with_timeout(
+            Arc::new(SlowTool),
+            "echo",
+            "",
+            &serde_json::json!({}),
+            Duration::from_millis(50),
+        ));
+        assert!(matches!(
+            result,
+            Err(Error::ToolTimeout { ref tool_name, secs: 0 }) if tool_name == "echo"
+        ));
+
+        let state = Arc::new(Mutex::new(RecordingLlmState::default()));
+        let llm = Arc::new(RecordingLlm { state: Arc::clone(&state) });
+        let brain = Brain::new(llm)
+            .with_tool(SlowTool)
+            .with_tool_timeout(Some(Duration::from_millis(50)));
+
+        let _runtime_guard = runtime.enter();
+        let (_output, _stop_reason) = brain.run(vec![LLMMessage::user("原始问题")]);
+
+        let state = state.lock().unwrap();
+        let tool_message = state.conversations[1]
+            .iter()
+            .find(|message| matches!(message.role, MessageRole::Tool))
+            .expect("follow-up inference should include the tool result");
+        assert!(tool_message.content_text().unwrap_or_default().contains("timed out"));
+    }
+
+    #[test]
+    fn tool_call_with_invalid_arguments_returns_the_validation_error() {
+        let state = Arc::new(Mutex::new(RecordingLlmState::default()));
+        let brain = Brain::new(Arc::new(RecordingLlm { state }));
+        let tool: Arc<dyn BrainTool> = Arc::new(EchoTool);
+
+        let output = brain.execute_tool_call(&tool, "", &json!({ "value": 1 }), "echo");
+        let result: serde_json::Value = serde_json::from_str(&output.result).unwrap();
+
+        assert!(result.get("echo").is_none(), "{result}");
+        assert!(result["error"].as_str().unwrap().contains("value"), "{result}");
+    }
+
+    #[test]
+    fn zero_argument_tool_call_with_absent_arguments_runs_the_tool() {
+        let state = Arc::new(Mutex::new(RecordingLlmState::default()));
+        let brain = Brain::new(Arc::new(RecordingLlm { state }));
+        let tool: Arc<dyn BrainTool> = Arc::new(NoArgumentTool);
+
+        let output = brain.execute_tool_call(&tool, "", &serde_json::Value::Null, "now");
+
+        assert_eq!(output.result, r#"{"arguments":{}}"#);
+    }
+
+    /// Answers the first inference with plain content and every later one with a final reply.
+    #[derive(Debug, Default)]
+    struct PlainFirstLlm {
+        conversations: Mutex<Vec<Vec<LLMMessage>>>,
+    }
+
+    impl LLMBase for PlainFirstLlm {
+        fn get_model_name(&self) -> &str {
+            "plain-first-llm"
+        }
+
+        fn inference(&self, param: &InferenceParam) -> LLMMessage {
+            let mut conversations = self.conversations.lock().unwrap();
+            conversations.push(param.messages.to_vec());
+            if conversations.len() == 1 {
+                LLMMessage::assistant_text("直接回答")
+            } else {
+                LLMMessage::assistant_text("整理后的回复")
+            }
+        }
+    }
+
+    fn run_with_fallback(fallback: NoToolFallback) -> (Vec<LLMMessage>, usize) {
+        let llm = Arc::new(PlainFirstLlm::default());
+        let brain = Brain::new(llm.clone()).with_tool(EchoTool).with_no_tool_fallback(fallback);
+        let (output, _stop_reason) = brain.run(vec![LLMMessage::user("原始问题")]);
+        let inferences = llm.conversations.lock().unwrap().len();
+        (output, inferences)
+    }
+
+    #[test]
+    fn send_content_fallback_replies_with_plain_content() {
+        let (output, inferences) = run_with_fallback(NoToolFallback::SendContent);
+
+        assert_eq!(inferences, 1);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].content_text(), Some("直接回答"));
+    }
+
+    #[test]
+    fn default_agent_fallback_dispatches_content_to_the_agent() {
+        let (output, inferences) = run_with_fallback(NoToolFallback::DefaultAgent("echo".to_string()));
+
+        assert_eq!(inferences, 2);
+        assert_eq!(output[0].tool_calls.len(), 1);
+        assert_eq!(output[0].tool_calls[0].function.name, "echo");
+        assert_eq!(output[0].tool_calls[0].function.arguments, json!({ "content": "直接回答" }));
+        assert!(matches!(output[1].role, MessageRole::Tool));
+        assert_eq!(output.last().and_then(LLMMessage::content_text), Some("整理后的回复"));
+    }
+
+    #[test]
+    fn silent_fallback_emits_no_reply_directive() {
+        let (output, inferences) = run_with_fallback(NoToolFallback::Silent);
+
+        assert_eq!(inferences, 1);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].content_text(), Some(NO_REPLY_DIRECTIVE));
+    }
+
+    /// Replies with each queued text in turn, then with the last one.
+    #[derive(Debug)]
+    struct ScriptedLlm {
+        replies: Vec<&'static str>,
+        calls: Mutex<usize>,
+    }
+
+    impl ScriptedLlm {
+        fn new(replies: Vec<&'static str>) -> Arc<Self> {
+            Arc::new(Self { replies, calls: Mutex::new(0) })
+        }
+    }
+
+    impl LLMBase for ScriptedLlm {
+        fn get_model_name(&self) -> &str {
+            "scripted-llm"
+        }
+
+        fn inference(&self, _param: &InferenceParam) -> LLMMessage {
+            let mut calls = self.calls.lock().unwrap();
+            let reply = self.replies[(*calls).min(self.replies.len() - 1)];
+            *calls += 1;
+            LLMMessage::assistant_text(reply)
+        }
+    }
+
+    #[test]
+    fn empty_reply_is_regenerated_once() {
+        let llm = ScriptedLlm::new(vec!["  \n", "重新想好的回复"]);
+        let (output, _stop_reason) = Brain::new(llm.clone()).run(vec![LLMMessage::user("你好")]);
+
+        assert_eq!(*llm.calls.lock().unwrap(), 2);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].content_text(), Some("重新想好的回复"));
+    }
+
+    #[test]
+    fn empty_reply_after_regeneration_stays_silent() {
+        let llm = ScriptedLlm::new(vec![""]);
+        let (output, _stop_reason) = Brain::new(llm.clone()).run(vec![LLMMessage::user("你好")]);
+
+        assert_eq!(*llm.calls.lock().unwrap(), 2);
+        assert_eq!(output[0].content_text(), Some(NO_REPLY_DIRECTIVE));
+    }
+
+    #[test]
+    fn canned_fallback_replaces_empty_reply_without_regenerating() {
+        let llm = ScriptedLlm::new(vec![" ", "不该用到"]);
+        let brain = Brain::new(llm.clone()).with_empty_reply_fallback(EmptyReplyFallback::Canned);
+        let (output, _stop_reason) = brain.run(vec![LLMMessage::user("你好")]);
+
+        assert_eq!(*llm.calls.lock().unwrap(), 1);
+        assert_eq!(output[0].content_text(), Some(EMPTY_REPLY_CANNED_MESSAGE));
+    }
+
+    /// Keeps calling `echo` with the same arguments for as long as tools are offered.
+    #[derive(Debug, Default)]
+    struct LoopingLlm {
+        calls: Mutex<usize>,
+    }
+
+    impl LLMBase for LoopingLlm {
+        fn get_model_name(&self) -> &str {
+            "looping-llm"
+        }
+
+        fn inference(&self, param: &InferenceParam) -> LLMMessage {
+            let mut calls = self.calls.lock().unwrap();
+            *calls += 1;
+            if param.tools.is_none() {
+                return LLMMessage::assistant_text("不再调用工具的回复");
+            }
+            let mut message = LLMMessage::assistant_text("");
+            message.tool_calls = vec![ToolCalls {
+                id: format!("call-{calls}"),
+                type_name: "function".to_string(),
+                function: ToolCallsFuncSpec {
+                    name: "echo".to_string(),
+                    arguments: json!({"value": "same"}),
+                },
+            }];
+            message
+        }
+    }
+
+    #[test]
+    fn repeated_identical_tool_calls_force_a_final_answer() {
+        let llm = Arc::new(LoopingLlm::default());
+        let (output, stop_reason) = Brain::new(llm.clone()).with_tool(EchoTool).run(vec![LLMMessage::user("你好")]);
+
+        assert!(matches!(stop_reason, BrainStopReason::Done));
+        assert_eq!(*llm.calls.lock().unwrap(), MAX_IDENTICAL_TOOL_CALLS + 2);
+        let tool_results: Vec<_> = output
+            .iter()
+            .filter(|message| matches!(message.role, MessageRole::Tool))
+            .filter_map(LLMMessage::content_text_owned)
+            .collect();
+        assert_eq!(tool_results.len(), MAX_IDENTICAL_TOOL_CALLS + 1);
+        assert!(tool_results[..MAX_IDENTICAL_TOOL_CALLS]
+            .iter()
+            .all(|result| result.contains("same")));
+        assert!(tool_results[MAX_IDENTICAL_TOOL_CALLS].contains("相同参数"));
+        assert_eq!(output.last().and_then(LLMMessage::content_text), Some("不再调用工具的回复"));
+    }
 }
@@ -8,6 +8,7 @@ use super::{
     LLM_KIND_NATURAL_LANGUAGE_REPLY,
 };
 use crate::error::{Error, Result};
+use crate::utils::sender_identity::{SenderIdentityPolicy, SenderNamePrecedence};
 
 thread_local! {
     static CURRENT_QQ_CHAT_AGENT_SERVICE_CONFIG: RefCell<Vec<QqChatAgentServiceConfig>> =
@@ -91,6 +92,58 @@ impl QqChatMessageRateLimitWindowUnit {
     }
 }
 
+/// Serialization format of message snapshots cached in Redis. Reads detect the
+/// stored format, so switching codecs does not invalidate existing entries.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RedisCacheCodec {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+/// What produces the replies of a QQ chat agent.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QqChatBrain {
+    /// The regular LLM brain loop.
+    #[default]
+    Llm,
+    /// Replies with the received message content. Needs no LLM; meant for
+    /// smoke-testing the connect → receive → persist → reply pipeline.
+    Echo,
+}
+
+/// What the brain does when the model answers without calling any tool.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoToolFallback {
+    /// Send the model's plain content as the reply.
+    #[default]
+    SendContent,
+    /// Hand the content to the named agent tool (e.g. `nl_reply_agent`) and let
+    /// the model answer from its result.
+    DefaultAgent(String),
+    /// Send nothing for this turn.
+    Silent,
+}
+
+/// Fixed reply sent for [`EmptyReplyFallback::Canned`].
+pub const EMPTY_REPLY_CANNED_MESSAGE: &str = "我暂时想不出怎么回复";
+
+/// What the brain does when the model's final reply is empty or whitespace-only.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyReplyFallback {
+    /// Ask the model once more; stay silent if that reply is empty too.
+    #[default]
+    Regenerate,
+    /// Send [`EMPTY_REPLY_CANNED_MESSAGE`] instead.
+    Canned,
+    /// Send nothing for this turn.
+    Silent,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QqChatMessageRateLimitRule {
     #[serde(default)]
@@ -149,6 +202,29 @@ pub struct QqChatMessageRateLimitUserRule {
     pub limit: QqChatMessageRateLimitRule,
 }
 
+/// Routes a conversation to another LLM profile. Every condition that is set must
+/// match; a rule with no conditions never matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QqChatLlmProfileOverride {
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// Intent label as produced by intent classification, e.g. `Write Code`.
+    #[serde(default)]
+    pub intent: Option<String>,
+    pub llm_ref_id: String,
+}
+
+impl QqChatLlmProfileOverride {
+    fn matches(&self, group_id: Option<&str>, intent: Option<&str>) -> bool {
+        let group_rule = self.group_id.as_deref().map(str::trim).filter(|value| !value.is_empty());
+        let intent_rule = self.intent.as_deref().map(str::trim).filter(|value| !value.is_empty());
+        if group_rule.is_none() && intent_rule.is_none() {
+            return false;
+        }
+        group_rule.is_none_or(|rule| group_id == Some(rule)) && intent_rule.is_none_or(|rule| intent == Some(rule))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QqChatAgentServiceConfig {
     pub ims_bot_adapter_connection_id: String,
@@ -175,8 +251,16 @@ pub struct QqChatAgentServiceConfig {
     #[serde(default)]
     pub tokenizer_connection_id: Option<String>,
     pub web_search_engine_connection_id: String,
+    /// Weather API connection backing the `get_weather` tool.
+    #[serde(default)]
+    pub weather_api_connection_id: Option<String>,
     #[serde(default)]
     pub rdb_id: Option<String>,
+    /// Connection holding the chat history searched by `search_chat_messages`,
+    /// e.g. a PostgreSQL connection. Inbound messages are also written there.
+    /// Unset searches the `rdb_id` database.
+    #[serde(default)]
+    pub message_history_connection_id: Option<String>,
     #[serde(default)]
     pub embedding: Option<EmbeddingServiceConfig>,
     #[serde(default)]
@@ -199,6 +283,10 @@ pub struct QqChatAgentServiceConfig {
     pub compact_context_length: usize,
     #[serde(default = "default_max_steer_count")]
     pub max_steer_count: usize,
+    /// Tool results longer than this many chars are truncated before being fed
+    /// back to the model. 0, the default, disables truncation.
+    #[serde(default)]
+    pub max_tool_result_chars: usize,
     #[serde(default = "default_qq_chat_default_tools_enabled")]
     pub default_tools_enabled: HashMap<String, bool>,
     #[serde(default = "default_qq_chat_tool_session_call_limits")]
@@ -215,6 +303,37 @@ pub struct QqChatAgentServiceConfig {
     pub emotion_dimensions: Vec<QqChatEmotionDimensionConfig>,
     #[serde(default)]
     pub event_handler_threads: Option<usize>,
+    #[serde(default = "default_message_cache_warmup_limit")]
+    pub message_cache_warmup_limit: usize,
+    /// Refuse to start when the message cache warm-up fails entirely.
+    #[serde(default)]
+    pub message_cache_warmup_strict: bool,
+    #[serde(default)]
+    pub llm_profile_overrides: Vec<QqChatLlmProfileOverride>,
+    #[serde(default)]
+    pub sender_name_precedence: SenderNamePrecedence,
+    /// When set, stored sender ids and names are replaced by keyed pseudonyms.
+    #[serde(default)]
+    pub sender_anonymization_key: Option<String>,
+    #[serde(default)]
+    pub brain: QqChatBrain,
+    /// In echo mode, also reply with the parsed message segments as JSON.
+    #[serde(default)]
+    pub echo_include_structure: bool,
+    #[serde(default)]
+    pub redis_cache_codec: RedisCacheCodec,
+    #[serde(default)]
+    pub no_tool_fallback: NoToolFallback,
+    #[serde(default)]
+    pub empty_reply_fallback: EmptyReplyFallback,
+    /// Seconds after a user addresses the bot in a group during which their
+    /// follow-ups are handled without another mention. 0 disables this.
+    #[serde(default)]
+    pub sticky_secs: u64,
+    /// Expiry in seconds of the message snapshots written to Redis. 0 keeps
+    /// them until Redis evicts them.
+    #[serde(default)]
+    pub redis_message_ttl_secs: u64,
 }
 
 impl QqChatAgentServiceConfig {
@@ -237,6 +356,27 @@ impl QqChatAgentServiceConfig {
             })
     }
 
+    pub fn sender_identity_policy(&self) -> SenderIdentityPolicy {
+        SenderIdentityPolicy {
+            precedence: self.sender_name_precedence,
+            anonymization_key: self
+                .sender_anonymization_key
+                .as_deref()
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(str::to_string),
+        }
+    }
+
+    /// LLM ref that should handle this conversation instead of the main one, if any.
+    /// The first matching override wins.
+    pub fn resolve_llm_profile_override(&self, group_id: Option<&str>, intent: Option<&str>) -> Option<&str> {
+        self.llm_profile_overrides
+            .iter()
+            .find(|rule| !rule.llm_ref_id.trim().is_empty() && rule.matches(group_id, intent))
+            .map(|rule| rule.llm_ref_id.trim())
+    }
+
     pub fn resolved_emotion_dimensions(&self) -> Vec<QqChatEmotionDimensionConfig> {
         let mut dimensions = Vec::new();
         for dimension in &self.emotion_dimensions {
@@ -320,6 +460,10 @@ fn default_max_steer_count() -> usize {
     4
 }
 
+fn default_message_cache_warmup_limit() -> usize {
+    1000
+}
+
 fn default_message_rate_limit_window_size() -> i64 {
     1
 }
@@ -329,8 +473,12 @@ fn default_qq_chat_default_tools_enabled() -> HashMap<String, bool> {
         "web_search",
         "get_agent_public_info",
         "get_function_list",
+        "get_current_time",
+        "get_weather",
+        "calculate",
         "get_recent_group_messages",
         "get_recent_user_messages",
+        "search_chat_messages",
         "search_similar_images",
         "save_image",
         "image_understand",
@@ -384,3 +532,46 @@ fn sanitize_emotion_dissipation_hours(hours: i64) -> i64 {
         default_emotion_dissipation_hours()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_mapped_to_code_profile_selects_code_llm_ref() {
+        let config: QqChatAgentServiceConfig = serde_json::from_value(serde_json::json!({
+            "ims_bot_adapter_connection_id": "bot",
+            "web_search_engine_connection_id": "search",
+            "llm_ref_id": "default",
+            "llm_profile_overrides": [
+                { "group_id": "10001", "llm_ref_id": "code" },
+                { "intent": "Write Code", "llm_ref_id": "code-intent" }
+            ]
+        }))
+        .expect("deserialize config");
+
+        assert_eq!(config.resolve_llm_profile_override(Some("10001"), None), Some("code"));
+        assert_eq!(
+            config.resolve_llm_profile_override(Some("20002"), Some("Write Code")),
+            Some("code-intent")
+        );
+        assert_eq!(config.resolve_llm_profile_override(Some("20002"), Some("Chat")), None);
+        assert_eq!(config.resolve_llm_profile_override(None, None), None);
+    }
+
+    #[test]
+    fn tool_result_truncation_is_off_unless_configured() {
+        let base = serde_json::json!({
+            "ims_bot_adapter_connection_id": "bot",
+            "web_search_engine_connection_id": "search",
+            "llm_ref_id": "default"
+        });
+        let config: QqChatAgentServiceConfig = serde_json::from_value(base.clone()).expect("deserialize config");
+        assert_eq!(config.max_tool_result_chars, 0);
+
+        let mut configured = base;
+        configured["max_tool_result_chars"] = serde_json::json!(16_000);
+        let config: QqChatAgentServiceConfig = serde_json::from_value(configured).expect("deserialize config");
+        assert_eq!(config.max_tool_result_chars, 16_000);
+    }
+}
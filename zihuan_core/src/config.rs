@@ -31,8 +31,10 @@ pub enum ConfigKind {
     ConnectionRustfs,
     ConnectionBotAdapter,
     ConnectionWebSearchEngine,
+    ConnectionWeatherApi,
     ConnectionTokenizer,
     ConnectionSqlite,
+    ConnectionPostgres,
     LlmRef,
     ServiceQqChat,
     ServiceHttpStream,
@@ -49,8 +51,10 @@ impl ConfigKind {
             | Self::ConnectionRustfs
             | Self::ConnectionBotAdapter
             | Self::ConnectionWebSearchEngine
+            | Self::ConnectionWeatherApi
             | Self::ConnectionTokenizer
-            | Self::ConnectionSqlite => ConfigCategory::Connection,
+            | Self::ConnectionSqlite
+            | Self::ConnectionPostgres => ConfigCategory::Connection,
             Self::LlmRef => ConfigCategory::LlmRef,
             Self::ServiceQqChat | Self::ServiceHttpStream => ConfigCategory::Service,
             Self::CommandPermission => ConfigCategory::Service,
@@ -26,6 +26,22 @@ pub const SQLITE_DDL: &str = "CREATE TABLE IF NOT EXISTS message_record (
         raw_message_json TEXT
     )";
 
+pub const POSTGRES_DDL: &str = "CREATE TABLE IF NOT EXISTS message_record (
+        id BIGSERIAL PRIMARY KEY,
+        message_id VARCHAR(64) NOT NULL,
+        sender_id VARCHAR(64) NOT NULL,
+        sender_name VARCHAR(128) NOT NULL,
+        send_time TIMESTAMP NOT NULL,
+        group_id VARCHAR(64),
+        group_name VARCHAR(128),
+        content VARCHAR(2048) NOT NULL,
+        at_target_list VARCHAR(512),
+        media_json TEXT,
+        raw_message_json TEXT
+    )";
+
 pub const MYSQL_INDEXES: &[&str] = &["CREATE INDEX idx_message_record_message_id ON message_record (message_id)"];
 pub const SQLITE_INDEXES: &[&str] =
     &["CREATE INDEX IF NOT EXISTS idx_message_record_message_id ON message_record (message_id)"];
+pub const POSTGRES_INDEXES: &[&str] =
+    &["CREATE INDEX IF NOT EXISTS idx_message_record_message_id ON message_record (message_id)"];
@@ -42,3 +42,7 @@ pub const SQLITE_TABLES: &[(&str, &[&str])] = &[
     (task_log::SQLITE_DDL, task_log::SQLITE_INDEXES),
     (task_progress::SQLITE_DDL, task_progress::SQLITE_INDEXES),
 ];
+
+/// PostgreSQL tables. Only the message store runs on PostgreSQL, so this
+/// covers `message_record` alone.
+pub const POSTGRES_TABLES: &[(&str, &[&str])] = &[(message_record::POSTGRES_DDL, message_record::POSTGRES_INDEXES)];
@@ -1,6 +1,7 @@
 pub mod ddl;
 
 use sqlx::mysql::MySqlConnection;
+use sqlx::postgres::PgConnection;
 use sqlx::sqlite::SqliteConnection;
 use sqlx::Row;
 
@@ -67,6 +68,28 @@ pub async fn ensure_tables_sqlite(conn: &mut SqliteConnection) -> Result<()> {
     Ok(())
 }
 
+/// Execute DDL and index creation statements against a PostgreSQL connection.
+pub async fn ensure_tables_postgres(conn: &mut PgConnection) -> Result<()> {
+    for (ddl, indexes) in ddl::POSTGRES_TABLES {
+        sqlx::query(ddl).execute(&mut *conn).await.map_err(|e| {
+            Error::Database(sqlx::Error::Protocol(format!(
+                "PostgreSQL DDL failed: {} — statement: {}",
+                e,
+                &ddl[..ddl.len().min(120)]
+            )))
+        })?;
+        for idx in *indexes {
+            sqlx::query(idx).execute(&mut *conn).await.map_err(|e| {
+                Error::Database(sqlx::Error::Protocol(format!(
+                    "PostgreSQL index creation failed: {} — statement: {}",
+                    e, idx
+                )))
+            })?;
+        }
+    }
+    Ok(())
+}
+
 async fn ensure_privilege_auth_columns_mysql(conn: &mut MySqlConnection) -> Result<()> {
     let columns = [
         (
@@ -2,6 +2,8 @@ use redis::RedisError;
 use std::io;
 use std::num::ParseFloatError;
 
+use crate::utils::backoff::is_retryable_http_status;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("IO error: {0}")]
@@ -42,10 +44,71 @@ pub enum Error {
 
     #[error("Invalid node input: {0}")]
     InvalidNodeInput(String),
+
+    #[error("Tool call '{tool_name}' timed out after {secs}s")]
+    ToolTimeout { tool_name: String, secs: u64 },
+
+    /// An LLM API request that failed after its retries. `status` is the HTTP
+    /// status of the last attempt, when one was received.
+    #[error("LLM API error: {message}")]
+    LlmApi { status: Option<u16>, message: String },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+    /// Whether the failed operation may succeed if retried unchanged: rate
+    /// limits, server errors, timeouts and dropped connections. Invalid input,
+    /// client errors and malformed data are never retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Io(err) => is_transient_io_error(err),
+            Error::Http(err) => match err.status() {
+                Some(status) => is_retryable_http_status(status.as_u16()),
+                None => err.is_timeout() || err.is_connect(),
+            },
+            Error::Redis(err) => {
+                err.is_timeout() || err.is_connection_refusal() || err.is_connection_dropped() || err.is_io_error()
+            }
+            Error::Database(err) => match err {
+                sqlx::Error::PoolTimedOut => true,
+                sqlx::Error::Io(io_err) => is_transient_io_error(io_err),
+                _ => false,
+            },
+            Error::WebSocket(err) => matches!(
+                err,
+                tokio_tungstenite::tungstenite::Error::ConnectionClosed
+                    | tokio_tungstenite::tungstenite::Error::AlreadyClosed
+                    | tokio_tungstenite::tungstenite::Error::Io(_)
+            ),
+            Error::ToolTimeout { .. } => true,
+            Error::LlmApi { status, .. } => status.is_some_and(is_retryable_http_status),
+            Error::StringError(_)
+            | Error::StaticStrError(_)
+            | Error::HttpHeader(_)
+            | Error::Json(_)
+            | Error::Yaml(_)
+            | Error::ParseFloat(_)
+            | Error::ValidationError(_)
+            | Error::InvalidNodeInput(_) => false,
+        }
+    }
+}
+
+fn is_transient_io_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
 impl From<String> for Error {
     fn from(s: String) -> Self {
         Error::StringError(s)
@@ -71,3 +134,56 @@ macro_rules! validation_error {
         $crate::error::Error::ValidationError(format!($($arg)*))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http_status_error(status: u16) -> Error {
+        let response = http::Response::builder().status(status).body("").unwrap();
+        reqwest::Response::from(response).error_for_status().unwrap_err().into()
+    }
+
+    #[test]
+    fn rate_limits_and_server_errors_are_retryable() {
+        assert!(http_status_error(429).is_retryable());
+        assert!(http_status_error(503).is_retryable());
+        assert!(!http_status_error(400).is_retryable());
+        assert!(!http_status_error(404).is_retryable());
+        assert!(!http_status_error(501).is_retryable());
+
+        let llm_api_error = |status| Error::LlmApi {
+            status,
+            message: "LLM API request failed after 1 attempt(s)".to_string(),
+        };
+        assert!(llm_api_error(Some(429)).is_retryable());
+        assert!(llm_api_error(Some(502)).is_retryable());
+        assert!(!llm_api_error(Some(401)).is_retryable());
+        assert!(!llm_api_error(Some(505)).is_retryable());
+        assert!(!llm_api_error(None).is_retryable());
+    }
+
+    #[test]
+    fn dropped_connections_and_timeouts_are_retryable() {
+        let refused = || io::Error::new(io::ErrorKind::ConnectionRefused, "refused");
+        assert!(Error::Io(refused()).is_retryable());
+        assert!(Error::Redis(RedisError::from(refused())).is_retryable());
+        assert!(Error::Database(sqlx::Error::PoolTimedOut).is_retryable());
+        assert!(Error::Database(sqlx::Error::Io(refused())).is_retryable());
+        assert!(Error::WebSocket(tokio_tungstenite::tungstenite::Error::ConnectionClosed).is_retryable());
+        assert!(Error::ToolTimeout {
+            tool_name: "web_search".to_string(),
+            secs: 60
+        }
+        .is_retryable());
+    }
+
+    #[test]
+    fn invalid_input_and_data_errors_are_not_retryable() {
+        assert!(!Error::ValidationError("bad".to_string()).is_retryable());
+        assert!(!Error::InvalidNodeInput("missing port".to_string()).is_retryable());
+        assert!(!Error::Database(sqlx::Error::RowNotFound).is_retryable());
+        assert!(!Error::Io(io::Error::new(io::ErrorKind::NotFound, "missing")).is_retryable());
+        assert!(!Error::from(serde_json::from_str::<serde_json::Value>("{").unwrap_err()).is_retryable());
+    }
+}
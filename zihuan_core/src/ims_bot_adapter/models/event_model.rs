@@ -1,10 +1,11 @@
+use chrono::{DateTime, Local, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use log::warn;
 use serde::de::Deserializer;
 
-use crate::ims_bot_adapter::models::message::Message;
+use crate::ims_bot_adapter::models::message::{at_target_ids, Message};
 
 /// Message type enum (private or group chat)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -53,6 +54,64 @@ pub struct MessageEvent {
     pub group_name: Option<String>,
     #[serde(default)]
     pub is_group_message: bool,
+    /// Server-side send time in unix seconds, from the OneBot `time` field.
+    #[serde(default)]
+    pub time: Option<i64>,
+}
+
+impl MessageEvent {
+    /// The server-side send time in local time, matching the clock used to
+    /// stamp stored records. `None` when the event carried no `time`.
+    pub fn send_time(&self) -> Option<NaiveDateTime> {
+        let time = self.time.filter(|time| *time > 0)?;
+        DateTime::from_timestamp(time, 0).map(|utc| utc.with_timezone(&Local).naive_local())
+    }
+
+    /// @-targets of the message in the order they appear.
+    pub fn at_target_list(&self) -> Vec<String> {
+        at_target_ids(&self.message_list)
+    }
+
+    /// Whether `bot_id` is one of the @-targets. Compares whole ids, so a bot
+    /// `1000` is not mentioned by `@10001`.
+    pub fn mentions_bot(&self, bot_id: &str) -> bool {
+        mentions_id(&self.at_target_list(), bot_id)
+    }
+
+    /// The text segments joined as sent, without @-targets, replies or media.
+    pub fn plain_text(&self) -> String {
+        self.message_list
+            .iter()
+            .filter_map(|message| match message {
+                Message::PlainText(plain) => Some(plain.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Numeric @-targets in order; `@all` and targets without an id are skipped.
+    pub fn mentions(&self) -> Vec<i64> {
+        self.message_list
+            .iter()
+            .filter_map(|message| match message {
+                Message::At(at) => at.target.as_deref()?.trim().parse().ok(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Id of the message this one replies to, from its first reply segment.
+    pub fn reply_target(&self) -> Option<i64> {
+        self.message_list.iter().find_map(|message| match message {
+            Message::Reply(reply) => Some(reply.id),
+            _ => None,
+        })
+    }
+}
+
+pub(crate) fn mentions_id(at_targets: &[String], bot_id: &str) -> bool {
+    let bot_id = bot_id.trim();
+    !bot_id.is_empty() && at_targets.iter().any(|target| target == bot_id)
 }
 
 /// Raw message event structure for deserialization and serialization
@@ -68,6 +127,8 @@ pub struct RawMessageEvent {
     pub group_id: Option<i64>,
     #[serde(default)]
     pub group_name: Option<String>,
+    #[serde(default)]
+    pub time: Option<i64>,
 }
 
 fn deserialize_message_vec_lenient<'de, D>(deserializer: D) -> Result<Vec<Message>, D::Error>
@@ -87,3 +148,267 @@ where
     }
     Ok(out)
 }
+
+/// A OneBot notice, such as a member joining a group (`group_increase`) or a
+/// new friend (`friend_add`). Fields not shared by every notice are optional.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoticeEvent {
+    pub notice_type: String,
+    #[serde(default)]
+    pub sub_type: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<i64>,
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    #[serde(default)]
+    pub operator_id: Option<i64>,
+    #[serde(default)]
+    pub time: Option<i64>,
+}
+
+impl NoticeEvent {
+    /// A member joined a group, either approved or invited (`sub_type`).
+    pub fn is_group_member_join(&self) -> bool {
+        self.notice_type == "group_increase"
+    }
+
+    /// The bot gained a friend.
+    pub fn is_friend_add(&self) -> bool {
+        self.notice_type == "friend_add"
+    }
+}
+
+/// A OneBot request waiting for the bot to answer: a friend request
+/// (`request_type = "friend"`), or a group join request or invite
+/// (`"group"` with `sub_type` `add` / `invite`). `flag` identifies the
+/// request when approving or rejecting it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestEvent {
+    pub request_type: String,
+    #[serde(default)]
+    pub sub_type: Option<String>,
+    pub user_id: i64,
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    #[serde(default)]
+    pub comment: String,
+    pub flag: String,
+    #[serde(default)]
+    pub time: Option<i64>,
+}
+
+impl RequestEvent {
+    pub fn is_friend_request(&self) -> bool {
+        self.request_type == "friend"
+    }
+
+    /// Someone invited the bot into `group_id`.
+    pub fn is_group_invite(&self) -> bool {
+        self.request_type == "group" && self.sub_type.as_deref() == Some("invite")
+    }
+}
+
+/// Non-message OneBot posts, tagged by `post_type`. Messages keep their own
+/// [`RawMessageEvent`] path; heartbeats and other meta events are not modelled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "post_type", rename_all = "snake_case")]
+pub enum Event {
+    Notice(NoticeEvent),
+    Request(RequestEvent),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ims_bot_adapter::models::message::{AtTargetMessage, PlainTextMessage, ReplyMessage};
+
+    fn event_mentioning(targets: &[&str]) -> MessageEvent {
+        let mut message_list: Vec<Message> = targets
+            .iter()
+            .map(|target| {
+                Message::At(AtTargetMessage {
+                    target: Some(target.to_string()),
+                })
+            })
+            .collect();
+        message_list.push(Message::PlainText(PlainTextMessage { text: " 在吗".to_string() }));
+        MessageEvent {
+            message_id: 1,
+            message_type: MessageType::Group,
+            sender: Sender {
+                user_id: 20002,
+                nickname: "member".to_string(),
+                card: String::new(),
+                role: None,
+            },
+            message_list,
+            group_id: Some(30003),
+            group_name: None,
+            is_group_message: true,
+            time: None,
+        }
+    }
+
+    #[test]
+    fn single_mention_of_the_bot() {
+        let event = event_mentioning(&["10001"]);
+
+        assert_eq!(event.at_target_list(), vec!["10001"]);
+        assert!(event.mentions_bot("10001"));
+        assert!(!event.mentions_bot("1000"));
+    }
+
+    #[test]
+    fn multiple_mentions_keep_their_order() {
+        let event = event_mentioning(&["30001", "20001"]);
+
+        assert_eq!(event.at_target_list(), vec!["30001", "20001"]);
+        assert!(!event.mentions_bot("10001"));
+        assert!(!event.mentions_bot("3000"));
+        assert!(!event.mentions_bot(""));
+    }
+
+    #[test]
+    fn bot_among_several_mentions() {
+        let event = event_mentioning(&["30001", "10001", "20001"]);
+
+        assert_eq!(event.at_target_list(), vec!["30001", "10001", "20001"]);
+        assert!(event.mentions_bot("10001"));
+    }
+
+    #[test]
+    fn mixed_segments_split_into_text_mentions_and_reply() {
+        let mut event = event_mentioning(&["10001", "all", "20001"]);
+        event
+            .message_list
+            .insert(0, Message::Reply(ReplyMessage { id: 42, message_source: None }));
+        event
+            .message_list
+            .insert(2, Message::PlainText(PlainTextMessage { text: "你好".to_string() }));
+        event.message_list.push(Message::At(AtTargetMessage { target: None }));
+
+        assert_eq!(event.plain_text(), "你好 在吗");
+        assert_eq!(event.mentions(), vec![10001, 20001]);
+        assert_eq!(event.reply_target(), Some(42));
+
+        let event = event_mentioning(&[]);
+        assert_eq!(event.plain_text(), " 在吗");
+        assert!(event.mentions().is_empty());
+        assert_eq!(event.reply_target(), None);
+    }
+
+    #[test]
+    fn reply_target_is_the_first_reply_segment() {
+        let mut event = event_mentioning(&["10001"]);
+        assert_eq!(event.reply_target(), None);
+
+        event
+            .message_list
+            .insert(0, Message::Reply(ReplyMessage { id: 42, message_source: None }));
+        event
+            .message_list
+            .push(Message::Reply(ReplyMessage { id: 43, message_source: None }));
+        assert_eq!(event.reply_target(), Some(42));
+    }
+
+    #[test]
+    fn raw_event_keeps_reply_image_and_face_segments() {
+        let raw: RawMessageEvent = serde_json::from_value(serde_json::json!({
+            "message_id": 7,
+            "message_type": "group",
+            "sender": { "user_id": 20002, "nickname": "member" },
+            "group_id": 30003,
+            "message": [
+                { "type": "reply", "data": { "id": "6" } },
+                { "type": "image", "data": { "file": "a.jpg", "url": "https://example.com/a.jpg" } },
+                { "type": "face", "data": { "id": 14 } },
+                { "type": "dice", "data": { "result": "3" } },
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(raw.message.len(), 3);
+        assert!(matches!(&raw.message[0], Message::Reply(reply) if reply.id == 6));
+        assert!(
+            matches!(&raw.message[1], Message::Image(image) if image.original_source() == Some("https://example.com/a.jpg"))
+        );
+        assert!(matches!(&raw.message[2], Message::Face(face) if face.id == "14"));
+        assert_eq!(raw.message[2].to_string(), "[Face 14]");
+
+        let face_json = serde_json::to_value(&raw.message[2]).unwrap();
+        assert_eq!(face_json, serde_json::json!({ "type": "face", "data": { "id": "14" } }));
+    }
+
+    #[test]
+    fn notice_and_request_posts_parse_by_post_type() {
+        let notice: Event = serde_json::from_value(serde_json::json!({
+            "time": 1714557600,
+            "self_id": 10001,
+            "post_type": "notice",
+            "notice_type": "group_increase",
+            "sub_type": "approve",
+            "group_id": 30003,
+            "user_id": 20002,
+            "operator_id": 40004,
+        }))
+        .unwrap();
+        match notice {
+            Event::Notice(notice) => {
+                assert!(notice.is_group_member_join());
+                assert!(!notice.is_friend_add());
+                assert_eq!(notice.group_id, Some(30003));
+                assert_eq!(notice.user_id, Some(20002));
+            }
+            other => panic!("expected a notice, got {other:?}"),
+        }
+
+        let request: Event = serde_json::from_value(serde_json::json!({
+            "post_type": "request",
+            "request_type": "group",
+            "sub_type": "invite",
+            "group_id": 30003,
+            "user_id": 20002,
+            "comment": "",
+            "flag": "invite-flag",
+        }))
+        .unwrap();
+        match request {
+            Event::Request(request) => {
+                assert!(request.is_group_invite());
+                assert!(!request.is_friend_request());
+                assert_eq!(request.flag, "invite-flag");
+            }
+            other => panic!("expected a request, got {other:?}"),
+        }
+
+        assert!(serde_json::from_value::<Event>(serde_json::json!({
+            "post_type": "meta_event",
+            "meta_event_type": "heartbeat",
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn raw_event_time_becomes_the_send_time() {
+        let raw: RawMessageEvent = serde_json::from_value(serde_json::json!({
+            "time": 1714557600,
+            "message_id": 8,
+            "message_type": "private",
+            "sender": { "user_id": 20002, "nickname": "member" },
+            "message": [],
+        }))
+        .unwrap();
+        assert_eq!(raw.time, Some(1714557600));
+
+        let mut event = event_mentioning(&[]);
+        event.time = raw.time;
+        let expected = DateTime::from_timestamp(1714557600, 0)
+            .unwrap()
+            .with_timezone(&Local)
+            .naive_local();
+        assert_eq!(event.send_time(), Some(expected));
+
+        event.time = None;
+        assert_eq!(event.send_time(), None);
+    }
+}
@@ -34,6 +34,14 @@ where
     }
 }
 
+fn deserialize_string_from_string_or_number<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_option_string_from_string_or_number(deserializer)?
+        .ok_or_else(|| de::Error::custom("expected string or number, got null"))
+}
+
 /// Base trait for all message types
 pub trait MessageBase: fmt::Display + fmt::Debug + Send + Sync {
     fn get_type(&self) -> &'static str;
@@ -51,6 +59,8 @@ pub enum Message {
     Reply(ReplyMessage),
     #[serde(rename = "image")]
     Image(ImageMessage),
+    #[serde(rename = "face")]
+    Face(FaceMessage),
     #[serde(rename = "forward")]
     Forward(ForwardMessage),
 }
@@ -62,6 +72,7 @@ impl fmt::Display for Message {
             Message::At(msg) => write!(f, "{}", msg),
             Message::Reply(msg) => write!(f, "{}", msg),
             Message::Image(msg) => write!(f, "{}", msg),
+            Message::Face(msg) => write!(f, "{}", msg),
             Message::Forward(msg) => write!(f, "{}", msg),
         }
     }
@@ -74,6 +85,7 @@ impl MessageBase for Message {
             Message::At(_) => "at",
             Message::Reply(_) => "reply",
             Message::Image(_) => "image",
+            Message::Face(_) => "face",
             Message::Forward(_) => "forward",
         }
     }
@@ -143,6 +155,25 @@ impl MessageBase for ReplyMessage {
     }
 }
 
+/// QQ built-in emoji ("face") segment, by face id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaceMessage {
+    #[serde(deserialize_with = "deserialize_string_from_string_or_number")]
+    pub id: String,
+}
+
+impl fmt::Display for FaceMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[Face {}]", self.id)
+    }
+}
+
+impl MessageBase for FaceMessage {
+    fn get_type(&self) -> &'static str {
+        "face"
+    }
+}
+
 /// Image message segment.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -411,6 +442,7 @@ pub fn render_messages_readable(messages: &[Message]) -> String {
             Message::At(at) => append_rendered_segment(&mut rendered, &at.to_string()),
             Message::Reply(reply) => append_rendered_segment(&mut rendered, &reply.to_string()),
             Message::Image(image) => append_rendered_segment(&mut rendered, &image.to_string()),
+            Message::Face(face) => append_rendered_segment(&mut rendered, &face.to_string()),
             Message::Forward(forward) => {
                 if forward.content.is_empty() {
                     append_rendered_segment(&mut rendered, &forward.to_string());
@@ -431,6 +463,17 @@ pub fn render_messages_readable(messages: &[Message]) -> String {
     rendered
 }
 
+/// Ids of the @-targets in `messages`, in message order.
+pub fn at_target_ids(messages: &[Message]) -> Vec<String> {
+    messages
+        .iter()
+        .filter_map(|message| match message {
+            Message::At(at) => Some(at.target_id()),
+            _ => None,
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug)]
 pub struct MessageProp {
     pub content: Option<String>,
@@ -474,6 +517,11 @@ pub fn collect_media_records(messages: &[Message]) -> Vec<MessageMediaRecord> {
 }
 
 impl MessageProp {
+    /// Whether `bot_id` is one of the @-targets (an exact id match).
+    pub fn mentions_bot(&self, bot_id: &str) -> bool {
+        super::event_model::mentions_id(&self.at_target_list, bot_id)
+    }
+
     fn text_mentions_bot_name(messages: &[Message], bot_name: Option<&str>) -> bool {
         let bot_name = match bot_name.map(str::trim) {
             Some(name) if !name.is_empty() => name,
@@ -535,16 +583,14 @@ impl MessageProp {
             }
         };
 
-        let is_at_me = match bot_id {
-            Some(id) => at_targets.iter().any(|t| t == id),
-            None => false,
-        } || Self::text_mentions_bot_name(messages, bot_name);
-
-        MessageProp {
+        let mut prop = MessageProp {
             content,
             ref_content,
-            is_at_me,
+            is_at_me: false,
             at_target_list: at_targets,
-        }
+        };
+        prop.is_at_me =
+            bot_id.is_some_and(|id| prop.mentions_bot(id)) || Self::text_mentions_bot_name(messages, bot_name);
+        prop
     }
 }
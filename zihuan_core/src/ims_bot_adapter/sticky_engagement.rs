@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Who a group message is addressed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Addressee {
+    /// The bot is mentioned, by @ or by name.
+    Bot,
+    /// Someone else is @-mentioned and the bot is not.
+    Others,
+    /// Nobody is mentioned.
+    Nobody,
+}
+
+/// Remembers when each user last addressed the bot in a group, so that their
+/// follow-ups within a short window pass the mention gate without another @.
+///
+/// The window starts at the last message that addressed the bot; follow-ups do
+/// not extend it. Addressing someone else ends it early.
+#[derive(Default)]
+pub struct StickyEngagementStore {
+    last_addressed: Mutex<HashMap<(i64, i64), Instant>>,
+}
+
+impl StickyEngagementStore {
+    /// Whether a message from `user_id` in `group_id` should be handled.
+    /// A zero `window` disables stickiness: only messages addressing the bot pass.
+    pub fn admit(&self, group_id: i64, user_id: i64, addressee: Addressee, window: Duration) -> bool {
+        self.admit_at(group_id, user_id, addressee, window, Instant::now())
+    }
+
+    fn admit_at(&self, group_id: i64, user_id: i64, addressee: Addressee, window: Duration, now: Instant) -> bool {
+        if window.is_zero() {
+            return addressee == Addressee::Bot;
+        }
+
+        let key = (group_id, user_id);
+        let mut last_addressed = self.last_addressed.lock().unwrap();
+        match addressee {
+            Addressee::Bot => {
+                last_addressed.retain(|_, addressed_at| now.duration_since(*addressed_at) <= window);
+                last_addressed.insert(key, now);
+                true
+            }
+            Addressee::Others => {
+                last_addressed.remove(&key);
+                false
+            }
+            Addressee::Nobody => match last_addressed.get(&key) {
+                Some(addressed_at) if now.duration_since(*addressed_at) <= window => true,
+                Some(_) => {
+                    last_addressed.remove(&key);
+                    false
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn follow_up_passes_within_the_window_and_is_gated_after_it() {
+        let store = StickyEngagementStore::default();
+        let start = Instant::now();
+
+        assert!(!store.admit_at(1, 100, Addressee::Nobody, WINDOW, start));
+        assert!(store.admit_at(1, 100, Addressee::Bot, WINDOW, start));
+        assert!(store.admit_at(1, 100, Addressee::Nobody, WINDOW, start + Duration::from_secs(30)));
+        // Only the addressed user in the addressed group is sticky.
+        assert!(!store.admit_at(1, 200, Addressee::Nobody, WINDOW, start + Duration::from_secs(30)));
+        assert!(!store.admit_at(2, 100, Addressee::Nobody, WINDOW, start + Duration::from_secs(30)));
+
+        assert!(!store.admit_at(1, 100, Addressee::Nobody, WINDOW, start + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn addressing_someone_else_ends_the_engagement() {
+        let store = StickyEngagementStore::default();
+        let start = Instant::now();
+
+        assert!(store.admit_at(1, 100, Addressee::Bot, WINDOW, start));
+        assert!(!store.admit_at(1, 100, Addressee::Others, WINDOW, start + Duration::from_secs(5)));
+        assert!(!store.admit_at(1, 100, Addressee::Nobody, WINDOW, start + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn zero_window_only_admits_messages_addressing_the_bot() {
+        let store = StickyEngagementStore::default();
+        let start = Instant::now();
+
+        assert!(store.admit_at(1, 100, Addressee::Bot, Duration::ZERO, start));
+        assert!(!store.admit_at(1, 100, Addressee::Nobody, Duration::ZERO, start));
+    }
+}
@@ -1,7 +1,13 @@
 pub mod utils {
+    pub mod backoff;
     pub mod bm25;
+    pub mod clock;
     pub mod hash_string;
+    pub mod math_expr;
+    pub mod rate_limiter;
+    pub mod sender_identity;
     pub mod string_utils;
+    pub mod template;
 }
 pub mod agent_config;
 pub mod command;
@@ -20,8 +26,11 @@ pub mod setup_wizard;
 pub mod steer;
 pub mod system_config;
 pub mod task_context;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod tool_runtime;
 pub mod url_utils;
+pub mod weather;
 pub mod weaviate;
 pub mod worker_pool;
 pub mod workspace;
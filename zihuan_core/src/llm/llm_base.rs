@@ -1,3 +1,4 @@
+use crate::error::Result;
 use crate::llm::model::{InferenceParam, LLMMessage};
 use crate::llm::StreamToken;
 use tokio::sync::mpsc;
@@ -15,6 +16,22 @@ pub trait LLMBase: std::fmt::Debug + Send + Sync {
 
     fn inference(&self, param: &InferenceParam) -> LLMMessage;
 
+    /// Like [`LLMBase::inference`], but a failed request is returned as an error
+    /// instead of an `"Error: ..."` reply. The default cannot tell the two apart
+    /// and always succeeds; HTTP-backed models override it.
+    fn try_inference(&self, param: &InferenceParam) -> Result<LLMMessage> {
+        Ok(self.inference(param))
+    }
+
+    /// Non-blocking variant of [`LLMBase::inference`] for callers on an async runtime.
+    /// The default runs the blocking call inline; HTTP-backed models override it.
+    fn inference_async<'a>(
+        &'a self,
+        param: &'a InferenceParam<'a>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = LLMMessage> + Send + 'a>> {
+        Box::pin(async move { self.inference(param) })
+    }
+
     fn as_streaming(&self) -> Option<&dyn StreamingLLMBase> {
         None
     }
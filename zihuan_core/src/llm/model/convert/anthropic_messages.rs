@@ -0,0 +1,88 @@
+use serde_json::{json, Value};
+
+use crate::message_part::MessagePart;
+
+use super::super::llm_message::LLMMessage;
+use super::super::message_role::MessageRole;
+
+/// Anthropic image source for a media locator: inline base64 for `data:` URLs,
+/// a URL source otherwise.
+fn image_source(locator: &str) -> Value {
+    if let Some((header, data)) = locator.strip_prefix("data:").and_then(|rest| rest.split_once(',')) {
+        let media_type = header.trim_end_matches(";base64");
+        return json!({
+            "type": "base64",
+            "media_type": media_type,
+            "data": data,
+        });
+    }
+    json!({ "type": "url", "url": locator })
+}
+
+fn content_blocks(message: &LLMMessage) -> Vec<Value> {
+    message
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            MessagePart::Text { text } if text.is_empty() => None,
+            MessagePart::Text { text } => Some(json!({ "type": "text", "text": text })),
+            MessagePart::Image { .. } if matches!(message.role, MessageRole::Assistant) => Some(json!({
+                "type": "text",
+                "text": format!("[image omitted] {}", part.media_locator().unwrap_or_default()),
+            })),
+            MessagePart::Image { .. } => Some(json!({
+                "type": "image",
+                "source": image_source(part.media_locator().unwrap_or_default()),
+            })),
+            MessagePart::Video { .. } => Some(json!({
+                "type": "text",
+                "text": format!("[video omitted] {}", part.media_locator().unwrap_or_default()),
+            })),
+        })
+        .collect()
+}
+
+/// `tool_use.input` must be an object; arguments kept as a JSON string are parsed.
+fn tool_input(arguments: &Value) -> Value {
+    match arguments {
+        Value::Object(_) => arguments.clone(),
+        Value::String(text) => serde_json::from_str::<Value>(text)
+            .ok()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| json!({})),
+        _ => json!({}),
+    }
+}
+
+/// System messages produce nothing here: the Messages API takes them in the
+/// top-level `system` field, which the request builder fills separately.
+pub(crate) fn convert(message: &LLMMessage) -> Vec<Value> {
+    match message.role {
+        MessageRole::System => Vec::new(),
+        MessageRole::Tool => vec![json!({
+            "role": "user",
+            "content": [{
+                "type": "tool_result",
+                "tool_use_id": message.tool_call_id.clone().unwrap_or_default(),
+                "content": message.content_text_owned().unwrap_or_default(),
+            }],
+        })],
+        MessageRole::Assistant => {
+            let mut blocks = content_blocks(message);
+            blocks.extend(message.tool_calls.iter().map(|tool_call| {
+                json!({
+                    "type": "tool_use",
+                    "id": tool_call.id,
+                    "name": tool_call.function.name,
+                    "input": tool_input(&tool_call.function.arguments),
+                })
+            }));
+            // The API rejects an empty `content` array, so a blank turn is dropped.
+            if blocks.is_empty() {
+                return Vec::new();
+            }
+            vec![json!({ "role": "assistant", "content": blocks })]
+        }
+        MessageRole::User => vec![json!({ "role": "user", "content": content_blocks(message) })],
+    }
+}
@@ -117,6 +117,14 @@ pub(crate) fn with_tool_fields(mut msg_obj: Value, message: &LLMMessage) -> Valu
     msg_obj
 }
 
+/// Adds the optional speaker `name` supported by Chat Completions messages.
+pub(crate) fn with_name(mut msg_obj: Value, message: &LLMMessage) -> Value {
+    if let Some(ref name) = message.name {
+        msg_obj["name"] = json!(name);
+    }
+    msg_obj
+}
+
 pub(crate) fn role_json(message: &LLMMessage) -> Value {
     json!(role_to_str(&message.role))
 }
@@ -1,3 +1,4 @@
+pub mod anthropic_messages;
 pub mod common;
 pub mod openai_chat_completions;
 pub mod openai_chat_completions_tencent_multimodal_compat;
@@ -7,4 +7,73 @@ use super::llm_message::LLMMessage;
 pub struct InferenceParam<'a> {
     pub messages: &'a Vec<LLMMessage>,
     pub tools: Option<&'a Vec<Arc<dyn FunctionTool>>>,
+    /// Send no tools at all for this call, even if `tools` is set.
+    pub disable_tools: bool,
+    /// Sampling overrides for this call; `None` keeps the server default.
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub stop: Option<Vec<String>>,
+    /// Make the model call the tool with this name instead of choosing freely.
+    pub required_tool: Option<&'a str>,
+}
+
+impl<'a> InferenceParam<'a> {
+    /// A call on `messages` without tools, using the server's sampling defaults.
+    pub fn new(messages: &'a Vec<LLMMessage>) -> Self {
+        Self {
+            messages,
+            tools: None,
+            disable_tools: false,
+            temperature: None,
+            top_p: None,
+            max_tokens: None,
+            stop: None,
+            required_tool: None,
+        }
+    }
+
+    pub fn with_tools(mut self, tools: Option<&'a Vec<Arc<dyn FunctionTool>>>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    pub fn without_tools(mut self) -> Self {
+        self.disable_tools = true;
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: Option<f32>) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    pub fn with_top_p(mut self, top_p: Option<f32>) -> Self {
+        self.top_p = top_p;
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: Option<u32>) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn with_stop(mut self, stop: Option<Vec<String>>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    pub fn with_required_tool(mut self, required_tool: &'a str) -> Self {
+        self.required_tool = Some(required_tool);
+        self
+    }
+
+    /// Tools to put in the request. `None` when tools are disabled or the list is
+    /// empty, in which case neither `tools` nor `tool_choice` should be sent.
+    pub fn active_tools(&self) -> Option<&Vec<Arc<dyn FunctionTool>>> {
+        if self.disable_tools {
+            return None;
+        }
+        self.tools.filter(|tools| !tools.is_empty())
+    }
 }
@@ -5,6 +5,10 @@ use crate::llm::tooling::ToolCalls;
 use crate::message_part::MessagePart;
 
 use super::message_role::MessageRole;
+
+/// Longest speaker name Chat Completions accepts.
+const MAX_NAME_CHARS: usize = 64;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenUsage {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -30,6 +34,10 @@ pub struct LLMMessage {
     pub tool_calls: Vec<ToolCalls>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
+    /// Speaker of a user or tool turn, e.g. a group member's display name, so
+    /// the model can tell participants apart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub usage: Option<TokenUsage>,
 }
@@ -41,6 +49,7 @@ pub enum LLMMessageConvertStyle {
     OpenAiResponses,
     OpenAiResponsesMessageCompat,
     OpenAiResponsesImageUrlObjectCompat,
+    AnthropicMessages,
 }
 
 impl LLMMessage {
@@ -52,6 +61,7 @@ impl LLMMessage {
             reasoning_content: None,
             tool_calls: Vec::new(),
             tool_call_id: None,
+            name: None,
             usage: None,
         }
     }
@@ -64,6 +74,7 @@ impl LLMMessage {
             reasoning_content: None,
             tool_calls: Vec::new(),
             tool_call_id: None,
+            name: None,
             usage: None,
         }
     }
@@ -76,6 +87,7 @@ impl LLMMessage {
             reasoning_content: None,
             tool_calls: Vec::new(),
             tool_call_id: None,
+            name: None,
             usage: None,
         }
     }
@@ -88,6 +100,7 @@ impl LLMMessage {
             reasoning_content: None,
             tool_calls: Vec::new(),
             tool_call_id: None,
+            name: None,
             usage: None,
         }
     }
@@ -100,10 +113,33 @@ impl LLMMessage {
             reasoning_content: None,
             tool_calls: Vec::new(),
             tool_call_id: Some(tool_call_id.into()),
+            name: None,
             usage: None,
         }
     }
 
+    /// Attribute this message to a named speaker.
+    ///
+    /// Chat Completions only accepts names matching `^[a-zA-Z0-9_-]{1,64}$`, so
+    /// whitespace becomes `_`, other characters are dropped and the result is
+    /// cut to 64 characters. A name with nothing left, e.g. an all-Chinese
+    /// nickname, clears it.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        let name: String = name
+            .into()
+            .trim()
+            .chars()
+            .filter_map(|c| match c {
+                c if c.is_ascii_alphanumeric() || c == '_' || c == '-' => Some(c),
+                c if c.is_whitespace() => Some('_'),
+                _ => None,
+            })
+            .take(MAX_NAME_CHARS)
+            .collect();
+        self.name = (!name.is_empty()).then_some(name);
+        self
+    }
+
     /// Return borrowed text only when the message is exactly one text part.
     pub fn content_text(&self) -> Option<&str> {
         match self.parts.as_slice() {
@@ -167,6 +203,7 @@ impl LLMMessage {
             LLMMessageConvertStyle::OpenAiResponsesImageUrlObjectCompat => {
                 super::convert::openai_responses_image_url_object_compat::convert(self)
             }
+            LLMMessageConvertStyle::AnthropicMessages => super::convert::anthropic_messages::convert(self),
         }
     }
 
@@ -183,3 +220,36 @@ impl LLMMessage {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_completions_payload_carries_name_only_when_set() {
+        let messages = vec![
+            LLMMessage::user("早上好").with_name("Alice"),
+            LLMMessage::user("晚上好"),
+            LLMMessage::user("无名").with_name("  "),
+        ];
+
+        for style in [
+            LLMMessageConvertStyle::OpenAiChatCompletions,
+            LLMMessageConvertStyle::OpenAiChatCompletionsTencentMultimodalCompat,
+        ] {
+            let payload = LLMMessage::convert_list(&messages, style, false);
+            assert_eq!(payload[0]["name"], "Alice");
+            assert!(payload[1].get("name").is_none());
+            assert!(payload[2].get("name").is_none());
+        }
+    }
+
+    #[test]
+    fn names_are_reduced_to_the_allowed_characters() {
+        assert_eq!(LLMMessage::user("hi").with_name("小明").name, None);
+        assert_eq!(LLMMessage::user("hi").with_name(" 小明 Bob-2 ").name.as_deref(), Some("_Bob-2"));
+        assert_eq!(LLMMessage::user("hi").with_name("a.b@c d").name.as_deref(), Some("abc_d"));
+        let long = LLMMessage::user("hi").with_name("x".repeat(100)).name.unwrap();
+        assert_eq!(long.len(), MAX_NAME_CHARS);
+    }
+}
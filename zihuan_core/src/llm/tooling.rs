@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use serde_json::{json, Value};
 
 use crate::error::Result;
@@ -19,9 +22,166 @@ pub trait FunctionTool: Send + Sync + std::fmt::Debug {
         })
     }
 
+    /// Checks `arguments` against [`FunctionTool::parameters`]: required fields,
+    /// `"type"` and `"enum"`, descending into nested properties and array items.
+    /// Callers dispatching model tool calls run this before [`FunctionTool::call`].
+    fn validate_arguments(&self, arguments: &Value) -> std::result::Result<(), String> {
+        validate_schema_value(&self.parameters(), arguments, "arguments")
+    }
+
     fn call(&self, arguments: Value) -> Result<Value>;
 }
 
+/// Providers send zero-argument tool calls with empty or absent arguments, which
+/// parse to `null`; tools and [`FunctionTool::validate_arguments`] expect `{}`.
+pub fn normalized_tool_arguments(arguments: &Value) -> Value {
+    match arguments {
+        Value::Null => json!({}),
+        other => other.clone(),
+    }
+}
+
+fn matches_schema_type(type_name: &str, value: &Value) -> bool {
+    match type_name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn validate_schema_value(schema: &Value, value: &Value, path: &str) -> std::result::Result<(), String> {
+    let type_names: Vec<&str> = match schema.get("type") {
+        Some(Value::String(type_name)) => vec![type_name.as_str()],
+        Some(Value::Array(type_names)) => type_names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !type_names.is_empty() && !type_names.iter().any(|type_name| matches_schema_type(type_name, value)) {
+        return Err(format!("{path} must be of type {}, got {value}", type_names.join(" | ")));
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed = allowed.iter().map(Value::to_string).collect::<Vec<_>>().join(", ");
+            return Err(format!("{path} must be one of [{allowed}], got {value}"));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        for required in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(field) = required.as_str().filter(|field| !object.contains_key(*field)) {
+                return Err(format!("{path} is missing required field '{field}'"));
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (field, field_value) in object {
+                if let Some(field_schema) = properties.get(field) {
+                    validate_schema_value(field_schema, field_value, &format!("{path}.{field}"))?;
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            validate_schema_value(item_schema, item, &format!("{path}[{index}]"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tools indexed by the name the model calls them by, kept in registration order
+/// so the `tools` array sent to the model stays stable between requests.
+///
+/// Registering a name again replaces the earlier tool in place.
+#[derive(Debug)]
+pub struct ToolRegistry<T: ?Sized = dyn FunctionTool> {
+    tools: HashMap<String, Arc<T>>,
+    order: Vec<String>,
+}
+
+impl<T: ?Sized> Default for ToolRegistry<T> {
+    fn default() -> Self {
+        Self {
+            tools: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for ToolRegistry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tools: self.tools.clone(),
+            order: self.order.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized> ToolRegistry<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `tool` under `name`, returning the tool it replaced.
+    pub fn insert(&mut self, name: impl Into<String>, tool: Arc<T>) -> Option<Arc<T>> {
+        let name = name.into();
+        let previous = self.tools.insert(name.clone(), tool);
+        if previous.is_none() {
+            self.order.push(name);
+        }
+        previous
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<T>> {
+        self.tools.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
+    }
+
+    /// Registered tools in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<T>> {
+        self.order.iter().filter_map(|name| self.tools.get(name))
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+impl ToolRegistry {
+    pub fn register(&mut self, tool: Arc<dyn FunctionTool>) -> Option<Arc<dyn FunctionTool>> {
+        let name = tool.name().to_string();
+        self.insert(name, tool)
+    }
+
+    /// The OpenAI-style `tools` array, see [`FunctionTool::get_json`].
+    pub fn all_json(&self) -> Vec<Value> {
+        self.iter().map(|tool| tool.get_json()).collect()
+    }
+}
+
+impl FromIterator<Arc<dyn FunctionTool>> for ToolRegistry {
+    fn from_iter<I: IntoIterator<Item = Arc<dyn FunctionTool>>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for tool in iter {
+            registry.register(tool);
+        }
+        registry
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ToolCallsFuncSpec {
     pub name: String,
@@ -63,3 +223,88 @@ impl FunctionTool for StaticFunctionToolSpec {
         Ok(Value::Null)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> StaticFunctionToolSpec {
+        StaticFunctionToolSpec {
+            name: "math",
+            description: "四则运算",
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "op": { "type": "string", "enum": ["add", "mul"] },
+                    "a": { "type": "number" },
+                    "b": { "type": "number" },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                },
+                "required": ["op", "a", "b"]
+            }),
+        }
+    }
+
+    #[test]
+    fn arguments_matching_the_schema_are_accepted() {
+        let spec = spec();
+        assert_eq!(spec.validate_arguments(&json!({ "op": "add", "a": 1, "b": 2.5 })), Ok(()));
+        assert_eq!(
+            spec.validate_arguments(&json!({ "op": "mul", "a": 1, "b": 2, "tags": ["x"], "extra": true })),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn missing_fields_wrong_types_and_unknown_enum_values_are_rejected() {
+        let spec = spec();
+        let error = |arguments: Value| spec.validate_arguments(&arguments).unwrap_err();
+
+        assert_eq!(error(json!({ "op": "add", "a": 1 })), "arguments is missing required field 'b'");
+        assert!(error(json!({ "op": "add", "a": "1", "b": 2 })).starts_with("arguments.a must be of type number"));
+        assert!(error(json!({ "op": "sub", "a": 1, "b": 2 })).starts_with("arguments.op must be one of"));
+        assert!(error(json!({ "op": "add", "a": 1, "b": 2, "tags": [1] })).starts_with("arguments.tags[0]"));
+        assert!(error(json!("not an object")).starts_with("arguments must be of type object"));
+    }
+
+    #[test]
+    fn absent_arguments_of_a_zero_argument_tool_validate_as_an_empty_object() {
+        let now = StaticFunctionToolSpec {
+            name: "now",
+            description: "当前时间",
+            parameters: json!({ "type": "object", "properties": {} }),
+        };
+        let arguments = normalized_tool_arguments(&Value::Null);
+
+        assert_eq!(arguments, json!({}));
+        assert_eq!(now.validate_arguments(&arguments), Ok(()));
+    }
+
+    #[test]
+    fn registry_resolves_tools_by_name_in_registration_order() {
+        let chat_history = StaticFunctionToolSpec {
+            name: "chat_history",
+            description: "查询聊天记录",
+            parameters: json!({ "type": "object", "properties": { "limit": { "type": "integer" } } }),
+        };
+        let mut registry = ToolRegistry::new();
+        assert!(registry.register(Arc::new(spec())).is_none());
+        assert!(registry.register(Arc::new(chat_history)).is_none());
+
+        assert_eq!(registry.get("math").map(|tool| tool.description()), Some("四则运算"));
+        assert_eq!(
+            registry.get("chat_history").map(|tool| tool.description()),
+            Some("查询聊天记录")
+        );
+        assert!(registry.get("unknown").is_none());
+        let names: Vec<_> = registry
+            .all_json()
+            .iter()
+            .map(|tool| tool["function"]["name"].clone())
+            .collect();
+        assert_eq!(names, vec![json!("math"), json!("chat_history")]);
+
+        assert!(registry.register(Arc::new(spec())).is_some());
+        assert_eq!(registry.len(), 2);
+    }
+}
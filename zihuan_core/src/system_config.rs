@@ -1,5 +1,7 @@
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -137,12 +139,35 @@ fn default_task_ttl_hours() -> u64 {
 pub struct GlobalSettings {
     #[serde(default = "default_task_ttl_hours")]
     pub task_ttl_hours: u64,
+    /// LLM API requests allowed in flight at once across all agents; excess
+    /// requests queue until one finishes. `0` means unlimited.
+    #[serde(default)]
+    pub max_concurrent_llm: usize,
+    /// LLM API requests started per second across all agents; excess requests
+    /// wait their turn. `0` means unlimited.
+    #[serde(default)]
+    pub llm_requests_per_second: f64,
+    /// LLM requests that may start back to back after an idle period.
+    #[serde(default)]
+    pub llm_burst: u32,
+    /// QQ messages sent per second through the bot adapter; excess messages
+    /// wait their turn. `0` means unlimited.
+    #[serde(default)]
+    pub qq_messages_per_second: f64,
+    /// QQ messages that may be sent back to back after an idle period.
+    #[serde(default)]
+    pub qq_message_burst: u32,
 }
 
 impl Default for GlobalSettings {
     fn default() -> Self {
         Self {
             task_ttl_hours: default_task_ttl_hours(),
+            max_concurrent_llm: 0,
+            llm_requests_per_second: 0.0,
+            llm_burst: 0,
+            qq_messages_per_second: 0.0,
+            qq_message_burst: 0,
         }
     }
 }
@@ -153,3 +178,86 @@ impl SystemConfigSection for GlobalSettingsSection {
     const SECTION_KEY: &'static str = "global_settings";
     type Value = GlobalSettings;
 }
+
+/// Operator-tunable timeouts, stored under `timeouts` in `system_config.json`.
+///
+/// Defaults: `llm_secs` 30, `tool_secs` 60, `agent_secs` 600, `http_secs` 30.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeoutSettings {
+    /// Request timeout for LLM refs that do not set their own `timeout_secs`.
+    #[serde(default = "default_llm_timeout_secs")]
+    pub llm_secs: u64,
+    /// Wall-clock limit for a single short-running tool call in the Brain loop.
+    #[serde(default = "default_tool_timeout_secs")]
+    pub tool_secs: u64,
+    /// Budget for one agent turn; once spent, the Brain stops calling tools and
+    /// answers with what it already has.
+    #[serde(default = "default_agent_timeout_secs")]
+    pub agent_secs: u64,
+    /// How long to wait for the bot server to answer an outbound action.
+    #[serde(default = "default_http_timeout_secs")]
+    pub http_secs: u64,
+}
+
+impl Default for TimeoutSettings {
+    fn default() -> Self {
+        Self {
+            llm_secs: default_llm_timeout_secs(),
+            tool_secs: default_tool_timeout_secs(),
+            agent_secs: default_agent_timeout_secs(),
+            http_secs: default_http_timeout_secs(),
+        }
+    }
+}
+
+impl TimeoutSettings {
+    pub fn llm(&self) -> Duration {
+        Duration::from_secs(self.llm_secs)
+    }
+
+    pub fn tool(&self) -> Duration {
+        Duration::from_secs(self.tool_secs)
+    }
+
+    pub fn agent(&self) -> Duration {
+        Duration::from_secs(self.agent_secs)
+    }
+
+    pub fn http(&self) -> Duration {
+        Duration::from_secs(self.http_secs)
+    }
+}
+
+fn default_llm_timeout_secs() -> u64 {
+    30
+}
+
+fn default_tool_timeout_secs() -> u64 {
+    60
+}
+
+fn default_agent_timeout_secs() -> u64 {
+    600
+}
+
+fn default_http_timeout_secs() -> u64 {
+    30
+}
+
+pub struct TimeoutSettingsSection;
+
+impl SystemConfigSection for TimeoutSettingsSection {
+    const SECTION_KEY: &'static str = "timeouts";
+    type Value = TimeoutSettings;
+}
+
+/// Timeouts loaded once per process; edits to the config take effect on restart.
+pub fn timeout_settings() -> &'static TimeoutSettings {
+    static SETTINGS: OnceLock<TimeoutSettings> = OnceLock::new();
+    SETTINGS.get_or_init(|| {
+        load_section::<TimeoutSettingsSection>().unwrap_or_else(|err| {
+            log::warn!("[system_config] failed to load timeouts, using defaults: {err}");
+            TimeoutSettings::default()
+        })
+    })
+}
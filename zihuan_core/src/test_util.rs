@@ -0,0 +1,132 @@
+//! Helpers shared by the workspace's tests; enabled with the `test-util` feature.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+/// How [`MockHttpServer`] answers one request.
+#[derive(Debug, Clone)]
+pub enum MockReply {
+    /// A response with this status and body.
+    Respond { status: u16, body: String },
+    /// Read the request but never answer, to exercise client timeouts.
+    Hang,
+}
+
+impl MockReply {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self::Respond { status, body: body.into() }
+    }
+}
+
+/// A request received by [`MockHttpServer`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// Request line and headers, as sent.
+    pub head: String,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// The value of header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.head.lines().skip(1).find_map(|line| {
+            let (header, value) = line.split_once(':')?;
+            header.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    pub fn body_json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).expect("request body should be JSON")
+    }
+}
+
+/// Minimal HTTP/1.1 server on localhost answering requests in turn with the
+/// queued replies, repeating the last one, and recording every request.
+pub struct MockHttpServer {
+    url: String,
+    requests: mpsc::UnboundedReceiver<RecordedRequest>,
+}
+
+impl MockHttpServer {
+    /// Starts serving at `path` (e.g. `"/v1/messages"`); see [`Self::url`].
+    pub async fn start(path: &str, replies: Vec<MockReply>) -> Self {
+        assert!(!replies.is_empty(), "MockHttpServer needs at least one reply");
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind mock HTTP server");
+        let url = format!("http://{}{}", listener.local_addr().expect("mock server address"), path);
+        let (request_tx, requests) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for index in 0.. {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let reply = replies[index.min(replies.len() - 1)].clone();
+                let request_tx = request_tx.clone();
+                tokio::spawn(async move {
+                    let Some(request) = read_request(&mut socket).await else {
+                        return;
+                    };
+                    let _ = request_tx.send(request);
+                    match reply {
+                        MockReply::Respond { status, body } => {
+                            let response = format!(
+                                "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                                body.len()
+                            );
+                            let _ = socket.write_all(response.as_bytes()).await;
+                        }
+                        MockReply::Hang => tokio::time::sleep(Duration::from_secs(60)).await,
+                    }
+                });
+            }
+        });
+        Self { url, requests }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// The next request received, waiting for it if needed.
+    pub async fn next_request(&mut self) -> RecordedRequest {
+        self.requests.recv().await.expect("mock HTTP server stopped")
+    }
+}
+
+/// Reads one request: the head up to the blank line, then `Content-Length` bytes.
+async fn read_request(socket: &mut tokio::net::TcpStream) -> Option<RecordedRequest> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let read = socket.read(&mut buf).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        request.extend_from_slice(&buf[..read]);
+        let Some(header_end) = request.windows(4).position(|window| window == b"\r\n\r\n") else {
+            continue;
+        };
+        let head = String::from_utf8_lossy(&request[..header_end]).into_owned();
+        let content_length = head
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("content-length")
+                    .then(|| value.trim().parse::<usize>().ok())?
+            })
+            .unwrap_or(0);
+        let body_start = header_end + 4;
+        if request.len() >= body_start + content_length {
+            return Some(RecordedRequest {
+                head,
+                body: request[body_start..body_start + content_length].to_vec(),
+            });
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Exponential backoff shared by the retry loops that talk to external services.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackoffPolicy {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: u32,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            multiplier: 2,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    pub fn new(max_attempts: u32, initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_delay,
+            max_delay,
+            ..Self::default()
+        }
+    }
+
+    /// Delay to wait after the given failed attempt (1-based).
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1);
+        let factor = self.multiplier.max(1).saturating_pow(exponent);
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// [`Self::delay_for_attempt`] plus up to half of it again at random, so
+    /// clients that failed together do not all retry at the same moment.
+    pub fn jittered_delay_for_attempt(&self, attempt: u32) -> Duration {
+        let delay = self.delay_for_attempt(attempt);
+        let spread = (delay.as_millis() / 2) as u64;
+        if spread == 0 {
+            return delay;
+        }
+        let random = RandomState::new().build_hasher().finish();
+        delay + Duration::from_millis(random % (spread + 1))
+    }
+
+    pub fn should_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts.max(1)
+    }
+}
+
+/// HTTP statuses worth retrying unchanged: rate limits, the transient
+/// gateway/server errors and 529, Anthropic's "overloaded".
+pub fn is_retryable_http_status(status: u16) -> bool {
+    matches!(status, 429 | 500 | 502 | 503 | 504 | 529)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_grows_exponentially_and_is_capped() {
+        let policy = BackoffPolicy::new(5, Duration::from_millis(100), Duration::from_millis(350));
+        assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(200));
+        assert_eq!(policy.delay_for_attempt(3), Duration::from_millis(350));
+        assert!(policy.should_retry(4));
+        assert!(!policy.should_retry(5));
+    }
+
+    #[test]
+    fn jitter_adds_at_most_half_the_delay() {
+        let policy = BackoffPolicy::new(5, Duration::from_millis(100), Duration::from_millis(350));
+        for _ in 0..20 {
+            let delay = policy.jittered_delay_for_attempt(2);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn only_transient_http_statuses_are_retryable() {
+        for status in [429, 500, 502, 503, 504, 529] {
+            assert!(is_retryable_http_status(status), "{status}");
+        }
+        for status in [400, 401, 404, 501, 505] {
+            assert!(!is_retryable_http_status(status), "{status}");
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+
+/// Source of the local wall-clock time stamped onto stored records.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// The system clock in local time, as used by message persistence.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        chrono::Local::now().naive_local()
+    }
+}
+
+/// A clock that always reports the same instant, for deterministic conversions.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub NaiveDateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> NaiveDateTime {
+        self.0
+    }
+}
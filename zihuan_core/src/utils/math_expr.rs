@@ -0,0 +1,249 @@
+use crate::error::{Error, Result};
+
+/// Longest expression accepted, in characters.
+const MAX_EXPRESSION_CHARS: usize = 4096;
+/// Deepest nesting of parentheses, unary signs and exponents accepted. The
+/// parser is recursive, so unbounded input would overflow the stack.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// Evaluate an arithmetic expression with `+ - * / % ^` (`**` is accepted
+/// for `^`), parentheses and unary signs. `^` binds tighter than unary minus
+/// and is right associative, so `-2^2` is `-4` and `2^3^2` is `512`.
+pub fn evaluate_expression(expr: &str) -> Result<f64> {
+    let chars: Vec<char> = expr.chars().collect();
+    if chars.len() > MAX_EXPRESSION_CHARS {
+        return Err(Error::ValidationError(format!(
+            "expression is {} characters long, the limit is {MAX_EXPRESSION_CHARS}",
+            chars.len()
+        )));
+    }
+    let mut parser = Parser { chars, pos: 0, depth: 0 };
+    if parser.peek().is_none() {
+        return Err(Error::ValidationError("expression is empty".to_string()));
+    }
+    let value = parser.expr()?;
+    if let Some(c) = parser.peek() {
+        return Err(Error::ValidationError(format!(
+            "unexpected '{c}' at position {} in expression",
+            parser.pos + 1
+        )));
+    }
+    finite(value)
+}
+
+/// Apply a named binary operator: `add`, `sub`, `mul`, `div`, `pow` or `mod`.
+pub fn apply_binary_op(op: &str, a: f64, b: f64) -> Result<f64> {
+    let value = match op {
+        "add" => a + b,
+        "sub" => a - b,
+        "mul" => a * b,
+        "div" => divide(a, b)?,
+        "pow" => a.powf(b),
+        "mod" => remainder(a, b)?,
+        other => return Err(Error::ValidationError(format!("unsupported op '{other}'"))),
+    };
+    finite(value)
+}
+
+fn divide(a: f64, b: f64) -> Result<f64> {
+    if b == 0.0 {
+        return Err(Error::ValidationError("division by zero".to_string()));
+    }
+    Ok(a / b)
+}
+
+fn remainder(a: f64, b: f64) -> Result<f64> {
+    if b == 0.0 {
+        return Err(Error::ValidationError("modulo by zero".to_string()));
+    }
+    Ok(a % b)
+}
+
+fn finite(value: f64) -> Result<f64> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(Error::ValidationError("result is not a finite number".to_string()))
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    /// Next non-whitespace character, skipping the whitespace before it.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_power(&mut self) -> bool {
+        if self.eat('^') {
+            return true;
+        }
+        if self.peek() == Some('*') && self.chars.get(self.pos..self.pos + 2) == Some(&['*', '*']) {
+            self.pos += 2;
+            return true;
+        }
+        false
+    }
+
+    fn expr(&mut self) -> Result<f64> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64> {
+        let mut value = self.unary()?;
+        loop {
+            if self.peek() == Some('*') && self.chars.get(self.pos + 1) != Some(&'*') {
+                self.pos += 1;
+                value *= self.unary()?;
+            } else if self.eat('/') {
+                value = divide(value, self.unary()?)?;
+            } else if self.eat('%') {
+                value = remainder(value, self.unary()?)?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Every recursive path of the grammar passes through here, so this is
+    /// where the nesting depth is bounded.
+    fn unary(&mut self) -> Result<f64> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(Error::ValidationError(format!(
+                "expression nests deeper than {MAX_NESTING_DEPTH} levels"
+            )));
+        }
+        self.depth += 1;
+        let value = self.signed();
+        self.depth -= 1;
+        value
+    }
+
+    fn signed(&mut self) -> Result<f64> {
+        if self.eat('-') {
+            return Ok(-self.unary()?);
+        }
+        if self.eat('+') {
+            return self.unary();
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<f64> {
+        let base = self.primary()?;
+        if self.eat_power() {
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<f64> {
+        if self.eat('(') {
+            let value = self.expr()?;
+            if !self.eat(')') {
+                return Err(Error::ValidationError("missing ')' in expression".to_string()));
+            }
+            return Ok(value);
+        }
+
+        let first = self.peek();
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(match first {
+                Some(c) => {
+                    Error::ValidationError(format!("unexpected '{c}' at position {} in expression", self.pos + 1))
+                }
+                None => Error::ValidationError("expression ends unexpectedly".to_string()),
+            });
+        }
+        let literal: String = self.chars[start..self.pos].iter().collect();
+        literal
+            .parse::<f64>()
+            .map_err(|_| Error::ValidationError(format!("invalid number '{literal}' in expression")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn respects_precedence_and_associativity() {
+        assert_eq!(evaluate_expression("1 + 2 * 3").unwrap(), 7.0);
+        assert_eq!(evaluate_expression("(1 + 2) * 3").unwrap(), 9.0);
+        assert_eq!(evaluate_expression("10 - 4 - 3").unwrap(), 3.0);
+        assert_eq!(evaluate_expression("2 ^ 3 ^ 2").unwrap(), 512.0);
+        assert_eq!(evaluate_expression("2 ** 10 / 4").unwrap(), 256.0);
+        assert_eq!(evaluate_expression("-2^2").unwrap(), -4.0);
+        assert_eq!(evaluate_expression("2^-1").unwrap(), 0.5);
+        assert_eq!(evaluate_expression("17 % 5 * 2").unwrap(), 4.0);
+        assert_eq!(evaluate_expression("-(1.5 + .5)").unwrap(), -2.0);
+    }
+
+    #[test]
+    fn reports_invalid_input() {
+        let message = |expr: &str| evaluate_expression(expr).unwrap_err().to_string();
+
+        assert!(message("1 / (2 - 2)").contains("division by zero"));
+        assert!(message("5 % 0").contains("modulo by zero"));
+        assert!(message("(1 + 2").contains("missing ')'"));
+        assert!(message("1 + ").contains("ends unexpectedly"));
+        assert!(message("2 x 3").contains("unexpected 'x'"));
+        assert!(message("1..2").contains("invalid number"));
+        assert!(message("1 2").contains("unexpected '2'"));
+        assert!(message("  ").contains("empty"));
+    }
+
+    #[test]
+    fn rejects_deeply_nested_input_without_overflowing() {
+        let nested = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+        assert!(evaluate_expression(&nested).unwrap_err().to_string().contains("limit"));
+
+        let nested = format!("{}1{}", "(".repeat(1_000), ")".repeat(1_000));
+        assert!(evaluate_expression(&nested).unwrap_err().to_string().contains("nests deeper"));
+        assert!(evaluate_expression(&format!("{}1", "-".repeat(1_000))).is_err());
+        assert!(evaluate_expression(&format!("{}1", "2^".repeat(1_000))).is_err());
+
+        let shallow = format!("{}1{}", "(".repeat(50), ")".repeat(50));
+        assert_eq!(evaluate_expression(&shallow).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn applies_named_binary_ops() {
+        assert_eq!(apply_binary_op("add", 1.0, 2.5).unwrap(), 3.5);
+        assert_eq!(apply_binary_op("pow", 2.0, 8.0).unwrap(), 256.0);
+        assert_eq!(apply_binary_op("mod", 7.0, 4.0).unwrap(), 3.0);
+        assert!(apply_binary_op("div", 1.0, 0.0).is_err());
+        assert!(apply_binary_op("root", 1.0, 2.0).is_err());
+    }
+}
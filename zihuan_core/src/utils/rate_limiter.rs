@@ -0,0 +1,137 @@
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting how often outbound requests start. Callers past the
+/// limit wait, blocking or async, for their turn instead of failing.
+///
+/// Each call reserves the next free slot before waiting, so waiters are served
+/// in arrival order and a burst of callers is spread out evenly.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Tokens added per second; `None` when unlimited.
+    rate: Option<f64>,
+    burst: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Negative when callers have reserved tokens that are not refilled yet.
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `requests_per_second` on average with up to `burst` requests at
+    /// once after an idle period. A rate of `0` or less means unlimited and a
+    /// `burst` of `0` is treated as `1`.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            rate: (requests_per_second > 0.0).then_some(requests_per_second),
+            burst,
+            state: Mutex::new(BucketState {
+                tokens: burst,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(0.0, 1)
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.rate.is_none()
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, BucketState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Take a token at `now` and return how long the caller must wait before
+    /// using it.
+    fn reserve_at(&self, now: Instant) -> Duration {
+        let Some(rate) = self.rate else {
+            return Duration::ZERO;
+        };
+        let mut state = self.lock_state();
+        let elapsed = now.saturating_duration_since(state.refilled_at).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * rate).min(self.burst);
+        state.refilled_at = now;
+        state.tokens -= 1.0;
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / rate)
+        }
+    }
+
+    /// Take the next slot and return how long to wait before using it, for
+    /// callers that must not wait in place and defer the call instead.
+    pub fn reserve(&self) -> Duration {
+        self.reserve_at(Instant::now())
+    }
+
+    /// Block the current thread until the call may proceed.
+    pub fn acquire(&self) {
+        let wait = self.reserve_at(Instant::now());
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Wait asynchronously until the call may proceed.
+    pub async fn acquire_async(&self) {
+        let wait = self.reserve_at(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_is_immediate_then_calls_are_spaced_by_the_rate() {
+        let limiter = RateLimiter::new(2.0, 3);
+        let start = limiter.lock_state().refilled_at;
+
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve_at(start), Duration::ZERO);
+        }
+        assert_eq!(limiter.reserve_at(start), Duration::from_millis(500));
+        assert_eq!(limiter.reserve_at(start), Duration::from_millis(1000));
+
+        // Two seconds later four tokens were refilled, two of which were
+        // already promised to the waiters above.
+        let later = start + Duration::from_secs(2);
+        assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(later), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn idle_time_refills_at_most_the_burst() {
+        let limiter = RateLimiter::new(4.0, 2);
+        let start = limiter.lock_state().refilled_at;
+        let later = start + Duration::from_secs(60);
+
+        assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(later), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn unlimited_limiter_never_waits() {
+        let limiter = RateLimiter::new(0.0, 0);
+        assert!(limiter.is_unlimited());
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire();
+        }
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+}
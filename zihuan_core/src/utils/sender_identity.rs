@@ -0,0 +1,113 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+const PSEUDONYM_HEX_LEN: usize = 16;
+
+/// Which QQ display name is stored for a sender when both are present.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SenderNamePrecedence {
+    /// Group card (群名片) first, falling back to the nickname.
+    #[default]
+    CardFirst,
+    /// Account nickname first, falling back to the group card.
+    NicknameFirst,
+}
+
+impl SenderNamePrecedence {
+    pub fn pick(&self, nickname: &str, card: &str) -> String {
+        let (preferred, fallback) = match self {
+            SenderNamePrecedence::CardFirst => (card.trim(), nickname.trim()),
+            SenderNamePrecedence::NicknameFirst => (nickname.trim(), card.trim()),
+        };
+        if preferred.is_empty() {
+            fallback.to_string()
+        } else {
+            preferred.to_string()
+        }
+    }
+}
+
+/// How sender ids and names are written to message storage.
+///
+/// With an anonymization key set, ids and names are replaced by an HMAC-SHA256
+/// pseudonym of the sender id. The same sender always maps to the same
+/// pseudonym, and only a holder of the key can recompute it for a known id to
+/// find that sender's records.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderIdentityPolicy {
+    pub precedence: SenderNamePrecedence,
+    pub anonymization_key: Option<String>,
+}
+
+impl SenderIdentityPolicy {
+    /// The anonymization key, unless unset or empty.
+    pub fn active_anonymization_key(&self) -> Option<&str> {
+        self.anonymization_key.as_deref().filter(|key| !key.is_empty())
+    }
+
+    /// The id to store for any QQ user `id`, e.g. an @-target: its pseudonym
+    /// when anonymizing, otherwise the id unchanged.
+    pub fn mask_id(&self, id: &str) -> String {
+        match self.active_anonymization_key() {
+            Some(key) => sender_pseudonym(key, id),
+            None => id.to_string(),
+        }
+    }
+
+    /// Returns the `(sender_id, sender_name)` pair to store.
+    pub fn resolve(&self, sender_id: &str, nickname: &str, card: &str) -> (String, String) {
+        match self.active_anonymization_key() {
+            Some(key) => {
+                let pseudonym = sender_pseudonym(key, sender_id);
+                let name = format!("用户-{pseudonym}");
+                (pseudonym, name)
+            }
+            None => (sender_id.to_string(), self.precedence.pick(nickname, card)),
+        }
+    }
+}
+
+pub fn sender_pseudonym(key: &str, sender_id: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(sender_id.as_bytes());
+    let digest = hex::encode(mac.finalize().into_bytes());
+    format!("anon-{}", &digest[..PSEUDONYM_HEX_LEN])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn card_first_prefers_card_and_falls_back_to_nickname() {
+        let precedence = SenderNamePrecedence::CardFirst;
+        assert_eq!(precedence.pick("小明", "群主小明"), "群主小明");
+        assert_eq!(precedence.pick("小明", "  "), "小明");
+    }
+
+    #[test]
+    fn nickname_first_prefers_nickname_and_falls_back_to_card() {
+        let precedence = SenderNamePrecedence::NicknameFirst;
+        assert_eq!(precedence.pick("小明", "群主小明"), "小明");
+        assert_eq!(precedence.pick("", "群主小明"), "群主小明");
+    }
+
+    #[test]
+    fn pseudonyms_are_stable_per_sender_and_depend_on_key() {
+        let policy = SenderIdentityPolicy {
+            precedence: SenderNamePrecedence::CardFirst,
+            anonymization_key: Some("secret".to_string()),
+        };
+
+        let first = policy.resolve("10001", "小明", "群主小明");
+        let renamed = policy.resolve("10001", "改名的小明", "");
+        let other = policy.resolve("10002", "小红", "");
+
+        assert_eq!(first, renamed);
+        assert_ne!(first.0, other.0);
+        assert!(!first.1.contains("小明"));
+        assert_ne!(first.0, sender_pseudonym("another-key", "10001"));
+    }
+}
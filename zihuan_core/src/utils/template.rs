@@ -0,0 +1,54 @@
+use crate::error::{Error, Result};
+
+/// Fill `${name}` placeholders in `template` with `lookup(name)`; whitespace
+/// around the name is ignored.
+///
+/// Single pass over `template`, so `${...}` text inside a substituted value is
+/// left as is, and an unclosed `${` is kept literally. A name `lookup` does not
+/// know is an error when `strict`, otherwise it renders as an empty string.
+pub fn render_template(template: &str, strict: bool, mut lookup: impl FnMut(&str) -> Option<String>) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("${") {
+        let Some(close) = rest[open + 2..].find('}') else {
+            break;
+        };
+        rendered.push_str(&rest[..open]);
+        let name = rest[open + 2..open + 2 + close].trim();
+        match lookup(name) {
+            Some(value) => rendered.push_str(&value),
+            None if strict => {
+                return Err(Error::ValidationError(format!("模板变量 '{name}' 未提供")));
+            }
+            None => {}
+        }
+        rest = &rest[open + 2 + close + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "name" => Some("紫幻".to_string()),
+            "echo" => Some("${name}".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn substitutes_in_one_pass_and_keeps_unclosed_openers() {
+        let rendered = render_template("${ name }/${echo}/{name}/${missing}/${name", false, lookup).unwrap();
+        assert_eq!(rendered, "紫幻/${name}/{name}//${name");
+    }
+
+    #[test]
+    fn strict_rendering_rejects_unknown_names() {
+        let err = render_template("${name}${missing}", true, lookup).unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+}
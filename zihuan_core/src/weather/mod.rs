@@ -0,0 +1,52 @@
+pub mod openweathermap;
+
+pub use openweathermap::OpenWeatherMap;
+
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Where to look up the weather. Coordinates win over a city name because
+/// many city names are ambiguous.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeatherLocation {
+    City(String),
+    Coordinates { latitude: f64, longitude: f64 },
+}
+
+/// Current conditions, normalised across providers. Temperatures are in °C
+/// and wind speed in m/s.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct WeatherReport {
+    pub location: String,
+    pub conditions: String,
+    pub temperature_c: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feels_like_c: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature_min_c: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature_max_c: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub humidity_percent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_speed_mps: Option<f64>,
+}
+
+pub trait WeatherProvider: Send + Sync {
+    fn current_weather(&self, location: &WeatherLocation) -> crate::error::Result<WeatherReport>;
+}
+
+#[derive(Clone)]
+pub struct WeatherRef {
+    pub provider: Arc<dyn WeatherProvider>,
+}
+
+impl WeatherRef {
+    pub fn new(provider: Arc<dyn WeatherProvider>) -> Self {
+        Self { provider }
+    }
+
+    pub fn current_weather(&self, location: &WeatherLocation) -> crate::error::Result<WeatherReport> {
+        self.provider.current_weather(location)
+    }
+}
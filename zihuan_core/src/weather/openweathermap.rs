@@ -0,0 +1,180 @@
+use reqwest::Client;
+use serde::Deserialize;
+use std::fmt;
+use std::time::Duration;
+
+use crate::runtime::block_async;
+
+use super::{WeatherLocation, WeatherProvider, WeatherReport};
+
+pub const OPENWEATHERMAP_DEFAULT_BASE_URL: &str = "https://api.openweathermap.org";
+
+/// Current weather from the OpenWeatherMap `data/2.5/weather` endpoint, or
+/// any service exposing the same API under `base_url`.
+pub struct OpenWeatherMap {
+    base_url: String,
+    api_key: String,
+    timeout: Duration,
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapResponse {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    weather: Vec<OpenWeatherMapCondition>,
+    main: OpenWeatherMapMain,
+    #[serde(default)]
+    wind: Option<OpenWeatherMapWind>,
+    #[serde(default)]
+    sys: Option<OpenWeatherMapSys>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapCondition {
+    description: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapMain {
+    temp: f64,
+    feels_like: Option<f64>,
+    temp_min: Option<f64>,
+    temp_max: Option<f64>,
+    humidity: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapWind {
+    speed: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapSys {
+    country: Option<String>,
+}
+
+impl OpenWeatherMap {
+    pub fn new(base_url: Option<&str>, api_key: impl Into<String>, timeout: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("failed to build reqwest client");
+        Self {
+            base_url: base_url
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .unwrap_or(OPENWEATHERMAP_DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            api_key: api_key.into(),
+            timeout,
+            client,
+        }
+    }
+
+    async fn current_weather_async(&self, location: &WeatherLocation) -> crate::error::Result<WeatherReport> {
+        let mut query = vec![
+            ("appid", self.api_key.clone()),
+            ("units", "metric".to_string()),
+            ("lang", "zh_cn".to_string()),
+        ];
+        match location {
+            WeatherLocation::City(city) => query.push(("q", city.clone())),
+            WeatherLocation::Coordinates { latitude, longitude } => {
+                query.push(("lat", latitude.to_string()));
+                query.push(("lon", longitude.to_string()));
+            }
+        }
+
+        let response = self
+            .client
+            .get(format!("{}/data/2.5/weather", self.base_url))
+            .query(&query)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::error::Error::StringError(format!(
+                "OpenWeatherMap request failed with status {}: {}",
+                status, body
+            )));
+        }
+
+        let body = response.text().await?;
+        parse_current_weather(&body)
+    }
+}
+
+fn parse_current_weather(body: &str) -> crate::error::Result<WeatherReport> {
+    let parsed: OpenWeatherMapResponse = serde_json::from_str(body)
+        .map_err(|err| crate::error::Error::StringError(format!("Failed to parse OpenWeatherMap response: {err}")))?;
+
+    let country = parsed.sys.and_then(|sys| sys.country).filter(|value| !value.is_empty());
+    let location = match country {
+        Some(country) if !parsed.name.is_empty() => format!("{}, {}", parsed.name, country),
+        _ => parsed.name,
+    };
+    let conditions = parsed
+        .weather
+        .into_iter()
+        .map(|condition| condition.description)
+        .collect::<Vec<_>>()
+        .join("，");
+
+    Ok(WeatherReport {
+        location,
+        conditions,
+        temperature_c: parsed.main.temp,
+        feels_like_c: parsed.main.feels_like,
+        temperature_min_c: parsed.main.temp_min,
+        temperature_max_c: parsed.main.temp_max,
+        humidity_percent: parsed.main.humidity,
+        wind_speed_mps: parsed.wind.and_then(|wind| wind.speed),
+    })
+}
+
+impl WeatherProvider for OpenWeatherMap {
+    fn current_weather(&self, location: &WeatherLocation) -> crate::error::Result<WeatherReport> {
+        block_async(self.current_weather_async(location))
+    }
+}
+
+impl fmt::Debug for OpenWeatherMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenWeatherMap")
+            .field("base_url", &self.base_url)
+            .field("api_key", &"<redacted>")
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_current_weather_response() {
+        let report = parse_current_weather(
+            r#"{
+                "coord": {"lon": 116.4, "lat": 39.9},
+                "weather": [{"id": 803, "main": "Clouds", "description": "多云"}],
+                "main": {"temp": 21.5, "feels_like": 20.9, "temp_min": 19.0, "temp_max": 23.2, "humidity": 48},
+                "wind": {"speed": 3.4},
+                "sys": {"country": "CN"},
+                "name": "Beijing"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(report.location, "Beijing, CN");
+        assert_eq!(report.conditions, "多云");
+        assert_eq!(report.temperature_c, 21.5);
+        assert_eq!(report.humidity_percent, Some(48.0));
+        assert_eq!(report.wind_speed_mps, Some(3.4));
+    }
+}
@@ -1,7 +1,9 @@
 use std::path::{Path, PathBuf};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use zihuan_core::error::{Error, Result};
+use zihuan_graph_engine::graph_boundary::{execute_root_graph, graph_inputs_from_args};
+use zihuan_graph_engine::graph_io::ValidationIssue;
 
 #[derive(Debug, Parser)]
 #[command(author, version, about = "Execute a zihuan graph from the command line")]
@@ -11,6 +13,25 @@ struct Args {
 
     #[arg(long, conflicts_with = "file")]
     workflow: Option<String>,
+
+    /// Validate and lint the graph without executing it
+    #[arg(long)]
+    validate: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run a graph with values for its declared inputs and print its outputs as JSON
+    Run {
+        graph: PathBuf,
+
+        /// Value for a graph input, as KEY=VALUE; repeat for several inputs
+        #[arg(long = "input", value_name = "KEY=VALUE")]
+        inputs: Vec<String>,
+    },
 }
 
 #[tokio::main]
@@ -24,8 +45,15 @@ async fn main() {
 async fn run() -> Result<()> {
     let args = Args::parse();
     init_node_registry()?;
+    if let Some(Command::Run { graph, inputs }) = &args.command {
+        return run_with_inputs(graph, inputs);
+    }
 
     let graph_path = resolve_graph_path(&args)?;
+    if args.validate {
+        let graph_def = zihuan_graph_engine::read_graph_definition_from_json(&graph_path)?;
+        return validate_graph(&graph_path, &graph_def);
+    }
     let graph_def = zihuan_graph_engine::load_graph_definition_from_json(&graph_path)?;
     let mut graph = zihuan_graph_engine::build_node_graph_from_definition(&graph_def)?;
     graph.execute()?;
@@ -33,6 +61,47 @@ async fn run() -> Result<()> {
     Ok(())
 }
 
+fn run_with_inputs(graph_path: &Path, raw_inputs: &[String]) -> Result<()> {
+    let graph_def = zihuan_graph_engine::load_graph_definition_from_json(graph_path)?;
+    let inputs = graph_inputs_from_args(&graph_def, raw_inputs)?;
+
+    let outputs = execute_root_graph(&graph_def, inputs)?;
+    let outputs: serde_json::Map<String, serde_json::Value> =
+        outputs.into_iter().map(|(name, value)| (name, value.to_json())).collect();
+    println!("{}", serde_json::to_string_pretty(&outputs)?);
+    Ok(())
+}
+
+fn validate_graph(graph_path: &Path, graph_def: &zihuan_graph_engine::NodeGraphDefinition) -> Result<()> {
+    let issues = zihuan_graph_engine::graph_io::validate_graph_definition(graph_def);
+    for issue in &issues {
+        eprintln!("[{}] {}", issue.severity, issue.message);
+    }
+    // Lint and cycle checks need a built graph, which a broken definition
+    // may not produce; the issues above already explain why.
+    let mut cycle_nodes = None;
+    match zihuan_graph_engine::build_node_graph_from_definition(graph_def) {
+        Ok(graph) => {
+            for warning in graph.lint() {
+                eprintln!("[warning] {warning}");
+            }
+            cycle_nodes = graph.detect_cycles();
+            if let Some(nodes) = &cycle_nodes {
+                eprintln!("[error] dependency cycle between nodes: {}", nodes.join(", "));
+            }
+        }
+        Err(err) => eprintln!("[error] {err}"),
+    }
+    if cycle_nodes.is_some() || issues.iter().any(ValidationIssue::is_error) {
+        return Err(Error::ValidationError(format!(
+            "Graph validation failed: {}",
+            graph_path.display()
+        )));
+    }
+    println!("Graph is valid: {}", graph_path.display());
+    Ok(())
+}
+
 fn resolve_graph_path(args: &Args) -> Result<PathBuf> {
     match (&args.file, &args.workflow) {
         (Some(path), None) => Ok(path.clone()),
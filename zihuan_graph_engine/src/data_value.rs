@@ -1,9 +1,12 @@
 use crate::object_storage::S3Ref;
+use crate::type_compatibility::TYPE_COMPATIBILITY_REGISTRY;
 use redis::{aio::Connection, AsyncCommands};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
@@ -643,8 +646,32 @@ impl DataType {
         match (self, other) {
             (DataType::Any, _) | (_, DataType::Any) => true,
             (DataType::Vector, DataType::Vector) => true,
-            (DataType::Vec(left), DataType::Vec(right)) => left.is_compatible_with(right),
-            _ => self == other,
+            (DataType::Vec(left), DataType::Vec(right)) if left.is_compatible_with(right) => true,
+            _ => self == other || TYPE_COMPATIBILITY_REGISTRY.are_compatible(self, other),
+        }
+    }
+
+    /// Type-level counterpart of [`DataValue::coerce_to`]: whether every value
+    /// of this type can be converted to `target` without loss.
+    pub fn can_coerce_to(&self, target: &DataType) -> bool {
+        match (self, target) {
+            (DataType::Vec(inner), DataType::Vec(target_inner)) => inner.can_coerce_to(target_inner),
+            _ if self.is_compatible_with(target) => true,
+            (DataType::Integer, DataType::Float) | (_, DataType::Json) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether an output port of type `source` may be linked to an input port
+    /// of this type. A `Json` input accepts any output and a `Vec<T>` input
+    /// accepts `Vec<U>` when `U` coerces to `T`; everything else must be
+    /// compatible as is. Linked values are converted with
+    /// [`DataValue::coerce_to`] before they reach the input.
+    pub fn accepts_link_from(&self, source: &DataType) -> bool {
+        match (self, source) {
+            (DataType::Json, _) => true,
+            (DataType::Vec(inner), DataType::Vec(source_inner)) => source_inner.can_coerce_to(inner),
+            _ => self.is_compatible_with(source),
         }
     }
 }
@@ -798,6 +825,22 @@ impl<'de> serde::Deserialize<'de> for DataType {
     }
 }
 
+/// JSON form of a float port value.
+///
+/// Finite values become JSON numbers and keep their float form, so `3.0` stays
+/// `3.0` rather than turning into the integer `3`. JSON has no representation for
+/// non-finite numbers, so `NaN`, `Infinity` and `-Infinity` are written as those
+/// exact strings; `json_to_data_value` parses them back into `Float`.
+pub fn float_to_json(value: f64) -> Value {
+    if value.is_nan() {
+        Value::String("NaN".to_string())
+    } else if value.is_infinite() {
+        Value::String(if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string())
+    } else {
+        serde_json::Number::from_f64(value).map(Value::Number).unwrap_or(Value::Null)
+    }
+}
+
 /// Actual data flowing through the dataflow graph
 #[derive(Clone)]
 pub enum DataValue {
@@ -880,11 +923,74 @@ impl DataValue {
         }
     }
 
+    /// Hash of the value's type and JSON form, used to compare values for
+    /// identity. Stable within a process, not across builds.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.data_type().to_string().hash(&mut hasher);
+        self.to_json().to_string().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Integer value of an `Integer`, or of a `Json` number that is an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            DataValue::Integer(value) => Some(*value),
+            DataValue::Json(value) => value.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// Float value of a `Float`, widening `Integer` and `Json` numbers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DataValue::Float(value) => Some(*value),
+            DataValue::Integer(value) => Some(*value as f64),
+            DataValue::Json(value) => value.as_f64(),
+            _ => None,
+        }
+    }
+
+    /// Text of a `String`, or of a `Json` string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DataValue::String(value) => Some(value),
+            DataValue::Json(value) => value.as_str(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            DataValue::Boolean(value) => Some(*value),
+            DataValue::Json(value) => value.as_bool(),
+            _ => None,
+        }
+    }
+
+    /// Converts the value to `target` when that loses nothing: values already
+    /// compatible with `target` are kept, `Integer` widens to `Float`, anything
+    /// becomes `Json`, and `Vec` elements are converted one by one. Returns
+    /// `None` for every other conversion.
+    pub fn coerce_to(&self, target: &DataType) -> Option<DataValue> {
+        match (self, target) {
+            (DataValue::Vec(_, items), DataType::Vec(inner)) => items
+                .iter()
+                .map(|item| item.coerce_to(inner))
+                .collect::<Option<Vec<_>>>()
+                .map(|items| DataValue::Vec(inner.clone(), items)),
+            _ if self.data_type().is_compatible_with(target) => Some(self.clone()),
+            (DataValue::Integer(value), DataType::Float) => Some(DataValue::Float(*value as f64)),
+            (_, DataType::Json) => Some(DataValue::Json(self.to_json())),
+            _ => None,
+        }
+    }
+
     pub fn to_json(&self) -> Value {
         match self {
             DataValue::String(s) => Value::String(s.clone()),
             DataValue::Integer(i) => Value::Number((*i).into()),
-            DataValue::Float(f) => serde_json::json!(f),
+            DataValue::Float(f) => float_to_json(*f),
             DataValue::Boolean(b) => Value::Bool(*b),
             DataValue::Json(v) => v.clone(),
             DataValue::Binary(bytes) => Value::Array(bytes.iter().map(|b| Value::Number((*b).into())).collect()),
@@ -1018,3 +1124,121 @@ impl Serialize for DataValue {
         self.to_json().serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_accessors_widen_but_never_truncate() {
+        assert_eq!(DataValue::Integer(42).as_i64(), Some(42));
+        assert_eq!(DataValue::Json(serde_json::json!(42)).as_i64(), Some(42));
+        assert_eq!(DataValue::Float(42.0).as_i64(), None);
+        assert_eq!(DataValue::Json(serde_json::json!(4.2)).as_i64(), None);
+        assert_eq!(DataValue::String("42".to_string()).as_i64(), None);
+
+        assert_eq!(DataValue::Float(1.5).as_f64(), Some(1.5));
+        assert_eq!(DataValue::Integer(3).as_f64(), Some(3.0));
+        assert_eq!(DataValue::Json(serde_json::json!(2.5)).as_f64(), Some(2.5));
+        assert_eq!(DataValue::Boolean(true).as_f64(), None);
+    }
+
+    #[test]
+    fn text_and_bool_accessors_accept_only_matching_values() {
+        assert_eq!(DataValue::String("hi".to_string()).as_str(), Some("hi"));
+        assert_eq!(DataValue::Json(serde_json::json!("hi")).as_str(), Some("hi"));
+        assert_eq!(DataValue::Password("secret".to_string()).as_str(), None);
+        assert_eq!(DataValue::Integer(1).as_str(), None);
+
+        assert_eq!(DataValue::Boolean(false).as_bool(), Some(false));
+        assert_eq!(DataValue::Json(serde_json::json!(true)).as_bool(), Some(true));
+        assert_eq!(DataValue::Integer(1).as_bool(), None);
+        assert_eq!(DataValue::String("true".to_string()).as_bool(), None);
+    }
+
+    #[test]
+    fn coerce_to_only_performs_safe_widening() {
+        assert!(matches!(
+            DataValue::Integer(7).coerce_to(&DataType::Float),
+            Some(DataValue::Float(value)) if value == 7.0
+        ));
+        assert!(matches!(
+            DataValue::Integer(7).coerce_to(&DataType::Integer),
+            Some(DataValue::Integer(7))
+        ));
+        assert!(matches!(
+            DataValue::Integer(7).coerce_to(&DataType::Any),
+            Some(DataValue::Integer(7))
+        ));
+        assert!(matches!(
+            DataValue::Boolean(true).coerce_to(&DataType::Json),
+            Some(DataValue::Json(Value::Bool(true)))
+        ));
+
+        let integers = DataValue::Vec(Box::new(DataType::Integer), vec![DataValue::Integer(1), DataValue::Integer(2)]);
+        match integers.coerce_to(&DataType::Vec(Box::new(DataType::Float))) {
+            Some(DataValue::Vec(ty, items)) => {
+                assert_eq!(*ty, DataType::Float);
+                assert_eq!(items.iter().filter_map(DataValue::as_f64).collect::<Vec<_>>(), vec![1.0, 2.0]);
+            }
+            other => panic!("unexpected coerced vec: {other:?}"),
+        }
+
+        assert!(DataValue::Float(7.0).coerce_to(&DataType::Integer).is_none());
+        assert!(DataValue::String("7".to_string()).coerce_to(&DataType::Integer).is_none());
+        assert!(DataValue::Json(serde_json::json!(7)).coerce_to(&DataType::Integer).is_none());
+        let strings = DataValue::Vec(Box::new(DataType::String), vec![DataValue::String("a".to_string())]);
+        assert!(strings.coerce_to(&DataType::Vec(Box::new(DataType::Float))).is_none());
+    }
+
+    #[test]
+    fn links_into_json_and_coercible_vecs_are_accepted() {
+        let vec_of = |inner: DataType| DataType::Vec(Box::new(inner));
+
+        assert!(DataType::Json.accepts_link_from(&DataType::String));
+        assert!(DataType::Json.accepts_link_from(&DataType::Integer));
+        assert!(DataType::Json.accepts_link_from(&DataType::MessageEvent));
+        assert!(DataType::Json.accepts_link_from(&vec_of(DataType::QQMessage)));
+        assert!(vec_of(DataType::Json).accepts_link_from(&vec_of(DataType::String)));
+        assert!(vec_of(DataType::Float).accepts_link_from(&vec_of(DataType::Integer)));
+        assert!(vec_of(vec_of(DataType::Json)).accepts_link_from(&vec_of(vec_of(DataType::Boolean))));
+        assert!(DataType::String.accepts_link_from(&DataType::Any));
+        assert!(vec_of(DataType::Any).accepts_link_from(&vec_of(DataType::Image)));
+
+        assert!(!DataType::String.accepts_link_from(&DataType::Json));
+        assert!(!DataType::String.accepts_link_from(&DataType::Integer));
+        assert!(!DataType::Float.accepts_link_from(&DataType::Integer));
+        assert!(!DataType::Integer.accepts_link_from(&DataType::Float));
+        assert!(!vec_of(DataType::Integer).accepts_link_from(&vec_of(DataType::Float)));
+        assert!(!vec_of(DataType::String).accepts_link_from(&vec_of(DataType::Json)));
+        assert!(!vec_of(DataType::Json).accepts_link_from(&DataType::Json));
+        assert!(!vec_of(DataType::String).accepts_link_from(&DataType::String));
+    }
+
+    #[test]
+    fn integer_valued_floats_keep_their_float_form() {
+        let json = DataValue::Float(3.0).to_json();
+        assert!(json.is_f64());
+        assert_eq!(serde_json::to_string(&json).unwrap(), "3.0");
+        assert_eq!(serde_json::to_string(&DataValue::Float(3.5).to_json()).unwrap(), "3.5");
+    }
+
+    #[test]
+    fn non_finite_floats_use_string_sentinels_that_round_trip() {
+        for (value, sentinel) in [
+            (f64::NAN, "NaN"),
+            (f64::INFINITY, "Infinity"),
+            (f64::NEG_INFINITY, "-Infinity"),
+        ] {
+            let json = DataValue::Float(value).to_json();
+            assert_eq!(json, Value::String(sentinel.to_string()));
+            assert!(serde_json::to_string(&json).is_ok());
+
+            match crate::registry::json_to_data_value(&json, &DataType::Float) {
+                Some(DataValue::Float(parsed)) if value.is_nan() => assert!(parsed.is_nan()),
+                Some(DataValue::Float(parsed)) => assert_eq!(parsed, value),
+                other => panic!("unexpected round trip for {sentinel}: {other:?}"),
+            }
+        }
+    }
+}
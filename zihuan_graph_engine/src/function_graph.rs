@@ -76,6 +76,7 @@ pub fn default_function_subgraph() -> NodeGraphDefinition {
         variables: Vec::new(),
         metadata: Default::default(),
         accepts_agent_events: false,
+        execution_mode: Default::default(),
         execution_results: HashMap::new(),
     }
 }
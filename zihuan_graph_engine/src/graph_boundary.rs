@@ -7,7 +7,10 @@ use crate::function_graph::{
     hidden_function_runtime_values_port, hidden_function_signature_port, FunctionPortDef, FUNCTION_SIGNATURE_PORT,
 };
 use crate::graph_io::{GraphPosition, GraphSize, NodeDefinition, NodeGraphDefinition};
-use crate::Port;
+use crate::registry::{build_node_graph_from_definition, json_to_data_value};
+use crate::util::function::data_value_from_json_with_declared_type;
+use crate::{DataType, DataValue, Port};
+use zihuan_core::error::{Error, Result};
 
 pub const GRAPH_INPUTS_NODE_TYPE: &str = "graph_inputs";
 pub const GRAPH_OUTPUTS_NODE_TYPE: &str = "graph_outputs";
@@ -20,7 +23,6 @@ const DEFAULT_BOUNDARY_HEIGHT: f32 = 120.0;
 
 fn agent_event_ports() -> Vec<crate::function_graph::FunctionPortDef> {
     use crate::function_graph::FunctionPortDef;
-    use crate::DataType;
     vec![
         FunctionPortDef {
             name: "content".to_string(),
@@ -204,6 +206,81 @@ pub fn root_graph_to_tool_subgraph(graph: &NodeGraphDefinition) -> NodeGraphDefi
     subgraph
 }
 
+/// Value for the graph input `port` given as text, e.g. a command-line argument.
+/// `String` ports take the text verbatim; other types parse it as JSON, and text
+/// that does not parse into the declared type is tried as a JSON string.
+pub fn graph_input_from_text(port: &FunctionPortDef, text: &str) -> Result<DataValue> {
+    if port.data_type == DataType::String {
+        return Ok(DataValue::String(text.to_string()));
+    }
+    let parsed = serde_json::from_str::<Value>(text)
+        .ok()
+        .and_then(|value| json_to_data_value(&value, &port.data_type));
+    match parsed {
+        Some(value) => Ok(value),
+        None => data_value_from_json_with_declared_type(port, &Value::String(text.to_string())),
+    }
+}
+
+/// Graph input values from `KEY=VALUE` arguments, as passed to
+/// `zihuan_graph_cli run --input`; each value is read by [`graph_input_from_text`].
+pub fn graph_inputs_from_args<S: AsRef<str>>(
+    graph: &NodeGraphDefinition,
+    args: impl IntoIterator<Item = S>,
+) -> Result<HashMap<String, DataValue>> {
+    let mut inputs = HashMap::new();
+    for arg in args {
+        let arg = arg.as_ref();
+        let Some((key, text)) = arg.split_once('=') else {
+            return Err(Error::ValidationError(format!("输入参数 '{arg}' 格式错误，应为 KEY=VALUE")));
+        };
+        let key = key.trim();
+        let port = graph
+            .graph_inputs
+            .iter()
+            .find(|port| port.name == key)
+            .ok_or_else(|| Error::ValidationError(format!("节点图没有名为 '{key}' 的输入")))?;
+        inputs.insert(key.to_string(), graph_input_from_text(port, text)?);
+    }
+    Ok(inputs)
+}
+
+/// Runs a root graph with `inputs` bound to its declared `graph_inputs` and
+/// returns the values that reached `graph_outputs`, keyed by output name.
+pub fn execute_root_graph(
+    graph: &NodeGraphDefinition,
+    inputs: HashMap<String, DataValue>,
+) -> Result<HashMap<String, DataValue>> {
+    if let Some(unknown) = inputs
+        .keys()
+        .find(|name| !graph.graph_inputs.iter().any(|port| &port.name == *name))
+    {
+        return Err(Error::ValidationError(format!("节点图没有名为 '{unknown}' 的输入")));
+    }
+
+    let mut node_graph = build_node_graph_from_definition(graph)?;
+    node_graph
+        .nodes
+        .get_mut(GRAPH_INPUTS_NODE_ID)
+        .ok_or_else(|| Error::ValidationError("节点图缺少 graph_inputs 边界节点".to_string()))?
+        .set_function_runtime_values(inputs.into())?;
+
+    let execution_result = node_graph.execute_and_capture_results();
+    if let Some(error_message) = execution_result.error_message {
+        return Err(Error::ValidationError(format!("节点图执行失败: {error_message}")));
+    }
+
+    let produced = execution_result.node_results.get(GRAPH_OUTPUTS_NODE_ID);
+    Ok(graph
+        .graph_outputs
+        .iter()
+        .filter_map(|port| {
+            let value = produced?.get(&port.name)?;
+            Some((port.name.clone(), value.clone()))
+        })
+        .collect())
+}
+
 fn upsert_boundary_node(graph: &mut NodeGraphDefinition, node_id: &str, replacement: NodeDefinition) -> bool {
     if let Some(existing) = graph.nodes.iter_mut().find(|node| node.id == node_id) {
         let position = existing.position.clone();
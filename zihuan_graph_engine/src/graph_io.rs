@@ -1,16 +1,18 @@
 /// Graph serialization and integrity management.
 ///
 /// Key functions:
-/// - `load_graph_definition_from_json` — Load graph from JSON file
+/// - `load_graph_definition_from_json` — Load graph from JSON file, rejecting corrupt definitions
+/// - `read_graph_definition_from_json` — Load without validation (editor opens stale graphs to fix them)
 /// - `save_graph_definition_to_json` — Persist to JSON
+/// - `NodeGraphDefinition::export_execution_results` / `load_execution_results` — Per-node output snapshots
 /// - `validate_graph_definition` / `auto_fix_graph_definition` — Registry validation and auto-repair
-/// - `find_cycle_node_ids` — Cycle detection (Tarjan SCC)
+/// - `find_cycles` / `find_cycle_node_ids` — Cycle detection (Tarjan SCC)
 /// - `auto_layout` — Topological hierarchical layout
 ///
 /// Handles `function` and Brain Tool subgraphs recursively.
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::Path;
 
@@ -119,6 +121,9 @@ pub struct NodeGraphDefinition {
     pub metadata: GraphMetadata,
     #[serde(default)]
     pub accepts_agent_events: bool,
+    /// How nodes that do not depend on each other are scheduled.
+    #[serde(default)]
+    pub execution_mode: crate::ExecutionMode,
     #[serde(skip)]
     pub execution_results: HashMap<String, NodeOutputFlow>,
 }
@@ -137,9 +142,9 @@ pub struct NodeDefinition {
     pub dynamic_output_ports: bool,
     pub position: Option<GraphPosition>,
     pub size: Option<GraphSize>,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted_map")]
     pub inline_values: HashMap<String, Value>,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted_map")]
     pub port_bindings: HashMap<String, PortBinding>,
     #[serde(default)]
     pub has_error: bool,
@@ -149,6 +154,16 @@ pub struct NodeDefinition {
     pub disabled: bool,
 }
 
+/// Writes map keys in sorted order so saving an unchanged graph reproduces the
+/// same file.
+fn serialize_sorted_map<S, V>(map: &HashMap<String, V>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EdgeDefinition {
     pub from_node_id: String,
@@ -171,7 +186,21 @@ pub struct GraphSize {
 
 pub type CycleEdgeKey = (String, String, String, String);
 
+/// Per-node output values as JSON, keyed by node id and then output port.
+/// Sorted so snapshots of the same run compare and diff cleanly.
+pub type ExecutionResultsSnapshot = BTreeMap<String, BTreeMap<String, Value>>;
+
+/// Load a graph and reject it when [`NodeGraphDefinition::validate`] finds
+/// errors.
 pub fn load_graph_definition_from_json(path: impl AsRef<Path>) -> Result<NodeGraphDefinition> {
+    let graph = read_graph_definition_from_json(path)?;
+    graph.validate()?;
+    Ok(graph)
+}
+
+/// Load a graph without validating it, for the editor, which reports the
+/// issues and offers to auto-fix them instead of refusing to open the file.
+pub fn read_graph_definition_from_json(path: impl AsRef<Path>) -> Result<NodeGraphDefinition> {
     let content = fs::read_to_string(path.as_ref())?;
     let mut graph: NodeGraphDefinition = serde_json::from_str(&content)?;
     refresh_port_types(&mut graph);
@@ -375,28 +404,50 @@ fn fix_function_node_input_types_from_edges(graph: &mut NodeGraphDefinition) {
 // Validation & Auto-Fix
 // ─────────────────────────────────────────────────────────────
 
+/// How serious a [`ValidationIssue`] is. Serialized as `"error"` or `"warning"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    /// The graph cannot run until this is fixed.
+    Error,
+    /// Stale but harmless, e.g. a port that no longer exists.
+    Warning,
+}
+
+impl std::fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+        })
+    }
+}
+
 /// A single compatibility issue found when validating a graph definition
 /// against the current node registry.
 #[derive(Debug, Clone)]
 pub struct ValidationIssue {
-    /// `"error"` or `"warning"`
-    pub severity: String,
+    pub severity: IssueSeverity,
     pub message: String,
 }
 
 impl ValidationIssue {
     fn error(msg: impl Into<String>) -> Self {
         Self {
-            severity: "error".into(),
+            severity: IssueSeverity::Error,
             message: msg.into(),
         }
     }
     fn warning(msg: impl Into<String>) -> Self {
         Self {
-            severity: "warning".into(),
+            severity: IssueSeverity::Warning,
             message: msg.into(),
         }
     }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
 }
 
 /// Validate a loaded `NodeGraphDefinition` against the live node registry.
@@ -496,6 +547,9 @@ fn validate_graph_definition_local(graph: &NodeGraphDefinition) -> Vec<Validatio
         }
     }
 
+    issues.extend(duplicate_node_id_problems(graph).into_iter().map(ValidationIssue::error));
+    issues.extend(edge_type_mismatch_problems(graph).into_iter().map(ValidationIssue::error));
+
     // Validate edges: node IDs and port names must exist
     for edge in &graph.edges {
         let from_ok = node_map
@@ -523,6 +577,38 @@ fn validate_graph_definition_local(graph: &NodeGraphDefinition) -> Vec<Validatio
     issues
 }
 
+fn duplicate_node_id_problems(graph: &NodeGraphDefinition) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    graph
+        .nodes
+        .iter()
+        .filter(|node| !seen.insert(node.id.as_str()) && reported.insert(node.id.as_str()))
+        .map(|node| format!("节点 ID \"{}\" 重复", node.id))
+        .collect()
+}
+
+/// Edges whose ports both exist but whose types cannot be linked.
+fn edge_type_mismatch_problems(graph: &NodeGraphDefinition) -> Vec<String> {
+    let node_map: HashMap<&str, &NodeDefinition> = graph.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    graph
+        .edges
+        .iter()
+        .filter_map(|edge| {
+            let from_node = node_map.get(edge.from_node_id.as_str())?;
+            let to_node = node_map.get(edge.to_node_id.as_str())?;
+            let from_port = from_node.output_ports.iter().find(|p| p.name == edge.from_port)?;
+            let to_port = to_node.input_ports.iter().find(|p| p.name == edge.to_port)?;
+            (!to_port.data_type.accepts_link_from(&from_port.data_type)).then(|| {
+                format!(
+                    "端口类型不匹配：\"{}\"的输出端口\"{}\" ({}) -> \"{}\"的输入端口\"{}\" ({})",
+                    from_node.name, edge.from_port, from_port.data_type, to_node.name, edge.to_port, to_port.data_type
+                )
+            })
+        })
+        .collect()
+}
+
 /// Apply automatic in-memory fixes to make the graph consistent with the
 /// current registry. Does NOT write anything to disk.
 ///
@@ -854,7 +940,13 @@ pub fn ensure_positions(graph: &mut NodeGraphDefinition) {
     }
 }
 
-fn collect_cycle_members(graph: &NodeGraphDefinition) -> (HashSet<String>, HashSet<CycleEdgeKey>) {
+/// Groups of nodes that form dependency cycles: strongly connected components
+/// of the `links` (from, to) with more than one node, or a node linked to
+/// itself. Found with Tarjan's algorithm; each group is sorted.
+pub(crate) fn cyclic_components<'a>(
+    node_ids: impl IntoIterator<Item = &'a str>,
+    links: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<Vec<String>> {
     struct TarjanState {
         next_index: usize,
         index_by_node: HashMap<String, usize>,
@@ -904,16 +996,14 @@ fn collect_cycle_members(graph: &NodeGraphDefinition) -> (HashSet<String>, HashS
         }
     }
 
+    let node_ids: Vec<&str> = node_ids.into_iter().collect();
     let mut adjacency: HashMap<String, Vec<String>> =
-        graph.nodes.iter().map(|node| (node.id.clone(), Vec::new())).collect();
+        node_ids.iter().map(|node_id| (node_id.to_string(), Vec::new())).collect();
     let mut self_loops = HashSet::new();
-    for edge in &graph.edges {
-        adjacency
-            .entry(edge.from_node_id.clone())
-            .or_default()
-            .push(edge.to_node_id.clone());
-        if edge.from_node_id == edge.to_node_id {
-            self_loops.insert(edge.from_node_id.clone());
+    for (from, to) in links {
+        adjacency.entry(from.to_string()).or_default().push(to.to_string());
+        if from == to {
+            self_loops.insert(from.to_string());
         }
     }
 
@@ -926,27 +1016,41 @@ fn collect_cycle_members(graph: &NodeGraphDefinition) -> (HashSet<String>, HashS
         components: Vec::new(),
     };
 
-    for node in &graph.nodes {
-        if !state.index_by_node.contains_key(&node.id) {
-            strong_connect(&node.id, &adjacency, &mut state);
+    for node_id in node_ids {
+        if !state.index_by_node.contains_key(node_id) {
+            strong_connect(node_id, &adjacency, &mut state);
         }
     }
 
-    let mut cycle_node_ids = HashSet::new();
-    let mut node_component_index = HashMap::new();
-    let mut cyclic_components = HashSet::new();
+    state
+        .components
+        .into_iter()
+        .filter(|component| {
+            component.len() > 1 || component.first().is_some_and(|node_id| self_loops.contains(node_id))
+        })
+        .map(|mut component| {
+            component.sort();
+            component
+        })
+        .collect()
+}
+
+/// The dependency cycles of `graph`, see [`cyclic_components`].
+pub fn find_cycles(graph: &NodeGraphDefinition) -> Vec<Vec<String>> {
+    cyclic_components(
+        graph.nodes.iter().map(|node| node.id.as_str()),
+        graph
+            .edges
+            .iter()
+            .map(|edge| (edge.from_node_id.as_str(), edge.to_node_id.as_str())),
+    )
+}
 
-    for (component_index, component) in state.components.iter().enumerate() {
-        let is_cycle_component =
-            component.len() > 1 || component.first().map(|node_id| self_loops.contains(node_id)).unwrap_or(false);
+fn collect_cycle_members(graph: &NodeGraphDefinition) -> (HashSet<String>, HashSet<CycleEdgeKey>) {
+    let mut node_component_index = HashMap::new();
+    for (component_index, component) in find_cycles(graph).into_iter().enumerate() {
         for node_id in component {
-            node_component_index.insert(node_id.clone(), component_index);
-            if is_cycle_component {
-                cycle_node_ids.insert(node_id.clone());
-            }
-        }
-        if is_cycle_component {
-            cyclic_components.insert(component_index);
+            node_component_index.insert(node_id, component_index);
         }
     }
 
@@ -956,20 +1060,18 @@ fn collect_cycle_members(graph: &NodeGraphDefinition) -> (HashSet<String>, HashS
         .filter_map(|edge| {
             let from_component = node_component_index.get(&edge.from_node_id)?;
             let to_component = node_component_index.get(&edge.to_node_id)?;
-            if from_component == to_component && cyclic_components.contains(from_component) {
-                Some((
+            (from_component == to_component).then(|| {
+                (
                     edge.from_node_id.clone(),
                     edge.from_port.clone(),
                     edge.to_node_id.clone(),
                     edge.to_port.clone(),
-                ))
-            } else {
-                None
-            }
+                )
+            })
         })
         .collect();
 
-    (cycle_node_ids, cycle_edge_keys)
+    (node_component_index.into_keys().collect(), cycle_edge_keys)
 }
 
 pub fn find_cycle_node_ids(graph: &NodeGraphDefinition) -> HashSet<String> {
@@ -1183,6 +1285,14 @@ pub fn auto_layout(graph: &mut NodeGraphDefinition) {
     }
 }
 
+/// Load a snapshot written by [`NodeGraphDefinition::export_execution_results`],
+/// e.g. to compare against [`NodeGraphDefinition::execution_results_snapshot`]
+/// of a later run.
+pub fn load_execution_results(path: impl AsRef<Path>) -> Result<ExecutionResultsSnapshot> {
+    let content = fs::read_to_string(path.as_ref())?;
+    Ok(serde_json::from_str(&content)?)
+}
+
 pub fn build_definition_from_graph(graph: &NodeGraph) -> NodeGraphDefinition {
     let mut nodes = Vec::with_capacity(graph.nodes.len());
     for (id, node) in &graph.nodes {
@@ -1222,6 +1332,7 @@ pub fn build_definition_from_graph(graph: &NodeGraph) -> NodeGraphDefinition {
         variables: Vec::new(),
         metadata: Default::default(),
         accepts_agent_events: false,
+        execution_mode: graph.execution_mode(),
         execution_results: HashMap::new(),
     }
 }
@@ -1247,6 +1358,29 @@ fn node_to_definition(id: &str, node: &dyn Node) -> NodeDefinition {
 }
 
 impl NodeGraphDefinition {
+    /// Reject the graph when [`validate_graph_definition`] reports any error,
+    /// such as duplicate node IDs, unknown node types, dangling edges or edges
+    /// between incompatible ports. The error lists every problem, one per line.
+    pub fn validate(&self) -> Result<()> {
+        let problems: Vec<String> = validate_graph_definition(self)
+            .into_iter()
+            .filter(ValidationIssue::is_error)
+            .map(|issue| issue.message)
+            .collect();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(zihuan_core::error::Error::ValidationError(format!(
+            "节点图定义无效，共 {} 个问题：\n{}",
+            problems.len(),
+            problems
+                .iter()
+                .map(|problem| format!("- {problem}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )))
+    }
+
     pub fn from_node_graph(graph: &NodeGraph) -> Self {
         build_definition_from_graph(graph)
     }
@@ -1254,4 +1388,130 @@ impl NodeGraphDefinition {
     pub fn to_json_value(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
+
+    /// `execution_results` in JSON form. Values without a JSON shape, such as
+    /// tools or model handles, appear as `DataValue::to_json` renders them.
+    pub fn execution_results_snapshot(&self) -> ExecutionResultsSnapshot {
+        self.execution_results
+            .iter()
+            .map(|(node_id, outputs)| {
+                let outputs = outputs.iter().map(|(port, value)| (port.clone(), value.to_json())).collect();
+                (node_id.clone(), outputs)
+            })
+            .collect()
+    }
+
+    /// Write [`Self::execution_results_snapshot`] to `path` as pretty JSON, for
+    /// debugging a run or keeping it as a regression snapshot.
+    pub fn export_execution_results(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.execution_results_snapshot())?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, node_type: &str, inputs: Vec<Port>, outputs: Vec<Port>) -> NodeDefinition {
+        NodeDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            node_type: node_type.to_string(),
+            input_ports: inputs,
+            output_ports: outputs,
+            dynamic_input_ports: false,
+            dynamic_output_ports: false,
+            position: None,
+            size: None,
+            inline_values: HashMap::new(),
+            port_bindings: HashMap::new(),
+            has_error: false,
+            has_cycle: false,
+            disabled: false,
+        }
+    }
+
+    fn edge(from: (&str, &str), to: (&str, &str)) -> EdgeDefinition {
+        EdgeDefinition {
+            from_node_id: from.0.to_string(),
+            from_port: from.1.to_string(),
+            to_node_id: to.0.to_string(),
+            to_port: to.1.to_string(),
+        }
+    }
+
+    fn registered_node(id: &str, node_type: &str) -> NodeDefinition {
+        let (inputs, outputs) = crate::registry::NODE_REGISTRY.get_node_ports(node_type).unwrap();
+        node(id, node_type, inputs, outputs)
+    }
+
+    #[test]
+    fn validate_lists_every_structural_problem() {
+        crate::registry::init_node_registry().unwrap();
+        let http = registered_node("http", "http_request");
+        let mut graph = NodeGraphDefinition {
+            nodes: vec![
+                http.clone(),
+                registered_node("template", "template"),
+                http,
+                node("mystery", "no_such_node_type", vec![], vec![]),
+            ],
+            edges: vec![
+                edge(("http", "status"), ("template", "variables")),
+                edge(("http", "response_body"), ("template", "template")),
+                edge(("http", "missing_port"), ("template", "variables")),
+                edge(("ghost", "output"), ("template", "template")),
+            ],
+            ..Default::default()
+        };
+
+        let message = graph.validate().unwrap_err().to_string();
+
+        assert!(message.contains("共 5 个问题"), "{message}");
+        assert!(message.contains("节点 ID \"http\" 重复"), "{message}");
+        assert!(message.contains("\"no_such_node_type\" 在注册表中不存在"), "{message}");
+        assert!(message.contains("输出端口 \"missing_port\" 不存在"), "{message}");
+        assert!(message.contains("源节点 \"ghost\" 的输出端口 \"output\" 不存在"), "{message}");
+        assert!(
+            message.contains("\"response_body\" (Json) -> \"template\"的输入端口\"template\" (String)"),
+            "{message}"
+        );
+
+        graph.nodes.truncate(2);
+        graph.edges.truncate(1);
+        assert!(graph.validate().is_ok(), "{:?}", graph.validate());
+    }
+
+    #[test]
+    fn execution_results_export_and_load_back() {
+        use crate::{DataValue, NodeOutputFlow};
+
+        let mut outputs = NodeOutputFlow::new();
+        outputs.insert("status", DataValue::Integer(200));
+        outputs.insert("body", DataValue::String("ok".to_string()));
+        outputs.insert(
+            "tags",
+            DataValue::Vec(
+                Box::new(DataType::String),
+                vec![DataValue::String("a".to_string()), DataValue::String("b".to_string())],
+            ),
+        );
+        let mut graph = NodeGraphDefinition::default();
+        graph.execution_results.insert("http".to_string(), outputs);
+
+        let path = std::env::temp_dir().join(format!("zihuan_execution_results_{}.json", std::process::id()));
+        graph.export_execution_results(&path).unwrap();
+        let loaded = load_execution_results(&path);
+        let _ = std::fs::remove_file(&path);
+        let loaded = loaded.unwrap();
+
+        assert_eq!(loaded, graph.execution_results_snapshot());
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::json!({ "http": { "body": "ok", "status": 200, "tags": ["a", "b"] } })
+        );
+    }
 }
@@ -2,9 +2,13 @@ extern crate self as zihuan_graph_engine;
 
 use serde_json::{json, Value};
 use std::backtrace::Backtrace;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc, RwLock,
 };
 
@@ -96,6 +100,26 @@ impl ExecutionResult {
     }
 }
 
+/// How [`NodeGraph`] schedules nodes that do not depend on each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    /// One node at a time, in topological order.
+    #[default]
+    Sequential,
+    /// Nodes of the same dependency level run concurrently on Tokio's
+    /// blocking pool. Only graphs with edges are levelled, and only on a
+    /// multi-threaded runtime; everywhere else nodes run sequentially.
+    Parallel,
+}
+
+/// A node that ran as part of a batch, see [`NodeGraph::run_batch`].
+struct NodeRun {
+    node_id: String,
+    inputs: Option<NodeInputFlow>,
+    outputs: NodeOutputFlow,
+}
+
 use serde::{Deserialize, Serialize};
 use zihuan_core::error::Result;
 
@@ -109,16 +133,22 @@ pub mod function_graph;
 pub mod graph_boundary;
 pub mod graph_io;
 pub mod hyperparam_store;
+pub mod lint;
+pub mod message_backend;
 pub mod message_persistence;
 pub mod message_rdb_chunking;
 pub mod message_rdb_get_group_history;
 pub mod message_rdb_get_user_history;
 pub mod message_rdb_history_common;
 pub mod message_rdb_search;
+pub mod message_record;
 pub mod message_restore;
 pub mod object_storage;
+pub mod preview_renderer;
 pub mod qq_message_list_rdb_persistence;
 pub mod registry;
+pub mod store_codec;
+pub mod type_compatibility;
 pub mod util;
 
 pub type RuntimeVariableStore = Arc<RwLock<RuntimeValueFlow>>;
@@ -128,9 +158,10 @@ pub use data_value::{DataType, DataValue};
 pub use flow::{NodeConfigFlow, NodeInputFlow, NodeOutputFlow, RuntimeValueFlow};
 #[allow(unused_imports)]
 pub use graph_io::{
-    ensure_positions, load_graph_definition_from_json, save_graph_definition_to_json, EdgeDefinition, GraphPosition,
-    NodeDefinition, NodeGraphDefinition,
+    ensure_positions, load_graph_definition_from_json, read_graph_definition_from_json, save_graph_definition_to_json,
+    EdgeDefinition, GraphPosition, NodeDefinition, NodeGraphDefinition,
 };
+pub use lint::{LintWarning, LintWarningKind};
 #[allow(unused_imports)]
 pub use node_macros::{node_input, node_input_flow, node_output, node_output_flow, return_with_node_output};
 #[allow(unused_imports)]
@@ -345,6 +376,19 @@ pub trait Node: Send + Sync {
     /// returns: output port name -> data value
     fn execute(&mut self, inputs: NodeInputFlow) -> Result<NodeOutputFlow>;
 
+    /// Async counterpart of [`Node::execute`] for nodes that wait on timers or
+    /// I/O. Defaults to the synchronous implementation.
+    ///
+    /// On a multi-threaded Tokio runtime the executor polls this future once
+    /// and only blocks on it if it is still pending, so synchronous nodes run
+    /// inline. Everywhere else it calls `execute`.
+    fn execute_async<'a>(
+        &'a mut self,
+        inputs: NodeInputFlow,
+    ) -> Pin<Box<dyn Future<Output = Result<NodeOutputFlow>> + Send + 'a>> {
+        Box::pin(async move { self.execute(inputs) })
+    }
+
     /// Called once at the start of each graph execution.
     ///
     /// Nodes with run-scoped state can reset themselves here so state persists
@@ -370,6 +414,11 @@ pub trait Node: Send + Sync {
     /// Inject a run-scoped variable store shared by the whole graph execution.
     fn set_runtime_variable_store(&mut self, _store: RuntimeVariableStore) {}
 
+    /// Inject the graph's state scope, see [`NodeGraph::state_scope`]. Nodes
+    /// that keep state between runs key it by this scope so two graphs never
+    /// share it.
+    fn set_state_scope(&mut self, _scope: &str) {}
+
     fn to_json(&self) -> Value {
         json!({
             "id": self.id(),
@@ -456,8 +505,20 @@ pub struct NodeGraph {
     stop_flag: Arc<AtomicBool>,
     execution_task_id: Option<String>,
     execution_callback: Option<Arc<dyn Fn(&str, &NodeInputFlow, &NodeOutputFlow) + Send + Sync>>,
+    execution_mode: ExecutionMode,
+    state_scope: String,
     edges: Vec<EdgeDefinition>,
     definition: Option<NodeGraphDefinition>,
+    resume_cache: HashMap<String, ResumeCacheEntry>,
+    node_outputs: OutputPool,
+}
+
+/// Outputs of a node from a previous `execute_resume` run, keyed by the
+/// fingerprint of the inputs that produced them.
+#[derive(Debug, Clone)]
+struct ResumeCacheEntry {
+    input_fingerprint: u64,
+    outputs: NodeOutputFlow,
 }
 
 impl NodeGraph {
@@ -469,8 +530,12 @@ impl NodeGraph {
             stop_flag: Arc::new(AtomicBool::new(false)),
             execution_task_id: None,
             execution_callback: None,
+            execution_mode: ExecutionMode::default(),
+            state_scope: Self::unique_state_scope(),
             edges: Vec::new(),
             definition: None,
+            resume_cache: HashMap::new(),
+            node_outputs: HashMap::new(),
         }
     }
 
@@ -481,19 +546,69 @@ impl NodeGraph {
         self.execution_callback = Some(Arc::new(callback));
     }
 
+    /// Outputs of each node that ran in the last successful `execute` or
+    /// `execute_resume`, keyed by node id. `execute_and_capture_results` hands
+    /// its results to the caller instead and leaves this empty.
+    pub fn node_outputs(&self) -> &HashMap<String, NodeOutputFlow> {
+        &self.node_outputs
+    }
+
     pub fn set_execution_task_id(&mut self, task_id: Option<String>) {
         self.execution_task_id = task_id;
     }
 
+    pub fn execution_mode(&self) -> ExecutionMode {
+        self.execution_mode
+    }
+
+    pub fn set_execution_mode(&mut self, mode: ExecutionMode) {
+        self.execution_mode = mode;
+    }
+
     pub fn set_edges(&mut self, edges: Vec<EdgeDefinition>) {
         self.edges = edges;
     }
 
     pub fn set_definition(&mut self, definition: NodeGraphDefinition) {
+        self.state_scope = Self::definition_state_scope(&definition);
         self.definition = Some(definition);
         self.reset_runtime_variables_from_definition();
     }
 
+    /// Identifies this graph to nodes that keep state between runs. Graphs
+    /// are rebuilt for every run, so the scope is derived from the definition
+    /// and stays the same across runs of an unchanged definition; a graph
+    /// without a definition gets a scope of its own.
+    pub fn state_scope(&self) -> &str {
+        &self.state_scope
+    }
+
+    /// Hashes only what decides how the graph runs, so moving, resizing or
+    /// renaming nodes in the editor keeps the state of a running graph.
+    fn definition_state_scope(definition: &NodeGraphDefinition) -> String {
+        let mut hasher = DefaultHasher::new();
+        for node in &definition.nodes {
+            (&node.id, &node.node_type, node.disabled).hash(&mut hasher);
+            serde_json::to_string(&(
+                &node.input_ports,
+                &node.output_ports,
+                node.inline_values.iter().collect::<BTreeMap<_, _>>(),
+                node.port_bindings.iter().collect::<BTreeMap<_, _>>(),
+            ))
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        }
+        for edge in &definition.edges {
+            (&edge.from_node_id, &edge.from_port, &edge.to_node_id, &edge.to_port).hash(&mut hasher);
+        }
+        format!("graph-{:016x}", hasher.finish())
+    }
+
+    fn unique_state_scope() -> String {
+        static NEXT_SCOPE: AtomicU64 = AtomicU64::new(0);
+        format!("graph-local-{}", NEXT_SCOPE.fetch_add(1, Ordering::Relaxed))
+    }
+
     /// If `port_name` on `node_id` is bound to a hyperparameter, return the HP name.
     /// Used to produce a clearer error message when the HP has no value set.
     fn port_binding_hp_name(&self, node_id: &str, port_name: &str) -> Option<String> {
@@ -530,6 +645,24 @@ impl NodeGraph {
         }
     }
 
+    /// Runs one node, awaiting `execute_async` on the current runtime for nodes
+    /// that provide it so their waits yield to other tasks.
+    fn run_node(node: &mut dyn Node, inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+        let handle = tokio::runtime::Handle::try_current()
+            .ok()
+            .filter(|handle| handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread);
+        let Some(handle) = handle else {
+            return node.execute(inputs);
+        };
+
+        let mut execution = node.execute_async(inputs);
+        let mut cx = std::task::Context::from_waker(futures_util::task::noop_waker_ref());
+        match execution.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(result) => result,
+            std::task::Poll::Pending => tokio::task::block_in_place(|| handle.block_on(execution)),
+        }
+    }
+
     fn wrap_node_error(
         node_id: &str,
         node: &dyn Node,
@@ -548,6 +681,152 @@ impl NodeGraph {
         )
     }
 
+    /// Runs one node and checks its outputs against its declared ports.
+    fn execute_node(node_id: &str, node: &mut dyn Node, inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+        let outputs =
+            Self::run_node(&mut *node, inputs).map_err(|e| Self::wrap_node_error(node_id, &*node, "execute", e))?;
+        node.validate_outputs(&outputs)
+            .map_err(|e| Self::wrap_node_error(node_id, &*node, "validate_outputs", e))?;
+        Ok(outputs)
+    }
+
+    /// Splits the topological `ordered` list into batches that may run
+    /// together. In parallel mode a batch is one dependency level, so a node
+    /// only depends on nodes of earlier batches; in sequential mode every
+    /// node is its own batch and the run order stays exactly `ordered`.
+    fn execution_batches(&self, ordered: Vec<String>, dependencies: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+        if self.execution_mode == ExecutionMode::Sequential {
+            return ordered.into_iter().map(|node_id| vec![node_id]).collect();
+        }
+
+        let mut levels: HashMap<String, usize> = HashMap::with_capacity(ordered.len());
+        let mut batches: Vec<Vec<String>> = Vec::new();
+        for node_id in ordered {
+            let level = dependencies
+                .get(&node_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|dependency| levels.get(dependency))
+                .map(|level| level + 1)
+                .max()
+                .unwrap_or(0);
+            levels.insert(node_id.clone(), level);
+            if batches.len() <= level {
+                batches.resize_with(level + 1, Vec::new);
+            }
+            batches[level].push(node_id);
+        }
+        batches
+    }
+
+    /// Runs a batch of mutually independent nodes and returns their outputs in
+    /// batch order, with a copy of their inputs when `keep_inputs` is set.
+    ///
+    /// Several nodes run concurrently when a multi-threaded runtime is
+    /// available: each node is moved into a blocking task for the duration of
+    /// the batch and put back before returning, and the first failure in batch
+    /// order is reported once all of them finished. A panic is re-raised only
+    /// after every node is back in the graph. Otherwise the nodes run one
+    /// after another and the first failure stops the batch.
+    fn run_batch(&mut self, batch: Vec<(String, NodeInputFlow)>, keep_inputs: bool) -> Result<Vec<NodeRun>> {
+        let handle = tokio::runtime::Handle::try_current()
+            .ok()
+            .filter(|handle| handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread);
+        let handle = match handle {
+            Some(handle) if batch.len() > 1 => handle,
+            _ => {
+                return batch
+                    .into_iter()
+                    .map(|(node_id, inputs)| {
+                        let node = self.nodes.get_mut(&node_id).ok_or_else(|| {
+                            zihuan_core::validation_error!("Node '{}' not found during execution", node_id)
+                        })?;
+                        let kept = keep_inputs.then(|| inputs.clone());
+                        let outputs = Self::execute_node(&node_id, node.as_mut(), inputs)?;
+                        Ok(NodeRun { node_id, inputs: kept, outputs })
+                    })
+                    .collect();
+            }
+        };
+
+        if let Some((node_id, _)) = batch.iter().find(|(node_id, _)| !self.nodes.contains_key(node_id)) {
+            return Err(zihuan_core::validation_error!("Node '{}' not found during execution", node_id));
+        }
+
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut task_nodes: HashMap<tokio::task::Id, String> = HashMap::new();
+        for (index, (node_id, inputs)) in batch.into_iter().enumerate() {
+            let Some(mut node) = self.nodes.remove(&node_id) else {
+                continue;
+            };
+            let kept = keep_inputs.then(|| inputs.clone());
+            let task_node_id = node_id.clone();
+            let task = tasks.spawn_blocking(move || {
+                // Catch a panic here rather than at the join, so the node
+                // comes back to the graph either way.
+                let outputs = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    Self::execute_node(&node_id, node.as_mut(), inputs)
+                }));
+                (index, node_id, node, kept, outputs)
+            });
+            task_nodes.insert(task.id(), task_node_id);
+        }
+        let finished = tokio::task::block_in_place(|| {
+            handle.block_on(async {
+                let mut finished = Vec::with_capacity(tasks.len());
+                while let Some(joined) = tasks.join_next().await {
+                    finished.push(joined);
+                }
+                finished
+            })
+        });
+
+        // Put every node back before surfacing a panic, so one panicking node
+        // does not take its siblings out of the graph. A task that was
+        // cancelled took its node with it; that is reported as an error.
+        let mut panic = None;
+        let mut lost_node = None;
+        let mut completed = Vec::with_capacity(finished.len());
+        for joined in finished {
+            match joined {
+                Ok((index, node_id, node, inputs, Ok(outputs))) => {
+                    self.nodes.insert(node_id.clone(), node);
+                    completed.push((index, node_id, inputs, outputs));
+                }
+                Ok((_, node_id, node, _, Err(payload))) => {
+                    self.nodes.insert(node_id, node);
+                    panic.get_or_insert(payload);
+                }
+                Err(err) => {
+                    let task_id = err.id();
+                    match err.try_into_panic() {
+                        Ok(payload) => {
+                            panic.get_or_insert(payload);
+                        }
+                        Err(_) => {
+                            lost_node.get_or_insert_with(|| task_nodes.remove(&task_id).unwrap_or_default());
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(payload) = panic {
+            std::panic::resume_unwind(payload);
+        }
+        if let Some(node_id) = lost_node {
+            return Err(zihuan_core::validation_error!(
+                "Node '{}' was cancelled during execution and is no longer in the graph",
+                node_id
+            ));
+        }
+        completed.sort_by_key(|(index, ..)| *index);
+
+        completed
+            .into_iter()
+            .map(|(_, node_id, inputs, outputs)| outputs.map(|outputs| NodeRun { node_id, inputs, outputs }))
+            .collect()
+    }
+
     pub fn set_runtime_variable_store(&mut self, store: RuntimeVariableStore) {
         self.runtime_variable_store = store.clone();
         for node in self.nodes.values_mut() {
@@ -600,10 +879,12 @@ impl NodeGraph {
 
     fn prepare_for_execution(&mut self) -> Result<()> {
         self.stop_flag.store(false, Ordering::Relaxed);
+        self.node_outputs.clear();
         self.reset_runtime_variables_from_definition();
 
         for (node_id, node) in self.nodes.iter_mut() {
             node.set_runtime_variable_store(self.runtime_variable_store.clone());
+            node.set_state_scope(&self.state_scope);
             node.on_graph_start().map_err(|e| {
                 let node_ref: &dyn Node = node.as_ref();
                 zihuan_core::validation_error!(
@@ -710,12 +991,13 @@ impl NodeGraph {
         }
 
         if ordered.len() != self.nodes.len() {
-            return Err(zihuan_core::error::Error::ValidationError(
-                "Cycle detected in node dependencies".to_string(),
-            ));
+            return Err(Self::cycle_error(&dependents));
         }
 
         let mut data_pool: HashMap<String, DataValue> = HashMap::new();
+        // Output key -> node that produced it, to regroup the pool per node at the end.
+        let mut produced_by: HashMap<String, String> = HashMap::new();
+        let mut node_outputs: OutputPool = HashMap::new();
         for node_id in ordered {
             if self.is_node_disabled(&node_id) {
                 continue;
@@ -740,11 +1022,11 @@ impl NodeGraph {
                 .nodes
                 .get_mut(&node_id)
                 .ok_or_else(|| zihuan_core::validation_error!("Node '{}' not found during execution", node_id))?;
-            let outputs = node
-                .execute(inputs)
+            let outputs = Self::run_node(node.as_mut(), inputs)
                 .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "execute", e))?;
             node.validate_outputs(&outputs)
                 .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "validate_outputs", e))?;
+            node_outputs.entry(node_id.clone()).or_default();
             for (key, value) in outputs.into_inner() {
                 if data_pool.contains_key(&key) {
                     return Err(zihuan_core::validation_error!(
@@ -753,13 +1035,53 @@ impl NodeGraph {
                         node_id
                     ));
                 }
+                produced_by.insert(key.clone(), node_id.clone());
                 data_pool.insert(key, value);
             }
         }
 
+        for (key, value) in data_pool {
+            if let Some(node_id) = produced_by.remove(&key) {
+                node_outputs.entry(node_id).or_default().insert(key, value);
+            }
+        }
+        self.node_outputs = node_outputs;
         Ok(())
     }
 
+    /// Execute the graph, reusing the outputs of nodes whose inputs are unchanged
+    /// since the previous `execute_resume` call. Only nodes that failed, never
+    /// ran, or received different inputs are executed again.
+    ///
+    /// Graphs without edges have no stable per-node inputs to compare, so they
+    /// always run in full.
+    pub fn execute_resume(&mut self) -> Result<()> {
+        if self.edges.is_empty() {
+            self.resume_cache.clear();
+            return self.execute();
+        }
+
+        self.prepare_for_execution()?;
+        self.execute_with_edges_inner(true)
+    }
+
+    /// Forget the outputs remembered by `execute_resume` so the next run starts from scratch.
+    pub fn clear_resume_cache(&mut self) {
+        self.resume_cache.clear();
+    }
+
+    fn input_fingerprint(inputs: &NodeInputFlow) -> u64 {
+        let mut entries: Vec<(&String, &DataValue)> = inputs.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut hasher = DefaultHasher::new();
+        for (name, value) in entries {
+            name.hash(&mut hasher);
+            value.fingerprint().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
     /// Execute the graph and capture results for each node
     pub fn execute_and_capture_results(&mut self) -> ExecutionResult {
         let mut node_results: HashMap<String, NodeOutputFlow> = HashMap::new();
@@ -890,9 +1212,7 @@ impl NodeGraph {
         }
 
         if ordered.len() != self.nodes.len() {
-            return Err(zihuan_core::error::Error::ValidationError(
-                "Cycle detected in node dependencies".to_string(),
-            ));
+            return Err(Self::cycle_error(&dependents));
         }
 
         let mut data_pool: HashMap<String, DataValue> = HashMap::new();
@@ -927,8 +1247,7 @@ impl NodeGraph {
                 None
             };
 
-            let outputs = node
-                .execute(inputs.clone())
+            let outputs = Self::run_node(node.as_mut(), inputs.clone())
                 .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "execute", e))?;
             node.validate_outputs(&outputs)
                 .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "validate_outputs", e))?;
@@ -961,6 +1280,10 @@ impl NodeGraph {
     }
 
     fn execute_with_edges(&mut self) -> Result<()> {
+        self.execute_with_edges_inner(false)
+    }
+
+    fn execute_with_edges_inner(&mut self, resume: bool) -> Result<()> {
         let (connected_nodes, dependents, dependencies, input_sources) = self.build_edge_maps()?;
 
         if connected_nodes.is_empty() {
@@ -1003,9 +1326,7 @@ impl NodeGraph {
         }
 
         if ordered.len() != self.nodes.len() {
-            return Err(zihuan_core::error::Error::ValidationError(
-                "Cycle detected in node dependencies".to_string(),
-            ));
+            return Err(Self::cycle_error(&dependents));
         }
 
         for node_id in &connected_nodes {
@@ -1041,58 +1362,72 @@ impl NodeGraph {
         }
 
         let mut data_pool: OutputPool = HashMap::new();
-        for node_id in ordered {
-            if !connected_nodes.contains(&node_id) {
-                continue;
-            }
-            if self.is_node_disabled(&node_id) {
-                continue;
-            }
-            let inputs = {
-                let node = self
-                    .nodes
-                    .get(&node_id)
-                    .ok_or_else(|| zihuan_core::validation_error!("Node '{}' not found during execution", node_id))?;
-                self.collect_inputs_with_edges_if_available(
-                    node.as_ref(),
-                    &data_pool,
-                    &input_sources,
-                    &node_id,
-                    self.inline_values.get(&node_id),
-                )?
-            };
+        for batch in self.execution_batches(ordered, &dependencies) {
+            let mut pending = Vec::with_capacity(batch.len());
+            let mut fingerprints = Vec::with_capacity(batch.len());
+            for node_id in batch {
+                if !connected_nodes.contains(&node_id) {
+                    continue;
+                }
+                if self.is_node_disabled(&node_id) {
+                    continue;
+                }
+                let inputs = {
+                    let node = self.nodes.get(&node_id).ok_or_else(|| {
+                        zihuan_core::validation_error!("Node '{}' not found during execution", node_id)
+                    })?;
+                    self.collect_inputs_with_edges_if_available(
+                        node.as_ref(),
+                        &data_pool,
+                        &input_sources,
+                        &node_id,
+                        self.inline_values.get(&node_id),
+                    )?
+                };
+
+                let Some(inputs) = inputs else {
+                    continue;
+                };
+
+                let fingerprint = resume.then(|| Self::input_fingerprint(&inputs));
+                if let Some(fingerprint) = fingerprint {
+                    let cached = self
+                        .resume_cache
+                        .get(&node_id)
+                        .filter(|entry| entry.input_fingerprint == fingerprint)
+                        .map(|entry| entry.outputs.clone());
+                    if let Some(outputs) = cached {
+                        self.insert_outputs(&mut data_pool, &node_id, outputs);
+                        continue;
+                    }
+                }
 
-            let Some(inputs) = inputs else {
-                continue;
-            };
+                pending.push((node_id, inputs));
+                fingerprints.push(fingerprint);
+            }
 
-            let inputs_clone = if self.execution_callback.is_some() {
-                Some(inputs.clone())
-            } else {
-                None
-            };
-            let outputs = {
-                let node = self
-                    .nodes
-                    .get_mut(&node_id)
-                    .ok_or_else(|| zihuan_core::validation_error!("Node '{}' not found during execution", node_id))?;
-                let outputs = node
-                    .execute(inputs)
-                    .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "execute", e))?;
-                node.validate_outputs(&outputs)
-                    .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "validate_outputs", e))?;
-                outputs
-            };
+            let runs = self.run_batch(pending, self.execution_callback.is_some())?;
+            for (run, fingerprint) in runs.into_iter().zip(fingerprints) {
+                if let Some(cb) = &self.execution_callback {
+                    if let Some(inp) = &run.inputs {
+                        cb(&run.node_id, inp, &run.outputs);
+                    }
+                }
 
-            if let Some(cb) = &self.execution_callback {
-                if let Some(inp) = inputs_clone {
-                    cb(&node_id, &inp, &outputs);
+                if let Some(input_fingerprint) = fingerprint {
+                    self.resume_cache.insert(
+                        run.node_id.clone(),
+                        ResumeCacheEntry {
+                            input_fingerprint,
+                            outputs: run.outputs.clone(),
+                        },
+                    );
                 }
+                self.insert_outputs(&mut data_pool, &run.node_id, run.outputs);
             }
-
-            self.insert_outputs(&mut data_pool, &node_id, outputs);
         }
 
+        self.node_outputs = data_pool;
         Ok(())
     }
 
@@ -1142,9 +1477,7 @@ impl NodeGraph {
         }
 
         if ordered.len() != self.nodes.len() {
-            return Err(zihuan_core::error::Error::ValidationError(
-                "Cycle detected in node dependencies".to_string(),
-            ));
+            return Err(Self::cycle_error(&dependents));
         }
 
         for node_id in &connected_nodes {
@@ -1180,62 +1513,48 @@ impl NodeGraph {
         }
 
         let mut data_pool: OutputPool = HashMap::new();
-        for node_id in ordered {
-            if !connected_nodes.contains(&node_id) {
-                continue;
-            }
-            if self.is_node_disabled(&node_id) {
-                continue;
+        for batch in self.execution_batches(ordered, &dependencies) {
+            let mut pending = Vec::with_capacity(batch.len());
+            for node_id in batch {
+                if !connected_nodes.contains(&node_id) {
+                    continue;
+                }
+                if self.is_node_disabled(&node_id) {
+                    continue;
+                }
+                let inputs = {
+                    let node = self.nodes.get(&node_id).ok_or_else(|| {
+                        zihuan_core::validation_error!("Node '{}' not found during execution", node_id)
+                    })?;
+                    self.collect_inputs_with_edges_if_available(
+                        node.as_ref(),
+                        &data_pool,
+                        &input_sources,
+                        &node_id,
+                        self.inline_values.get(&node_id),
+                    )?
+                };
+
+                let Some(inputs) = inputs else {
+                    continue;
+                };
+                pending.push((node_id, inputs));
             }
-            let inputs = {
-                let node = self
-                    .nodes
-                    .get(&node_id)
-                    .ok_or_else(|| zihuan_core::validation_error!("Node '{}' not found during execution", node_id))?;
-                self.collect_inputs_with_edges_if_available(
-                    node.as_ref(),
-                    &data_pool,
-                    &input_sources,
-                    &node_id,
-                    self.inline_values.get(&node_id),
-                )?
-            };
 
-            let Some(inputs) = inputs else {
-                continue;
-            };
-
-            let inputs_clone = if self.execution_callback.is_some() {
-                Some(inputs.clone())
-            } else {
-                None
-            };
-            let outputs = {
-                let node = self
-                    .nodes
-                    .get_mut(&node_id)
-                    .ok_or_else(|| zihuan_core::validation_error!("Node '{}' not found during execution", node_id))?;
-                let outputs = node
-                    .execute(inputs.clone())
-                    .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "execute", e))?;
-                node.validate_outputs(&outputs)
-                    .map_err(|e| Self::wrap_node_error(&node_id, node.as_ref(), "validate_outputs", e))?;
-                outputs
-            };
+            for run in self.run_batch(pending, true)? {
+                let inputs = run.inputs.unwrap_or_default();
+                if let Some(cb) = &self.execution_callback {
+                    cb(&run.node_id, &inputs, &run.outputs);
+                }
 
-            if let Some(cb) = &self.execution_callback {
-                if let Some(inp) = inputs_clone {
-                    cb(&node_id, &inp, &outputs);
+                let mut result = NodeOutputFlow::from(inputs.into_inner());
+                for (key, value) in run.outputs.iter() {
+                    result.insert(key.clone(), value.clone());
                 }
-            }
+                node_results.insert(run.node_id.clone(), result);
 
-            let mut result = NodeOutputFlow::from(inputs.clone().into_inner());
-            for (key, value) in outputs.iter() {
-                result.insert(key.clone(), value.clone());
+                self.insert_outputs(&mut data_pool, &run.node_id, run.outputs);
             }
-            node_results.insert(node_id.clone(), result);
-
-            self.insert_outputs(&mut data_pool, &node_id, outputs);
         }
 
         Ok(())
@@ -1288,7 +1607,7 @@ impl NodeGraph {
                     )
                 })?;
 
-            if !from_port.data_type.is_compatible_with(&to_port.data_type) {
+            if !to_port.data_type.accepts_link_from(&from_port.data_type) {
                 return Err(zihuan_core::validation_error!(
                     "端口类型不匹配：\"{}\"的输出端口\"{}\" -> \"{}\"的输入端口\"{}\" [NODE_ERROR:{}]",
                     from_node.name(),
@@ -1341,7 +1660,14 @@ impl NodeGraph {
             if let Some(source_map) = sources.and_then(|m| m.get(&port.name)) {
                 let (from_node_id, from_port) = source_map;
                 if let Some(value) = data_pool.get(from_node_id).and_then(|from_outputs| from_outputs.get(from_port)) {
-                    inputs.insert(port.name.clone(), value.clone());
+                    // Links may widen the type (e.g. into a `Json` input), so
+                    // convert before the input is validated.
+                    let value = if port.data_type.is_compatible_with(&value.data_type()) {
+                        value.clone()
+                    } else {
+                        value.coerce_to(&port.data_type).unwrap_or_else(|| value.clone())
+                    };
+                    inputs.insert(port.name.clone(), value);
                     continue;
                 }
 
@@ -1366,6 +1692,65 @@ impl NodeGraph {
         Ok(Some(inputs))
     }
 
+    /// Node ids that form dependency cycles, sorted, or `None` when the graph is
+    /// acyclic. Follows `edges`, or matching port names for graphs without edges,
+    /// the same way `execute` orders nodes.
+    pub fn detect_cycles(&self) -> Option<Vec<String>> {
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+        if self.edges.is_empty() {
+            let mut output_producers: HashMap<String, &str> = HashMap::new();
+            for (node_id, node) in &self.nodes {
+                for port in node.output_ports() {
+                    output_producers.insert(port.name, node_id);
+                }
+            }
+            for (node_id, node) in &self.nodes {
+                for port in node.input_ports() {
+                    match output_producers.get(&port.name) {
+                        Some(producer) if *producer != node_id => {
+                            dependents.entry(producer.to_string()).or_default().push(node_id.clone());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        } else {
+            for edge in &self.edges {
+                if self.nodes.contains_key(&edge.from_node_id) && self.nodes.contains_key(&edge.to_node_id) {
+                    dependents
+                        .entry(edge.from_node_id.clone())
+                        .or_default()
+                        .push(edge.to_node_id.clone());
+                }
+            }
+        }
+
+        let cycle_nodes = Self::cycle_nodes(&dependents);
+        (!cycle_nodes.is_empty()).then_some(cycle_nodes)
+    }
+
+    /// Error for a dependency cycle found while ordering nodes for execution.
+    fn cycle_error(dependents: &HashMap<String, Vec<String>>) -> zihuan_core::error::Error {
+        zihuan_core::validation_error!(
+            "Cycle detected in node dependencies involving nodes: {}",
+            Self::cycle_nodes(dependents).join(", ")
+        )
+    }
+
+    /// Nodes on a dependency cycle of the `dependents` links (producer to
+    /// consumers), leaving out those merely downstream of one. Sorted.
+    fn cycle_nodes(dependents: &HashMap<String, Vec<String>>) -> Vec<String> {
+        let links = dependents
+            .iter()
+            .flat_map(|(from, to)| to.iter().map(move |to| (from.as_str(), to.as_str())));
+        let mut nodes: Vec<String> = crate::graph_io::cyclic_components(dependents.keys().map(String::as_str), links)
+            .into_iter()
+            .flatten()
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
     fn insert_outputs(&self, pool: &mut OutputPool, node_id: &str, outputs: NodeOutputFlow) {
         let entry = pool.entry(node_id.to_string()).or_default();
         for (key, value) in outputs.into_inner() {
@@ -1435,3 +1820,378 @@ impl Default for NodeGraph {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    struct AddNode {
+        id: String,
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl Node for AddNode {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        node_input![
+            port! { name = "in", ty = Integer, desc = "upstream value", optional },
+            port! { name = "bias", ty = Integer, desc = "added to the upstream value", optional },
+        ];
+
+        node_output![port! { name = "out", ty = Integer, desc = "in + bias" },];
+
+        fn execute(&mut self, inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            let value = |name: &str| match inputs.get(name) {
+                Some(DataValue::Integer(value)) => *value,
+                _ => 0,
+            };
+            crate::return_with_node_output![self; "out" => DataValue::Integer(value("in") + value("bias"))]
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> EdgeDefinition {
+        EdgeDefinition {
+            from_node_id: from.to_string(),
+            from_port: "out".to_string(),
+            to_node_id: to.to_string(),
+            to_port: "in".to_string(),
+        }
+    }
+
+    fn graph_of(ids: &[&str], edges: Vec<EdgeDefinition>) -> NodeGraph {
+        let mut graph = NodeGraph::new();
+        for id in ids {
+            graph
+                .add_node(Box::new(AddNode {
+                    id: id.to_string(),
+                    runs: Arc::new(AtomicUsize::new(0)),
+                }))
+                .unwrap();
+        }
+        graph.set_edges(edges);
+        graph
+    }
+
+    #[test]
+    fn node_outputs_are_kept_after_execution() {
+        let mut graph = graph_of(&["a", "b"], vec![edge("a", "b")]);
+        graph.inline_values.insert(
+            "a".to_string(),
+            NodeConfigFlow::from(HashMap::from([("bias".to_string(), DataValue::Integer(3))])),
+        );
+        graph.inline_values.insert(
+            "b".to_string(),
+            NodeConfigFlow::from(HashMap::from([("bias".to_string(), DataValue::Integer(4))])),
+        );
+
+        graph.execute().unwrap();
+
+        let out = |node_id: &str| match graph.node_outputs().get(node_id).and_then(|outputs| outputs.get("out")) {
+            Some(DataValue::Integer(value)) => *value,
+            other => panic!("unexpected output of {node_id}: {other:?}"),
+        };
+        assert_eq!(out("a"), 3);
+        assert_eq!(out("b"), 7);
+    }
+
+    #[test]
+    fn node_outputs_are_kept_for_graphs_without_edges() {
+        let mut graph = graph_of(&["a"], vec![]);
+        graph.inline_values.insert(
+            "a".to_string(),
+            NodeConfigFlow::from(HashMap::from([("bias".to_string(), DataValue::Integer(3))])),
+        );
+
+        graph.execute().unwrap();
+
+        assert!(matches!(
+            graph.node_outputs().get("a").and_then(|outputs| outputs.get("out")),
+            Some(DataValue::Integer(3))
+        ));
+    }
+
+    #[test]
+    fn state_scope_ignores_layout_but_not_inline_values() {
+        let definition = graph_of(&["a", "b"], vec![edge("a", "b")]).to_definition();
+        let scope = NodeGraph::definition_state_scope(&definition);
+
+        let mut moved = definition.clone();
+        moved.nodes[0].position = Some(graph_io::GraphPosition { x: 40.0, y: 80.0 });
+        moved.nodes[0].name = "renamed".to_string();
+        assert_eq!(NodeGraph::definition_state_scope(&moved), scope);
+
+        let mut configured = definition;
+        configured.nodes[0].inline_values.insert("bias".to_string(), json!(2));
+        assert_ne!(NodeGraph::definition_state_scope(&configured), scope);
+    }
+
+    #[test]
+    fn cycle_error_names_the_nodes_on_the_cycle() {
+        let mut graph = graph_of(
+            &["a", "b", "c", "d"],
+            vec![
+                edge("a", "b"),
+                edge("b", "c"),
+                EdgeDefinition {
+                    to_port: "bias".to_string(),
+                    ..edge("c", "b")
+                },
+                edge("c", "d"),
+            ],
+        );
+
+        let err = graph.execute().unwrap_err().to_string();
+
+        assert!(err.contains("involving nodes: b, c"), "{err}");
+        assert!(graph.node_outputs().is_empty());
+    }
+
+    #[test]
+    fn detect_cycles_reports_cycle_members_or_none() {
+        let acyclic = graph_of(&["a", "b", "c"], vec![edge("a", "b"), edge("b", "c")]);
+        assert_eq!(acyclic.detect_cycles(), None);
+
+        let cyclic = graph_of(
+            &["a", "b", "c", "d"],
+            vec![
+                edge("a", "b"),
+                edge("b", "c"),
+                EdgeDefinition {
+                    to_port: "bias".to_string(),
+                    ..edge("c", "b")
+                },
+                edge("c", "d"),
+            ],
+        );
+        assert_eq!(cyclic.detect_cycles(), Some(vec!["b".to_string(), "c".to_string()]));
+
+        let self_loop = graph_of(&["a"], vec![edge("a", "a")]);
+        assert_eq!(self_loop.detect_cycles(), Some(vec!["a".to_string()]));
+    }
+
+    struct JsonSinkNode {
+        id: String,
+    }
+
+    impl Node for JsonSinkNode {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        node_input![port! { name = "in", ty = Json, desc = "any upstream value" },];
+
+        node_output![port! { name = "out", ty = Json, desc = "the received value" },];
+
+        fn execute(&mut self, inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+            let value = inputs.get("in").cloned().unwrap_or(DataValue::Json(Value::Null));
+            crate::return_with_node_output![self; "out" => value]
+        }
+    }
+
+    #[test]
+    fn integer_output_links_into_json_input_as_json() {
+        let mut graph = graph_of(&["a"], vec![edge("a", "sink")]);
+        graph.add_node(Box::new(JsonSinkNode { id: "sink".to_string() })).unwrap();
+        graph.inline_values.insert(
+            "a".to_string(),
+            NodeConfigFlow::from(HashMap::from([("bias".to_string(), DataValue::Integer(5))])),
+        );
+
+        graph.execute().unwrap();
+
+        assert!(matches!(
+            graph.node_outputs().get("sink").and_then(|outputs| outputs.get("out")),
+            Some(DataValue::Json(value)) if value == &json!(5)
+        ));
+    }
+
+    #[test]
+    fn json_output_into_integer_input_is_still_rejected() {
+        let mut graph = graph_of(&["b"], vec![]);
+        graph.add_node(Box::new(JsonSinkNode { id: "sink".to_string() })).unwrap();
+        graph.set_edges(vec![EdgeDefinition {
+            from_node_id: "sink".to_string(),
+            from_port: "out".to_string(),
+            to_node_id: "b".to_string(),
+            to_port: "in".to_string(),
+        }]);
+
+        let err = graph.execute().unwrap_err().to_string();
+
+        assert!(err.contains("端口类型不匹配"), "{err}");
+    }
+
+    #[test]
+    fn resume_only_reruns_nodes_whose_inputs_changed() {
+        let runs: HashMap<&str, Arc<AtomicUsize>> = ["a", "b", "c"]
+            .into_iter()
+            .map(|id| (id, Arc::new(AtomicUsize::new(0))))
+            .collect();
+        let mut graph = NodeGraph::new();
+        for (id, counter) in &runs {
+            graph
+                .add_node(Box::new(AddNode {
+                    id: id.to_string(),
+                    runs: Arc::clone(counter),
+                }))
+                .unwrap();
+        }
+        graph.set_edges(vec![edge("a", "b"), edge("b", "c")]);
+
+        graph.execute_resume().unwrap();
+        let counts = || {
+            runs.iter()
+                .map(|(id, c)| (*id, c.load(Ordering::SeqCst)))
+                .collect::<HashMap<_, _>>()
+        };
+        assert_eq!(counts(), HashMap::from([("a", 1), ("b", 1), ("c", 1)]));
+
+        graph.execute_resume().unwrap();
+        assert_eq!(counts(), HashMap::from([("a", 1), ("b", 1), ("c", 1)]));
+
+        graph.inline_values.insert(
+            "b".to_string(),
+            NodeConfigFlow::from(HashMap::from([("bias".to_string(), DataValue::Integer(2))])),
+        );
+        graph.execute_resume().unwrap();
+        assert_eq!(counts(), HashMap::from([("a", 1), ("b", 2), ("c", 2)]));
+    }
+
+    /// Sleeps briefly while tracking how many of its kind run at once.
+    struct SlowNode {
+        id: String,
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    impl Node for SlowNode {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        node_input![];
+
+        node_output![port! { name = "out", ty = Integer, desc = "always 1" },];
+
+        fn execute(&mut self, _inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            crate::return_with_node_output![self; "out" => DataValue::Integer(1)]
+        }
+    }
+
+    /// Two slow branches `a` and `b` joined by `sum`.
+    fn wide_graph(mode: ExecutionMode, peak: &Arc<AtomicUsize>) -> NodeGraph {
+        let mut graph = graph_of(&["sum"], vec![]);
+        let running = Arc::new(AtomicUsize::new(0));
+        for id in ["a", "b"] {
+            graph
+                .add_node(Box::new(SlowNode {
+                    id: id.to_string(),
+                    running: Arc::clone(&running),
+                    peak: Arc::clone(peak),
+                }))
+                .unwrap();
+        }
+        graph.set_edges(vec![
+            edge("a", "sum"),
+            EdgeDefinition {
+                to_port: "bias".to_string(),
+                ..edge("b", "sum")
+            },
+        ]);
+        graph.set_execution_mode(mode);
+        graph
+    }
+
+    fn sum_output(outputs: &HashMap<String, NodeOutputFlow>) -> Option<i64> {
+        match outputs.get("sum")?.get("out")? {
+            DataValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn parallel_mode_runs_independent_branches_together() {
+        for (mode, expected_peak) in [(ExecutionMode::Sequential, 1), (ExecutionMode::Parallel, 2)] {
+            let peak = Arc::new(AtomicUsize::new(0));
+            let mut graph = wide_graph(mode, &peak);
+
+            graph.execute().unwrap();
+            assert_eq!(peak.load(Ordering::SeqCst), expected_peak, "{mode:?}");
+            assert_eq!(sum_output(graph.node_outputs()), Some(2), "{mode:?}");
+
+            let result = graph.execute_and_capture_results();
+            assert_eq!(result.error_message, None, "{mode:?}");
+            assert_eq!(sum_output(&result.node_results), Some(2), "{mode:?}");
+            assert!(graph.node_outputs().is_empty(), "{mode:?}");
+            assert_eq!(graph.nodes.len(), 3, "{mode:?}");
+        }
+    }
+
+    struct PanicNode {
+        id: String,
+    }
+
+    impl Node for PanicNode {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        node_input![];
+
+        node_output![port! { name = "out", ty = Integer, desc = "never produced" },];
+
+        fn execute(&mut self, _inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+            panic!("{} panicked", self.id);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn parallel_panic_keeps_every_node_in_the_graph() {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut graph = wide_graph(ExecutionMode::Parallel, &peak);
+        graph.nodes.insert("b".to_string(), Box::new(PanicNode { id: "b".to_string() }));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| graph.execute()));
+
+        assert!(result.is_err(), "{result:?}");
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        let mut ids: Vec<&str> = graph.nodes.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["a", "b", "sum"]);
+    }
+
+    #[test]
+    fn parallel_mode_without_a_runtime_runs_sequentially() {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut graph = wide_graph(ExecutionMode::Parallel, &peak);
+
+        graph.execute().unwrap();
+
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        assert_eq!(sum_output(graph.node_outputs()), Some(2));
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{DataType, NodeGraph};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintWarningKind {
+    /// An output port that no edge (or, without edges, no input port of the same name) consumes.
+    UnusedOutput,
+    /// A node that will never run because it is not wired into the graph or one of its
+    /// required inputs comes from a node that never runs.
+    UnreachableNode,
+    /// A link whose values are converted between numeric types in a way that may lose
+    /// precision, e.g. a `Float` output into an `Integer` input.
+    LossyCoercion,
+}
+
+impl LintWarningKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintWarningKind::UnusedOutput => "unused_output",
+            LintWarningKind::UnreachableNode => "unreachable_node",
+            LintWarningKind::LossyCoercion => "lossy_coercion",
+        }
+    }
+}
+
+/// A non-fatal finding from [`NodeGraph::lint`]; the graph still executes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintWarning {
+    pub kind: LintWarningKind,
+    pub node_id: String,
+    pub port: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.kind.as_str(), self.message)
+    }
+}
+
+impl NodeGraph {
+    /// Collects authoring warnings that `execute` would not reject. Warnings are
+    /// sorted by node id so the output is stable across runs.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let mut warnings = Vec::new();
+        let runnable = self.lint_runnable_nodes();
+
+        let mut node_ids: Vec<&String> = self.nodes.keys().collect();
+        node_ids.sort();
+
+        for node_id in &node_ids {
+            if self.is_node_disabled(node_id) || runnable.contains(*node_id) {
+                continue;
+            }
+            let node = &self.nodes[*node_id];
+            warnings.push(LintWarning {
+                kind: LintWarningKind::UnreachableNode,
+                node_id: (*node_id).clone(),
+                port: None,
+                message: format!(
+                    "节点 \"{}\" ({}) 不会被执行：未连接到图中或其必要输入来自不会执行的节点",
+                    node.name(),
+                    node_id
+                ),
+            });
+        }
+
+        let consumed = self.lint_consumed_outputs();
+        for node_id in &node_ids {
+            if self.is_node_disabled(node_id) {
+                continue;
+            }
+            let node = &self.nodes[*node_id];
+            for port in node.output_ports() {
+                if port.hidden || consumed.contains(&((*node_id).clone(), port.name.clone())) {
+                    continue;
+                }
+                warnings.push(LintWarning {
+                    kind: LintWarningKind::UnusedOutput,
+                    node_id: (*node_id).clone(),
+                    port: Some(port.name.clone()),
+                    message: format!(
+                        "节点 \"{}\" ({}) 的输出端口 \"{}\" 没有被任何节点使用",
+                        node.name(),
+                        node_id,
+                        port.name
+                    ),
+                });
+            }
+        }
+
+        warnings.extend(self.lint_lossy_coercions());
+        warnings
+    }
+
+    /// One warning per link between enabled nodes whose conversion may lose
+    /// precision, attached to the receiving input and ordered by node id.
+    fn lint_lossy_coercions(&self) -> Vec<LintWarning> {
+        let mut warnings: Vec<LintWarning> = self
+            .edges
+            .iter()
+            .filter(|edge| !self.is_node_disabled(&edge.from_node_id) && !self.is_node_disabled(&edge.to_node_id))
+            .filter_map(|edge| {
+                let source = self.nodes.get(&edge.from_node_id)?;
+                let target = self.nodes.get(&edge.to_node_id)?;
+                let source_type = source
+                    .output_ports()
+                    .into_iter()
+                    .find(|port| port.name == edge.from_port)?
+                    .data_type;
+                let target_type = target
+                    .input_ports()
+                    .into_iter()
+                    .find(|port| port.name == edge.to_port)?
+                    .data_type;
+                if !target_type.accepts_link_from(&source_type) || !is_lossy_coercion(&source_type, &target_type) {
+                    return None;
+                }
+                Some(LintWarning {
+                    kind: LintWarningKind::LossyCoercion,
+                    node_id: edge.to_node_id.clone(),
+                    port: Some(edge.to_port.clone()),
+                    message: format!(
+                        "节点 \"{}\" ({}) 的输入端口 \"{}\" 将 {} 类型的 \"{}.{}\" 转换为 {}，可能丢失精度",
+                        target.name(),
+                        edge.to_node_id,
+                        edge.to_port,
+                        source_type,
+                        edge.from_node_id,
+                        edge.from_port,
+                        target_type
+                    ),
+                })
+            })
+            .collect();
+        warnings.sort_by(|left, right| left.node_id.cmp(&right.node_id));
+        warnings
+    }
+
+    fn lint_consumed_outputs(&self) -> HashSet<(String, String)> {
+        if !self.edges.is_empty() {
+            return self
+                .edges
+                .iter()
+                .map(|edge| (edge.from_node_id.clone(), edge.from_port.clone()))
+                .collect();
+        }
+
+        // Without explicit edges, outputs are matched to inputs by port name.
+        let mut consumed = HashSet::new();
+        for (node_id, node) in &self.nodes {
+            let input_names: HashSet<String> = self
+                .nodes
+                .iter()
+                .filter(|(other_id, _)| *other_id != node_id)
+                .flat_map(|(_, other)| other.input_ports().into_iter().map(|port| port.name))
+                .collect();
+            for port in node.output_ports() {
+                if input_names.contains(&port.name) {
+                    consumed.insert((node_id.clone(), port.name));
+                }
+            }
+        }
+        consumed
+    }
+
+    fn lint_runnable_nodes(&self) -> HashSet<String> {
+        if self.edges.is_empty() {
+            return self
+                .nodes
+                .keys()
+                .filter(|node_id| !self.is_node_disabled(node_id))
+                .cloned()
+                .collect();
+        }
+
+        let mut connected: HashSet<&str> = HashSet::new();
+        let mut input_sources: HashMap<(&str, &str), &str> = HashMap::new();
+        for edge in &self.edges {
+            connected.insert(&edge.from_node_id);
+            connected.insert(&edge.to_node_id);
+            input_sources.insert((&edge.to_node_id, &edge.to_port), &edge.from_node_id);
+        }
+
+        let mut runnable: HashSet<String> = HashSet::new();
+        loop {
+            let mut changed = false;
+            for (node_id, node) in &self.nodes {
+                if runnable.contains(node_id) || !connected.contains(node_id.as_str()) || self.is_node_disabled(node_id)
+                {
+                    continue;
+                }
+                let inputs_ready =
+                    node.input_ports().iter().filter(|port| port.required).all(|port| {
+                        match input_sources.get(&(node_id.as_str(), port.name.as_str())) {
+                            Some(source) => runnable.contains(*source),
+                            None => true,
+                        }
+                    });
+                if inputs_ready {
+                    runnable.insert(node_id.clone());
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        runnable
+    }
+}
+
+/// Whether converting a `source` value to `target` may change it: `Float` to
+/// `Integer` drops the fraction and `Integer` to `Float` rounds values beyond
+/// 2^53.
+fn is_lossy_coercion(source: &DataType, target: &DataType) -> bool {
+    match (source, target) {
+        (DataType::Vec(source), DataType::Vec(target)) => is_lossy_coercion(source, target),
+        (DataType::Float, DataType::Integer) | (DataType::Integer, DataType::Float) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        node_input, node_output, DataType, DataValue, EdgeDefinition, Node, NodeInputFlow, NodeOutputFlow, Port,
+    };
+    use zihuan_core::error::Result;
+
+    struct PassNode {
+        id: String,
+    }
+
+    impl Node for PassNode {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        node_input![port! { name = "in", ty = String, desc = "input", optional },];
+
+        node_output![
+            port! { name = "out", ty = String, desc = "output" },
+            port! { name = "extra", ty = String, desc = "extra output" },
+        ];
+
+        fn execute(&mut self, _inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+            crate::return_with_node_output![self; "out" => DataValue::String(String::new())]
+        }
+    }
+
+    fn graph_with(node_ids: &[&str], edges: &[(&str, &str)]) -> NodeGraph {
+        let mut graph = NodeGraph::new();
+        for id in node_ids {
+            graph.add_node(Box::new(PassNode { id: id.to_string() })).unwrap();
+        }
+        graph.set_edges(
+            edges
+                .iter()
+                .map(|(from, to)| EdgeDefinition {
+                    from_node_id: from.to_string(),
+                    from_port: "out".to_string(),
+                    to_node_id: to.to_string(),
+                    to_port: "in".to_string(),
+                })
+                .collect(),
+        );
+        graph
+    }
+
+    #[test]
+    fn reports_output_ports_without_consumers() {
+        let graph = graph_with(&["a", "b"], &[("a", "b")]);
+        let unused: Vec<(String, Option<String>)> = graph
+            .lint()
+            .into_iter()
+            .filter(|warning| warning.kind == LintWarningKind::UnusedOutput)
+            .map(|warning| (warning.node_id, warning.port))
+            .collect();
+
+        assert_eq!(
+            unused,
+            vec![
+                ("a".to_string(), Some("extra".to_string())),
+                ("b".to_string(), Some("out".to_string())),
+                ("b".to_string(), Some("extra".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_nodes_that_are_not_wired_into_the_graph() {
+        let graph = graph_with(&["a", "b", "orphan"], &[("a", "b")]);
+        let unreachable: Vec<String> = graph
+            .lint()
+            .into_iter()
+            .filter(|warning| warning.kind == LintWarningKind::UnreachableNode)
+            .map(|warning| warning.node_id)
+            .collect();
+
+        assert_eq!(unreachable, vec!["orphan".to_string()]);
+    }
+
+    struct NumberNode {
+        id: String,
+    }
+
+    impl Node for NumberNode {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        node_input![port! { name = "ratios", ty = Vec(Float), desc = "input", optional },];
+
+        node_output![port! { name = "counts", ty = Vec(Integer), desc = "output" },];
+
+        fn execute(&mut self, _inputs: NodeInputFlow) -> Result<NodeOutputFlow> {
+            crate::return_with_node_output![self; "counts" => DataValue::Vec(Box::new(DataType::Integer), vec![])]
+        }
+    }
+
+    #[test]
+    fn reports_links_converting_integers_to_floats() {
+        let mut graph = NodeGraph::new();
+        for id in ["a", "b"] {
+            graph.add_node(Box::new(NumberNode { id: id.to_string() })).unwrap();
+        }
+        graph.set_edges(vec![EdgeDefinition {
+            from_node_id: "a".to_string(),
+            from_port: "counts".to_string(),
+            to_node_id: "b".to_string(),
+            to_port: "ratios".to_string(),
+        }]);
+
+        let lossy: Vec<(String, Option<String>)> = graph
+            .lint()
+            .into_iter()
+            .filter(|warning| warning.kind == LintWarningKind::LossyCoercion)
+            .map(|warning| (warning.node_id, warning.port))
+            .collect();
+
+        assert_eq!(lossy, vec![("b".to_string(), Some("ratios".to_string()))]);
+        assert!(is_lossy_coercion(&DataType::Float, &DataType::Integer));
+        assert!(!is_lossy_coercion(&DataType::Integer, &DataType::Json));
+    }
+}
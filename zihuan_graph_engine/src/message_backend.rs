@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use sqlx::mysql::MySqlPool;
+use sqlx::postgres::PgPool;
+use sqlx::sqlite::SqlitePool;
+use zihuan_core::data_refs::RelationalDbConnection;
+use zihuan_core::error::{Error, Result};
+
+use crate::message_record::MessageRecord;
+
+/// Escape character for `LIKE` patterns; a backslash would need different
+/// quoting in MySQL, SQLite and PostgreSQL.
+const LIKE_ESCAPE: char = '!';
+
+const INSERT_SQL: &str = "INSERT INTO message_record
+    (message_id, sender_id, sender_name, send_time, group_id, group_name, content, at_target_list, media_json, raw_message_json)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+
+type MessageRecordRow = (
+    String,
+    String,
+    String,
+    NaiveDateTime,
+    Option<String>,
+    Option<String>,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+/// Long messages are persisted as several `message_record` rows sharing one
+/// `message_id`, so `LIMIT` is applied to the distinct messages matching
+/// `filter` in the derived table, then every chunk of those messages is read
+/// back in insertion order. `filter` is always one of the fixed conditions
+/// below; values are bound as parameters.
+fn history_sql(filter: &str) -> String {
+    format!(
+        r#"
+    SELECT r.message_id, r.sender_id, r.sender_name, r.send_time, r.group_id, r.group_name,
+           r.content, r.at_target_list, r.media_json, r.raw_message_json
+    FROM message_record r
+    JOIN (
+        SELECT message_id, MAX(send_time) AS latest_send_time, MAX(id) AS latest_id
+        FROM message_record
+        WHERE {filter}
+        GROUP BY message_id
+        ORDER BY latest_send_time DESC, latest_id DESC
+        LIMIT ?
+    ) latest ON r.message_id = latest.message_id
+    ORDER BY latest.latest_send_time DESC, latest.latest_id DESC, r.id ASC
+    "#
+    )
+}
+
+/// `%keyword%` with the `LIKE` wildcards in `keyword` matched literally.
+fn contains_pattern(keyword: &str) -> String {
+    let mut pattern = String::with_capacity(keyword.len() + 2);
+    pattern.push('%');
+    for ch in keyword.chars() {
+        if matches!(ch, '%' | '_') || ch == LIKE_ESCAPE {
+            pattern.push(LIKE_ESCAPE);
+        }
+        pattern.push(ch);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Which messages a history lookup selects.
+#[derive(Debug, Clone, Copy)]
+pub enum HistoryQuery<'a> {
+    Group(&'a str),
+    Sender(&'a str),
+    /// Case-insensitive substring match on `content`, optionally only in
+    /// `group_id`.
+    Search {
+        keyword: &'a str,
+        group_id: Option<&'a str>,
+    },
+}
+
+impl HistoryQuery<'_> {
+    /// The query with `?` placeholders, and the values for all but the final
+    /// `LIMIT` placeholder.
+    pub fn to_sql(&self) -> (String, Vec<String>) {
+        match *self {
+            HistoryQuery::Group(group_id) => (history_sql("group_id = ?"), vec![group_id.to_string()]),
+            HistoryQuery::Sender(sender_id) => (history_sql("sender_id = ?"), vec![sender_id.to_string()]),
+            HistoryQuery::Search { keyword, group_id } => {
+                // `LIKE` alone is case-insensitive under MySQL's default
+                // collations but case-sensitive on PostgreSQL; lowering both
+                // sides matches the same rows on every backend.
+                let content_filter = format!("LOWER(content) LIKE LOWER(?) ESCAPE '{LIKE_ESCAPE}'");
+                let pattern = contains_pattern(keyword);
+                match group_id {
+                    Some(group_id) => (
+                        history_sql(&format!("{content_filter} AND group_id = ?")),
+                        vec![pattern, group_id.to_string()],
+                    ),
+                    None => (history_sql(&content_filter), vec![pattern]),
+                }
+            }
+        }
+    }
+}
+
+/// Database access for `message_record` history, one implementation per SQL
+/// dialect. Every backend stores the same `message_record` schema: one row per
+/// content chunk, sharing the message's `message_id`.
+#[async_trait::async_trait]
+pub trait MessageBackend: Send + Sync {
+    /// Insert the rows of one message, first chunk first.
+    async fn insert_rows(&self, rows: &[MessageRecord]) -> Result<()>;
+
+    /// The rows of the latest `limit` messages selected by `query`, newest
+    /// message first and the chunks of each message in insertion order.
+    async fn fetch_history(&self, query: &HistoryQuery<'_>, limit: i64) -> Result<Vec<MessageRecord>>;
+}
+
+/// Defines a `MessageBackend` over an sqlx pool. `$sql` adapts the shared
+/// `?`-placeholder SQL to the dialect. The rows of one message are inserted in
+/// a single transaction, so a failed insert never leaves a partial message.
+macro_rules! sql_message_backend {
+    ($name:ident, $pool:ty, $sql:expr) => {
+        pub struct $name {
+            pool: $pool,
+        }
+
+        impl $name {
+            pub fn new(pool: $pool) -> Self {
+                Self { pool }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl MessageBackend for $name {
+            async fn insert_rows(&self, rows: &[MessageRecord]) -> Result<()> {
+                let sql = $sql(INSERT_SQL);
+                let mut tx = self.pool.begin().await?;
+                for row in rows {
+                    sqlx::query(&sql)
+                        .bind(&row.message_id)
+                        .bind(&row.sender_id)
+                        .bind(&row.sender_name)
+                        .bind(row.send_time)
+                        .bind(&row.group_id)
+                        .bind(&row.group_name)
+                        .bind(&row.content)
+                        .bind(&row.at_target_list)
+                        .bind(&row.media_json)
+                        .bind(&row.raw_message_json)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                tx.commit().await?;
+                Ok(())
+            }
+
+            async fn fetch_history(&self, query: &HistoryQuery<'_>, limit: i64) -> Result<Vec<MessageRecord>> {
+                let (sql, params) = query.to_sql();
+                let sql = $sql(&sql);
+                let mut query = sqlx::query_as::<_, MessageRecordRow>(&sql);
+                for param in &params {
+                    query = query.bind(param);
+                }
+                let rows = query.bind(limit).fetch_all(&self.pool).await?;
+                Ok(rows.into_iter().map(record_from_row).collect())
+            }
+        }
+    };
+}
+
+sql_message_backend!(MySqlMessageBackend, MySqlPool, str::to_string);
+sql_message_backend!(SqliteMessageBackend, SqlitePool, str::to_string);
+sql_message_backend!(PostgresMessageBackend, PgPool, numbered_placeholders);
+
+/// The SQL backend for `connection`, sharing its pool. Fails when the
+/// connection has no pool yet.
+pub fn message_backend_for_connection(connection: &RelationalDbConnection) -> Result<Arc<dyn MessageBackend>> {
+    Ok(match connection {
+        RelationalDbConnection::MySql(mysql) => {
+            let pool = mysql
+                .pool
+                .clone()
+                .ok_or_else(|| Error::ValidationError("failed to get mysql pool for message history".to_string()))?;
+            Arc::new(MySqlMessageBackend::new(pool))
+        }
+        RelationalDbConnection::Sqlite(sqlite) => {
+            let pool = sqlite
+                .pool
+                .clone()
+                .ok_or_else(|| Error::ValidationError("failed to get sqlite pool for message history".to_string()))?;
+            Arc::new(SqliteMessageBackend::new(pool))
+        }
+    })
+}
+
+/// Rewrites `?` placeholders as PostgreSQL's `$1, $2, ...`. The shared SQL has
+/// no `?` inside string literals.
+fn numbered_placeholders(sql: &str) -> String {
+    let mut numbered = String::with_capacity(sql.len() + 16);
+    let mut index = 0;
+    for ch in sql.chars() {
+        if ch == '?' {
+            index += 1;
+            numbered.push('$');
+            numbered.push_str(&index.to_string());
+        } else {
+            numbered.push(ch);
+        }
+    }
+    numbered
+}
+
+fn record_from_row(row: MessageRecordRow) -> MessageRecord {
+    let (
+        message_id,
+        sender_id,
+        sender_name,
+        send_time,
+        group_id,
+        group_name,
+        content,
+        at_target_list,
+        media_json,
+        raw_message_json,
+    ) = row;
+    MessageRecord {
+        message_id,
+        sender_id,
+        sender_name,
+        send_time,
+        group_id,
+        group_name,
+        content,
+        at_target_list,
+        media_json,
+        raw_message_json,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postgres_placeholders_are_numbered_in_order() {
+        let (sql, params) = HistoryQuery::Search {
+            keyword: "爬山",
+            group_id: Some("9"),
+        }
+        .to_sql();
+        assert_eq!(params, vec!["%爬山%".to_string(), "9".to_string()]);
+        let sql = numbered_placeholders(&sql);
+        assert!(sql.contains("LOWER(content) LIKE LOWER($1) ESCAPE '!' AND group_id = $2"));
+        assert!(sql.contains("LIMIT $3"));
+        assert!(!sql.contains('?'));
+    }
+}
@@ -1,24 +1,33 @@
 use crate::data_value::RedisConfig;
-use crate::message_rdb_chunking::{
-    split_content_chunks, truncate_field_if_needed, truncate_optional_field_if_needed, AT_TARGET_LIST_MAX_CHARS,
-    CONTENT_MAX_CHARS, GROUP_ID_MAX_CHARS, GROUP_NAME_MAX_CHARS, MEDIA_JSON_MAX_CHARS, MESSAGE_ID_MAX_CHARS,
-    SENDER_ID_MAX_CHARS, SENDER_NAME_MAX_CHARS,
-};
+use crate::message_backend::{message_backend_for_connection, MessageBackend};
+use crate::message_rdb_chunking::message_record_rows;
+use crate::message_record::MessageRecord;
 use crate::message_restore::{
     cache_message_snapshot, register_rdb_pool, register_redis_ref, CachedMessageSnapshotPayload,
 };
+use crate::store_codec::store_codec;
 use log::{info, warn};
 use once_cell::sync::Lazy;
 use redis::AsyncCommands;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tokio::task::block_in_place;
+use zihuan_core::agent_config::qq_chat::RedisCacheCodec;
 use zihuan_core::data_refs::{MySqlConfig, RelationalDbConnection, SqliteConfig};
 use zihuan_core::error::Result;
 use zihuan_core::ims_bot_adapter::models::event_model::MessageEvent;
-use zihuan_core::ims_bot_adapter::models::message::{collect_media_records, Message};
+use zihuan_core::ims_bot_adapter::models::message::{
+    collect_media_records, AtTargetMessage, ForwardMessage, ForwardNodeMessage, Message,
+};
+use zihuan_core::utils::clock::SystemClock;
+use zihuan_core::utils::sender_identity::SenderIdentityPolicy;
 
 static LATEST_RDB_POOL: Lazy<RwLock<Option<RelationalDbConnection>>> = Lazy::new(|| RwLock::new(None));
 static LATEST_REDIS_REF: Lazy<RwLock<Option<Arc<RedisConfig>>>> = Lazy::new(|| RwLock::new(None));
+static SENDER_IDENTITY_POLICY: Lazy<RwLock<SenderIdentityPolicy>> =
+    Lazy::new(|| RwLock::new(SenderIdentityPolicy::default()));
+static REDIS_CACHE_CODEC: Lazy<RwLock<RedisCacheCodec>> = Lazy::new(|| RwLock::new(RedisCacheCodec::default()));
+static REDIS_MESSAGE_TTL: Lazy<RwLock<Option<Duration>>> = Lazy::new(|| RwLock::new(None));
 
 pub fn register_rdb_persistence_pool(pool: RelationalDbConnection) {
     if let Ok(mut guard) = LATEST_RDB_POOL.write() {
@@ -34,6 +43,45 @@ pub fn register_redis_persistence_ref(config: Arc<RedisConfig>) {
     register_redis_ref(config);
 }
 
+/// Sets how sender ids and names are written to `message_record` from now on.
+pub fn register_sender_identity_policy(policy: SenderIdentityPolicy) {
+    if let Ok(mut guard) = SENDER_IDENTITY_POLICY.write() {
+        *guard = policy;
+    }
+}
+
+/// Sets the codec used for message snapshots written to Redis from now on.
+pub fn register_redis_cache_codec(codec: RedisCacheCodec) {
+    if let Ok(mut guard) = REDIS_CACHE_CODEC.write() {
+        *guard = codec;
+    }
+}
+
+/// Sets the expiry of message snapshots written to Redis from now on; `None`
+/// keeps them until evicted by Redis itself. Sub-second TTLs round up to one
+/// second, the smallest `EXPIRE` Redis accepts.
+pub fn register_redis_message_ttl(ttl: Option<Duration>) {
+    if let Ok(mut guard) = REDIS_MESSAGE_TTL.write() {
+        *guard = ttl;
+    }
+}
+
+fn redis_message_ttl_secs() -> Option<u64> {
+    REDIS_MESSAGE_TTL.read().ok().and_then(|guard| *guard).map(expire_secs)
+}
+
+fn expire_secs(ttl: Duration) -> u64 {
+    (ttl.as_secs_f64().ceil() as u64).max(1)
+}
+
+fn redis_cache_codec() -> RedisCacheCodec {
+    REDIS_CACHE_CODEC.read().map(|guard| *guard).unwrap_or_default()
+}
+
+fn sender_identity_policy() -> SenderIdentityPolicy {
+    SENDER_IDENTITY_POLICY.read().map(|guard| guard.clone()).unwrap_or_default()
+}
+
 fn latest_rdb_pool() -> Option<RelationalDbConnection> {
     LATEST_RDB_POOL.read().ok().and_then(|guard| guard.clone())
 }
@@ -50,6 +98,99 @@ fn render_content(messages: &[Message]) -> String {
     zihuan_core::ims_bot_adapter::models::message::render_messages_readable(messages)
 }
 
+/// `(sender_id, sender_name)` to store for `event`, under the registered sender identity policy.
+pub fn resolve_event_sender(event: &MessageEvent) -> (String, String) {
+    sender_identity_policy().resolve(&event.sender.user_id.to_string(), &event.sender.nickname, &event.sender.card)
+}
+
+/// Comma-separated @-targets of `event` in message order, or `None` without any @.
+/// Targets are pseudonymized like sender ids when the policy anonymizes.
+pub fn event_at_target_list(event: &MessageEvent) -> Option<String> {
+    event_at_target_list_under(event, &sender_identity_policy())
+}
+
+/// Rendered text content of `event` as stored in `message_record.content`, with
+/// @-mentions and forwarded senders pseudonymized when the policy anonymizes.
+pub fn render_event_content(event: &MessageEvent) -> String {
+    render_event_content_under(event, &sender_identity_policy())
+}
+
+/// Segment JSON of `event` for `message_record.raw_message_json`. `None` when
+/// the policy anonymizes, since the raw segments carry every id and name.
+pub fn event_raw_message_json(event: &MessageEvent) -> Result<Option<String>> {
+    event_raw_message_json_under(event, &sender_identity_policy())
+}
+
+fn event_at_target_list_under(event: &MessageEvent, policy: &SenderIdentityPolicy) -> Option<String> {
+    let at_targets: Vec<String> = event
+        .at_target_list()
+        .iter()
+        .map(|target| mask_at_target(policy, target))
+        .collect();
+    (!at_targets.is_empty()).then(|| at_targets.join(","))
+}
+
+fn render_event_content_under(event: &MessageEvent, policy: &SenderIdentityPolicy) -> String {
+    if policy.active_anonymization_key().is_none() {
+        return render_content(&event.message_list);
+    }
+    render_content(&pseudonymize_messages(&event.message_list, policy))
+}
+
+fn event_raw_message_json_under(event: &MessageEvent, policy: &SenderIdentityPolicy) -> Result<Option<String>> {
+    if policy.active_anonymization_key().is_some() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::to_string(&event.message_list)?))
+}
+
+/// `@all` names nobody, so it is kept as is.
+fn mask_at_target(policy: &SenderIdentityPolicy, target: &str) -> String {
+    if target == "all" {
+        return target.to_string();
+    }
+    policy.mask_id(target)
+}
+
+/// Copy of `messages` with @-targets and forwarded senders replaced by their
+/// pseudonyms and forwarded nicknames dropped.
+fn pseudonymize_messages(messages: &[Message], policy: &SenderIdentityPolicy) -> Vec<Message> {
+    let mut pseudonymized = Vec::with_capacity(messages.len());
+    for message in messages {
+        let message = match message {
+            Message::At(at) => Message::At(AtTargetMessage {
+                target: at.target.as_deref().map(|target| mask_at_target(policy, target)),
+            }),
+            Message::Forward(forward) => Message::Forward(ForwardMessage {
+                id: forward.id.clone(),
+                content: forward
+                    .content
+                    .iter()
+                    .map(|node| ForwardNodeMessage {
+                        user_id: node.user_id.as_deref().map(|id| policy.mask_id(id)),
+                        nickname: None,
+                        id: node.id.clone(),
+                        content: pseudonymize_messages(&node.content, policy),
+                    })
+                    .collect(),
+            }),
+            other => other.clone(),
+        };
+        pseudonymized.push(message);
+    }
+    pseudonymized
+}
+
+/// Media records of `event` serialized for `message_record.media_json`.
+pub fn event_media_json(event: &MessageEvent) -> Result<Option<String>> {
+    let records = collect_media_records(&event.message_list);
+    if records.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(serde_json::to_string(&records)?))
+    }
+}
+
 fn persist_message_to_redis(
     message_id: &str,
     payload: &CachedMessageSnapshotPayload,
@@ -61,7 +202,8 @@ fn persist_message_to_redis(
 
     let redis_ref = Arc::clone(redis_ref);
     let message_id = message_id.to_string();
-    let payload = serde_json::to_string(payload)?;
+    let payload = store_codec(redis_cache_codec()).encode(payload)?;
+    let ttl_secs = redis_message_ttl_secs();
 
     let run = async move {
         let mut cm_guard = redis_ref.redis_cm.lock().await;
@@ -78,7 +220,14 @@ fn persist_message_to_redis(
         }
 
         if let Some(cm) = cm_guard.as_mut() {
-            let _: () = cm.set(&message_id, &payload).await?;
+            match ttl_secs {
+                Some(ttl_secs) => {
+                    let _: () = cm.set_ex(&message_id, &payload, ttl_secs).await?;
+                }
+                None => {
+                    let _: () = cm.set(&message_id, &payload).await?;
+                }
+            }
         }
 
         Ok::<(), zihuan_core::error::Error>(())
@@ -91,182 +240,35 @@ fn persist_message_to_redis(
     }
 }
 
-fn persist_message_to_rdb(event: &MessageEvent, connection: &RelationalDbConnection) -> Result<()> {
-    let raw_message_id = event.message_id.to_string();
-    let message_id =
-        truncate_field_if_needed("message_id", raw_message_id.clone(), MESSAGE_ID_MAX_CHARS, &raw_message_id);
-    let sender_id =
-        truncate_field_if_needed("sender_id", event.sender.user_id.to_string(), SENDER_ID_MAX_CHARS, &message_id);
-    let sender_name = if event.sender.card.is_empty() {
-        event.sender.nickname.clone()
-    } else {
-        event.sender.card.clone()
-    };
-    let sender_name = truncate_field_if_needed("sender_name", sender_name, SENDER_NAME_MAX_CHARS, &message_id);
-    let send_time = chrono::Local::now().naive_local().to_string();
-    let group_id = truncate_optional_field_if_needed(
-        "group_id",
-        event.group_id.map(|id| id.to_string()),
-        GROUP_ID_MAX_CHARS,
-        &message_id,
-    );
-    let group_name =
-        truncate_optional_field_if_needed("group_name", event.group_name.clone(), GROUP_NAME_MAX_CHARS, &message_id);
-    let content = render_content(&event.message_list);
-    let at_targets: Vec<String> = event
-        .message_list
-        .iter()
-        .filter_map(|message| match message {
-            Message::At(at) => Some(at.target_id()),
-            _ => None,
-        })
-        .collect();
-    let at_target_list = truncate_optional_field_if_needed(
-        "at_target_list",
-        (!at_targets.is_empty()).then(|| at_targets.join(",")),
-        AT_TARGET_LIST_MAX_CHARS,
-        &message_id,
-    );
-    let media_json = {
-        let records = collect_media_records(&event.message_list);
-        if records.is_empty() {
-            None
-        } else {
-            Some(serde_json::to_string(&records)?)
-        }
-    };
-    let media_json = truncate_optional_field_if_needed("media_json", media_json, MEDIA_JSON_MAX_CHARS, &message_id);
-    let raw_message_json = Some(serde_json::to_string(&event.message_list)?);
-    let content_chunks = split_content_chunks(&content, CONTENT_MAX_CHARS);
+fn persist_message_record(event: &MessageEvent, backend: &dyn MessageBackend) -> Result<()> {
+    let record = MessageRecord::from_event(event, &SystemClock, "")?;
+    let rows = message_record_rows(&record);
 
     info!(
         "[message_persistence] Persisting message {} (sender={}, group={:?}, chunks={}) to relational DB",
-        message_id,
-        sender_id,
-        group_id,
-        content_chunks.len()
+        record.message_id,
+        record.sender_id,
+        record.group_id,
+        rows.len()
     );
 
-    let message_id_for_bind = message_id.clone();
-    let sender_id_for_bind = sender_id.clone();
-    let sender_name_for_bind = sender_name.clone();
-    let send_time_for_bind = send_time.clone();
-    let group_id_for_bind = group_id.clone();
-    let group_name_for_bind = group_name.clone();
-    let at_target_list_for_bind = at_target_list.clone();
-    let media_json_for_bind = media_json.clone();
-    let raw_message_json_for_bind = raw_message_json.clone();
-    let content_chunks_for_bind = content_chunks.clone();
-    let result = match connection {
-        RelationalDbConnection::MySql(config) => {
-            let pool = mysql_pool(config)?.clone();
-            let run = async move {
-                for (chunk_index, content_chunk) in content_chunks_for_bind.iter().enumerate() {
-                    let chunk_at_target_list = if chunk_index == 0 {
-                        at_target_list_for_bind.as_ref()
-                    } else {
-                        None
-                    };
-                    let chunk_media_json = if chunk_index == 0 {
-                        media_json_for_bind.as_ref()
-                    } else {
-                        None
-                    };
-                    let chunk_raw_message_json = if chunk_index == 0 {
-                        raw_message_json_for_bind.as_ref()
-                    } else {
-                        None
-                    };
-
-                    sqlx::query(
-                        r#"
-                        INSERT INTO message_record
-                        (message_id, sender_id, sender_name, send_time, group_id, group_name, content, at_target_list, media_json, raw_message_json)
-                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-                        "#,
-                    )
-                    .bind(&message_id_for_bind)
-                    .bind(&sender_id_for_bind)
-                    .bind(&sender_name_for_bind)
-                    .bind(&send_time_for_bind)
-                    .bind(&group_id_for_bind)
-                    .bind(&group_name_for_bind)
-                    .bind(content_chunk)
-                    .bind(chunk_at_target_list)
-                    .bind(chunk_media_json)
-                    .bind(chunk_raw_message_json)
-                    .execute(&pool)
-                    .await?;
-                }
-
-                Ok::<(), sqlx::Error>(())
-            };
-
-            if let Ok(handle) = tokio::runtime::Handle::try_current() {
-                block_in_place(|| handle.block_on(run))
-            } else {
-                tokio::runtime::Runtime::new()?.block_on(run)
-            }
-        }
-        RelationalDbConnection::Sqlite(config) => {
-            let pool = sqlite_pool(config)?.clone();
-            let run = async move {
-                for (chunk_index, content_chunk) in content_chunks_for_bind.iter().enumerate() {
-                    let chunk_at_target_list = if chunk_index == 0 {
-                        at_target_list_for_bind.as_ref()
-                    } else {
-                        None
-                    };
-                    let chunk_media_json = if chunk_index == 0 {
-                        media_json_for_bind.as_ref()
-                    } else {
-                        None
-                    };
-                    let chunk_raw_message_json = if chunk_index == 0 {
-                        raw_message_json_for_bind.as_ref()
-                    } else {
-                        None
-                    };
-
-                    sqlx::query(
-                        r#"
-                        INSERT INTO message_record
-                        (message_id, sender_id, sender_name, send_time, group_id, group_name, content, at_target_list, media_json, raw_message_json)
-                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-                        "#,
-                    )
-                    .bind(&message_id_for_bind)
-                    .bind(&sender_id_for_bind)
-                    .bind(&sender_name_for_bind)
-                    .bind(&send_time_for_bind)
-                    .bind(&group_id_for_bind)
-                    .bind(&group_name_for_bind)
-                    .bind(content_chunk)
-                    .bind(chunk_at_target_list)
-                    .bind(chunk_media_json)
-                    .bind(chunk_raw_message_json)
-                    .execute(&pool)
-                    .await?;
-                }
-
-                Ok::<(), sqlx::Error>(())
-            };
-
-            if let Ok(handle) = tokio::runtime::Handle::try_current() {
-                block_in_place(|| handle.block_on(run))
-            } else {
-                tokio::runtime::Runtime::new()?.block_on(run)
-            }
-        }
+    let run = backend.insert_rows(&rows);
+    let result = if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        block_in_place(|| handle.block_on(run))
+    } else {
+        tokio::runtime::Runtime::new()?.block_on(run)
     };
-
     if let Err(error) = result {
         warn!(
             "[message_persistence] relational DB persist failed for message {}: {}",
-            message_id, error
+            record.message_id, error
         );
     }
+    Ok(())
+}
 
+fn persist_media_records(event: &MessageEvent, connection: &RelationalDbConnection) -> Result<()> {
+    let message_id = event.message_id.to_string();
     let media_records = collect_media_records(&event.message_list);
     if !media_records.is_empty() {
         let persist_result = match connection {
@@ -352,19 +354,25 @@ pub fn persist_message_event(
     event: &MessageEvent,
     rdb_pool: Option<&RelationalDbConnection>,
     redis_ref: Option<&Arc<RedisConfig>>,
+) -> Result<()> {
+    persist_message_event_with_history(event, rdb_pool, None, redis_ref)
+}
+
+/// Like [`persist_message_event`], but the `message_record` rows go to
+/// `history` when it is given; `rdb_pool` then only receives the media records.
+pub fn persist_message_event_with_history(
+    event: &MessageEvent,
+    rdb_pool: Option<&RelationalDbConnection>,
+    history: Option<&dyn MessageBackend>,
+    redis_ref: Option<&Arc<RedisConfig>>,
 ) -> Result<()> {
     cache_message_snapshot(event);
 
     let message_id = event.message_id.to_string();
+    // The Redis snapshot restores quoted messages for the bot, so it keeps the
+    // original segments; only `message_record` rows are pseudonymized.
     let content = render_content(&event.message_list);
-    let media_json = {
-        let records = collect_media_records(&event.message_list);
-        if records.is_empty() {
-            None
-        } else {
-            Some(serde_json::to_string(&records)?)
-        }
-    };
+    let media_json = event_media_json(event)?;
     let raw_message_json = Some(serde_json::to_string(&event.message_list)?);
     let redis_payload = CachedMessageSnapshotPayload {
         message_id: message_id.clone(),
@@ -383,9 +391,17 @@ pub fn persist_message_event(
         }
     }
 
-    if let Some(rdb_pool) = rdb_pool.cloned().or_else(latest_rdb_pool) {
+    let rdb_pool = rdb_pool.cloned().or_else(latest_rdb_pool);
+    if let Some(rdb_pool) = &rdb_pool {
         register_rdb_pool(rdb_pool.clone());
-        persist_message_to_rdb(event, &rdb_pool)?;
+    }
+    match (history, &rdb_pool) {
+        (Some(history), _) => persist_message_record(event, history)?,
+        (None, Some(rdb_pool)) => persist_message_record(event, message_backend_for_connection(rdb_pool)?.as_ref())?,
+        (None, None) => {}
+    }
+    if let Some(rdb_pool) = &rdb_pool {
+        persist_media_records(event, rdb_pool)?;
     }
 
     Ok(())
@@ -402,3 +418,73 @@ fn sqlite_pool(config: &Arc<SqliteConfig>) -> Result<&sqlx::sqlite::SqlitePool>
         zihuan_core::error::Error::ValidationError("message persistence sqlite pool is not initialized".to_string())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use zihuan_core::ims_bot_adapter::models::event_model::{MessageType, Sender};
+    use zihuan_core::ims_bot_adapter::models::message::PlainTextMessage;
+    use zihuan_core::utils::sender_identity::SenderNamePrecedence;
+
+    use super::*;
+
+    #[test]
+    fn anonymized_row_does_not_contain_any_raw_qq_id_or_name() {
+        let event = MessageEvent {
+            message_id: 42,
+            message_type: MessageType::Group,
+            sender: Sender {
+                user_id: 123456789,
+                nickname: "小明".to_string(),
+                card: "群名片小明".to_string(),
+                role: None,
+            },
+            message_list: vec![
+                Message::At(AtTargetMessage {
+                    target: Some("987654321".to_string()),
+                }),
+                Message::PlainText(PlainTextMessage { text: " 看这个".to_string() }),
+                Message::Forward(ForwardMessage {
+                    id: None,
+                    content: vec![ForwardNodeMessage {
+                        user_id: Some("555666777".to_string()),
+                        nickname: Some("小红".to_string()),
+                        id: None,
+                        content: vec![Message::PlainText(PlainTextMessage {
+                            text: "转发内容".to_string()
+                        })],
+                    }],
+                }),
+            ],
+            group_id: Some(30003),
+            group_name: None,
+            is_group_message: true,
+            time: None,
+        };
+        let policy = SenderIdentityPolicy {
+            precedence: SenderNamePrecedence::CardFirst,
+            anonymization_key: Some("secret".to_string()),
+        };
+
+        let (sender_id, sender_name) =
+            policy.resolve(&event.sender.user_id.to_string(), &event.sender.nickname, &event.sender.card);
+        let content = render_event_content_under(&event, &policy);
+        let row = format!(
+            "{sender_id} {sender_name} {content} {:?} {:?}",
+            event_at_target_list_under(&event, &policy),
+            event_raw_message_json_under(&event, &policy).unwrap()
+        );
+
+        for identity in ["123456789", "987654321", "555666777", "小明", "小红"] {
+            assert!(!row.contains(identity), "{identity} leaked into {row}");
+        }
+        assert!(content.contains("转发内容"), "{content}");
+        assert!(content.contains(&policy.mask_id("987654321")), "{content}");
+    }
+
+    #[test]
+    fn redis_ttls_round_up_to_whole_seconds() {
+        assert_eq!(expire_secs(Duration::from_millis(10)), 1);
+        assert_eq!(expire_secs(Duration::from_millis(1500)), 2);
+        assert_eq!(expire_secs(Duration::from_secs(3)), 3);
+    }
+}
@@ -1,5 +1,7 @@
 use log::warn;
 
+use crate::message_record::MessageRecord;
+
 pub const MESSAGE_ID_MAX_CHARS: usize = 64;
 pub const SENDER_ID_MAX_CHARS: usize = 64;
 pub const SENDER_NAME_MAX_CHARS: usize = 128;
@@ -62,3 +64,70 @@ pub fn split_content_chunks(content: &str, max_chars: usize) -> Vec<String> {
     chunks.push(content[chunk_start..].to_string());
     chunks
 }
+
+/// The `message_record` rows for `record`: over-long columns are truncated and
+/// the content is split into `CONTENT_MAX_CHARS` chunks, one row each. Only the
+/// first row carries the mention, media and raw message columns.
+pub fn message_record_rows(record: &MessageRecord) -> Vec<MessageRecord> {
+    let message_id = truncate_field_if_needed(
+        "message_id",
+        record.message_id.clone(),
+        MESSAGE_ID_MAX_CHARS,
+        &record.message_id,
+    );
+    let first = MessageRecord {
+        sender_id: truncate_field_if_needed("sender_id", record.sender_id.clone(), SENDER_ID_MAX_CHARS, &message_id),
+        sender_name: truncate_field_if_needed(
+            "sender_name",
+            record.sender_name.clone(),
+            SENDER_NAME_MAX_CHARS,
+            &message_id,
+        ),
+        send_time: record.send_time,
+        group_id: truncate_optional_field_if_needed(
+            "group_id",
+            record.group_id.clone(),
+            GROUP_ID_MAX_CHARS,
+            &message_id,
+        ),
+        group_name: truncate_optional_field_if_needed(
+            "group_name",
+            record.group_name.clone(),
+            GROUP_NAME_MAX_CHARS,
+            &message_id,
+        ),
+        content: String::new(),
+        at_target_list: truncate_optional_field_if_needed(
+            "at_target_list",
+            record.at_target_list.clone(),
+            AT_TARGET_LIST_MAX_CHARS,
+            &message_id,
+        ),
+        media_json: truncate_optional_field_if_needed(
+            "media_json",
+            record.media_json.clone(),
+            MEDIA_JSON_MAX_CHARS,
+            &message_id,
+        ),
+        raw_message_json: record.raw_message_json.clone(),
+        message_id,
+    };
+
+    split_content_chunks(&record.content, CONTENT_MAX_CHARS)
+        .into_iter()
+        .enumerate()
+        .map(|(chunk_index, content)| {
+            if chunk_index == 0 {
+                MessageRecord { content, ..first.clone() }
+            } else {
+                MessageRecord {
+                    content,
+                    at_target_list: None,
+                    media_json: None,
+                    raw_message_json: None,
+                    ..first.clone()
+                }
+            }
+        })
+        .collect()
+}
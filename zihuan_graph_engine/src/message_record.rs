@@ -0,0 +1,141 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use zihuan_core::error::Result;
+use zihuan_core::ims_bot_adapter::models::event_model::MessageEvent;
+use zihuan_core::utils::clock::Clock;
+
+use crate::message_persistence::{
+    event_at_target_list, event_media_json, event_raw_message_json, render_event_content, resolve_event_sender,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MessageRecord {
+    pub message_id: String,
+    pub sender_id: String,
+    pub sender_name: String,
+    pub send_time: NaiveDateTime,
+    pub group_id: Option<String>,
+    pub group_name: Option<String>,
+    pub content: String,
+    pub at_target_list: Option<String>,
+    pub media_json: Option<String>,
+    pub raw_message_json: Option<String>,
+}
+
+impl MessageRecord {
+    /// Builds the record that message persistence writes for `event`, before the
+    /// per-column truncation and content chunking applied when inserting.
+    ///
+    /// `send_time` is the event's server-side time, or `clock.now()` when the
+    /// event has none. Messages sent by the bot itself (`sender.user_id ==
+    /// bot_id`) without a display name are attributed to `bot_id`, like
+    /// outbound message events.
+    /// Fails when the media or raw message list cannot be serialized.
+    pub fn from_event(event: &MessageEvent, clock: &dyn Clock, bot_id: &str) -> Result<Self> {
+        let (sender_id, mut sender_name) = resolve_event_sender(event);
+        if sender_name.is_empty() && event.sender.user_id.to_string() == bot_id {
+            sender_name = bot_id.to_string();
+        }
+
+        Ok(Self {
+            message_id: event.message_id.to_string(),
+            sender_id,
+            sender_name,
+            send_time: event.send_time().unwrap_or_else(|| clock.now()),
+            group_id: event.group_id.map(|id| id.to_string()),
+            group_name: event.group_name.clone(),
+            content: render_event_content(event),
+            at_target_list: event_at_target_list(event),
+            media_json: event_media_json(event)?,
+            raw_message_json: event_raw_message_json(event)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use zihuan_core::ims_bot_adapter::models::event_model::{MessageType, Sender};
+    use zihuan_core::ims_bot_adapter::models::message::{
+        render_messages_readable, AtTargetMessage, Message, PlainTextMessage,
+    };
+    use zihuan_core::utils::clock::FixedClock;
+
+    use super::*;
+
+    #[test]
+    fn group_message_with_mention_matches_persisted_columns() {
+        let message_list = vec![
+            Message::At(AtTargetMessage {
+                target: Some("10001".to_string()),
+            }),
+            Message::PlainText(PlainTextMessage {
+                text: " 今天天气怎么样".to_string(),
+            }),
+        ];
+        let event = MessageEvent {
+            message_id: 42,
+            message_type: MessageType::Group,
+            sender: Sender {
+                user_id: 20002,
+                nickname: "小明".to_string(),
+                card: "群名片".to_string(),
+                role: None,
+            },
+            message_list: message_list.clone(),
+            group_id: Some(30003),
+            group_name: Some("测试群".to_string()),
+            is_group_message: true,
+            time: None,
+        };
+        let now = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap().and_hms_opt(3, 4, 5).unwrap();
+
+        let record = MessageRecord::from_event(&event, &FixedClock(now), "10001").unwrap();
+
+        // Column values as derived inline by message persistence before the conversion existed.
+        let expected = MessageRecord {
+            message_id: "42".to_string(),
+            sender_id: "20002".to_string(),
+            sender_name: "群名片".to_string(),
+            send_time: now,
+            group_id: Some("30003".to_string()),
+            group_name: Some("测试群".to_string()),
+            content: render_messages_readable(&message_list),
+            at_target_list: Some("10001".to_string()),
+            media_json: None,
+            raw_message_json: Some(serde_json::to_string(&message_list).unwrap()),
+        };
+        assert_eq!(record, expected);
+    }
+
+    #[test]
+    fn server_time_takes_precedence_over_the_clock() {
+        let event = MessageEvent {
+            message_id: 43,
+            message_type: MessageType::Private,
+            sender: Sender {
+                user_id: 20002,
+                nickname: "小明".to_string(),
+                card: String::new(),
+                role: None,
+            },
+            message_list: vec![],
+            group_id: None,
+            group_name: None,
+            is_group_message: false,
+            time: Some(1714557600),
+        };
+        let now = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap().and_hms_opt(3, 4, 5).unwrap();
+
+        let record = MessageRecord::from_event(&event, &FixedClock(now), "10001").unwrap();
+        assert_eq!(Some(record.send_time), event.send_time());
+
+        let untimed = MessageEvent { time: None, ..event };
+        assert_eq!(
+            MessageRecord::from_event(&untimed, &FixedClock(now), "10001")
+                .unwrap()
+                .send_time,
+            now
+        );
+    }
+}
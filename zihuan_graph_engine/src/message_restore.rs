@@ -1,5 +1,6 @@
 use crate::data_value::RedisConfig;
-use log::{debug, warn};
+use crate::store_codec::decode_cached_payload;
+use log::{debug, info, warn};
 use once_cell::sync::Lazy;
 use redis::AsyncCommands;
 use sqlx::Row;
@@ -12,10 +13,16 @@ use zihuan_core::ims_bot_adapter::models::event_model::MessageEvent;
 use zihuan_core::ims_bot_adapter::models::message::{
     ImageMessage, Message, MessageMediaRecord, PersistedMedia, PersistedMediaSource, PlainTextMessage,
 };
+use zihuan_core::utils::backoff::BackoffPolicy;
 
 static RUNTIME_MESSAGE_INDEX: Lazy<RwLock<HashMap<String, Vec<Message>>>> = Lazy::new(|| RwLock::new(HashMap::new()));
 static LATEST_RDB_POOL: Lazy<RwLock<Option<RelationalDbConnection>>> = Lazy::new(|| RwLock::new(None));
 static LATEST_REDIS_REF: Lazy<RwLock<Option<Arc<RedisConfig>>>> = Lazy::new(|| RwLock::new(None));
+/// Latest warm-up outcome per bot adapter connection id.
+static MESSAGE_INDEX_WARMUP_STATUS: Lazy<RwLock<HashMap<String, MessageIndexWarmupStatus>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub const DEFAULT_MESSAGE_INDEX_WARMUP_LIMIT: usize = 1000;
 
 const LOOKUP_SQL: &str = r#"
     SELECT content, media_json, raw_message_json
@@ -24,6 +31,21 @@ const LOOKUP_SQL: &str = r#"
     ORDER BY id ASC
     "#;
 
+// Long messages span several rows, so the limit picks the latest message ids
+// first and then loads every row of those messages.
+const WARMUP_SQL: &str = r#"
+    SELECT r.message_id, r.content, r.media_json, r.raw_message_json
+    FROM message_record r
+    JOIN (
+        SELECT message_id, MAX(id) AS last_id
+        FROM message_record
+        GROUP BY message_id
+        ORDER BY last_id DESC
+        LIMIT ?
+    ) recent ON recent.message_id = r.message_id
+    ORDER BY r.id DESC
+    "#;
+
 const MEDIA_RECORD_LOOKUP_SQL: &str = r#"
     SELECT source, original_source, rustfs_path, name, description, mime_type
     FROM media_record
@@ -72,6 +94,15 @@ pub struct RestoredMessageSnapshot {
     pub source: MessageRestoreSource,
 }
 
+/// Outcome of preloading recent messages into the runtime message index at startup.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MessageIndexWarmupStatus {
+    pub succeeded: bool,
+    pub loaded_count: usize,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CachedMessageSnapshotPayload {
     pub message_id: String,
@@ -98,6 +129,135 @@ pub fn register_redis_ref(config: Arc<RedisConfig>) {
     }
 }
 
+/// The last warm-up outcome recorded for the bot adapter `connection_id`.
+pub fn message_index_warmup_status(connection_id: &str) -> Option<MessageIndexWarmupStatus> {
+    MESSAGE_INDEX_WARMUP_STATUS
+        .read()
+        .ok()
+        .and_then(|guard| guard.get(connection_id).cloned())
+}
+
+/// Preloads the latest `limit` messages into the runtime index, retrying with
+/// `policy` on failure, and records the outcome under the bot adapter
+/// `connection_id`. A failed warm-up leaves the index empty rather than
+/// aborting; callers decide whether that is fatal.
+pub async fn warm_up_message_index(
+    connection_id: &str,
+    pool: &RelationalDbConnection,
+    limit: usize,
+    policy: &BackoffPolicy,
+) -> MessageIndexWarmupStatus {
+    let mut attempt = 0;
+    let status = loop {
+        attempt += 1;
+        match load_recent_message_snapshots(pool, limit).await {
+            Ok(snapshots) => {
+                let loaded_count = snapshots.len();
+                if let Ok(mut guard) = RUNTIME_MESSAGE_INDEX.write() {
+                    guard.extend(snapshots);
+                }
+                info!(
+                    "[message_restore] warmed up message index with {} messages (attempt {})",
+                    loaded_count, attempt
+                );
+                break MessageIndexWarmupStatus {
+                    succeeded: true,
+                    loaded_count,
+                    attempts: attempt,
+                    last_error: None,
+                };
+            }
+            Err(error) if policy.should_retry(attempt) => {
+                let delay = policy.delay_for_attempt(attempt);
+                warn!(
+                    "[message_restore] message index warm-up attempt {} failed: {}; retrying in {:?}",
+                    attempt, error, delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(error) => {
+                warn!(
+                    "[message_restore] message index warm-up failed after {} attempts, continuing with an empty cache: {}",
+                    attempt, error
+                );
+                break MessageIndexWarmupStatus {
+                    succeeded: false,
+                    loaded_count: 0,
+                    attempts: attempt,
+                    last_error: Some(error.to_string()),
+                };
+            }
+        }
+    };
+
+    if let Ok(mut guard) = MESSAGE_INDEX_WARMUP_STATUS.write() {
+        guard.insert(connection_id.to_string(), status.clone());
+    }
+    status
+}
+
+async fn load_recent_message_snapshots(
+    pool: &RelationalDbConnection,
+    limit: usize,
+) -> Result<HashMap<String, Vec<Message>>> {
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows: Vec<(String, String, Option<String>, Option<String>)> = match pool {
+        RelationalDbConnection::MySql(config) => sqlx::query(WARMUP_SQL)
+            .bind(limit)
+            .fetch_all(mysql_pool(config)?)
+            .await?
+            .into_iter()
+            .map(|row| {
+                (
+                    row.get("message_id"),
+                    row.get("content"),
+                    row.get("media_json"),
+                    row.get("raw_message_json"),
+                )
+            })
+            .collect(),
+        RelationalDbConnection::Sqlite(config) => sqlx::query(WARMUP_SQL)
+            .bind(limit)
+            .fetch_all(sqlite_pool(config)?)
+            .await?
+            .into_iter()
+            .map(|row| {
+                (
+                    row.get("message_id"),
+                    row.get("content"),
+                    row.get("media_json"),
+                    row.get("raw_message_json"),
+                )
+            })
+            .collect(),
+    };
+
+    // Rows come newest-first; long messages are split across consecutive rows, so
+    // reassemble chunks in insertion order before rebuilding.
+    let mut chunks: HashMap<String, (String, Option<String>, Option<String>)> = HashMap::new();
+    for (message_id, content, media_json, raw_message_json) in rows.into_iter().rev() {
+        let entry = chunks.entry(message_id).or_default();
+        entry.0.push_str(&content);
+        if entry.1.is_none() {
+            entry.1 = media_json;
+        }
+        if entry.2.is_none() {
+            entry.2 = raw_message_json;
+        }
+    }
+
+    Ok(chunks
+        .into_iter()
+        .filter_map(|(message_id, (content, media_json, raw_message_json))| {
+            let messages = raw_message_json
+                .as_deref()
+                .and_then(rebuild_message_list_from_raw_json)
+                .unwrap_or_else(|| rebuild_message_list(&content, media_json.as_deref()));
+            (!messages.is_empty()).then_some((message_id, messages))
+        })
+        .collect())
+}
+
 pub fn restore_message_snapshot(message_id: i64) -> Result<Option<RestoredMessageSnapshot>> {
     let message_id_str = message_id.to_string();
 
@@ -377,11 +537,11 @@ fn restore_message_snapshot_from_redis(
         }
 
         let Some(cm) = cm_guard.as_mut() else {
-            return Ok::<Option<String>, zihuan_core::error::Error>(None);
+            return Ok::<Option<Vec<u8>>, zihuan_core::error::Error>(None);
         };
 
-        let payload: Option<String> = cm.get(&message_id_for_get).await?;
-        Ok::<Option<String>, zihuan_core::error::Error>(payload)
+        let payload: Option<Vec<u8>> = cm.get(&message_id_for_get).await?;
+        Ok::<Option<Vec<u8>>, zihuan_core::error::Error>(payload)
     };
 
     let payload = if let Ok(handle) = tokio::runtime::Handle::try_current() {
@@ -394,7 +554,7 @@ fn restore_message_snapshot_from_redis(
         return Ok(None);
     };
 
-    let snapshot: CachedMessageSnapshotPayload = match serde_json::from_str(&payload) {
+    let snapshot = match decode_cached_payload(&payload) {
         Ok(value) => value,
         Err(error) => {
             warn!(
@@ -570,6 +730,68 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn warm_up_failure_reports_empty_failed_cache() {
+        let pool = RelationalDbConnection::MySql(Arc::new(MySqlConfig {
+            url: None,
+            reconnect_max_attempts: None,
+            reconnect_interval_secs: None,
+            pool: None,
+            runtime_handle: None,
+        }));
+        let policy = BackoffPolicy::new(2, std::time::Duration::ZERO, std::time::Duration::ZERO);
+
+        let status = warm_up_message_index("bot", &pool, DEFAULT_MESSAGE_INDEX_WARMUP_LIMIT, &policy).await;
+
+        assert!(!status.succeeded);
+        assert_eq!(status.loaded_count, 0);
+        assert_eq!(status.attempts, 2);
+        assert!(status.last_error.is_some());
+        assert_eq!(message_index_warmup_status("bot"), Some(status));
+        assert_eq!(message_index_warmup_status("other"), None);
+    }
+
+    #[tokio::test]
+    async fn warm_up_limit_counts_messages_not_chunk_rows() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        for (ddl, _) in zihuan_core::database::ddl::SQLITE_TABLES {
+            sqlx::query(ddl).execute(&pool).await.unwrap();
+        }
+        for (message_id, content) in [
+            ("1", "旧消息"),
+            ("2", "很长的消息"),
+            ("2", "的第二段"),
+            ("2", "的第三段"),
+        ] {
+            sqlx::query(
+                "INSERT INTO message_record (message_id, sender_id, sender_name, send_time, content)
+                 VALUES (?, '100', '用户', '2024-05-01 10:00:00', ?)",
+            )
+            .bind(message_id)
+            .bind(content)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let pool = RelationalDbConnection::Sqlite(Arc::new(SqliteConfig {
+            path: ":memory:".to_string(),
+            pool: Some(pool),
+            runtime_handle: None,
+        }));
+
+        let snapshots = load_recent_message_snapshots(&pool, 2).await.unwrap();
+
+        assert_eq!(snapshots.len(), 2);
+        match snapshots["2"].as_slice() {
+            [Message::PlainText(text)] => assert_eq!(text.text, "很长的消息的第二段的第三段"),
+            other => panic!("expected one text message, got {other:?}"),
+        }
+    }
+
     #[test]
     fn redis_snapshot_payload_roundtrip_restores_media_ids() {
         let messages = vec![Message::Image(ImageMessage::new(PersistedMedia::new(
@@ -0,0 +1,176 @@
+use crate::graph_io::NodeGraphDefinition;
+use crate::util::preview_message_list::PreviewMessageListRenderer;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Renders the text preview shown inside a node card before the graph runs.
+pub trait NodeRenderer: Send + Sync {
+    /// `inline_inputs` are the node's own inline values; `graph` is available to
+    /// resolve inputs that arrive over edges.
+    fn render_preview(
+        &self,
+        node_id: &str,
+        graph: &NodeGraphDefinition,
+        inline_inputs: &HashMap<String, Value>,
+    ) -> String;
+}
+
+/// Maps node types to their preview renderers, so integrators can add previews
+/// for their own node types without the UI hardcoding the dispatch.
+pub struct RendererRegistry {
+    renderers: RwLock<HashMap<String, Arc<dyn NodeRenderer>>>,
+}
+
+impl RendererRegistry {
+    pub fn new() -> Self {
+        Self {
+            renderers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Registry with the renderers of the built-in preview nodes.
+    pub fn with_defaults() -> Self {
+        let registry = Self::new();
+        registry.register("preview_message_list", Arc::new(PreviewMessageListRenderer));
+        registry
+    }
+
+    /// Register (or replace) the renderer for `node_type`.
+    pub fn register(&self, node_type: impl Into<String>, renderer: Arc<dyn NodeRenderer>) {
+        self.renderers.write().unwrap().insert(node_type.into(), renderer);
+    }
+
+    pub fn get(&self, node_type: &str) -> Option<Arc<dyn NodeRenderer>> {
+        self.renderers.read().unwrap().get(node_type).cloned()
+    }
+
+    /// Preview for `node_id` through the renderer of `node_type`. Node types
+    /// without a renderer get one `port: value` line per inline input.
+    pub fn render_preview(
+        &self,
+        node_type: &str,
+        node_id: &str,
+        graph: &NodeGraphDefinition,
+        inline_inputs: &HashMap<String, Value>,
+    ) -> String {
+        if let Some(renderer) = self.get(node_type) {
+            return renderer.render_preview(node_id, graph, inline_inputs);
+        }
+
+        let mut ports: Vec<_> = inline_inputs.iter().collect();
+        ports.sort_by(|a, b| a.0.cmp(b.0));
+        ports
+            .into_iter()
+            .map(|(port, value)| match value {
+                Value::String(text) => format!("{port}: {text}"),
+                other => format!("{port}: {other}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Default for RendererRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+pub static RENDERER_REGISTRY: Lazy<RendererRegistry> = Lazy::new(RendererRegistry::with_defaults);
+
+/// Register a preview renderer in the global [`RENDERER_REGISTRY`].
+pub fn register_renderer(node_type: impl Into<String>, renderer: Arc<dyn NodeRenderer>) {
+    RENDERER_REGISTRY.register(node_type, renderer);
+}
+
+/// Render a node preview through the global [`RENDERER_REGISTRY`].
+pub fn render_preview(
+    node_type: &str,
+    node_id: &str,
+    graph: &NodeGraphDefinition,
+    inline_inputs: &HashMap<String, Value>,
+) -> String {
+    RENDERER_REGISTRY.render_preview(node_type, node_id, graph, inline_inputs)
+}
+
+/// Value of `node_id`'s input `port`: the inline value if set, otherwise the
+/// inline value of the upstream port connected to it (e.g. a data-source node).
+pub fn resolve_preview_input<'a>(
+    node_id: &str,
+    port: &str,
+    graph: &'a NodeGraphDefinition,
+    inline_inputs: &'a HashMap<String, Value>,
+) -> Option<&'a Value> {
+    if let Some(value) = inline_inputs.get(port) {
+        return Some(value);
+    }
+    let edge = graph
+        .edges
+        .iter()
+        .find(|edge| edge.to_node_id == node_id && edge.to_port == port)?;
+    graph
+        .nodes
+        .iter()
+        .find(|node| node.id == edge.from_node_id)?
+        .inline_values
+        .get(&edge.from_port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_io::{EdgeDefinition, NodeDefinition};
+    use serde_json::json;
+
+    fn node(id: &str, node_type: &str, inline_values: HashMap<String, Value>) -> NodeDefinition {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": id,
+            "description": null,
+            "node_type": node_type,
+            "input_ports": [],
+            "output_ports": [],
+            "position": null,
+            "size": null,
+            "inline_values": inline_values,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn message_list_preview_is_dispatched_through_the_registry() {
+        let messages = json!([
+            { "role": "system", "parts": [{ "type": "text", "text": "你是紫幻" }] },
+            { "role": "user", "parts": [{ "type": "text", "text": "你好" }], "name": "小明" },
+        ]);
+        let graph = NodeGraphDefinition {
+            nodes: vec![
+                node("data", "message_list_data", HashMap::from([("messages".to_string(), messages)])),
+                node("preview", "preview_message_list", HashMap::new()),
+            ],
+            edges: vec![EdgeDefinition {
+                from_node_id: "data".to_string(),
+                from_port: "messages".to_string(),
+                to_node_id: "preview".to_string(),
+                to_port: "messages".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        let registry = RendererRegistry::with_defaults();
+        let preview = registry.render_preview("preview_message_list", "preview", &graph, &HashMap::new());
+
+        assert_eq!(preview, "[system] 你是紫幻\n[user·小明] 你好");
+        assert_eq!(
+            registry.render_preview(
+                "unknown",
+                "preview",
+                &graph,
+                &HashMap::from([("text".to_string(), json!("原文"))]),
+            ),
+            "text: 原文"
+        );
+    }
+}
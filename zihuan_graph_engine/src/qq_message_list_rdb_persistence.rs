@@ -1,24 +1,17 @@
-use crate::message_rdb_chunking::{
-    split_content_chunks, truncate_field_if_needed, truncate_optional_field_if_needed, AT_TARGET_LIST_MAX_CHARS,
-    CONTENT_MAX_CHARS, GROUP_ID_MAX_CHARS, GROUP_NAME_MAX_CHARS, MEDIA_JSON_MAX_CHARS, MESSAGE_ID_MAX_CHARS,
-    SENDER_ID_MAX_CHARS, SENDER_NAME_MAX_CHARS,
-};
+use crate::message_backend::message_backend_for_connection;
+use crate::message_rdb_chunking::message_record_rows;
+use crate::message_record::MessageRecord;
 use crate::{node_input, node_output, DataType, DataValue, Node, NodeType, Port};
 use chrono::Local;
-use log::{debug, error, info, warn};
-use sqlx;
+use log::{error, info, warn};
 use tokio::task::block_in_place;
 use zihuan_core::error::Result;
 use zihuan_core::ims_bot_adapter::models::message::{collect_media_records, Message};
 
-/// Returns true for errors that indicate a dropped/stale connection rather than
-/// a SQL-level problem (constraint violation, syntax error, etc.).
-fn is_connection_error(e: &sqlx::Error) -> bool {
-    matches!(e, sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_))
-}
-
-/// QQMessage List MySQL Persistence Node — stores a raw Vec<QQMessage> together
-/// with caller-supplied metadata into the `message_record` MySQL table.
+/// QQMessage List RDB Persistence Node — stores a raw Vec<QQMessage> together
+/// with caller-supplied metadata into the `message_record` table of a MySQL or
+/// SQLite connection, with the same truncation and chunking as message
+/// persistence.
 ///
 /// Unlike the service-layer persistence, this node does not require a full
 /// `MessageEvent`.  The caller must provide `message_id`, `sender_id`, and
@@ -52,7 +45,7 @@ impl Node for QQMessageListRdbPersistenceNode {
     }
 
     fn description(&self) -> Option<&str> {
-        Some("QQMessage列表MySQL持久化 - 将Vec<QQMessage>及元数据存储到MySQL数据库")
+        Some("QQMessage列表关系数据库持久化 - 将Vec<QQMessage>及元数据存储到MySQL或SQLite数据库")
     }
 
     node_input![
@@ -81,15 +74,13 @@ impl Node for QQMessageListRdbPersistenceNode {
             .ok_or_else(|| zihuan_core::error::Error::InvalidNodeInput("qq_message_list is required".to_string()))?;
 
         // extract metadata strings
-        let raw_message_id = inputs
+        let message_id = inputs
             .get("message_id")
             .and_then(|v| match v {
                 DataValue::String(s) => Some(s.clone()),
                 _ => None,
             })
             .ok_or_else(|| zihuan_core::error::Error::InvalidNodeInput("message_id is required".to_string()))?;
-        let message_id =
-            truncate_field_if_needed("message_id", raw_message_id.clone(), MESSAGE_ID_MAX_CHARS, &raw_message_id);
 
         let sender_id = inputs
             .get("sender_id")
@@ -98,7 +89,6 @@ impl Node for QQMessageListRdbPersistenceNode {
                 _ => None,
             })
             .ok_or_else(|| zihuan_core::error::Error::InvalidNodeInput("sender_id is required".to_string()))?;
-        let sender_id = truncate_field_if_needed("sender_id", sender_id, SENDER_ID_MAX_CHARS, &message_id);
 
         let sender_name = inputs
             .get("sender_name")
@@ -107,20 +97,17 @@ impl Node for QQMessageListRdbPersistenceNode {
                 _ => None,
             })
             .ok_or_else(|| zihuan_core::error::Error::InvalidNodeInput("sender_name is required".to_string()))?;
-        let sender_name = truncate_field_if_needed("sender_name", sender_name, SENDER_NAME_MAX_CHARS, &message_id);
 
         // Optional: treat absent or empty string as NULL.
         let group_id: Option<String> = inputs.get("group_id").and_then(|v| match v {
             DataValue::String(s) if !s.is_empty() => Some(s.clone()),
             _ => None,
         });
-        let group_id = truncate_optional_field_if_needed("group_id", group_id, GROUP_ID_MAX_CHARS, &message_id);
 
         let group_name: Option<String> = inputs.get("group_name").and_then(|v| match v {
             DataValue::String(s) if !s.is_empty() => Some(s.clone()),
             _ => None,
         });
-        let group_name = truncate_optional_field_if_needed("group_name", group_name, GROUP_NAME_MAX_CHARS, &message_id);
 
         // RDB pool
         let rdb_pool = inputs
@@ -131,36 +118,12 @@ impl Node for QQMessageListRdbPersistenceNode {
             })
             .ok_or_else(|| zihuan_core::error::Error::InvalidNodeInput("rdb_ref is required".to_string()))?;
 
-        let rdb_config = match rdb_pool {
-            zihuan_core::data_refs::RelationalDbConnection::MySql(config) => config,
-            _ => {
-                return Err(zihuan_core::error::Error::InvalidNodeInput(
-                    "rdb_ref must be a MySQL connection".to_string(),
-                ))
-            }
-        };
-
         let passthrough = DataValue::Vec(msg_item_type, msg_items.clone());
 
-        let pool = match rdb_config.pool.clone() {
-            Some(p) => {
-                let size = p.size();
-                let idle = p.num_idle();
-                let in_use = size.saturating_sub(idle as u32);
-                debug!(
-                    "[QQMessageListRdbPersistenceNode] pool size={}, idle={}, in-use={}",
-                    size, idle, in_use
-                );
-                if idle == 0 {
-                    warn!(
-                        "[QQMessageListRdbPersistenceNode] No idle connections (all {} in-use) — INSERT may stall",
-                        in_use
-                    );
-                }
-                p
-            }
-            None => {
-                error!("[QQMessageListRdbPersistenceNode] rdb_pool has no active pool");
+        let backend = match message_backend_for_connection(&rdb_pool) {
+            Ok(backend) => backend,
+            Err(e) => {
+                error!("[QQMessageListRdbPersistenceNode] rdb_pool has no active pool: {}", e);
                 return crate::return_with_node_output![self;
                     "success" => DataValue::Boolean(false),
                     "qq_message_list" => passthrough,
@@ -178,7 +141,6 @@ impl Node for QQMessageListRdbPersistenceNode {
             .collect();
 
         let content: String = messages.iter().map(|m| m.to_string()).collect::<Vec<_>>().join("");
-        let content_chunks = split_content_chunks(&content, CONTENT_MAX_CHARS);
 
         let at_targets: Vec<String> = messages
             .iter()
@@ -196,8 +158,6 @@ impl Node for QQMessageListRdbPersistenceNode {
         } else {
             Some(at_targets.join(","))
         };
-        let at_target_list =
-            truncate_optional_field_if_needed("at_target_list", at_target_list, AT_TARGET_LIST_MAX_CHARS, &message_id);
         let media_json = {
             let records = collect_media_records(&messages);
             if records.is_empty() {
@@ -206,55 +166,38 @@ impl Node for QQMessageListRdbPersistenceNode {
                 Some(serde_json::to_string(&records)?)
             }
         };
-        let media_json = truncate_optional_field_if_needed("media_json", media_json, MEDIA_JSON_MAX_CHARS, &message_id);
 
-        let send_time = Local::now().naive_local();
-        let message_id_log = message_id.clone();
+        let rows = message_record_rows(&MessageRecord {
+            message_id,
+            sender_id,
+            sender_name,
+            send_time: Local::now().naive_local(),
+            group_id,
+            group_name,
+            content,
+            at_target_list,
+            media_json,
+            raw_message_json: None,
+        });
+        let message_id_log = rows[0].message_id.clone();
 
         info!(
             "[QQMessageListRdbPersistenceNode] Inserting message {} (sender={}, group={:?}, chunks={})",
             message_id_log,
-            sender_id,
-            group_id,
-            content_chunks.len(),
+            rows[0].sender_id,
+            rows[0].group_id,
+            rows.len(),
         );
 
         // Insert with single retry on connection errors
+        let runtime_handle = match &rdb_pool {
+            zihuan_core::data_refs::RelationalDbConnection::MySql(config) => config.runtime_handle.clone(),
+            zihuan_core::data_refs::RelationalDbConnection::Sqlite(config) => config.runtime_handle.clone(),
+        };
         let mut success = false;
         for attempt in 1u32..=2 {
-            let run = async {
-                for (chunk_index, content_chunk) in content_chunks.iter().enumerate() {
-                    let chunk_at_target_list = if chunk_index == 0 {
-                        at_target_list.as_ref()
-                    } else {
-                        None
-                    };
-                    let chunk_media_json = if chunk_index == 0 { media_json.as_ref() } else { None };
-
-                    sqlx::query(
-                        r#"
-                        INSERT INTO message_record
-                        (message_id, sender_id, sender_name, send_time, group_id, group_name, content, at_target_list, media_json)
-                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-                        "#,
-                    )
-                    .bind(&message_id)
-                    .bind(&sender_id)
-                    .bind(&sender_name)
-                    .bind(send_time)
-                    .bind(&group_id)
-                    .bind(&group_name)
-                    .bind(content_chunk)
-                    .bind(chunk_at_target_list)
-                    .bind(chunk_media_json)
-                    .execute(&pool)
-                    .await?;
-                }
-
-                Ok::<(), sqlx::Error>(())
-            };
-
-            let result: std::result::Result<(), sqlx::Error> = if let Some(handle) = rdb_config.runtime_handle.clone() {
+            let run = backend.insert_rows(&rows);
+            let result = if let Some(handle) = runtime_handle.clone() {
                 if tokio::runtime::Handle::try_current().is_ok() {
                     block_in_place(|| handle.block_on(run))
                 } else {
@@ -263,8 +206,7 @@ impl Node for QQMessageListRdbPersistenceNode {
             } else if let Ok(handle) = tokio::runtime::Handle::try_current() {
                 block_in_place(|| handle.block_on(run))
             } else {
-                let rt = tokio::runtime::Runtime::new().unwrap();
-                rt.block_on(run)
+                tokio::runtime::Runtime::new()?.block_on(run)
             };
 
             match result {
@@ -280,7 +222,7 @@ impl Node for QQMessageListRdbPersistenceNode {
                     success = true;
                     break;
                 }
-                Err(ref e) if attempt < 2 && is_connection_error(e) => {
+                Err(ref e) if attempt < 2 && e.is_retryable() => {
                     warn!(
                         "[QQMessageListRdbPersistenceNode] Message {} attempt {} connection error ({}); retrying",
                         message_id_log, attempt, e
@@ -146,6 +146,7 @@ macro_rules! register_node {
 pub fn build_node_graph_from_definition(definition: &crate::graph_io::NodeGraphDefinition) -> Result<crate::NodeGraph> {
     let mut graph = crate::NodeGraph::new();
     graph.set_definition(definition.clone());
+    graph.set_execution_mode(definition.execution_mode);
 
     if !definition.edges.is_empty() {
         graph.set_edges(definition.edges.clone());
@@ -311,6 +312,7 @@ pub(crate) fn json_to_data_value(json: &Value, target_type: &DataType) -> Option
                 reasoning_content: None,
                 tool_calls: Vec::new(),
                 tool_call_id: None,
+                name: None,
                 usage: None,
             }))
         }
@@ -360,15 +362,16 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
     use crate::util::{
         AndThenNode, AnyOfNode, ArrayGetNode, AtQQTargetMessageNode, BinaryToImageMessagePartNode, BooleanBranchNode,
         BooleanNotNode, BuildMultimodalUserMessageNode, ConcatVecNode, ConditionalNode, ConditionalRouterNode,
-        CurrentTimeNode, FormatStringNode, FunctionInputsNode, FunctionNode, FunctionOutputsNode, GraphInputsNode,
-        GraphOutputsNode, JoinStringNode, JsonExtractNode, JsonParserNode, JsonToQQMessageVecNode,
-        LLMMessageContentAsJsonNode, LLMMessageSessionCacheClearNode, LLMMessageSessionCacheGetNode,
-        LLMMessageSessionCacheNode, LLMMessageSessionCacheSetNode, LLMMessageToStringNode, MessageContentNode,
-        MessageListDataNode, PreviewMessageListNode, PreviewQQMessageListNode, PreviewStringNode, PushBackVecNode,
-        QQMessageListDataNode, QQMessageToImageNode, SessionStateClearNode, SessionStateGetNode,
-        SessionStateReleaseNode, SessionStateTryClaimNode, SetVariableNode, StackNode, StringDataNode,
-        StringIsNotEmptyNode, StringToImageMessagePartNode, StringToLLMMessageNode, StringToPlainTextNode, SwitchNode,
-        ToolResultNode,
+        ContextInjectNode, CurrentTimeNode, DebounceNode, DelayNode, DiffNode, FormatStringNode, FunctionInputsNode,
+        FunctionNode, FunctionOutputsNode, GraphInputsNode, GraphOutputsNode, HttpRequestNode, JoinStringNode,
+        JsonExtractNode, JsonParserNode, JsonSchemaValidateNode, JsonToQQMessageVecNode, LLMMessageContentAsJsonNode,
+        LLMMessageSessionCacheClearNode, LLMMessageSessionCacheGetNode, LLMMessageSessionCacheNode,
+        LLMMessageSessionCacheSetNode, LLMMessageToStringNode, LanguageDetectNode, MapToolNode, MessageContentNode,
+        MessageListDataNode, MessageWindowNode, MessagesToPromptNode, PreviewMessageListNode, PreviewQQMessageListNode,
+        PreviewStringNode, PushBackVecNode, QQMessageListDataNode, QQMessageToImageNode, SessionStateClearNode,
+        SessionStateGetNode, SessionStateReleaseNode, SessionStateTryClaimNode, SetVariableNode, StackNode,
+        StringDataNode, StringIsNotEmptyNode, StringToImageMessagePartNode, StringToLLMMessageNode,
+        StringToPlainTextNode, SwitchNode, TemplateNode, ToolResultNode, ToolResultToMessageNode,
     };
 
     register_node!(
@@ -392,6 +395,13 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "通过 ${变量名} 模板语法将输入变量格式化为字符串",
         FormatStringNode
     );
+    register_node!(
+        "template",
+        "模板渲染",
+        "工具",
+        "使用 variables 对象中的字段替换模板里的 ${变量名}，模板和变量都可由上游节点提供",
+        TemplateNode
+    );
     register_node!(
         "function",
         "函数",
@@ -456,6 +466,14 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "根据 condition 将 input 送到 true 或 false 分支，未选中的分支不会输出",
         BooleanBranchNode
     );
+    register_node!(
+        "debounce",
+        "防抖聚合",
+        "工具",
+        "收集静默窗口内连续到达的输入，输入停止后只输出一次聚合结果",
+        DebounceNode
+    );
+    register_node!("delay", "延迟", "工具", "等待 delay_ms 毫秒后原样透传输入", DelayNode);
     register_node!("boolean_not", "布尔取反", "工具", "对输入的 Boolean 值取反", BooleanNotNode);
     register_node!(
         "array_get",
@@ -472,6 +490,27 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "将 vec2 拼接到 vec1 后面，要求两个列表的元素类型一致",
         ConcatVecNode
     );
+    register_node!(
+        "diff",
+        "列表差异",
+        "工具",
+        "比较上一次与本次的列表，输出新增、移除和未变化的元素，可按 key 字段比较对象",
+        DiffNode
+    );
+    register_node!(
+        "map_tool",
+        "批量调用工具",
+        "工具",
+        "对参数列表中的每一项调用同一个工具，按原顺序输出结果，失败项输出 error 标记",
+        MapToolNode
+    );
+    register_node!(
+        "http_request",
+        "HTTP 请求",
+        "工具",
+        "向外部 HTTP 接口发送请求，输出状态码、响应体和是否成功，可用于调用 Webhook 和第三方服务",
+        HttpRequestNode
+    );
     register_node!(
         "join_string",
         "拼接字符串列表",
@@ -493,6 +532,20 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "将JSON字符串解析为结构化数据",
         JsonParserNode
     );
+    register_node!(
+        "json_schema_validate",
+        "JSON Schema 校验",
+        "工具",
+        "使用 JSON Schema 校验 JSON 数据，在传入敏感节点前拦截不合规的结构化数据",
+        JsonSchemaValidateNode
+    );
+    register_node!(
+        "language_detect",
+        "语言检测",
+        "工具",
+        "检测文本语言并输出 ISO 语言代码与置信度，可用于按语言路由到翻译等分支",
+        LanguageDetectNode
+    );
     register_node!(
         "json_extract",
         "提取 JSON 字段",
@@ -528,6 +581,13 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "将 LLMMessage 的 reasoning_content（如有）与 content 拼接为字符串",
         LLMMessageToStringNode
     );
+    register_node!(
+        "messages_to_prompt",
+        "消息列表转提示词",
+        "消息",
+        "按角色模板把消息列表拼接成单个提示词字符串，用于只接受 prompt 的补全接口",
+        MessagesToPromptNode
+    );
     register_node!(
         "as_system_llm_message",
         "字符串转 LLMMessage",
@@ -584,6 +644,13 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "LLMMessage 列表数据源，通过 UI 容器编辑器提供列表数据",
         MessageListDataNode
     );
+    register_node!(
+        "message_window",
+        "消息滑动窗口",
+        "消息",
+        "按会话保存最近 N 条消息，每次输入后输出当前窗口内的消息列表",
+        MessageWindowNode
+    );
     register_node!(
         "qq_message_list_data",
         "QQMessageList Data",
@@ -626,6 +693,13 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "将工具执行结果封装为 role=tool 的 LLMMessage，供 agentic loop 回写对话列表",
         ToolResultNode
     );
+    register_node!(
+        "tool_result_to_message",
+        "Tool 结果转消息列表",
+        "AI",
+        "将工具返回的 JSON 结果转换为只含一条 role=tool 消息的列表，可用拼接列表节点并入对话",
+        ToolResultToMessageNode
+    );
     register_node!(
         "llm_message_session_cache",
         "LLMMessage 会话暂存",
@@ -696,6 +770,13 @@ pub fn init_node_registry() -> zihuan_core::error::Result<()> {
         "将二进制字节 + MIME 编码为 base64 data URL，并封装为 LLM 多模态 MessagePart",
         BinaryToImageMessagePartNode
     );
+    register_node!(
+        "context_inject",
+        "资料注入",
+        "消息",
+        "将检索到的资料片段注入 system 消息并附上用户问题，组装可复用的 RAG 提示词消息列表",
+        ContextInjectNode
+    );
     register_node!(
         "build_multimodal_user_message",
         "构建多模态 LLMMessage",
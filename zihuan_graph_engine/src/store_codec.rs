@@ -0,0 +1,109 @@
+use zihuan_core::agent_config::qq_chat::RedisCacheCodec;
+use zihuan_core::error::{Error, Result};
+
+use crate::message_restore::CachedMessageSnapshotPayload;
+
+/// Leading byte of MessagePack payloads. `0xC1` is never used by MessagePack and
+/// cannot start a JSON document, so payloads written before codecs existed (plain
+/// JSON, no prefix) stay readable.
+const MESSAGE_PACK_PREFIX: u8 = 0xC1;
+
+/// Serialization format for message snapshots cached in Redis.
+pub trait StoreCodec: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn encode(&self, payload: &CachedMessageSnapshotPayload) -> Result<Vec<u8>>;
+
+    fn decode(&self, bytes: &[u8]) -> Result<CachedMessageSnapshotPayload>;
+}
+
+pub struct JsonStoreCodec;
+
+impl StoreCodec for JsonStoreCodec {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn encode(&self, payload: &CachedMessageSnapshotPayload) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(payload)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<CachedMessageSnapshotPayload> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+pub struct MessagePackStoreCodec;
+
+impl StoreCodec for MessagePackStoreCodec {
+    fn name(&self) -> &'static str {
+        "message_pack"
+    }
+
+    fn encode(&self, payload: &CachedMessageSnapshotPayload) -> Result<Vec<u8>> {
+        let mut bytes = vec![MESSAGE_PACK_PREFIX];
+        rmp_serde::encode::write_named(&mut bytes, payload)
+            .map_err(|err| Error::StringError(format!("MessagePack encode failed: {err}")))?;
+        Ok(bytes)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<CachedMessageSnapshotPayload> {
+        let body = bytes
+            .strip_prefix(&[MESSAGE_PACK_PREFIX])
+            .ok_or_else(|| Error::ValidationError("payload is missing the MessagePack prefix byte".to_string()))?;
+        rmp_serde::from_slice(body).map_err(|err| Error::StringError(format!("MessagePack decode failed: {err}")))
+    }
+}
+
+pub fn store_codec(kind: RedisCacheCodec) -> &'static dyn StoreCodec {
+    match kind {
+        RedisCacheCodec::Json => &JsonStoreCodec,
+        RedisCacheCodec::MessagePack => &MessagePackStoreCodec,
+    }
+}
+
+/// Decodes a cached payload with whichever codec wrote it, independent of the
+/// currently configured one.
+pub fn decode_cached_payload(bytes: &[u8]) -> Result<CachedMessageSnapshotPayload> {
+    let kind = if bytes.first() == Some(&MESSAGE_PACK_PREFIX) {
+        RedisCacheCodec::MessagePack
+    } else {
+        RedisCacheCodec::Json
+    };
+    store_codec(kind).decode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_payload() -> CachedMessageSnapshotPayload {
+        CachedMessageSnapshotPayload {
+            message_id: "123456".to_string(),
+            content: "你好 [图片]".to_string(),
+            media_json: Some(r#"[{"media_id":"media-1"}]"#.to_string()),
+            raw_message_json: None,
+        }
+    }
+
+    #[test]
+    fn both_codecs_round_trip_and_are_detected_on_read() {
+        for kind in [RedisCacheCodec::Json, RedisCacheCodec::MessagePack] {
+            let bytes = store_codec(kind).encode(&sample_payload()).unwrap();
+            let decoded = decode_cached_payload(&bytes).unwrap();
+
+            assert_eq!(decoded.message_id, "123456");
+            assert_eq!(decoded.content, "你好 [图片]");
+            assert_eq!(decoded.media_json, sample_payload().media_json);
+            assert_eq!(decoded.raw_message_json, None);
+        }
+    }
+
+    #[test]
+    fn message_pack_payload_is_smaller_than_json() {
+        let json = store_codec(RedisCacheCodec::Json).encode(&sample_payload()).unwrap();
+        let message_pack = store_codec(RedisCacheCodec::MessagePack).encode(&sample_payload()).unwrap();
+
+        assert!(message_pack.len() < json.len());
+    }
+}
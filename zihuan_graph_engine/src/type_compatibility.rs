@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+
+use crate::DataType;
+
+/// Extra type pairs that count as compatible on top of the built-in rules,
+/// e.g. so a plugin's `Custom("Embedding")` ports can be linked with
+/// `Vec<Float>` ports. Pairs are symmetric.
+pub struct TypeCompatibilityRegistry {
+    pairs: RwLock<HashSet<(DataType, DataType)>>,
+}
+
+impl TypeCompatibilityRegistry {
+    fn new() -> Self {
+        Self {
+            pairs: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Declare `a` and `b` compatible in both directions.
+    pub fn register_compatible(&self, a: DataType, b: DataType) {
+        let mut pairs = self.pairs.write().unwrap();
+        pairs.insert((b.clone(), a.clone()));
+        pairs.insert((a, b));
+    }
+
+    pub fn are_compatible(&self, a: &DataType, b: &DataType) -> bool {
+        let pairs = self.pairs.read().unwrap();
+        !pairs.is_empty() && pairs.contains(&(a.clone(), b.clone()))
+    }
+
+    /// Every registered pair, each in both orders, for clients that check
+    /// links themselves.
+    pub fn pairs(&self) -> Vec<(DataType, DataType)> {
+        self.pairs.read().unwrap().iter().cloned().collect()
+    }
+}
+
+/// Global singleton consulted by [`DataType::is_compatible_with`].
+pub static TYPE_COMPATIBILITY_REGISTRY: Lazy<TypeCompatibilityRegistry> = Lazy::new(TypeCompatibilityRegistry::new);
+
+/// Declare `a` and `b` compatible for links and port validation.
+pub fn register_compatible(a: DataType, b: DataType) {
+    TYPE_COMPATIBILITY_REGISTRY.register_compatible(a, b);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_custom_type_aliases_a_builtin_in_both_directions() {
+        let embedding = DataType::Custom("TestEmbedding".to_string());
+        let floats = DataType::Vec(Box::new(DataType::Float));
+        assert!(!embedding.is_compatible_with(&floats));
+
+        register_compatible(embedding.clone(), floats.clone());
+
+        assert!(embedding.is_compatible_with(&floats));
+        assert!(floats.is_compatible_with(&embedding));
+        assert!(floats.accepts_link_from(&embedding));
+        assert!(embedding.accepts_link_from(&floats));
+        assert!(DataType::Vec(Box::new(embedding.clone())).accepts_link_from(&DataType::Vec(Box::new(floats.clone()))));
+
+        let other = DataType::Custom("TestOtherEmbedding".to_string());
+        assert!(!other.is_compatible_with(&floats));
+        assert!(!embedding.is_compatible_with(&other));
+        assert!(!embedding.is_compatible_with(&DataType::Vec(Box::new(DataType::Integer))));
+
+        let pairs = TYPE_COMPATIBILITY_REGISTRY.pairs();
+        assert!(pairs.contains(&(embedding.clone(), floats.clone())));
+        assert!(pairs.contains(&(floats, embedding)));
+    }
+}
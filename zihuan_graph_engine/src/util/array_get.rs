@@ -47,14 +47,10 @@ impl Node for ArrayGetNode {
             }
         };
 
-        let index = match inputs.get("index") {
-            Some(DataValue::Integer(i)) => *i,
-            _ => {
-                return Err(zihuan_core::error::Error::ValidationError(
-                    "index 输入必须为 Integer 类型".to_string(),
-                ))
-            }
-        };
+        let index = inputs
+            .get("index")
+            .and_then(DataValue::as_i64)
+            .ok_or_else(|| zihuan_core::error::Error::ValidationError("index 输入必须为 Integer 类型".to_string()))?;
 
         let len = list.len() as i64;
         let actual = if index < 0 { len + index } else { index };
@@ -43,14 +43,10 @@ impl Node for BooleanBranchNode {
     fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
         self.validate_inputs(&inputs)?;
 
-        let condition = match inputs.get("condition") {
-            Some(DataValue::Boolean(value)) => *value,
-            _ => {
-                return Err(zihuan_core::error::Error::ValidationError(
-                    "condition 输入必须为 Boolean".to_string(),
-                ))
-            }
-        };
+        let condition = inputs
+            .get("condition")
+            .and_then(DataValue::as_bool)
+            .ok_or_else(|| zihuan_core::error::Error::ValidationError("condition 输入必须为 Boolean".to_string()))?;
 
         let input = inputs
             .get("input")
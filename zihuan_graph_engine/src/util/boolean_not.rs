@@ -35,14 +35,10 @@ impl Node for BooleanNotNode {
     fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
         self.validate_inputs(&inputs)?;
 
-        let input = match inputs.get("input") {
-            Some(DataValue::Boolean(value)) => *value,
-            _ => {
-                return Err(zihuan_core::error::Error::ValidationError(
-                    "input 输入必须为 Boolean 类型".to_string(),
-                ))
-            }
-        };
+        let input = inputs
+            .get("input")
+            .and_then(DataValue::as_bool)
+            .ok_or_else(|| zihuan_core::error::Error::ValidationError("input 输入必须为 Boolean 类型".to_string()))?;
 
         crate::return_with_node_output![self;
             "result" => DataValue::Boolean(!input),
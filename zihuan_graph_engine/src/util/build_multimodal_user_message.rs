@@ -77,6 +77,7 @@ impl Node for BuildMultimodalUserMessageNode {
             reasoning_content: None,
             tool_calls: Vec::new(),
             tool_call_id: None,
+            name: None,
             usage: None,
         };
 
@@ -0,0 +1,182 @@
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::LLMMessage;
+
+pub const DEFAULT_MAX_CONTEXT_CHARS: usize = 4000;
+const DEFAULT_INSTRUCTION: &str = "请参考以下资料回答用户的问题。资料不足以回答时请直接说明，不要编造。";
+
+/// Assembles a RAG prompt: a system message listing the numbered `snippets`
+/// after `instruction`, followed by the user `text`.
+///
+/// Snippets are kept in order until their total length reaches `max_context_chars`;
+/// a snippet that crosses the limit is cut at it and the rest are dropped. Returns
+/// the messages and how many snippets made it into the prompt.
+pub fn assemble_context_messages(
+    text: &str,
+    snippets: &[String],
+    instruction: &str,
+    max_context_chars: usize,
+) -> (Vec<LLMMessage>, usize) {
+    let mut remaining = max_context_chars;
+    let mut context_lines = Vec::new();
+    for snippet in snippets
+        .iter()
+        .map(|snippet| snippet.trim())
+        .filter(|snippet| !snippet.is_empty())
+    {
+        if remaining == 0 {
+            break;
+        }
+        let kept: String = snippet.chars().take(remaining).collect();
+        remaining -= kept.chars().count();
+        context_lines.push(format!("[{}] {}", context_lines.len() + 1, kept));
+    }
+
+    let used = context_lines.len();
+    let system = if context_lines.is_empty() {
+        instruction.to_string()
+    } else {
+        format!("{instruction}\n\n参考资料：\n{}", context_lines.join("\n"))
+    };
+    (vec![LLMMessage::system(system), LLMMessage::user(text)], used)
+}
+
+pub struct ContextInjectNode {
+    id: String,
+    name: String,
+}
+
+impl ContextInjectNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+impl Node for ContextInjectNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(
+            "将检索到的资料片段注入 system 消息，并把用户问题作为最后一条 user 消息，输出可直接用于 LLM 推理的消息列表",
+        )
+    }
+
+    node_input![
+        port! { name = "text", ty = String, desc = "用户问题" },
+        port! { name = "contexts", ty = Vec(String), desc = "资料片段列表，例如搜索节点的结果" },
+        port! { name = "instruction", ty = String, desc = "放在资料之前的 system 指令，默认要求仅依据资料作答", optional },
+        port! { name = "max_context_chars", ty = Integer, desc = "注入资料的总字符数上限，默认 4000", optional },
+    ];
+
+    node_output![
+        port! { name = "messages", ty = Vec(LLMMessage), desc = "system 资料消息加用户问题组成的消息列表" },
+        port! { name = "used_contexts", ty = Integer, desc = "实际注入的资料片段数量" },
+    ];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::String(text)) = inputs.get("text") else {
+            return Err(Error::ValidationError("text 输入不存在".to_string()));
+        };
+        let snippets: Vec<String> = match inputs.get("contexts") {
+            Some(DataValue::Vec(_, items)) => items
+                .iter()
+                .map(|item| match item {
+                    DataValue::String(snippet) => snippet.clone(),
+                    other => other.to_display_string(),
+                })
+                .collect(),
+            _ => return Err(Error::ValidationError("contexts 输入不存在".to_string())),
+        };
+        let instruction = match inputs.get("instruction") {
+            Some(DataValue::String(instruction)) if !instruction.trim().is_empty() => instruction.trim(),
+            _ => DEFAULT_INSTRUCTION,
+        };
+        let max_context_chars = match inputs.get("max_context_chars") {
+            Some(DataValue::Integer(value)) if *value >= 0 => *value as usize,
+            Some(DataValue::Integer(value)) => {
+                return Err(Error::ValidationError(format!("max_context_chars 不能为负数：{value}")));
+            }
+            _ => DEFAULT_MAX_CONTEXT_CHARS,
+        };
+
+        let (messages, used) = assemble_context_messages(text, &snippets, instruction, max_context_chars);
+
+        crate::return_with_node_output![self;
+            "messages" => DataValue::Vec(
+                Box::new(DataType::LLMMessage),
+                messages.into_iter().map(DataValue::LLMMessage).collect(),
+            ),
+            "used_contexts" => DataValue::Integer(used as i64),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use zihuan_core::llm::MessageRole;
+
+    #[test]
+    fn snippets_go_into_the_system_message_and_user_text_comes_last() {
+        let mut node = ContextInjectNode::new("context_inject", "context_inject");
+        let outputs = node
+            .execute(crate::NodeInputFlow::from(HashMap::from([
+                ("text".to_string(), DataValue::String("紫幻的生日是哪天？".to_string())),
+                (
+                    "contexts".to_string(),
+                    DataValue::Vec(
+                        Box::new(DataType::String),
+                        vec![
+                            DataValue::String("紫幻的生日是 3 月 14 日。".to_string()),
+                            DataValue::String("紫幻喜欢喝奶茶。".to_string()),
+                        ],
+                    ),
+                ),
+            ])))
+            .expect("context inject should execute");
+
+        let Some(DataValue::Vec(_, items)) = outputs.get("messages") else {
+            panic!("messages output missing");
+        };
+        let messages: Vec<&LLMMessage> = items
+            .iter()
+            .map(|item| match item {
+                DataValue::LLMMessage(message) => message,
+                other => panic!("unexpected item {other:?}"),
+            })
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0].role, MessageRole::System));
+        let system = messages[0].content_text().unwrap_or_default();
+        assert!(system.contains("[1] 紫幻的生日是 3 月 14 日。"));
+        assert!(system.contains("[2] 紫幻喜欢喝奶茶。"));
+        let last = messages.last().unwrap();
+        assert!(matches!(last.role, MessageRole::User));
+        assert_eq!(last.content_text(), Some("紫幻的生日是哪天？"));
+        assert!(matches!(outputs.get("used_contexts"), Some(DataValue::Integer(2))));
+    }
+
+    #[test]
+    fn context_is_capped_at_the_character_limit() {
+        let snippets = vec!["a".repeat(30), "b".repeat(30), "c".repeat(30)];
+        let (messages, used) = assemble_context_messages("q", &snippets, "指令", 45);
+
+        assert_eq!(used, 2);
+        let system = messages[0].content_text().unwrap_or_default();
+        assert!(system.contains(&format!("[2] {}", "b".repeat(15))));
+        assert!(!system.contains(&"b".repeat(16)));
+        assert!(!system.contains('c'));
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+
+const DEFAULT_QUIET_MS: i64 = 1500;
+
+struct DebounceBuffer {
+    items: Vec<DataValue>,
+    generation: u64,
+}
+
+// Each event triggers its own graph run, so the buffer has to outlive a single
+// node instance and be shared between concurrent executions. Buffers are keyed
+// by the graph's state scope, so graphs never see each other's inputs, and a
+// buffer is removed once it is emitted or the run that would emit it is dropped.
+static DEBOUNCE_BUFFERS: Lazy<Mutex<HashMap<String, DebounceBuffer>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Removes and returns the buffer under `key` if no input arrived after
+/// `generation`.
+fn take_latest(key: &str, generation: u64) -> Option<Vec<DataValue>> {
+    let mut buffers = DEBOUNCE_BUFFERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if buffers.get(key).map(|buffer| buffer.generation) != Some(generation) {
+        return None;
+    }
+    buffers.remove(key).map(|buffer| buffer.items)
+}
+
+/// The run holding the latest input of a buffer. If that run is dropped in the
+/// quiet window, e.g. because the graph run was cancelled, nothing else would
+/// emit the buffer, so the guard discards it instead of leaking it.
+struct PendingEmission {
+    key: Option<String>,
+    generation: u64,
+}
+
+impl PendingEmission {
+    fn take_items(mut self) -> Option<Vec<DataValue>> {
+        let key = self.key.take()?;
+        take_latest(&key, self.generation)
+    }
+}
+
+impl Drop for PendingEmission {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            take_latest(&key, self.generation);
+        }
+    }
+}
+
+pub struct DebounceNode {
+    id: String,
+    name: String,
+    state_scope: String,
+}
+
+impl DebounceNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            state_scope: String::new(),
+        }
+    }
+
+    /// Adds the input to its buffer and returns the pending emission it started
+    /// and how long to wait for the next input.
+    fn push(&self, inputs: &crate::NodeInputFlow) -> Result<(PendingEmission, Duration)> {
+        self.validate_inputs(inputs)?;
+
+        let input = inputs
+            .get("input")
+            .cloned()
+            .ok_or_else(|| Error::ValidationError("input 输入不存在".to_string()))?;
+        let key = match inputs.get("key") {
+            Some(DataValue::String(key)) if !key.trim().is_empty() => {
+                format!("{}:{}:{}", self.state_scope, self.id, key.trim())
+            }
+            _ => format!("{}:{}", self.state_scope, self.id),
+        };
+        let quiet_ms = match inputs.get("quiet_ms").and_then(DataValue::as_i64) {
+            Some(value) if value >= 0 => value,
+            Some(value) => {
+                return Err(Error::ValidationError(format!("quiet_ms 不能为负数：{value}")));
+            }
+            _ => DEFAULT_QUIET_MS,
+        };
+
+        let mut buffers = DEBOUNCE_BUFFERS.lock().unwrap();
+        let buffer = buffers.entry(key.clone()).or_insert_with(|| DebounceBuffer {
+            items: Vec::new(),
+            generation: 0,
+        });
+        buffer.items.push(input);
+        buffer.generation += 1;
+        let pending = PendingEmission {
+            key: Some(key),
+            generation: buffer.generation,
+        };
+        Ok((pending, Duration::from_millis(quiet_ms as u64)))
+    }
+
+    /// Emits the buffer if no input arrived after `pending`.
+    fn finish(&self, pending: PendingEmission) -> Result<crate::NodeOutputFlow> {
+        let Some(items) = pending.take_items() else {
+            return crate::return_with_node_output![self;
+                "emitted" => DataValue::Boolean(false),
+            ];
+        };
+
+        let text = items
+            .iter()
+            .map(|item| match item {
+                DataValue::String(text) => text.clone(),
+                other => other.to_display_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        crate::return_with_node_output![self;
+            "items" => DataValue::Vec(Box::new(DataType::Any), items),
+            "text" => DataValue::String(text),
+            "emitted" => DataValue::Boolean(true),
+        ]
+    }
+}
+
+impl Node for DebounceNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("收集静默窗口内连续到达的输入，输入停止 quiet_ms 后仅由最后一次执行输出聚合结果")
+    }
+
+    node_input![
+        port! { name = "input", ty = Any, desc = "要聚合的输入值" },
+        port! { name = "key", ty = String, desc = "聚合分组键，例如会话 ID；默认使用节点 ID", optional },
+        port! { name = "quiet_ms", ty = Integer, desc = "静默窗口毫秒数，默认 1500", optional },
+    ];
+
+    node_output![
+        port! { name = "items", ty = Vec(Any), desc = "窗口内收集到的全部输入，仅在 emitted=true 时输出" },
+        port! { name = "text", ty = String, desc = "按换行拼接的文本形式，仅在 emitted=true 时输出" },
+        port! { name = "emitted", ty = Boolean, desc = "本次执行是否为窗口内最后一次输入并输出了聚合结果" },
+    ];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        let (pending, quiet) = self.push(&inputs)?;
+        thread::sleep(quiet);
+        self.finish(pending)
+    }
+
+    fn execute_async<'a>(
+        &'a mut self,
+        inputs: crate::NodeInputFlow,
+    ) -> Pin<Box<dyn Future<Output = Result<crate::NodeOutputFlow>> + Send + 'a>> {
+        Box::pin(async move {
+            let (pending, quiet) = self.push(&inputs)?;
+            tokio::time::sleep(quiet).await;
+            self.finish(pending)
+        })
+    }
+
+    fn set_state_scope(&mut self, scope: &str) {
+        self.state_scope = scope.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rapid_inputs_are_aggregated_into_a_single_emission() {
+        let handles: Vec<_> = ["第一句", "第二句", "第三句"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| {
+                thread::sleep(Duration::from_millis(if index == 0 { 0 } else { 30 }));
+                thread::spawn(move || {
+                    let mut node = DebounceNode::new("debounce_test", "debounce");
+                    node.execute(crate::NodeInputFlow::from(HashMap::from([
+                        ("input".to_string(), DataValue::String(text.to_string())),
+                        ("quiet_ms".to_string(), DataValue::Integer(200)),
+                    ])))
+                    .expect("debounce should execute")
+                })
+            })
+            .collect();
+
+        let outputs: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+        let emitted: Vec<_> = outputs
+            .iter()
+            .filter(|output| matches!(output.get("emitted"), Some(DataValue::Boolean(true))))
+            .collect();
+
+        assert_eq!(emitted.len(), 1);
+        match emitted[0].get("text") {
+            Some(DataValue::String(text)) => assert_eq!(text, "第一句\n第二句\n第三句"),
+            other => panic!("unexpected text output: {other:?}"),
+        }
+        match emitted[0].get("items") {
+            Some(DataValue::Vec(_, items)) => assert_eq!(items.len(), 3),
+            other => panic!("unexpected items output: {other:?}"),
+        }
+    }
+
+    fn input(text: &str) -> crate::NodeInputFlow {
+        crate::NodeInputFlow::from(HashMap::from([
+            ("input".to_string(), DataValue::String(text.to_string())),
+            ("quiet_ms".to_string(), DataValue::Integer(100)),
+        ]))
+    }
+
+    fn scoped(scope: &str) -> DebounceNode {
+        let mut node = DebounceNode::new("debounce_scoped", "debounce");
+        node.set_state_scope(scope);
+        node
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn graphs_debounce_independently_without_blocking_the_runtime() {
+        let (mut first, mut second, mut same_graph) = (scoped("graph-a"), scoped("graph-b"), scoped("graph-a"));
+
+        let (first, second, same_graph) =
+            tokio::join!(first.execute_async(input("甲")), second.execute_async(input("乙")), async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                same_graph.execute_async(input("丙")).await
+            },);
+
+        let emitted =
+            |outputs: &crate::NodeOutputFlow| matches!(outputs.get("emitted"), Some(DataValue::Boolean(true)));
+        let text = |outputs: &crate::NodeOutputFlow| match outputs.get("text") {
+            Some(DataValue::String(text)) => text.clone(),
+            other => panic!("unexpected text output: {other:?}"),
+        };
+        assert!(!emitted(&first.unwrap()));
+        let (second, same_graph) = (second.unwrap(), same_graph.unwrap());
+        assert!(emitted(&second) && emitted(&same_graph));
+        assert_eq!(text(&second), "乙");
+        assert_eq!(text(&same_graph), "甲\n丙");
+    }
+
+    #[tokio::test]
+    async fn cancelled_run_does_not_leave_its_buffer_behind() {
+        let mut node = scoped("graph-cancelled");
+
+        let run = tokio::time::timeout(Duration::from_millis(20), node.execute_async(input("丁"))).await;
+
+        assert!(run.is_err());
+        assert!(!DEBOUNCE_BUFFERS.lock().unwrap().contains_key("graph-cancelled:debounce_scoped"));
+    }
+}
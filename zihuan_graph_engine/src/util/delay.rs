@@ -0,0 +1,144 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::thread;
+use std::time::Duration;
+
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+
+pub struct DelayNode {
+    id: String,
+    name: String,
+}
+
+impl DelayNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+fn delay_input(inputs: &crate::NodeInputFlow) -> Result<Duration> {
+    match inputs.get("delay_ms").and_then(DataValue::as_i64) {
+        Some(value) if value >= 0 => Ok(Duration::from_millis(value as u64)),
+        Some(value) => Err(Error::ValidationError(format!("delay_ms 不能为负数：{value}"))),
+        None => Err(Error::ValidationError("delay_ms 输入必须为 Integer 类型".to_string())),
+    }
+}
+
+fn passthrough(inputs: &crate::NodeInputFlow) -> Result<DataValue> {
+    inputs
+        .get("input")
+        .cloned()
+        .ok_or_else(|| Error::ValidationError("input 输入不存在".to_string()))
+}
+
+impl Node for DelayNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("等待 delay_ms 毫秒后原样透传输入；在异步运行时中等待期间不占用工作线程")
+    }
+
+    node_input![
+        port! { name = "input", ty = Any, desc = "要延迟透传的值" },
+        port! { name = "delay_ms", ty = Integer, desc = "等待的毫秒数" },
+    ];
+
+    node_output![port! { name = "output", ty = Any, desc = "等待结束后透传的输入值" },];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let delay = delay_input(&inputs)?;
+        let value = passthrough(&inputs)?;
+        thread::sleep(delay);
+
+        crate::return_with_node_output![self;
+            "output" => value,
+        ]
+    }
+
+    fn execute_async<'a>(
+        &'a mut self,
+        inputs: crate::NodeInputFlow,
+    ) -> Pin<Box<dyn Future<Output = Result<crate::NodeOutputFlow>> + Send + 'a>> {
+        Box::pin(async move {
+            self.validate_inputs(&inputs)?;
+
+            let delay = delay_input(&inputs)?;
+            let value = passthrough(&inputs)?;
+            tokio::time::sleep(delay).await;
+
+            crate::return_with_node_output![self;
+                "output" => value,
+            ]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    fn inputs(delay_ms: i64) -> crate::NodeInputFlow {
+        crate::NodeInputFlow::from(HashMap::from([
+            ("input".to_string(), DataValue::String("ping".to_string())),
+            ("delay_ms".to_string(), DataValue::Integer(delay_ms)),
+        ]))
+    }
+
+    fn output(outputs: &crate::NodeOutputFlow) -> String {
+        match outputs.get("output") {
+            Some(DataValue::String(value)) => value.clone(),
+            other => panic!("unexpected output: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn sync_execute_waits_before_passing_input_through() {
+        let started = Instant::now();
+        let outputs = DelayNode::new("delay", "delay").execute(inputs(50)).unwrap();
+
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(output(&outputs), "ping");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn async_execute_lets_other_tasks_run_while_waiting() {
+        let mut node = DelayNode::new("delay", "delay");
+        let started = Instant::now();
+
+        let (outputs, ticked_at) = tokio::join!(node.execute_async(inputs(100)), async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Instant::now()
+        });
+
+        assert!(ticked_at - started < Duration::from_millis(100));
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(output(&outputs.unwrap()), "ping");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn executor_drives_the_async_path_on_a_multi_thread_runtime() {
+        let mut node = DelayNode::new("delay", "delay");
+        let outputs = crate::NodeGraph::run_node(&mut node, inputs(20)).unwrap();
+        assert_eq!(output(&outputs), "ping");
+    }
+
+    #[test]
+    fn negative_delay_is_rejected() {
+        let err = DelayNode::new("delay", "delay").execute(inputs(-1)).unwrap_err();
+        assert!(err.to_string().contains("delay_ms 不能为负数"));
+    }
+}
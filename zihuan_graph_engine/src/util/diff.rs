@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+
+pub struct DiffNode {
+    id: String,
+    name: String,
+}
+
+impl DiffNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+/// Identity of `item`: the fingerprint of the whole element, or of its `key`
+/// field when a key is given.
+fn identity(item: &DataValue, key: Option<&str>) -> Result<u64> {
+    let Some(key) = key else {
+        return Ok(item.fingerprint());
+    };
+    match item.to_json().get(key) {
+        Some(value) => Ok(DataValue::Json(value.clone()).fingerprint()),
+        None => Err(Error::ValidationError(format!(
+            "列表元素缺少 key 字段 \"{key}\"：{}",
+            item.to_display_string()
+        ))),
+    }
+}
+
+fn identities(items: &[DataValue], key: Option<&str>) -> Result<HashSet<u64>> {
+    items.iter().map(|item| identity(item, key)).collect()
+}
+
+impl Node for DiffNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("比较上一次与本次的列表，输出新增、移除和未变化的元素；对象元素可按 key 字段判断是否为同一项")
+    }
+
+    node_input![
+        port! { name = "previous", ty = Vec(Any), desc = "上一次运行的列表" },
+        port! { name = "current", ty = Vec(Any), desc = "本次运行的列表" },
+        port! { name = "key", ty = String, desc = "可选：对象元素用于判断同一项的字段名；为空时比较整个元素", optional },
+    ];
+
+    node_output![
+        port! { name = "added", ty = Vec(Any), desc = "current 中有而 previous 中没有的元素" },
+        port! { name = "removed", ty = Vec(Any), desc = "previous 中有而 current 中没有的元素" },
+        port! { name = "unchanged", ty = Vec(Any), desc = "两次都存在的元素，取 current 中的版本" },
+    ];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::Vec(previous_type, previous)) = inputs.get("previous") else {
+            return Err(Error::ValidationError("previous 输入必须为 Vec 类型".to_string()));
+        };
+        let Some(DataValue::Vec(current_type, current)) = inputs.get("current") else {
+            return Err(Error::ValidationError("current 输入必须为 Vec 类型".to_string()));
+        };
+        if !previous_type.is_compatible_with(current_type) {
+            return Err(Error::ValidationError(format!(
+                "previous 与 current 的元素类型不一致：previous 为 {}，current 为 {}",
+                previous_type, current_type
+            )));
+        }
+        let key = match inputs.get("key") {
+            Some(DataValue::String(key)) if !key.trim().is_empty() => Some(key.trim()),
+            _ => None,
+        };
+
+        let previous_ids = identities(previous, key)?;
+        let current_ids = identities(current, key)?;
+
+        let mut added = Vec::new();
+        let mut unchanged = Vec::new();
+        for item in current {
+            if previous_ids.contains(&identity(item, key)?) {
+                unchanged.push(item.clone());
+            } else {
+                added.push(item.clone());
+            }
+        }
+        let mut removed = Vec::new();
+        for item in previous {
+            if !current_ids.contains(&identity(item, key)?) {
+                removed.push(item.clone());
+            }
+        }
+
+        let current_type: DataType = (**current_type).clone();
+        crate::return_with_node_output![self;
+            "added" => DataValue::Vec(Box::new(current_type.clone()), added),
+            "removed" => DataValue::Vec(previous_type.clone(), removed),
+            "unchanged" => DataValue::Vec(Box::new(current_type), unchanged),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    fn run(previous: DataValue, current: DataValue, key: Option<&str>) -> (Vec<Value>, Vec<Value>, Vec<Value>) {
+        let mut inputs = HashMap::from([("previous".to_string(), previous), ("current".to_string(), current)]);
+        if let Some(key) = key {
+            inputs.insert("key".to_string(), DataValue::String(key.to_string()));
+        }
+        let outputs = DiffNode::new("diff", "diff")
+            .execute(crate::NodeInputFlow::from(inputs))
+            .expect("diff should execute");
+        let list = |port: &str| match outputs.get(port) {
+            Some(DataValue::Vec(_, items)) => items.iter().map(DataValue::to_json).collect::<Vec<_>>(),
+            other => panic!("unexpected {port} output: {other:?}"),
+        };
+        (list("added"), list("removed"), list("unchanged"))
+    }
+
+    fn strings(items: &[&str]) -> DataValue {
+        DataValue::Vec(
+            Box::new(DataType::String),
+            items.iter().map(|item| DataValue::String(item.to_string())).collect(),
+        )
+    }
+
+    fn members(items: Vec<Value>) -> DataValue {
+        DataValue::Vec(Box::new(DataType::Json), items.into_iter().map(DataValue::Json).collect())
+    }
+
+    #[test]
+    fn scalar_lists_are_compared_by_value() {
+        let (added, removed, unchanged) = run(strings(&["alice", "bob"]), strings(&["bob", "carol"]), None);
+
+        assert_eq!(added, vec![json!("carol")]);
+        assert_eq!(removed, vec![json!("alice")]);
+        assert_eq!(unchanged, vec![json!("bob")]);
+    }
+
+    #[test]
+    fn empty_previous_marks_everything_added() {
+        let (added, removed, unchanged) = run(strings(&[]), strings(&["alice", "bob"]), None);
+
+        assert_eq!(added, vec![json!("alice"), json!("bob")]);
+        assert!(removed.is_empty());
+        assert!(unchanged.is_empty());
+    }
+
+    #[test]
+    fn object_lists_are_compared_by_key() {
+        let previous = members(vec![
+            json!({ "user_id": 1, "card": "老成员" }),
+            json!({ "user_id": 2, "card": "退群的人" }),
+        ]);
+        let current = members(vec![
+            json!({ "user_id": 1, "card": "改了群名片" }),
+            json!({ "user_id": 3, "card": "新人" }),
+        ]);
+
+        let (added, removed, unchanged) = run(previous, current, Some("user_id"));
+
+        assert_eq!(added, vec![json!({ "user_id": 3, "card": "新人" })]);
+        assert_eq!(removed, vec![json!({ "user_id": 2, "card": "退群的人" })]);
+        assert_eq!(unchanged, vec![json!({ "user_id": 1, "card": "改了群名片" })]);
+    }
+}
@@ -1,23 +1,19 @@
 use crate::{node_output, DataType, DataValue, Node, Port};
 use std::collections::{HashMap, HashSet};
 use zihuan_core::error::Result;
+use zihuan_core::utils::template::render_template;
 
+/// Names of the `${name}` placeholders in `template`, in first-use order.
 fn extract_variables(template: &str) -> Vec<String> {
     let mut vars = vec![];
     let mut seen = HashSet::new();
-    let mut pos = 0;
-    while let Some(rel) = template[pos..].find("${") {
-        let start = pos + rel + 2;
-        if let Some(end_rel) = template[start..].find('}') {
-            let name = template[start..start + end_rel].trim().to_string();
-            if !name.is_empty() && seen.insert(name.clone()) {
-                vars.push(name);
-            }
-            pos = start + end_rel + 1;
-        } else {
-            break;
+    render_template(template, false, |name| {
+        if !name.is_empty() && seen.insert(name.to_string()) {
+            vars.push(name.to_string());
         }
-    }
+        None
+    })
+    .expect("non-strict template rendering does not fail");
     vars
 }
 
@@ -134,14 +130,7 @@ impl Node for FormatStringNode {
     fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
         self.validate_inputs(&inputs)?;
 
-        let mut result = self.template.clone();
-        for var in &self.variables {
-            let value = match inputs.get(var) {
-                Some(v) => v.to_display_string(),
-                None => String::new(),
-            };
-            result = result.replace(&format!("${{{var}}}"), &value);
-        }
+        let result = render_template(&self.template, false, |name| inputs.get(name).map(DataValue::to_display_string))?;
 
         let mut outputs = HashMap::new();
         outputs.insert("output".to_string(), DataValue::String(result));
@@ -150,3 +139,27 @@ impl Node for FormatStringNode {
         Ok(outputs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_each_placeholder_once_from_its_input() {
+        let mut node = FormatStringNode::new("format", "format");
+        let inline = crate::NodeConfigFlow::from(HashMap::from([(
+            "template".to_string(),
+            DataValue::String("${name}/${ name }/${other}".to_string()),
+        )]));
+        node.apply_inline_config(&inline).unwrap();
+        assert_eq!(node.variables, vec!["name".to_string(), "other".to_string()]);
+
+        let inputs = HashMap::from([
+            ("name".to_string(), DataValue::String("${other}".to_string())),
+            ("other".to_string(), DataValue::Integer(3)),
+        ]);
+        let outputs = node.execute(crate::NodeInputFlow::from(inputs)).unwrap();
+
+        assert!(matches!(outputs.get("output"), Some(DataValue::String(output)) if output == "${other}/${other}/3"));
+    }
+}
@@ -0,0 +1,223 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Shared so that every execution reuses pooled connections.
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("failed to build HTTP client")
+});
+
+/// Calls an external HTTP endpoint. Non-2xx responses are reported through
+/// `success`; only a request that cannot be sent at all fails the node.
+pub struct HttpRequestNode {
+    id: String,
+    name: String,
+}
+
+impl HttpRequestNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+struct HttpRequestSpec {
+    url: String,
+    method: Method,
+    headers: HeaderMap,
+    body: Option<Value>,
+}
+
+fn request_spec(inputs: &crate::NodeInputFlow) -> Result<HttpRequestSpec> {
+    let url = match inputs.get("url") {
+        Some(DataValue::String(url)) if !url.trim().is_empty() => url.trim().to_string(),
+        _ => return Err(Error::ValidationError("url 输入不能为空".to_string())),
+    };
+
+    let method = match inputs.get("method") {
+        Some(DataValue::String(method)) if !method.trim().is_empty() => {
+            Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes())
+                .map_err(|_| Error::ValidationError(format!("不支持的 HTTP 方法：{method}")))?
+        }
+        _ => Method::GET,
+    };
+
+    let mut headers = HeaderMap::new();
+    match inputs.get("headers") {
+        Some(DataValue::Json(Value::Object(entries))) => {
+            for (name, value) in entries {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    other => other.to_string(),
+                };
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| Error::ValidationError(format!("无效的请求头名称：{name}")))?;
+                let value = HeaderValue::from_str(&value)
+                    .map_err(|_| Error::ValidationError(format!("请求头 {name} 的值无效")))?;
+                headers.insert(name, value);
+            }
+        }
+        Some(DataValue::Json(Value::Null)) | None => {}
+        Some(_) => return Err(Error::ValidationError("headers 必须为 JSON 对象".to_string())),
+    }
+
+    let body = match inputs.get("body") {
+        Some(DataValue::Json(Value::Null)) | None => None,
+        Some(DataValue::Json(body)) => Some(body.clone()),
+        Some(_) => return Err(Error::ValidationError("body 必须为 JSON".to_string())),
+    };
+
+    Ok(HttpRequestSpec { url, method, headers, body })
+}
+
+/// `(status, response_body, success)`. A body that is not JSON is returned as
+/// a JSON string so plain-text endpoints remain usable.
+async fn send_request(spec: HttpRequestSpec) -> Result<(i64, Value, bool)> {
+    let mut request = HTTP_CLIENT.request(spec.method, &spec.url).headers(spec.headers);
+    if let Some(body) = spec.body {
+        request = request.json(&body);
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    let text = response.text().await?;
+    let body = if text.trim().is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(&text).unwrap_or(Value::String(text))
+    };
+
+    Ok((i64::from(status.as_u16()), body, status.is_success()))
+}
+
+impl Node for HttpRequestNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("向外部 HTTP 接口发送请求，输出状态码和响应体；响应体不是 JSON 时以字符串输出")
+    }
+
+    node_input![
+        port! { name = "url", ty = String, desc = "请求地址" },
+        port! { name = "method", ty = String, desc = "HTTP 方法，例如 GET / POST / PUT / DELETE，默认 GET", optional },
+        port! { name = "headers", ty = Json, desc = "请求头对象，例如 {\"Authorization\": \"Bearer ...\"}", optional },
+        port! { name = "body", ty = Json, desc = "以 JSON 形式发送的请求体，可选", optional },
+    ];
+
+    node_output![
+        port! { name = "status", ty = Integer, desc = "HTTP 状态码" },
+        port! { name = "response_body", ty = Json, desc = "响应体；为空时输出 null" },
+        port! { name = "success", ty = Boolean, desc = "状态码是否为 2xx" },
+    ];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        zihuan_core::runtime::block_async(self.execute_async(inputs))
+    }
+
+    fn execute_async<'a>(
+        &'a mut self,
+        inputs: crate::NodeInputFlow,
+    ) -> Pin<Box<dyn Future<Output = Result<crate::NodeOutputFlow>> + Send + 'a>> {
+        Box::pin(async move {
+            self.validate_inputs(&inputs)?;
+
+            let spec = request_spec(&inputs)?;
+            let (status, body, success) = send_request(spec).await?;
+
+            crate::return_with_node_output![self;
+                "status" => DataValue::Integer(status),
+                "response_body" => DataValue::Json(body),
+                "success" => DataValue::Boolean(success),
+            ]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use zihuan_core::test_util::{MockHttpServer, MockReply};
+
+    fn inputs(entries: Vec<(&str, DataValue)>) -> crate::NodeInputFlow {
+        crate::NodeInputFlow::from(
+            entries
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+
+    #[tokio::test]
+    async fn posts_json_body_with_headers_and_parses_the_response() {
+        let mut server = MockHttpServer::start("/hook", vec![MockReply::new(201, r#"{"id":7}"#)]).await;
+        let mut node = HttpRequestNode::new("http", "http");
+
+        let outputs = node
+            .execute_async(inputs(vec![
+                ("url", DataValue::String(server.url())),
+                ("method", DataValue::String("post".to_string())),
+                ("headers", DataValue::Json(serde_json::json!({"X-Token": "abc"}))),
+                ("body", DataValue::Json(serde_json::json!({"text": "hi"}))),
+            ]))
+            .await
+            .unwrap();
+
+        let request = server.next_request().await;
+        assert!(request.head.starts_with("POST /hook"));
+        assert_eq!(request.header("x-token"), Some("abc"));
+        assert_eq!(request.body_text(), r#"{"text":"hi"}"#);
+        assert!(matches!(outputs.get("status"), Some(DataValue::Integer(201))));
+        assert!(matches!(outputs.get("response_body"), Some(DataValue::Json(body)) if body["id"] == 7));
+        assert!(matches!(outputs.get("success"), Some(DataValue::Boolean(true))));
+    }
+
+    #[tokio::test]
+    async fn error_status_is_reported_without_failing_the_node() {
+        let server = MockHttpServer::start("/hook", vec![MockReply::new(404, "not found")]).await;
+        let mut node = HttpRequestNode::new("http", "http");
+
+        let outputs = node
+            .execute_async(inputs(vec![("url", DataValue::String(server.url()))]))
+            .await
+            .unwrap();
+
+        assert!(matches!(outputs.get("status"), Some(DataValue::Integer(404))));
+        assert!(
+            matches!(outputs.get("response_body"), Some(DataValue::Json(Value::String(body))) if body == "not found")
+        );
+        assert!(matches!(outputs.get("success"), Some(DataValue::Boolean(false))));
+    }
+
+    #[test]
+    fn invalid_method_is_rejected() {
+        let err = HttpRequestNode::new("http", "http")
+            .execute(inputs(vec![
+                ("url", DataValue::String("http://127.0.0.1:1/".to_string())),
+                ("method", DataValue::String("BAD METHOD".to_string())),
+            ]))
+            .unwrap_err();
+        assert!(err.to_string().contains("不支持的 HTTP 方法"));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+
+pub struct JsonSchemaValidateNode {
+    id: String,
+    name: String,
+}
+
+impl JsonSchemaValidateNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+/// A compiled JSON Schema, for checking several values against the same schema.
+pub struct JsonSchemaValidator(jsonschema::Validator);
+
+impl JsonSchemaValidator {
+    pub fn new(schema: &serde_json::Value) -> Result<Self> {
+        jsonschema::validator_for(schema)
+            .map(Self)
+            .map_err(|err| Error::ValidationError(format!("schema 不是有效的 JSON Schema：{err}")))
+    }
+
+    /// Errors for `instance`, each prefixed with the JSON pointer of the
+    /// offending value (`/` for the document root).
+    pub fn errors(&self, instance: &serde_json::Value) -> Vec<String> {
+        self.0
+            .iter_errors(instance)
+            .map(|err| {
+                let path = err.instance_path.to_string();
+                format!("{}: {}", if path.is_empty() { "/" } else { path.as_str() }, err)
+            })
+            .collect()
+    }
+}
+
+/// Validates `instance` against `schema`, see [`JsonSchemaValidator::errors`].
+pub fn validate_json_against_schema(instance: &serde_json::Value, schema: &serde_json::Value) -> Result<Vec<String>> {
+    Ok(JsonSchemaValidator::new(schema)?.errors(instance))
+}
+
+impl Node for JsonSchemaValidateNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("使用 JSON Schema 校验 JSON 数据，输出是否通过以及全部校验错误")
+    }
+
+    node_input![
+        port! { name = "json", ty = Json, desc = "待校验的 JSON 数据" },
+        port! { name = "schema", ty = Json, desc = "JSON Schema 定义" },
+    ];
+
+    node_output![
+        port! { name = "valid", ty = Boolean, desc = "数据是否符合 schema" },
+        port! { name = "errors", ty = Vec(String), desc = "校验错误信息，通过时为空列表" },
+    ];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::Json(instance)) = inputs.get("json") else {
+            return Err(Error::ValidationError("json 输入不存在".to_string()));
+        };
+        let Some(DataValue::Json(schema)) = inputs.get("schema") else {
+            return Err(Error::ValidationError("schema 输入不存在".to_string()));
+        };
+
+        let errors = validate_json_against_schema(instance, schema)?;
+
+        crate::return_with_node_output![self;
+            "valid" => DataValue::Boolean(errors.is_empty()),
+            "errors" => DataValue::Vec(
+                Box::new(DataType::String),
+                errors.into_iter().map(DataValue::String).collect(),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn run(instance: serde_json::Value, schema: serde_json::Value) -> Result<crate::NodeOutputFlow> {
+        JsonSchemaValidateNode::new("validate", "validate").execute(crate::NodeInputFlow::from(HashMap::from([
+            ("json".to_string(), DataValue::Json(instance)),
+            ("schema".to_string(), DataValue::Json(schema)),
+        ])))
+    }
+
+    fn user_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer", "minimum": 0 }
+            },
+            "required": ["name"]
+        })
+    }
+
+    #[test]
+    fn conforming_document_is_valid() {
+        let outputs = run(json!({ "name": "紫幻", "age": 3 }), user_schema()).unwrap();
+
+        assert!(matches!(outputs.get("valid"), Some(DataValue::Boolean(true))));
+        assert!(matches!(outputs.get("errors"), Some(DataValue::Vec(_, errors)) if errors.is_empty()));
+    }
+
+    #[test]
+    fn non_conforming_document_reports_each_violation() {
+        let outputs = run(json!({ "age": -1 }), user_schema()).unwrap();
+
+        assert!(matches!(outputs.get("valid"), Some(DataValue::Boolean(false))));
+        let Some(DataValue::Vec(_, errors)) = outputs.get("errors") else {
+            panic!("missing errors output");
+        };
+        let mut errors: Vec<String> = errors.iter().map(DataValue::to_display_string).collect();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "/: \"name\" is a required property".to_string(),
+                "/age: -1 is less than the minimum of 0".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_schema_is_rejected() {
+        let err = run(json!({}), json!({ "type": "not-a-type" })).unwrap_err();
+
+        assert!(err.to_string().contains("schema 不是有效的 JSON Schema"));
+    }
+}
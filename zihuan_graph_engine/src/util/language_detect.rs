@@ -0,0 +1,99 @@
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+
+pub const UNKNOWN_LANGUAGE: &str = "unknown";
+/// Below this many non-whitespace chars the detector mostly guesses.
+const MIN_DETECT_CHARS: usize = 4;
+
+/// Detects the language of `text` as an ISO 639-3 code (e.g. `eng`, `cmn`).
+///
+/// Short or ambiguous text yields `("unknown", 0.0)` instead of a guess.
+pub fn detect_language(text: &str) -> (String, f64) {
+    if text.chars().filter(|ch| !ch.is_whitespace()).count() < MIN_DETECT_CHARS {
+        return (UNKNOWN_LANGUAGE.to_string(), 0.0);
+    }
+    match whatlang::detect(text) {
+        Some(info) if info.is_reliable() => (info.lang().code().to_string(), info.confidence()),
+        _ => (UNKNOWN_LANGUAGE.to_string(), 0.0),
+    }
+}
+
+pub struct LanguageDetectNode {
+    id: String,
+    name: String,
+}
+
+impl LanguageDetectNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+impl Node for LanguageDetectNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("检测文本语言，输出 ISO 639-3 语言代码与置信度；文本过短或无法判断时输出 unknown")
+    }
+
+    node_input![port! { name = "text", ty = String, desc = "待检测语言的文本" },];
+
+    node_output![
+        port! { name = "language", ty = String, desc = "ISO 639-3 语言代码，例如 eng、cmn；无法判断时为 unknown" },
+        port! { name = "confidence", ty = Float, desc = "检测置信度，范围 0~1；unknown 时为 0" },
+        port! { name = "detected", ty = Boolean, desc = "是否成功识别出语言" },
+    ];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::String(text)) = inputs.get("text") else {
+            return Err(Error::ValidationError("text 输入不存在".to_string()));
+        };
+        let (language, confidence) = detect_language(text);
+
+        crate::return_with_node_output![self;
+            "detected" => DataValue::Boolean(language != UNKNOWN_LANGUAGE),
+            "language" => DataValue::String(language),
+            "confidence" => DataValue::Float(confidence),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_clear_english() {
+        let (language, confidence) =
+            detect_language("The weather is lovely today, shall we take a walk in the park after lunch?");
+
+        assert_eq!(language, "eng");
+        assert!(confidence > 0.5, "confidence was {confidence}");
+    }
+
+    #[test]
+    fn detects_clear_chinese() {
+        let (language, confidence) = detect_language("今天天气很好，我们吃完午饭以后一起去公园散步吧。");
+
+        assert_eq!(language, "cmn");
+        assert!(confidence > 0.5, "confidence was {confidence}");
+    }
+
+    #[test]
+    fn short_ambiguous_text_is_unknown() {
+        for text in ["ok", "666", "  哈 "] {
+            assert_eq!(detect_language(text), (UNKNOWN_LANGUAGE.to_string(), 0.0), "input {text:?}");
+        }
+    }
+}
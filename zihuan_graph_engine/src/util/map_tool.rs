@@ -0,0 +1,202 @@
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::{json, Value};
+
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::tooling::{normalized_tool_arguments, FunctionTool};
+
+const DEFAULT_CONCURRENCY: i64 = 4;
+
+pub struct MapToolNode {
+    id: String,
+    name: String,
+}
+
+impl MapToolNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+/// Result slot for one argument set: the tool output, or `{"error": ...}` when
+/// the arguments do not match the tool's schema or the call failed, so a single
+/// bad element does not abort the whole list.
+fn call_tool(tool: &dyn FunctionTool, arguments: Value) -> Value {
+    let arguments = normalized_tool_arguments(&arguments);
+    if let Err(message) = tool.validate_arguments(&arguments) {
+        return json!({ "error": message });
+    }
+    match tool.call(arguments) {
+        Ok(result) => result,
+        Err(err) => json!({ "error": err.to_string() }),
+    }
+}
+
+/// Calls `tool` once per argument set, running at most `concurrency` calls at a
+/// time. Results keep the order of `arguments`.
+fn map_tool(tool: &Arc<dyn FunctionTool>, arguments: Vec<Value>, concurrency: usize) -> Vec<Value> {
+    if concurrency <= 1 {
+        return arguments.into_iter().map(|args| call_tool(tool.as_ref(), args)).collect();
+    }
+
+    let mut results = Vec::with_capacity(arguments.len());
+    let mut pending = arguments.into_iter().peekable();
+    while pending.peek().is_some() {
+        let batch: Vec<Value> = pending.by_ref().take(concurrency).collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = batch
+                .into_iter()
+                .map(|args| scope.spawn(move || call_tool(tool.as_ref(), args)))
+                .collect();
+            for handle in handles {
+                results.push(handle.join().unwrap_or_else(|_| json!({ "error": "工具调用发生 panic" })));
+            }
+        });
+    }
+    results
+}
+
+impl Node for MapToolNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("对参数列表中的每一项调用同一个工具，按原顺序输出结果列表；单项失败时该位置输出 {\"error\": ...}")
+    }
+
+    node_input![
+        port! { name = "tools", ty = FunctionTools, desc = "可用的工具列表" },
+        port! { name = "tool_name", ty = String, desc = "要调用的工具名称" },
+        port! { name = "arguments", ty = Vec(Json), desc = "每次调用的参数对象列表" },
+        port! { name = "concurrency", ty = Integer, desc = "同时进行的调用数，默认 4", optional },
+    ];
+
+    node_output![port! { name = "results", ty = Vec(Json), desc = "与 arguments 按下标对应的调用结果" },];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::FunctionTools(tools)) = inputs.get("tools") else {
+            return Err(Error::ValidationError("tools 输入不存在".to_string()));
+        };
+        let Some(DataValue::String(tool_name)) = inputs.get("tool_name") else {
+            return Err(Error::ValidationError("tool_name 输入不存在".to_string()));
+        };
+        let Some(DataValue::Vec(_, arguments)) = inputs.get("arguments") else {
+            return Err(Error::ValidationError("arguments 输入必须为 Vec 类型".to_string()));
+        };
+        let concurrency = match inputs.get("concurrency") {
+            Some(DataValue::Integer(value)) if *value > 0 => *value,
+            Some(DataValue::Integer(value)) => {
+                return Err(Error::ValidationError(format!("concurrency 必须大于 0：{value}")));
+            }
+            _ => DEFAULT_CONCURRENCY,
+        };
+
+        let tool_name = tool_name.trim();
+        let Some(tool) = tools.iter().find(|tool| tool.name() == tool_name) else {
+            return Err(Error::ValidationError(format!("找不到名为 \"{tool_name}\" 的工具")));
+        };
+        let arguments: Vec<Value> = arguments.iter().map(DataValue::to_json).collect();
+
+        let results = map_tool(tool, arguments, concurrency as usize);
+
+        crate::return_with_node_output![self;
+            "results" => DataValue::Vec(Box::new(DataType::Json), results.into_iter().map(DataValue::Json).collect()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug)]
+    struct MathTool;
+
+    impl FunctionTool for MathTool {
+        fn name(&self) -> &str {
+            "math"
+        }
+
+        fn description(&self) -> &str {
+            "对 a、b 做四则运算"
+        }
+
+        fn parameters(&self) -> Value {
+            json!({
+                "type": "object",
+                "properties": {
+                    "op": { "type": "string", "enum": ["add", "mul", "div"] },
+                    "a": { "type": "number" },
+                    "b": { "type": "number" }
+                },
+                "required": ["op", "a", "b"]
+            })
+        }
+
+        fn call(&self, arguments: Value) -> Result<Value> {
+            let a = arguments["a"].as_f64().unwrap_or_default();
+            let b = arguments["b"].as_f64().unwrap_or_default();
+            match arguments["op"].as_str() {
+                Some("add") => Ok(json!(a + b)),
+                Some("mul") => Ok(json!(a * b)),
+                Some("div") if b == 0.0 => Err(Error::ValidationError("除数不能为 0".to_string())),
+                Some("div") => Ok(json!(a / b)),
+                other => Err(Error::ValidationError(format!("不支持的运算：{other:?}"))),
+            }
+        }
+    }
+
+    fn run(arguments: Vec<Value>, concurrency: i64) -> Vec<Value> {
+        let tools: Vec<Arc<dyn FunctionTool>> = vec![Arc::new(MathTool)];
+        let outputs = MapToolNode::new("map_tool", "map_tool")
+            .execute(crate::NodeInputFlow::from(HashMap::from([
+                ("tools".to_string(), DataValue::FunctionTools(tools)),
+                ("tool_name".to_string(), DataValue::String("math".to_string())),
+                (
+                    "arguments".to_string(),
+                    DataValue::Vec(Box::new(DataType::Json), arguments.into_iter().map(DataValue::Json).collect()),
+                ),
+                ("concurrency".to_string(), DataValue::Integer(concurrency)),
+            ])))
+            .expect("map tool should execute");
+        match outputs.get("results") {
+            Some(DataValue::Vec(_, items)) => items.iter().map(DataValue::to_json).collect(),
+            other => panic!("unexpected results output: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn math_tool_is_mapped_over_arguments_in_order() {
+        let arguments = vec![
+            json!({ "op": "add", "a": 1, "b": 2 }),
+            json!({ "op": "div", "a": 1, "b": 0 }),
+            json!({ "op": "mul", "a": 3, "b": 4 }),
+            json!({ "op": "add", "a": 1 }),
+            json!({ "op": "pow", "a": 2, "b": 3 }),
+        ];
+
+        for concurrency in [1, 2] {
+            let results = run(arguments.clone(), concurrency);
+
+            assert_eq!(results.len(), 5);
+            assert_eq!(results[0], json!(3.0));
+            assert!(results[1]["error"].as_str().unwrap().contains("除数不能为 0"));
+            assert_eq!(results[2], json!(12.0));
+            assert!(results[3]["error"].as_str().unwrap().contains("missing required field 'b'"));
+            assert!(results[4]["error"].as_str().unwrap().contains("arguments.op must be one of"));
+        }
+    }
+}
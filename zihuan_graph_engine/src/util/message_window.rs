@@ -0,0 +1,170 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::LLMMessage;
+
+const DEFAULT_WINDOW_SIZE: i64 = 20;
+/// Conversations kept at once; the one updated least recently is dropped first.
+const MAX_CONVERSATIONS: usize = 4096;
+
+#[derive(Default)]
+struct MessageWindows {
+    windows: HashMap<String, (u64, VecDeque<LLMMessage>)>,
+    clock: u64,
+}
+
+impl MessageWindows {
+    /// Appends `message` to the window of `key`, trims it to `size` and returns a copy.
+    fn push(&mut self, key: String, message: LLMMessage, size: usize) -> Vec<LLMMessage> {
+        self.clock += 1;
+        if !self.windows.contains_key(&key) && self.windows.len() >= MAX_CONVERSATIONS {
+            if let Some(stale) = self
+                .windows
+                .iter()
+                .min_by_key(|(_, (touched, _))| *touched)
+                .map(|(key, _)| key.clone())
+            {
+                self.windows.remove(&stale);
+            }
+        }
+
+        let (touched, window) = self.windows.entry(key).or_default();
+        *touched = self.clock;
+        window.push_back(message);
+        while window.len() > size {
+            window.pop_front();
+        }
+        window.iter().cloned().collect()
+    }
+}
+
+// Each event triggers its own graph run, so windows have to outlive a single
+// node instance, like the debounce buffers. Keys carry the graph's state scope
+// so graphs reusing a node id keep separate windows.
+static MESSAGE_WINDOWS: Lazy<Mutex<MessageWindows>> = Lazy::new(|| Mutex::new(MessageWindows::default()));
+
+pub struct MessageWindowNode {
+    id: String,
+    name: String,
+    state_scope: String,
+}
+
+impl MessageWindowNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            state_scope: String::new(),
+        }
+    }
+}
+
+impl Node for MessageWindowNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("按会话保存最近 N 条消息的滑动窗口，每次输入后输出当前窗口内的消息列表")
+    }
+
+    node_input![
+        port! { name = "message", ty = LLMMessage, desc = "追加到窗口的消息" },
+        port! { name = "conversation_id", ty = String, desc = "会话 ID，不同会话的窗口互相独立；默认使用节点 ID", optional },
+        port! { name = "window_size", ty = Integer, desc = "窗口保留的消息条数，默认 20", optional },
+    ];
+
+    node_output![port! { name = "messages", ty = Vec(LLMMessage), desc = "当前窗口内的消息，按时间先后排列" },];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::LLMMessage(message)) = inputs.get("message") else {
+            return Err(Error::ValidationError("message 输入不存在".to_string()));
+        };
+        let key = match inputs.get("conversation_id") {
+            Some(DataValue::String(id)) if !id.trim().is_empty() => {
+                format!("{}:{}:{}", self.state_scope, self.id, id.trim())
+            }
+            _ => format!("{}:{}", self.state_scope, self.id),
+        };
+        let window_size = match inputs.get("window_size") {
+            Some(DataValue::Integer(value)) if *value > 0 => *value,
+            Some(DataValue::Integer(value)) => {
+                return Err(Error::ValidationError(format!("window_size 必须大于 0：{value}")));
+            }
+            _ => DEFAULT_WINDOW_SIZE,
+        };
+
+        let window = MESSAGE_WINDOWS.lock().unwrap().push(key, message.clone(), window_size as usize);
+
+        crate::return_with_node_output![self;
+            "messages" => DataValue::Vec(
+                Box::new(DataType::LLMMessage),
+                window.into_iter().map(DataValue::LLMMessage).collect(),
+            ),
+        ]
+    }
+
+    fn set_state_scope(&mut self, scope: &str) {
+        self.state_scope = scope.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(conversation_id: &str, text: &str) -> Vec<String> {
+        push_in("", conversation_id, text)
+    }
+
+    fn push_in(scope: &str, conversation_id: &str, text: &str) -> Vec<String> {
+        let mut node = MessageWindowNode::new("message_window_test", "window");
+        node.set_state_scope(scope);
+        let outputs = node
+            .execute(crate::NodeInputFlow::from(HashMap::from([
+                ("message".to_string(), DataValue::LLMMessage(LLMMessage::user(text))),
+                ("conversation_id".to_string(), DataValue::String(conversation_id.to_string())),
+                ("window_size".to_string(), DataValue::Integer(3)),
+            ])))
+            .expect("message window should execute");
+        match outputs.get("messages") {
+            Some(DataValue::Vec(_, items)) => items
+                .iter()
+                .map(|item| match item {
+                    DataValue::LLMMessage(message) => message.content_text_owned().unwrap_or_default(),
+                    other => panic!("unexpected window item: {other:?}"),
+                })
+                .collect(),
+            other => panic!("unexpected messages output: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn oldest_message_is_evicted_per_conversation() {
+        push("group:1", "一");
+        push("group:1", "二");
+        assert_eq!(push("group:2", "别的会话"), vec!["别的会话"]);
+        push("group:1", "三");
+
+        assert_eq!(push("group:1", "四"), vec!["二", "三", "四"]);
+        assert_eq!(push("group:2", "继续"), vec!["别的会话", "继续"]);
+    }
+
+    #[test]
+    fn graphs_sharing_a_node_id_keep_separate_windows() {
+        push_in("graph-a", "group:9", "甲");
+
+        assert_eq!(push_in("graph-b", "group:9", "乙"), vec!["乙"]);
+        assert_eq!(push_in("graph-a", "group:9", "丙"), vec!["甲", "丙"]);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::{LLMMessage, MessageRole};
+
+const DEFAULT_SYSTEM_TEMPLATE: &str = "System: {}\n";
+const DEFAULT_USER_TEMPLATE: &str = "User: {}\n";
+const DEFAULT_ASSISTANT_TEMPLATE: &str = "Assistant: {}\n";
+const DEFAULT_TOOL_TEMPLATE: &str = "Tool: {}\n";
+
+pub struct MessagesToPromptNode {
+    id: String,
+    name: String,
+}
+
+impl MessagesToPromptNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+/// Per-role templates; `{}` in a template is replaced by the message text.
+struct RoleTemplates {
+    system: String,
+    user: String,
+    assistant: String,
+    tool: String,
+}
+
+impl RoleTemplates {
+    fn render(&self, message: &LLMMessage) -> String {
+        let template = match message.role {
+            MessageRole::System => &self.system,
+            MessageRole::User => &self.user,
+            MessageRole::Assistant => &self.assistant,
+            MessageRole::Tool => &self.tool,
+        };
+        template.replace("{}", &message.content_text_owned().unwrap_or_default())
+    }
+}
+
+fn template_input(inputs: &crate::NodeInputFlow, port: &str, default: &str) -> String {
+    match inputs.get(port) {
+        Some(DataValue::String(template)) if !template.is_empty() => template.clone(),
+        _ => default.to_string(),
+    }
+}
+
+impl Node for MessagesToPromptNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("按角色模板把消息列表依次拼接成单个提示词字符串，用于只接受 prompt 字符串的补全接口")
+    }
+
+    node_input![
+        port! { name = "messages", ty = Vec(LLMMessage), desc = "要拼接的消息列表" },
+        port! { name = "system_template", ty = String, desc = "system 消息模板，{} 处替换为消息内容，默认 \"System: {}\\n\"", optional },
+        port! { name = "user_template", ty = String, desc = "user 消息模板，默认 \"User: {}\\n\"", optional },
+        port! { name = "assistant_template", ty = String, desc = "assistant 消息模板，默认 \"Assistant: {}\\n\"", optional },
+        port! { name = "tool_template", ty = String, desc = "tool 消息模板，默认 \"Tool: {}\\n\"", optional },
+    ];
+
+    node_output![port! { name = "prompt", ty = String, desc = "按消息顺序拼接后的提示词" },];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::Vec(_, messages)) = inputs.get("messages") else {
+            return Err(Error::ValidationError("messages 输入必须为 Vec 类型".to_string()));
+        };
+        let templates = RoleTemplates {
+            system: template_input(&inputs, "system_template", DEFAULT_SYSTEM_TEMPLATE),
+            user: template_input(&inputs, "user_template", DEFAULT_USER_TEMPLATE),
+            assistant: template_input(&inputs, "assistant_template", DEFAULT_ASSISTANT_TEMPLATE),
+            tool: template_input(&inputs, "tool_template", DEFAULT_TOOL_TEMPLATE),
+        };
+
+        let mut prompt = String::new();
+        for item in messages {
+            let DataValue::LLMMessage(message) = item else {
+                return Err(Error::ValidationError(format!(
+                    "messages 中的元素必须为 LLMMessage，实际为 {}",
+                    item.data_type()
+                )));
+            };
+            prompt.push_str(&templates.render(message));
+        }
+
+        crate::return_with_node_output![self;
+            "prompt" => DataValue::String(prompt),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn run(messages: Vec<LLMMessage>, extra: Vec<(&str, &str)>) -> String {
+        let mut inputs = HashMap::from([(
+            "messages".to_string(),
+            DataValue::Vec(
+                Box::new(DataType::LLMMessage),
+                messages.into_iter().map(DataValue::LLMMessage).collect(),
+            ),
+        )]);
+        for (port, value) in extra {
+            inputs.insert(port.to_string(), DataValue::String(value.to_string()));
+        }
+        let outputs = MessagesToPromptNode::new("messages_to_prompt", "messages_to_prompt")
+            .execute(crate::NodeInputFlow::from(inputs))
+            .expect("messages to prompt should execute");
+        match outputs.get("prompt") {
+            Some(DataValue::String(prompt)) => prompt.clone(),
+            other => panic!("unexpected prompt output: {other:?}"),
+        }
+    }
+
+    fn conversation() -> Vec<LLMMessage> {
+        vec![
+            LLMMessage::system("你是一个助手"),
+            LLMMessage::user("1+1 等于几？"),
+            LLMMessage::assistant_text("等于 2"),
+        ]
+    }
+
+    #[test]
+    fn messages_are_flattened_in_order_with_role_prefixes() {
+        assert_eq!(
+            run(conversation(), vec![]),
+            "System: 你是一个助手\nUser: 1+1 等于几？\nAssistant: 等于 2\n"
+        );
+    }
+
+    #[test]
+    fn role_templates_can_be_overridden() {
+        let prompt = run(
+            conversation(),
+            vec![
+                ("user_template", "### 问：{}\n"),
+                ("assistant_template", "### 答：{}\n"),
+            ],
+        );
+
+        assert_eq!(prompt, "System: 你是一个助手\n### 问：1+1 等于几？\n### 答：等于 2\n");
+    }
+}
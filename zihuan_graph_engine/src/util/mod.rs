@@ -9,24 +9,34 @@ pub mod build_multimodal_user_message;
 pub mod concat_vec;
 pub mod conditional;
 pub mod conditional_router;
+pub mod context_inject;
 pub mod current_time;
+pub mod debounce;
+pub mod delay;
+pub mod diff;
 pub mod format_string;
 pub mod function;
 pub mod function_inputs;
 pub mod function_outputs;
 pub mod graph_inputs;
 pub mod graph_outputs;
+pub mod http_request;
 pub mod join_string;
 pub mod json_extract;
 pub mod json_parser;
+pub mod json_schema_validate;
 pub mod json_to_qq_message_vec;
+pub mod language_detect;
 pub mod llm_message_content_as_json;
 pub mod llm_message_session_cache;
 pub mod llm_message_session_cache_get;
 pub mod llm_message_session_cache_set;
 pub mod llm_message_to_string;
+pub mod map_tool;
 pub mod message_content;
 pub mod message_list_data;
+pub mod message_window;
+pub mod messages_to_prompt;
 pub mod preview_message_list;
 pub mod preview_qq_message_list;
 pub mod preview_string;
@@ -45,7 +55,9 @@ pub mod string_to_image_content_part;
 pub mod string_to_llm_message;
 pub mod string_to_plain_text;
 pub mod switch;
+pub mod template;
 pub mod tool_result_node;
+pub mod tool_result_to_message;
 
 pub mod llm_message_session_cache_clear {
     use crate::data_value::LLMMessageSessionCacheRef;
@@ -137,25 +149,35 @@ pub use build_multimodal_user_message::BuildMultimodalUserMessageNode;
 pub use concat_vec::ConcatVecNode;
 pub use conditional::ConditionalNode;
 pub use conditional_router::ConditionalRouterNode;
+pub use context_inject::ContextInjectNode;
 pub use current_time::CurrentTimeNode;
+pub use debounce::DebounceNode;
+pub use delay::DelayNode;
+pub use diff::DiffNode;
 pub use format_string::FormatStringNode;
 pub use function::FunctionNode;
 pub use function_inputs::FunctionInputsNode;
 pub use function_outputs::FunctionOutputsNode;
 pub use graph_inputs::GraphInputsNode;
 pub use graph_outputs::GraphOutputsNode;
+pub use http_request::HttpRequestNode;
 pub use join_string::JoinStringNode;
 pub use json_extract::JsonExtractNode;
 pub use json_parser::JsonParserNode;
+pub use json_schema_validate::JsonSchemaValidateNode;
 pub use json_to_qq_message_vec::JsonToQQMessageVecNode;
+pub use language_detect::LanguageDetectNode;
 pub use llm_message_content_as_json::LLMMessageContentAsJsonNode;
 pub use llm_message_session_cache::LLMMessageSessionCacheNode;
 pub use llm_message_session_cache_clear::LLMMessageSessionCacheClearNode;
 pub use llm_message_session_cache_get::LLMMessageSessionCacheGetNode;
 pub use llm_message_session_cache_set::LLMMessageSessionCacheSetNode;
 pub use llm_message_to_string::LLMMessageToStringNode;
+pub use map_tool::MapToolNode;
 pub use message_content::MessageContentNode;
 pub use message_list_data::MessageListDataNode;
+pub use message_window::MessageWindowNode;
+pub use messages_to_prompt::MessagesToPromptNode;
 pub use preview_message_list::PreviewMessageListNode;
 pub use preview_qq_message_list::PreviewQQMessageListNode;
 pub use preview_string::PreviewStringNode;
@@ -174,4 +196,6 @@ pub use string_to_image_content_part::StringToImageMessagePartNode;
 pub use string_to_llm_message::StringToLLMMessageNode;
 pub use string_to_plain_text::StringToPlainTextNode;
 pub use switch::SwitchNode;
+pub use template::TemplateNode;
 pub use tool_result_node::ToolResultNode;
+pub use tool_result_to_message::ToolResultToMessageNode;
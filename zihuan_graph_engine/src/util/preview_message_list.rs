@@ -1,6 +1,10 @@
+use crate::graph_io::NodeGraphDefinition;
+use crate::preview_renderer::{resolve_preview_input, NodeRenderer};
 use crate::{node_input, node_output, DataType, Node, Port};
+use serde_json::Value;
 use std::collections::HashMap;
 use zihuan_core::error::Result;
+use zihuan_core::llm::{role_to_str, LLMMessage, MessagePart};
 
 pub struct PreviewMessageListNode {
     id: String,
@@ -48,3 +52,47 @@ impl Node for PreviewMessageListNode {
         Ok(outputs)
     }
 }
+
+/// Renders the `messages` input as one `[role] text` line per message.
+pub struct PreviewMessageListRenderer;
+
+impl NodeRenderer for PreviewMessageListRenderer {
+    fn render_preview(
+        &self,
+        node_id: &str,
+        graph: &NodeGraphDefinition,
+        inline_inputs: &HashMap<String, Value>,
+    ) -> String {
+        let Some(value) = resolve_preview_input(node_id, "messages", graph, inline_inputs) else {
+            return String::new();
+        };
+        let Ok(messages) = serde_json::from_value::<Vec<LLMMessage>>(value.clone()) else {
+            return value.to_string();
+        };
+
+        messages
+            .iter()
+            .map(|message| {
+                let role = role_to_str(&message.role);
+                let speaker = match message.name.as_deref() {
+                    Some(name) => format!("{role}·{name}"),
+                    None => role.to_string(),
+                };
+                let mut text = message
+                    .parts
+                    .iter()
+                    .map(|part| match part {
+                        MessagePart::Text { text } => text.as_str(),
+                        MessagePart::Image { .. } => "[图片]",
+                        MessagePart::Video { .. } => "[视频]",
+                    })
+                    .collect::<String>();
+                for call in &message.tool_calls {
+                    text.push_str(&format!(" → {}()", call.function.name));
+                }
+                format!("[{speaker}] {}", text.trim())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
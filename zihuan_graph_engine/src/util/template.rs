@@ -0,0 +1,114 @@
+use serde_json::{Map, Value};
+
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+use zihuan_core::utils::template::render_template;
+
+/// Renders a `${name}` template whose text and variables both arrive through
+/// ports, unlike `FormatStringNode` whose template is fixed in the editor.
+pub struct TemplateNode {
+    id: String,
+    name: String,
+}
+
+impl TemplateNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+fn variable_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+impl Node for TemplateNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("用 variables 对象中的同名字段替换模板中的 ${变量名}，输出渲染后的字符串")
+    }
+
+    node_input![
+        port! { name = "template", ty = String, desc = "模板字符串，使用 ${变量名} 引用 variables 中的字段" },
+        port! { name = "variables", ty = Json, desc = "变量对象，键为变量名；非字符串值按 JSON 文本插入" },
+        port! { name = "strict", ty = Boolean, desc = "为 true 时缺少变量会报错，否则替换为空字符串，默认 false", optional },
+    ];
+
+    node_output![port! { name = "output", ty = String, desc = "渲染后的字符串" },];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let template = match inputs.get("template") {
+            Some(DataValue::String(template)) => template,
+            _ => return Err(Error::ValidationError("template 输入必须为 String 类型".to_string())),
+        };
+        let variables = match inputs.get("variables") {
+            Some(DataValue::Json(Value::Object(variables))) => variables.clone(),
+            Some(DataValue::Json(Value::Null)) => Map::new(),
+            _ => return Err(Error::ValidationError("variables 输入必须为 JSON 对象".to_string())),
+        };
+        let strict = matches!(inputs.get("strict"), Some(DataValue::Boolean(true)));
+
+        let output = render_template(template, strict, |name| variables.get(name).map(variable_text))?;
+
+        crate::return_with_node_output![self;
+            "output" => DataValue::String(output),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn run(template: &str, variables: Value, strict: Option<bool>) -> Result<String> {
+        let mut inputs = HashMap::from([
+            ("template".to_string(), DataValue::String(template.to_string())),
+            ("variables".to_string(), DataValue::Json(variables)),
+        ]);
+        if let Some(strict) = strict {
+            inputs.insert("strict".to_string(), DataValue::Boolean(strict));
+        }
+        let outputs = TemplateNode::new("template", "template").execute(crate::NodeInputFlow::from(inputs))?;
+        match outputs.get("output") {
+            Some(DataValue::String(output)) => Ok(output.clone()),
+            other => panic!("unexpected output: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn substitutes_strings_and_json_values() {
+        let output = run(
+            "${name} 今年 ${ age } 岁，标签：${tags}，${note}",
+            serde_json::json!({"name": "紫幻", "age": 3, "tags": ["bot"], "note": "${name}"}),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(output, "紫幻 今年 3 岁，标签：[\"bot\"]，${name}");
+    }
+
+    #[test]
+    fn missing_variables_are_blank_unless_strict() {
+        let variables = serde_json::json!({"name": "紫幻"});
+
+        assert_eq!(run("${name}:${missing}", variables.clone(), Some(false)).unwrap(), "紫幻:");
+        let err = run("${name}:${missing}", variables, Some(true)).unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{node_input, node_output, DataType, DataValue, Node, Port};
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::LLMMessage;
+
+pub struct ToolResultToMessageNode {
+    id: String,
+    name: String,
+}
+
+impl ToolResultToMessageNode {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+}
+
+/// Content of the tool message for `result`: JSON strings are used as-is, any
+/// other value is serialized.
+fn tool_result_content(result: &serde_json::Value) -> String {
+    match result {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl Node for ToolResultToMessageNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("将工具返回的 JSON 结果转换为只含一条 role=tool 消息的列表，可直接与对话列表拼接")
+    }
+
+    node_input![
+        port! { name = "result", ty = Json, desc = "工具执行结果，字符串原样作为内容，其余值序列化为 JSON 文本" },
+        port! { name = "tool_call_id", ty = String, desc = "发起调用的 tool_call id" },
+        port! { name = "tool_name", ty = String, desc = "可选：工具名称，写入消息的 name 字段", optional },
+    ];
+
+    node_output![port! { name = "messages", ty = Vec(LLMMessage), desc = "只含一条 role=tool 消息的列表" },];
+
+    fn execute(&mut self, inputs: crate::NodeInputFlow) -> Result<crate::NodeOutputFlow> {
+        self.validate_inputs(&inputs)?;
+
+        let Some(DataValue::Json(result)) = inputs.get("result") else {
+            return Err(Error::ValidationError("result is required".to_string()));
+        };
+        let tool_call_id = match inputs.get("tool_call_id") {
+            Some(DataValue::String(id)) if !id.trim().is_empty() => id.trim().to_string(),
+            _ => return Err(Error::ValidationError("tool_call_id is required".to_string())),
+        };
+
+        let mut message = LLMMessage::tool_result(tool_call_id, tool_result_content(result));
+        if let Some(DataValue::String(tool_name)) = inputs.get("tool_name") {
+            message = message.with_name(tool_name.as_str());
+        }
+
+        crate::return_with_node_output![self;
+            "messages" => DataValue::Vec(Box::new(DataType::LLMMessage), vec![DataValue::LLMMessage(message)]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use zihuan_core::llm::MessageRole;
+
+    #[test]
+    fn tool_result_becomes_single_tool_message() {
+        let result = json!({ "temperature": 21, "city": "上海" });
+        let outputs = ToolResultToMessageNode::new("tool_msg", "tool_msg")
+            .execute(crate::NodeInputFlow::from(HashMap::from([
+                ("result".to_string(), DataValue::Json(result.clone())),
+                ("tool_call_id".to_string(), DataValue::String("call-7".to_string())),
+                ("tool_name".to_string(), DataValue::String("get_weather".to_string())),
+            ])))
+            .unwrap();
+
+        let Some(DataValue::Vec(item_type, items)) = outputs.get("messages") else {
+            panic!("missing messages output");
+        };
+        assert_eq!(**item_type, DataType::LLMMessage);
+        let [DataValue::LLMMessage(message)] = items.as_slice() else {
+            panic!("expected exactly one LLMMessage, got {items:?}");
+        };
+        assert_eq!(message.role, MessageRole::Tool);
+        assert_eq!(message.tool_call_id.as_deref(), Some("call-7"));
+        assert_eq!(message.name.as_deref(), Some("get_weather"));
+        assert_eq!(message.content_text_owned(), Some(result.to_string()));
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use zihuan_graph_engine::graph_io::save_graph_definition_to_json;
+use zihuan_graph_engine::read_graph_definition_from_json;
+
+fn workflow_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../workflow_set").join(name)
+}
+
+fn temp_path(tag: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("zihuan_graph_roundtrip_{tag}_{}.json", std::process::id()))
+}
+
+/// Loading, saving and loading again must not drift: the editor saves every
+/// graph it opens, so any change here would show up as a spurious diff.
+fn assert_roundtrip_is_stable(name: &str) {
+    zihuan_graph_engine::registry::init_node_registry().unwrap();
+    let loaded = read_graph_definition_from_json(workflow_path(name)).unwrap();
+
+    let first = temp_path(&format!("{name}_first"));
+    let second = temp_path(&format!("{name}_second"));
+    save_graph_definition_to_json(&first, &loaded).unwrap();
+    let reloaded = read_graph_definition_from_json(&first).unwrap();
+    save_graph_definition_to_json(&second, &reloaded).unwrap();
+
+    let first_content = std::fs::read_to_string(&first).unwrap();
+    let second_content = std::fs::read_to_string(&second).unwrap();
+    let _ = std::fs::remove_file(&first);
+    let _ = std::fs::remove_file(&second);
+
+    assert_eq!(serde_json::to_value(&loaded).unwrap(), serde_json::to_value(&reloaded).unwrap());
+    assert_eq!(first_content, second_content);
+    assert_eq!(loaded.nodes.len(), reloaded.nodes.len());
+    assert_eq!(loaded.edges.len(), reloaded.edges.len());
+}
+
+#[test]
+fn research_workflow_roundtrips_unchanged() {
+    assert_roundtrip_is_stable("research.json");
+}
+
+#[test]
+fn deep_search_workflow_roundtrips_unchanged() {
+    assert_roundtrip_is_stable("deep_search_qq_message.json");
+}
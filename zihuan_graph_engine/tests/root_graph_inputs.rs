@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use zihuan_graph_engine::graph_boundary::{execute_root_graph, graph_inputs_from_args, sync_root_graph_io};
+use zihuan_graph_engine::graph_io::save_graph_definition_to_json;
+use zihuan_graph_engine::{load_graph_definition_from_json, DataValue, NodeGraphDefinition};
+
+/// `name` → string_is_not_empty → `has_name`, with `trim` as its option and
+/// `count` passed straight through to the outputs. Saved and reloaded the same
+/// way the editor does, so the boundary nodes exist on disk.
+fn load_tiny_graph(tag: &str) -> NodeGraphDefinition {
+    let graph = json!({
+        "nodes": [{
+            "id": "check",
+            "name": "非空判断",
+            "node_type": "string_is_not_empty",
+            "input_ports": [
+                { "name": "input", "data_type": "String", "required": true },
+                { "name": "trim_before_check", "data_type": "Boolean", "required": false }
+            ],
+            "output_ports": [{ "name": "result", "data_type": "Boolean", "required": true }]
+        }],
+        "edges": [
+            { "from_node_id": "__graph_inputs__", "from_port": "name", "to_node_id": "check", "to_port": "input" },
+            { "from_node_id": "__graph_inputs__", "from_port": "trim", "to_node_id": "check", "to_port": "trim_before_check" },
+            { "from_node_id": "check", "from_port": "result", "to_node_id": "__graph_outputs__", "to_port": "has_name" },
+            { "from_node_id": "__graph_inputs__", "from_port": "count", "to_node_id": "__graph_outputs__", "to_port": "count" }
+        ],
+        "graph_inputs": [
+            { "name": "name", "data_type": "String" },
+            { "name": "trim", "data_type": "Boolean" },
+            { "name": "count", "data_type": "Integer" }
+        ],
+        "graph_outputs": [
+            { "name": "has_name", "data_type": "Boolean" },
+            { "name": "count", "data_type": "Integer" }
+        ]
+    });
+    let mut graph: NodeGraphDefinition = serde_json::from_value(graph).unwrap();
+    sync_root_graph_io(&mut graph);
+    let path = std::env::temp_dir().join(format!("zihuan_root_graph_{tag}_{}.json", std::process::id()));
+    save_graph_definition_to_json(&path, &graph).unwrap();
+    let definition = load_graph_definition_from_json(&path).expect("tiny graph should load");
+    let _ = std::fs::remove_file(&path);
+    definition
+}
+
+fn run_with_cli_inputs(graph: &NodeGraphDefinition, args: &[&str]) -> HashMap<String, DataValue> {
+    let inputs = graph_inputs_from_args(graph, args).expect("arguments should parse");
+    execute_root_graph(graph, inputs).expect("graph should execute")
+}
+
+#[test]
+fn cli_inputs_are_coerced_and_reach_graph_outputs() {
+    zihuan_graph_engine::registry::init_node_registry().unwrap();
+    let graph = load_tiny_graph("cli_inputs");
+
+    let outputs = run_with_cli_inputs(&graph, &["name=   ", "trim=true", "count=3"]);
+    assert!(matches!(outputs.get("has_name"), Some(DataValue::Boolean(false))));
+    assert!(matches!(outputs.get("count"), Some(DataValue::Integer(3))));
+
+    let outputs = run_with_cli_inputs(&graph, &["name=alice", "trim=false", "count=7"]);
+    assert!(matches!(outputs.get("has_name"), Some(DataValue::Boolean(true))));
+    assert!(matches!(outputs.get("count"), Some(DataValue::Integer(7))));
+}
+
+#[test]
+fn unknown_input_is_rejected() {
+    zihuan_graph_engine::registry::init_node_registry().unwrap();
+    let graph = load_tiny_graph("unknown_input");
+
+    let err = execute_root_graph(&graph, HashMap::from([("nope".to_string(), DataValue::Integer(1))])).unwrap_err();
+    assert!(err.to_string().contains("nope"));
+}
+
+#[test]
+fn malformed_and_unknown_arguments_are_rejected() {
+    zihuan_graph_engine::registry::init_node_registry().unwrap();
+    let graph = load_tiny_graph("bad_args");
+
+    let err = graph_inputs_from_args(&graph, ["count"]).unwrap_err();
+    assert!(err.to_string().contains("KEY=VALUE"));
+    let err = graph_inputs_from_args(&graph, ["missing=1"]).unwrap_err();
+    assert!(err.to_string().contains("missing"));
+}
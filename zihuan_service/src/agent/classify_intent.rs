@@ -557,10 +557,7 @@ pub fn classify_intent_with_trace(
         }
     }
     messages.push(LLMMessage::user(message.to_string()));
-    let response = llm.inference(&InferenceParam {
-        messages: &messages,
-        tools: None,
-    });
+    let response = llm.inference(&InferenceParam::new(&messages));
     let label = response.content_text_owned().unwrap_or_default();
     let trimmed = label.trim();
     let category = IntentCategory::from_label(trimmed).unwrap_or(IntentCategory::Other);
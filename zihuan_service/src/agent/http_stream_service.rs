@@ -95,6 +95,8 @@ impl InferenceToolProvider for HttpStreamInferenceToolProvider {
             None,
             None,
             None,
+            None,
+            None,
             self.resources.weaviate_memory_ref.clone(),
             self.resources.elasticsearch_memory_ref.clone(),
             self.resources.embedding_model.clone(),
@@ -320,6 +320,7 @@ fn build_brain(
     tool_definitions: Vec<BrainToolDefinition>,
 ) -> Brain {
     let mut brain = Brain::new(llm);
+    brain.apply_timeout_settings(zihuan_core::system_config::timeout_settings());
 
     for tool in default_tools {
         brain.add_tool(DynBrainToolWrapper(tool));
@@ -12,7 +12,7 @@ use zihuan_agent::brain::{Brain, BrainStopReason, LongTaskContext};
 use zihuan_agent::emotion::utils::emotion_dimensions_snapshot_text;
 use zihuan_agent::session_state::{EmotionAdjustmentDirection, QqChatAgentServiceSessionState};
 use zihuan_core::agent_config::qq_chat::current_qq_chat_agent_service_config;
-use zihuan_core::agent_config::qq_chat::QqChatEmotionDimensionConfig;
+use zihuan_core::agent_config::qq_chat::{QqChatAgentServiceConfig, QqChatEmotionDimensionConfig};
 use zihuan_core::command::{CommandChannel, CommandContext, DispatchResult};
 use zihuan_core::error::{Error, Result};
 use zihuan_core::llm::{InferenceParam, LLMMessage, TokenUsage};
@@ -30,13 +30,15 @@ use ims_bot_adapter::tools::qq_profile::{GetBotProfileBrainTool, GetQqUserProfil
 
 use super::super::super::tools::{
     format_public_info_message, review_and_rewrite_reply, AgentMemoryBackend, AgentMemoryToolResources,
-    EditableQqAgentTool, GetAgentPublicInfoBrainTool, GetFunctionListBrainTool, GetRecentGroupMessagesBrainTool,
-    GetRecentUserMessagesBrainTool, ImageUnderstandBrainTool, ListAvailableMemoryKeysBrainTool, ModelIdentityContext,
-    QqReplyReviewRequest, RememberContentBrainTool, ReplyMessageBrainTool, RunResearchSubagentBrainTool,
-    SaveImageBrainTool, SearchMemoryContentBrainTool, SearchSimilarImagesBrainTool, ToolNotificationTarget,
-    WebSearchBrainTool, DEFAULT_TOOL_GET_AGENT_PUBLIC_INFO, DEFAULT_TOOL_GET_FUNCTION_LIST,
-    DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES, DEFAULT_TOOL_GET_RECENT_USER_MESSAGES, DEFAULT_TOOL_IMAGE_UNDERSTAND,
-    DEFAULT_TOOL_LIST_AVAILABLE_MEMORY_KEYS, DEFAULT_TOOL_REMEMBER_CONTENT, DEFAULT_TOOL_SAVE_IMAGE,
+    CalculateBrainTool, ChatSearchBrainTool, EditableQqAgentTool, GetAgentPublicInfoBrainTool, GetCurrentTimeBrainTool,
+    GetFunctionListBrainTool, GetRecentGroupMessagesBrainTool, GetRecentUserMessagesBrainTool,
+    ImageUnderstandBrainTool, ListAvailableMemoryKeysBrainTool, ModelIdentityContext, QqReplyReviewRequest,
+    RememberContentBrainTool, ReplyMessageBrainTool, RunResearchSubagentBrainTool, SaveImageBrainTool,
+    SearchMemoryContentBrainTool, SearchSimilarImagesBrainTool, ToolNotificationTarget, WeatherBrainTool,
+    WebSearchBrainTool, DEFAULT_TOOL_CALCULATE, DEFAULT_TOOL_GET_AGENT_PUBLIC_INFO, DEFAULT_TOOL_GET_CURRENT_TIME,
+    DEFAULT_TOOL_GET_FUNCTION_LIST, DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES, DEFAULT_TOOL_GET_RECENT_USER_MESSAGES,
+    DEFAULT_TOOL_GET_WEATHER, DEFAULT_TOOL_IMAGE_UNDERSTAND, DEFAULT_TOOL_LIST_AVAILABLE_MEMORY_KEYS,
+    DEFAULT_TOOL_REMEMBER_CONTENT, DEFAULT_TOOL_SAVE_IMAGE, DEFAULT_TOOL_SEARCH_CHAT_MESSAGES,
     DEFAULT_TOOL_SEARCH_MEMORY_CONTENT, DEFAULT_TOOL_SEARCH_SIMILAR_IMAGES, DEFAULT_TOOL_WEB_SEARCH,
     QQ_CHAT_EMIT_TOOL_PROGRESS_NOTIFICATIONS,
 };
@@ -44,7 +46,7 @@ use storage_handler::AgentMemoryAccessContext;
 
 use crate::nodes::tool_subgraph::{ToolResultMode, ToolSubgraphRunner};
 use crate::storage::qq_chat_history_store::{
-    conversation_history_key, chat_preprompt_history_key, load_history, save_history,
+    chat_preprompt_history_key, conversation_history_key, load_history, save_history,
 };
 
 use crate::agent::classify_intent::{classify_intent_with_trace, IntentCategory};
@@ -63,6 +65,7 @@ use super::{
 use super::super::chat_preprompt::run_chat_preprompt_agent;
 
 use super::super::steer::QqChatServiceSteerHook;
+use super::super::system_prompt_template::SystemPromptVars;
 use super::super::tool_quota::wrap_brain_tool_with_quota;
 use crate::agent::qq_chat::language_style_store::LanguageStyleScope;
 use crate::agent::qq_chat::privilege_gate::{
@@ -248,10 +251,16 @@ impl QqChatAgentServiceInner {
         let has_passthrough = passthrough_text.is_some();
         if result.inject_to_llm {
             let is_group = matches!(cmd_ctx.channel, CommandChannel::QqChat { is_group: true, .. });
+            let prompt_vars = SystemPromptVars {
+                nickname: ctx.bot_name,
+                qq_id: bot_id,
+                group_name: hydrated_event.group_name.as_deref(),
+                persona: ctx.agent_system_prompt,
+            };
             let cmd_system_prompt = if is_group {
-                build_group_system_prompt(ctx.bot_name, ctx.agent_system_prompt)
+                build_group_system_prompt(&prompt_vars, ctx.weather.is_some())
             } else {
-                build_private_system_prompt(ctx.bot_name, ctx.agent_system_prompt)
+                build_private_system_prompt(&prompt_vars, ctx.weather.is_some())
             };
             let mut cmd_session_state = ctx.session_state_store.lock().unwrap().clone();
             let cmd_emotion_dimensions = current_qq_chat_agent_service_config()?.resolved_emotion_dimensions();
@@ -264,14 +273,14 @@ impl QqChatAgentServiceInner {
                     ctx.llm.supports_multimodal_input(),
                     &cmd_system_prompt,
                     ctx.resolved_language_style.as_ref().map(|item| item.style_prompt.as_str()),
-                   message_rate_limit_warning,
-                   &mut cmd_session_state,
-                   &cmd_emotion_dimensions,
-                   None,
-               ),
-               ctx.llm.api_style(),
-           );
-           history.push(user_msg_for_cmd);
+                    message_rate_limit_warning,
+                    &mut cmd_session_state,
+                    &cmd_emotion_dimensions,
+                    None,
+                ),
+                ctx.llm.api_style(),
+            );
+            history.push(user_msg_for_cmd);
             history.push(message_with_api_style(
                 LLMMessage::assistant_text(result.reply),
                 ctx.llm.api_style(),
@@ -312,13 +321,16 @@ impl QqChatAgentServiceInner {
         &self,
         ctx: &'a QqChatAgentServiceContext<'_>,
         intent_category: IntentCategory,
+        group_id: Option<&str>,
     ) -> (&'a Arc<dyn zihuan_core::llm::llm_base::LLMBase>, &'a str) {
-        match intent_category {
-            IntentCategory::SolveComplexProblem | IntentCategory::WriteCode => {
-                (ctx.math_programming_llm, "math_programming")
-            }
-            _ => (ctx.llm, "main"),
-        }
+        route_turn_llm(
+            ctx.qq_chat_config,
+            ctx.llm_profile_overrides,
+            ctx.llm,
+            ctx.math_programming_llm,
+            intent_category,
+            group_id,
+        )
     }
 
     /// Processes a single QQ chat turn end-to-end for a claimed message.
@@ -897,10 +909,16 @@ impl QqChatAgentServiceInner {
             &self.default_tools_enabled,
         );
 
+        let prompt_vars = SystemPromptVars {
+            nickname: ctx.bot_name,
+            qq_id: bot_id,
+            group_name: prepared_input.event.group_name.as_deref(),
+            persona: ctx.agent_system_prompt,
+        };
         let base_system_prompt = if is_group {
-            build_group_system_prompt(ctx.bot_name, ctx.agent_system_prompt)
+            build_group_system_prompt(&prompt_vars, ctx.weather.is_some())
         } else {
-            build_private_system_prompt(ctx.bot_name, ctx.agent_system_prompt)
+            build_private_system_prompt(&prompt_vars, ctx.weather.is_some())
         };
 
         let intent_trace = classify_intent_with_trace(
@@ -910,7 +928,8 @@ impl QqChatAgentServiceInner {
             Some(&history),
             800,
         );
-        let (turn_llm, routed_model) = self.selected_turn_llm(ctx, intent_trace.category);
+        let (turn_llm, routed_model) =
+            self.selected_turn_llm(ctx, intent_trace.category, is_group.then_some(target_id));
         trace.record_intent_classification(&intent_trace, routed_model);
 
         let user_msg = {
@@ -995,6 +1014,11 @@ impl QqChatAgentServiceInner {
         let tool_quota = ctx.tool_quota.clone();
         let mut brain = Brain::new(Arc::clone(turn_llm));
         brain.set_observer(Arc::new(QqChatBrainObserver { trace: trace.clone() }));
+        let max_tool_result_chars = ctx.qq_chat_config.max_tool_result_chars;
+        brain.set_max_tool_result_chars((max_tool_result_chars > 0).then_some(max_tool_result_chars));
+        brain.apply_timeout_settings(zihuan_core::system_config::timeout_settings());
+        brain.set_no_tool_fallback(ctx.qq_chat_config.no_tool_fallback.clone());
+        brain.set_empty_reply_fallback(ctx.qq_chat_config.empty_reply_fallback);
         brain.set_iteration_hook(Arc::new(QqChatServiceSteerHook {
             pending_steer: Arc::clone(ctx.pending_steer),
             sender_id: sender_id.to_string(),
@@ -1010,10 +1034,10 @@ impl QqChatAgentServiceInner {
             shared_runtime_values: Arc::clone(&shared_runtime_values),
             system_prompt: base_system_prompt.clone(),
             style_prompt: ctx.resolved_language_style.as_ref().map(|item| item.style_prompt.clone()),
-           session_state: Arc::clone(&turn_session_state),
-           emotion_dimensions: emotion_dimensions.clone(),
-           preprompt_context: preprompt_context.clone(),
-       }));
+            session_state: Arc::clone(&turn_session_state),
+            emotion_dimensions: emotion_dimensions.clone(),
+            preprompt_context: preprompt_context.clone(),
+        }));
 
         let memory_backend = ctx
             .elasticsearch_memory_ref
@@ -1084,6 +1108,21 @@ impl QqChatAgentServiceInner {
             brain.add_tool(wrap_brain_tool_with_quota(GetFunctionListBrainTool, tool_quota.clone()));
         }
 
+        if self.is_default_tool_enabled(DEFAULT_TOOL_GET_CURRENT_TIME) {
+            brain.add_tool(wrap_brain_tool_with_quota(GetCurrentTimeBrainTool, tool_quota.clone()));
+        }
+
+        if let Some(weather_ref) = ctx.weather.filter(|_| self.is_default_tool_enabled(DEFAULT_TOOL_GET_WEATHER)) {
+            brain.add_tool(wrap_brain_tool_with_quota(
+                WeatherBrainTool::new(Arc::clone(weather_ref)),
+                tool_quota.clone(),
+            ));
+        }
+
+        if self.is_default_tool_enabled(DEFAULT_TOOL_CALCULATE) {
+            brain.add_tool(wrap_brain_tool_with_quota(CalculateBrainTool, tool_quota.clone()));
+        }
+
         brain.add_tool(wrap_brain_tool_with_quota(
             RunResearchSubagentBrainTool::new(
                 Arc::clone(ctx.math_programming_llm),
@@ -1160,6 +1199,23 @@ impl QqChatAgentServiceInner {
             ));
         }
 
+        if self.is_default_tool_enabled(DEFAULT_TOOL_SEARCH_CHAT_MESSAGES) {
+            brain.add_tool(wrap_brain_tool_with_quota(
+                ChatSearchBrainTool::new(
+                    ctx.rdb_pool.cloned(),
+                    ctx.message_history.cloned(),
+                    ToolNotificationTarget::new(
+                        Some(ctx.adapter.clone()),
+                        target_id.to_string(),
+                        if is_group { Some(sender_id.to_string()) } else { None },
+                        is_group,
+                        false,
+                    ),
+                ),
+                tool_quota.clone(),
+            ));
+        }
+
         if self.is_default_tool_enabled(DEFAULT_TOOL_SEARCH_SIMILAR_IMAGES) {
             brain.add_tool(wrap_brain_tool_with_quota(
                 SearchSimilarImagesBrainTool::new(
@@ -1511,10 +1567,7 @@ impl QqChatAgentServiceInner {
         ];
 
         trace.mark_llm_request_started();
-        let response = ctx.llm.inference(&InferenceParam {
-            messages: &meta_messages,
-            tools: None,
-        });
+        let response = ctx.llm.inference(&InferenceParam::new(&meta_messages));
         let candidate_message = response.content_text_owned().unwrap_or_default();
         let candidate_message = candidate_message.trim();
         if candidate_message.is_empty() {
@@ -1639,3 +1692,83 @@ fn build_model_identity_context(ctx: &QqChatAgentServiceContext<'_>) -> ModelIde
         model_list: build_service_model_list(ctx),
     }
 }
+
+/// The LLM for a turn and the label recorded in its trace: a matching profile
+/// override first, then the math/programming model for those intents, else the
+/// main model.
+fn route_turn_llm<'a>(
+    config: &QqChatAgentServiceConfig,
+    overrides: &'a HashMap<String, Arc<dyn zihuan_core::llm::llm_base::LLMBase>>,
+    main: &'a Arc<dyn zihuan_core::llm::llm_base::LLMBase>,
+    math_programming: &'a Arc<dyn zihuan_core::llm::llm_base::LLMBase>,
+    intent_category: IntentCategory,
+    group_id: Option<&str>,
+) -> (&'a Arc<dyn zihuan_core::llm::llm_base::LLMBase>, &'a str) {
+    if let Some((llm_ref_id, llm)) = config
+        .resolve_llm_profile_override(group_id, Some(intent_category.label()))
+        .and_then(|llm_ref_id| overrides.get_key_value(llm_ref_id))
+    {
+        return (llm, llm_ref_id.as_str());
+    }
+    match intent_category {
+        IntentCategory::SolveComplexProblem | IntentCategory::WriteCode => (math_programming, "math_programming"),
+        _ => (main, "main"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zihuan_core::llm::llm_base::LLMBase;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct NamedLlm(&'static str);
+
+    impl LLMBase for NamedLlm {
+        fn get_model_name(&self) -> &str {
+            self.0
+        }
+
+        fn inference(&self, _param: &InferenceParam) -> LLMMessage {
+            LLMMessage::assistant_text(self.0)
+        }
+    }
+
+    fn llm(name: &'static str) -> Arc<dyn LLMBase> {
+        Arc::new(NamedLlm(name))
+    }
+
+    #[test]
+    fn turn_llm_prefers_overrides_then_the_intent_model() {
+        let config: QqChatAgentServiceConfig = serde_json::from_value(serde_json::json!({
+            "ims_bot_adapter_connection_id": "bot",
+            "web_search_engine_connection_id": "search",
+            "llm_ref_id": "default",
+            "llm_profile_overrides": [
+                { "group_id": "10001", "llm_ref_id": "code" },
+                { "group_id": "10002", "llm_ref_id": "missing" }
+            ]
+        }))
+        .expect("deserialize config");
+        let overrides = HashMap::from([("code".to_string(), llm("code-model"))]);
+        let (main, math) = (llm("main-model"), llm("math-model"));
+        let route = |intent, group_id| {
+            let (llm, label) = route_turn_llm(&config, &overrides, &main, &math, intent, group_id);
+            (llm.get_model_name().to_string(), label.to_string())
+        };
+
+        assert_eq!(
+            route(IntentCategory::Chat, Some("10001")),
+            ("code-model".to_string(), "code".to_string())
+        );
+        assert_eq!(
+            route(IntentCategory::WriteCode, Some("10002")),
+            ("math-model".to_string(), "math_programming".to_string())
+        );
+        assert_eq!(
+            route(IntentCategory::Chat, None),
+            ("main-model".to_string(), "main".to_string())
+        );
+    }
+}
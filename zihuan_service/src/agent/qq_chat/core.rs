@@ -10,8 +10,9 @@ use zihuan_agent::utils::build_state_system_prefix_lines;
 
 pub(crate) use super::super::tools::build_info_brain_tools;
 use super::super::tools::{
-    DEFAULT_TOOL_GET_AGENT_PUBLIC_INFO, DEFAULT_TOOL_GET_FUNCTION_LIST, DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES,
-    DEFAULT_TOOL_GET_RECENT_USER_MESSAGES, DEFAULT_TOOL_IMAGE_UNDERSTAND, DEFAULT_TOOL_LIST_AVAILABLE_MEMORY_KEYS,
+    DEFAULT_TOOL_CALCULATE, DEFAULT_TOOL_GET_AGENT_PUBLIC_INFO, DEFAULT_TOOL_GET_CURRENT_TIME,
+    DEFAULT_TOOL_GET_FUNCTION_LIST, DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES, DEFAULT_TOOL_GET_RECENT_USER_MESSAGES,
+    DEFAULT_TOOL_GET_WEATHER, DEFAULT_TOOL_IMAGE_UNDERSTAND, DEFAULT_TOOL_LIST_AVAILABLE_MEMORY_KEYS,
     DEFAULT_TOOL_REMEMBER_CONTENT, DEFAULT_TOOL_SAVE_IMAGE, DEFAULT_TOOL_SEARCH_MEMORY_CONTENT,
     DEFAULT_TOOL_SEARCH_SIMILAR_IMAGES, DEFAULT_TOOL_WEB_SEARCH,
 };
@@ -20,6 +21,9 @@ use super::msg_send::{
     build_long_task_complete_content, build_long_task_start_text, send_forward_content, send_notification_text,
     QqChatServiceSendContext,
 };
+use super::system_prompt_template::{
+    render_system_prompt_file, SystemPromptVars, GROUP_SYSTEM_PROMPT_FILE, PRIVATE_SYSTEM_PROMPT_FILE,
+};
 use crate::nodes::tool_subgraph::{validate_shared_inputs, validate_tool_definitions, ToolResultMode};
 use crate::storage::qq_chat_history_store::clear_history;
 use crate::storage::qq_chat_session_store::build_outbound_persistence;
@@ -29,6 +33,7 @@ use zihuan_core::agent_config::qq_chat::QqChatEmotionDimensionConfig;
 use zihuan_core::command::{CommandChannel, CommandContext, NewConversationRequest, SideEffectContext};
 use zihuan_core::data_refs::RelationalDbConnection;
 use zihuan_core::error::{Error, Result};
+use zihuan_core::ims_bot_adapter::sticky_engagement::StickyEngagementStore;
 use zihuan_core::llm::embedding_base::EmbeddingBase;
 use zihuan_core::llm::{LLMMessage, MessagePart};
 use zihuan_core::rag::WebSearchEngineRef;
@@ -99,27 +104,67 @@ impl SideEffectContext for QqCommandSideEffectContext<'_> {
     }
 }
 
-fn default_tools_enabled_map() -> HashMap<String, bool> {
-    [
-        DEFAULT_TOOL_WEB_SEARCH,
-        DEFAULT_TOOL_GET_AGENT_PUBLIC_INFO,
-        DEFAULT_TOOL_GET_FUNCTION_LIST,
-        DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES,
-        DEFAULT_TOOL_GET_RECENT_USER_MESSAGES,
-        DEFAULT_TOOL_SEARCH_SIMILAR_IMAGES,
-        DEFAULT_TOOL_SAVE_IMAGE,
-        DEFAULT_TOOL_IMAGE_UNDERSTAND,
-        DEFAULT_TOOL_LIST_AVAILABLE_MEMORY_KEYS,
-        DEFAULT_TOOL_SEARCH_MEMORY_CONTENT,
-        DEFAULT_TOOL_REMEMBER_CONTENT,
-    ]
-    .into_iter()
-    .map(|name| (name.to_string(), true))
-    .collect()
+const DEFAULT_TOOL_NAMES: [&str; 14] = [
+    DEFAULT_TOOL_WEB_SEARCH,
+    DEFAULT_TOOL_GET_AGENT_PUBLIC_INFO,
+    DEFAULT_TOOL_GET_FUNCTION_LIST,
+    DEFAULT_TOOL_GET_CURRENT_TIME,
+    DEFAULT_TOOL_GET_WEATHER,
+    DEFAULT_TOOL_CALCULATE,
+    DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES,
+    DEFAULT_TOOL_GET_RECENT_USER_MESSAGES,
+    DEFAULT_TOOL_SEARCH_SIMILAR_IMAGES,
+    DEFAULT_TOOL_SAVE_IMAGE,
+    DEFAULT_TOOL_IMAGE_UNDERSTAND,
+    DEFAULT_TOOL_LIST_AVAILABLE_MEMORY_KEYS,
+    DEFAULT_TOOL_SEARCH_MEMORY_CONTENT,
+    DEFAULT_TOOL_REMEMBER_CONTENT,
+];
+
+/// Every default tool's guidance, except `get_weather`'s when no weather API
+/// connection backs it and the tool is not registered.
+fn default_tools_enabled_map(weather_available: bool) -> HashMap<String, bool> {
+    DEFAULT_TOOL_NAMES
+        .into_iter()
+        .map(|name| (name.to_string(), name != DEFAULT_TOOL_GET_WEATHER || weather_available))
+        .collect()
+}
+
+/// [`default_tools_enabled_map`] with the agent's `default_tools_enabled`
+/// overrides applied; names that are not default tools are ignored.
+fn default_tools_enabled_with_overrides(
+    weather_available: bool,
+    overrides: &HashMap<String, bool>,
+) -> HashMap<String, bool> {
+    let mut enabled_map = default_tools_enabled_map(weather_available);
+    for (tool_name, enabled) in overrides {
+        if let Some(entry) = enabled_map.get_mut(tool_name) {
+            *entry = *enabled;
+        }
+    }
+    enabled_map
+}
+
+/// Tools missing from `default_tools_enabled` count as enabled.
+fn is_default_tool_enabled(default_tools_enabled: &HashMap<String, bool>, tool_name: &str) -> bool {
+    default_tools_enabled.get(tool_name).copied().unwrap_or(true)
+}
+
+/// The default tools an agent with these `default_tools_enabled` overrides
+/// registers, in registration order.
+pub(crate) fn enabled_default_tool_names(
+    overrides: &HashMap<String, bool>,
+    weather_available: bool,
+) -> Vec<&'static str> {
+    let enabled_map = default_tools_enabled_with_overrides(weather_available, overrides);
+    DEFAULT_TOOL_NAMES
+        .into_iter()
+        .filter(|name| is_default_tool_enabled(&enabled_map, name))
+        .collect()
 }
 
 fn build_tool_instruction_rules(default_tools_enabled: &HashMap<String, bool>) -> Vec<String> {
-    let is_enabled = |name: &str| -> bool { *default_tools_enabled.get(name).unwrap_or(&true) };
+    let is_enabled = |name: &str| is_default_tool_enabled(default_tools_enabled, name);
 
     let mut lines = Vec::new();
 
@@ -195,6 +240,18 @@ fn build_tool_instruction_rules(default_tools_enabled: &HashMap<String, bool>) -
         );
     }
 
+    if is_enabled(DEFAULT_TOOL_GET_CURRENT_TIME) {
+        lines.push("- 用户询问其他时区的时间，或需要按特定格式给出日期时间时，调用 `get_current_time`".to_string());
+    }
+
+    if is_enabled(DEFAULT_TOOL_GET_WEATHER) {
+        lines.push("- 用户询问某地天气、气温或是否下雨时，调用 `get_weather`，不要凭印象回答".to_string());
+    }
+
+    if is_enabled(DEFAULT_TOOL_CALCULATE) {
+        lines.push("- 需要给出具体数值计算结果时，调用 `calculate` 计算，不要心算".to_string());
+    }
+
     let has_recent_group = is_enabled(DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES);
     let has_recent_user = is_enabled(DEFAULT_TOOL_GET_RECENT_USER_MESSAGES);
     if has_recent_group || has_recent_user {
@@ -206,11 +263,7 @@ fn build_tool_instruction_rules(default_tools_enabled: &HashMap<String, bool>) -
     lines
 }
 
-fn build_common_system_rules(
-    identity_example: &str,
-    agent_system_prompt: Option<&str>,
-    default_tools_enabled: &HashMap<String, bool>,
-) -> String {
+fn build_common_system_rules(identity_example: &str, agent_system_prompt: Option<&str>, tool_rules: &str) -> String {
     let mut rules = format!(
         "你是一个管理QQ机器人的思考状态的Agent,你正在维护的机器人名叫`{identity_example}`。\n\
          你需要对事件进行处理。比如用户向你发送消息的时候，你需要生成向用户的回复或者选择不回复此条消息。\n\
@@ -220,9 +273,8 @@ fn build_common_system_rules(
          你往往需要对旧的记忆进行更新。\n",
     );
 
-    let tool_lines = build_tool_instruction_rules(default_tools_enabled);
-    if !tool_lines.is_empty() {
-        rules.push_str(&tool_lines.join("\n"));
+    if !tool_rules.is_empty() {
+        rules.push_str(tool_rules);
         rules.push('\n');
     }
 
@@ -233,16 +285,23 @@ fn build_common_system_rules(
     rules
 }
 
-/// System prompt template (shared, private variant).
-pub(crate) fn build_private_system_prompt(bot_name: &str, agent_system_prompt: Option<&str>) -> String {
-    build_common_system_rules(bot_name, agent_system_prompt, &default_tools_enabled_map())
+/// System prompt (private variant). `prompts/qq_chat_private_system.md`
+/// replaces the built-in text when present.
+pub(crate) fn build_private_system_prompt(vars: &SystemPromptVars<'_>, weather_available: bool) -> String {
+    let tool_rules = build_tool_instruction_rules(&default_tools_enabled_map(weather_available)).join("\n");
+    render_system_prompt_file(PRIVATE_SYSTEM_PROMPT_FILE, vars, &tool_rules)
+        .unwrap_or_else(|| build_common_system_rules(vars.nickname, vars.persona, &tool_rules))
 }
 
-/// System prompt template (group variant).
-pub(crate) fn build_group_system_prompt(bot_name: &str, agent_system_prompt: Option<&str>) -> String {
-    let mut rules = build_common_system_rules(bot_name, agent_system_prompt, &default_tools_enabled_map());
-    rules.push_str("\n- 群聊里如需引用某条 QQ 消息，请调用 `reply_message` 设置 reply 目标。");
-    rules
+/// System prompt (group variant). `prompts/qq_chat_group_system.md`
+/// replaces the built-in text when present.
+pub(crate) fn build_group_system_prompt(vars: &SystemPromptVars<'_>, weather_available: bool) -> String {
+    let tool_rules = build_tool_instruction_rules(&default_tools_enabled_map(weather_available)).join("\n");
+    render_system_prompt_file(GROUP_SYSTEM_PROMPT_FILE, vars, &tool_rules).unwrap_or_else(|| {
+        let mut rules = build_common_system_rules(vars.nickname, vars.persona, &tool_rules);
+        rules.push_str("\n- 群聊里如需引用某条 QQ 消息，请调用 `reply_message` 设置 reply 目标。");
+        rules
+    })
 }
 
 pub(crate) fn merge_character_and_style_prompt(character_instructions: &str, style_prompt: Option<&str>) -> String {
@@ -387,8 +446,15 @@ pub(crate) fn build_user_message(
         state_lines.join("\n"),
     );
 
+    // In groups several members talk to the bot, so tag the turn with its speaker.
+    let speaker_name = if current_input.event.message_type.as_str() == "group" {
+        sender_name.as_str()
+    } else {
+        ""
+    };
+
     if !llm_supports_multimodal_input || !current_input.has_media {
-        return LLMMessage::user(user_text);
+        return LLMMessage::user(user_text).with_name(speaker_name);
     }
 
     let state_text = format!("{}\n", state_lines.join("\n"));
@@ -423,7 +489,7 @@ pub(crate) fn build_user_message(
     flush_text_part(&mut parts, &mut text_buffer);
     parts.push(MessagePart::text(PROCESSING_INSTRUCTION.to_string()));
 
-    LLMMessage::user_with_parts(parts)
+    LLMMessage::user_with_parts(parts).with_name(speaker_name)
 }
 
 pub(crate) fn build_state_delta_lines(
@@ -589,6 +655,7 @@ mod build_user_message_tests {
                 group_id: Some(200),
                 group_name: Some("测试群".to_string()),
                 is_group_message: true,
+                time: None,
             },
             current_text: "你好".to_string(),
             reference_blocks: Vec::new(),
@@ -774,24 +841,20 @@ impl QqChatAgentServiceInner {
     pub fn new(id: impl Into<String>) -> Self {
         Self {
             id: id.into(),
-            default_tools_enabled: default_tools_enabled_map(),
+            default_tools_enabled: default_tools_enabled_map(true),
             shared_inputs: Vec::new(),
             tool_definitions: Vec::new(),
         }
     }
 
     fn set_default_tools_enabled(&mut self, overrides: HashMap<String, bool>) {
-        let mut enabled_map = default_tools_enabled_map();
-        for (tool_name, enabled) in overrides {
-            if enabled_map.contains_key(&tool_name) {
-                enabled_map.insert(tool_name, enabled);
-            }
-        }
-        self.default_tools_enabled = enabled_map;
+        // `get_weather` is registered only when the agent has a weather
+        // connection, so it is left on here.
+        self.default_tools_enabled = default_tools_enabled_with_overrides(true, &overrides);
     }
 
     pub(crate) fn is_default_tool_enabled(&self, tool_name: &str) -> bool {
-        self.default_tools_enabled.get(tool_name).copied().unwrap_or(true)
+        is_default_tool_enabled(&self.default_tools_enabled, tool_name)
     }
 
     pub(crate) fn wrap_err(&self, msg: impl Into<String>) -> Error {
@@ -832,6 +895,7 @@ impl QqChatAgentService {
             inner,
             config,
             pending_steer: Arc::new(PendingSteerStore::default()),
+            sticky_engagement: Arc::new(StickyEngagementStore::default()),
         })
     }
 
@@ -861,17 +925,21 @@ impl QqChatAgentService {
             intent_classification_llm: &self.config.intent_classification_llm,
             math_programming_llm: &self.config.math_programming_llm,
             natural_language_reply_llm: &self.config.natural_language_reply_llm,
+            qq_chat_config: &self.config.qq_chat_config,
+            llm_profile_overrides: &self.config.llm_profile_overrides,
             natural_language_reply_system_prompt: self
                 .config
                 .qq_chat_config
                 .natural_language_reply_system_prompt
                 .as_deref(),
             rdb_pool: self.config.rdb_pool.as_ref(),
+            message_history: self.config.message_history.as_ref(),
             weaviate_image_ref: self.config.weaviate_image_ref.as_ref(),
             weaviate_memory_ref: self.config.weaviate_memory_ref.as_ref(),
             elasticsearch_memory_ref: self.config.elasticsearch_memory_ref.as_ref(),
             embedding_model: self.config.embedding_model.as_ref(),
             web_search_engine: &self.config.web_search_engine,
+            weather: self.config.weather.as_ref(),
             s3_ref: self.config.s3_ref.as_ref(),
             max_message_length: self.config.max_message_length,
             compact_context_length: self.config.compact_context_length,
@@ -880,6 +948,7 @@ impl QqChatAgentService {
             shared_runtime_values: self.config.shared_runtime_values.clone(),
             session_state_store: &self.config.session_state_store,
             pending_steer: &self.pending_steer,
+            sticky_engagement: &self.sticky_engagement,
             task_runtime: self.config.task_runtime.clone(),
             task_db_connection_id,
             tool_quota,
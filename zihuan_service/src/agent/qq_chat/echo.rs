@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use ims_bot_adapter::active_adapter_manager::ActiveAdapterManager;
+use ims_bot_adapter::echo_brain::EchoBrainAgent;
+use ims_bot_adapter::event::EventHandler;
+use log::{info, warn};
+use model_inference::system_config::AgentConfig;
+use storage_handler::{build_relational_db_connection_for_connection, ConnectionConfig};
+use tokio::task::JoinHandle;
+use zihuan_core::agent_config::qq_chat::QqChatAgentServiceConfig;
+use zihuan_core::error::Result;
+use zihuan_graph_engine::message_persistence::{
+    persist_message_event, register_redis_cache_codec, register_sender_identity_policy,
+};
+
+use super::super::{AgentManager, AgentRuntimeState, AgentRuntimeStatus, OnFinishShared};
+
+const LOG_PREFIX: &str = "[service][qq_echo]";
+
+/// Starts a QQ chat agent that installs [`EchoBrainAgent`] on its bot adapter.
+///
+/// Inbound messages are still persisted like in the LLM agent, but no models are
+/// built, so it runs with only a bot connection configured.
+pub(super) async fn spawn(
+    manager: &AgentManager,
+    agent: AgentConfig,
+    config: QqChatAgentServiceConfig,
+    connections: Vec<ConnectionConfig>,
+    on_finish: OnFinishShared,
+) -> Result<JoinHandle<()>> {
+    let rdb_pool = match config.resolved_rdb_id() {
+        Some(connection_id) => Some(build_relational_db_connection_for_connection(connection_id, &connections).await?),
+        None => None,
+    };
+    register_sender_identity_policy(config.sender_identity_policy());
+    register_redis_cache_codec(config.redis_cache_codec);
+
+    let adapter = ActiveAdapterManager::shared()
+        .get_or_create_with_object_storage(&config.ims_bot_adapter_connection_id, None)
+        .await?;
+
+    let handler_id = format!("qq_chat_echo:{}", agent.id);
+    {
+        let handler: EventHandler = Arc::new(move |event| {
+            let event = event.clone();
+            let rdb_pool = rdb_pool.clone();
+            Box::pin(async move {
+                if let Err(err) = persist_message_event(&event, rdb_pool.as_ref(), None) {
+                    warn!("{LOG_PREFIX} Message persistence failed: {err}");
+                }
+                Ok(())
+            })
+        });
+        let mut guard = adapter.lock().await;
+        guard.register_event_handler_with_id(handler_id.clone(), handler);
+        guard.set_brain_agent(Some(Box::new(EchoBrainAgent::new(config.echo_include_structure))));
+    }
+
+    let manager = manager.clone();
+    let agent_id = agent.id.clone();
+    let agent_name = agent.name.clone();
+    {
+        let adapter = adapter.clone();
+        let handler_id = handler_id.clone();
+        let mut guard = on_finish.lock().unwrap();
+        let user_on_finish = guard.take();
+        *guard = Some(Box::new(move |success, error_msg| {
+            tokio::spawn(async move {
+                let mut guard = adapter.lock().await;
+                guard.unregister_event_handler(&handler_id);
+                guard.set_brain_agent(None);
+            });
+            if let Some(cb) = user_on_finish {
+                cb(success, error_msg);
+            }
+        }));
+    }
+
+    Ok(tokio::spawn(async move {
+        info!("{LOG_PREFIX} starting echo QQ Chat Agent Service '{}'", agent_name);
+        std::future::pending::<()>().await;
+        {
+            let mut guard = adapter.lock().await;
+            guard.unregister_event_handler(&handler_id);
+            guard.set_brain_agent(None);
+        }
+        manager.update_state(
+            &agent_id,
+            AgentRuntimeState {
+                instance_id: None,
+                status: AgentRuntimeStatus::Stopped,
+                started_at: None,
+                last_error: None,
+            },
+        );
+        if let Some(cb) = on_finish.lock().unwrap().take() {
+            cb(true, None);
+        }
+    }))
+}
@@ -1,5 +1,6 @@
 mod core;
 mod chat_preprompt;
+mod echo;
 pub mod ignore_store;
 mod inbox;
 pub mod language_style_store;
@@ -11,6 +12,7 @@ pub mod privilege_gate;
 pub mod privilege_store;
 mod steer;
 pub mod style_learner;
+pub(crate) mod system_prompt_template;
 pub(crate) mod tool_quota;
 pub mod tool_quota_store;
 mod user_input;
@@ -18,7 +20,9 @@ mod user_input;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+pub(crate) use self::core::enabled_default_tool_names;
 use self::core::{
     build_info_brain_tools, expand_messages_for_inference, prepare_current_turn_user_input_from_event, QqChatTaskTrace,
     LOG_PREFIX, LOG_TEXT_PREVIEW_CHARS,
@@ -51,15 +55,17 @@ use log::{error, info, warn};
 use model_inference::nn::embedding::embedding_runtime_manager::RuntimeEmbeddingModelManager;
 use model_inference::system_config::{load_llm_refs, AgentConfig};
 use storage_handler::{
-    build_elasticsearch_ref, build_relational_db_connection_for_connection, build_s3_ref, build_weaviate_ref,
-    build_web_search_engine_ref, find_connection, ConnectionConfig, ConnectionKind, WeaviateCollectionSchema,
+    build_elasticsearch_ref, build_relational_db_connection_for_connection, build_s3_ref, build_weather_ref,
+    build_weaviate_ref, build_web_search_engine_ref, find_connection, ConnectionConfig, ConnectionKind, MessageStore,
+    WeaviateCollectionSchema,
 };
 use tokio::task::JoinHandle;
 use zihuan_agent::brain::BrainTool;
 use zihuan_agent::session_state::QqChatAgentServiceSessionState;
-use zihuan_core::agent_config::qq_chat::{current_qq_chat_agent_service_config, QqChatAgentServiceConfig};
+use zihuan_core::agent_config::qq_chat::{current_qq_chat_agent_service_config, QqChatAgentServiceConfig, QqChatBrain};
 use zihuan_core::data_refs::RelationalDbConnection;
 use zihuan_core::error::{Error, Result};
+use zihuan_core::ims_bot_adapter::sticky_engagement::Addressee;
 use zihuan_core::llm::embedding_base::EmbeddingBase;
 use zihuan_core::llm::llm_base::LLMBase;
 use zihuan_core::llm::LLMMessage;
@@ -69,13 +75,17 @@ use zihuan_core::steer::PendingSteerEvent;
 use zihuan_core::task_context::{
     scope_task_id, scope_task_runtime, AgentTaskRequest, AgentTaskResult, AgentTaskRuntime, AgentTaskStatus,
 };
+use zihuan_core::utils::backoff::BackoffPolicy;
 use zihuan_core::utils::string_utils::shorten_text;
 use zihuan_core::weaviate::WeaviateRef;
 use zihuan_graph_engine::brain_tool_spec::BrainToolDefinition;
 use zihuan_graph_engine::data_value::{LLMMessageSessionCacheRef, SessionStateRef};
 use zihuan_graph_engine::function_graph::FunctionPortDef;
-use zihuan_graph_engine::message_persistence::persist_message_event;
-use zihuan_graph_engine::message_restore::register_rdb_pool;
+use zihuan_graph_engine::message_persistence::{
+    persist_message_event_with_history, register_redis_cache_codec, register_redis_message_ttl,
+    register_sender_identity_policy,
+};
+use zihuan_graph_engine::message_restore::{register_rdb_pool, warm_up_message_index};
 use zihuan_graph_engine::object_storage::S3Ref;
 use zihuan_nlp::{build_segmenter, TextSegmenter};
 
@@ -121,7 +131,9 @@ impl InferenceToolProvider for QqInferenceToolProvider {
         build_info_brain_tools(
             &self.resources.default_tools_enabled,
             self.resources.web_search_engine_ref.clone(),
+            self.resources.weather_ref.clone(),
             self.resources.rdb_pool.clone(),
+            self.resources.message_history.clone(),
             self.resources.s3_ref.clone(),
             self.resources.weaviate_image_ref.clone(),
             self.resources.elasticsearch_image_ref.clone(),
@@ -193,6 +205,17 @@ fn load_qq_resources(
         warn!("[inference][qq_agent] web search engine connection unavailable: {e}");
         None
     });
+    let weather_ref = build_weather_ref(
+        config
+            .weather_api_connection_id
+            .as_deref()
+            .filter(|value| !value.trim().is_empty()),
+        connections,
+    )
+    .unwrap_or_else(|e| {
+        warn!("[inference][qq_agent] weather api connection unavailable: {e}");
+        None
+    });
 
     let rdb_pool = match config.resolved_rdb_id() {
         Some(connection_id) => {
@@ -200,6 +223,10 @@ fn load_qq_resources(
         }
         None => None,
     };
+    let message_history = block_async(build_message_history(config, connections)).unwrap_or_else(|e| {
+        warn!("[inference][qq_agent] message history connection unavailable: {e}");
+        None
+    });
     let s3_ref = block_async(build_s3_ref(config.rustfs_connection_id.as_deref(), connections)).unwrap_or_else(|e| {
         warn!("[inference][qq_agent] rustfs connection unavailable: {e}");
         None
@@ -303,7 +330,9 @@ fn load_qq_resources(
         },
         default_tools_enabled: config.default_tools_enabled.clone(),
         web_search_engine_ref,
+        weather_ref,
         rdb_pool,
+        message_history,
         s3_ref,
         weaviate_image_ref,
         elasticsearch_image_ref,
@@ -337,6 +366,9 @@ pub async fn spawn(
     on_finish: super::OnFinishShared,
     task_runtime: Option<Arc<dyn AgentTaskRuntime>>,
 ) -> Result<JoinHandle<()>> {
+    if config.brain == QqChatBrain::Echo {
+        return echo::spawn(manager, agent, config, connections, on_finish).await;
+    }
     if config
         .weaviate_memory_connection_id
         .as_deref()
@@ -379,6 +411,15 @@ pub async fn spawn(
     let natural_language_reply_llm_config =
         resolve_llm_service_config(config.natural_language_reply_llm_ref_id.as_deref(), &llm_refs, &agent.name)?;
     let natural_language_reply_llm = build_llm_model(&natural_language_reply_llm_config)?;
+    let mut llm_profile_overrides = HashMap::new();
+    for rule in &config.llm_profile_overrides {
+        let llm_ref_id = rule.llm_ref_id.trim();
+        if llm_ref_id.is_empty() || llm_profile_overrides.contains_key(llm_ref_id) {
+            continue;
+        }
+        let override_config = resolve_llm_service_config(Some(llm_ref_id), &llm_refs, &agent.name)?;
+        llm_profile_overrides.insert(llm_ref_id.to_string(), build_llm_model(&override_config)?);
+    }
     let embedding_model = if let Some(model_ref_id) = config.embedding_model_ref_id.as_deref() {
         let model_name = resolve_local_embedding_model_name(Some(model_ref_id), &llm_refs, &agent.name)?;
         match model_name {
@@ -395,11 +436,23 @@ pub async fn spawn(
     let web_search_engine =
         build_web_search_engine_ref(Some(&config.web_search_engine_connection_id), &connections)?
             .ok_or_else(|| Error::ValidationError("missing web search engine connection".to_string()))?;
+    let weather = build_weather_ref(
+        config
+            .weather_api_connection_id
+            .as_deref()
+            .filter(|value| !value.trim().is_empty()),
+        &connections,
+    )
+    .unwrap_or_else(|e| {
+        warn!("[service][qq_agent] weather api connection unavailable: {e}");
+        None
+    });
     let object_storage = build_s3_ref(config.rustfs_connection_id.as_deref(), &connections).await?;
     let rdb_pool = match config.resolved_rdb_id() {
         Some(connection_id) => Some(build_relational_db_connection_for_connection(connection_id, &connections).await?),
         None => None,
     };
+    let message_history = build_message_history(&config, &connections).await?;
     let redis_ref = resolve_inbox_redis_ref(&connections)?;
     let weaviate_image_ref = tokio::task::block_in_place(|| {
         build_weaviate_ref(
@@ -429,8 +482,29 @@ pub async fn spawn(
     let tool_definitions = build_enabled_tool_definitions(&agent.tools)?;
     let tokenizer_segmenter = resolve_tokenizer_segmenter(&config, &connections);
 
+    register_sender_identity_policy(config.sender_identity_policy());
+    register_redis_cache_codec(config.redis_cache_codec);
+    register_redis_message_ttl(
+        (config.redis_message_ttl_secs > 0).then(|| Duration::from_secs(config.redis_message_ttl_secs)),
+    );
     if let Some(ref rdb_pool) = rdb_pool {
         register_rdb_pool(rdb_pool.clone());
+        if config.message_cache_warmup_limit > 0 {
+            let status = warm_up_message_index(
+                &config.ims_bot_adapter_connection_id,
+                rdb_pool,
+                config.message_cache_warmup_limit,
+                &BackoffPolicy::default(),
+            )
+            .await;
+            if !status.succeeded && config.message_cache_warmup_strict {
+                return Err(Error::StringError(format!(
+                    "message cache warm-up failed after {} attempts: {}",
+                    status.attempts,
+                    status.last_error.unwrap_or_default()
+                )));
+            }
+        }
     }
 
     let service = Arc::new(QqChatAgentService::new(QqChatAgentServiceRuntimeConfig {
@@ -449,12 +523,15 @@ pub async fn spawn(
         intent_classification_llm,
         math_programming_llm,
         natural_language_reply_llm,
+        llm_profile_overrides,
         rdb_pool,
+        message_history,
         weaviate_image_ref,
         weaviate_memory_ref,
         elasticsearch_memory_ref,
         embedding_model,
         web_search_engine,
+        weather,
         s3_ref: object_storage.clone(),
         max_message_length: config.max_message_length,
         compact_context_length: config.compact_context_length,
@@ -482,6 +559,7 @@ pub async fn spawn(
     );
 
     let handler_id = format!("qq_chat_service:{}", agent.id);
+    let event_handlers = adapter.lock().await.event_handlers();
     {
         let inbox = inbox.clone();
         let handler: EventHandler = Arc::new(move |event| {
@@ -493,28 +571,23 @@ pub async fn spawn(
                 Ok(())
             })
         });
-        adapter.lock().await.register_event_handler_with_id(handler_id.clone(), handler);
+        event_handlers.register_with_id(handler_id.clone(), handler);
     }
 
     let manager = manager.clone();
     let agent_id = agent.id.clone();
     let agent_name = agent.name.clone();
-    let adapter_for_cleanup = adapter.clone();
     let handler_id_for_cleanup = handler_id.clone();
     let user_on_finish = {
         let mut guard = on_finish.lock().unwrap();
         guard.take()
     };
     {
-        let adapter = adapter_for_cleanup.clone();
+        let event_handlers = event_handlers.clone();
         let handler_id = handler_id_for_cleanup.clone();
         let mut guard = on_finish.lock().unwrap();
         *guard = Some(Box::new(move |success, error_msg| {
-            let adapter = adapter.clone();
-            let handler_id = handler_id.clone();
-            tokio::spawn(async move {
-                adapter.lock().await.unregister_event_handler(&handler_id);
-            });
+            event_handlers.unregister(&handler_id);
             if let Some(cb) = user_on_finish {
                 cb(success, error_msg);
             }
@@ -527,10 +600,7 @@ pub async fn spawn(
         inbox.spawn_consumers(&mut tasks);
         std::future::pending::<()>().await;
         inbox.request_shutdown();
-        adapter_for_cleanup
-            .lock()
-            .await
-            .unregister_event_handler(&handler_id_for_cleanup);
+        event_handlers.unregister(&handler_id_for_cleanup);
 
         let success = true;
         let error_msg = None;
@@ -604,6 +674,23 @@ fn resolve_tokenizer_segmenter(
     build_segmenter(tokenizer_path.as_deref())
 }
 
+async fn build_message_history(
+    config: &QqChatAgentServiceConfig,
+    connections: &[ConnectionConfig],
+) -> Result<Option<Arc<MessageStore>>> {
+    match config
+        .message_history_connection_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(connection_id) => Ok(Some(Arc::new(
+            MessageStore::from_connection_id(connection_id, connections).await?,
+        ))),
+        None => Ok(None),
+    }
+}
+
 fn resolve_inbox_redis_ref(
     connections: &[ConnectionConfig],
 ) -> Result<Option<Arc<zihuan_graph_engine::data_value::RedisConfig>>> {
@@ -622,7 +709,8 @@ impl QqChatAgentServiceInner {
     ///
     /// The flow is:
     /// - **Validation** — persists the message and checks ignore rules.
-    /// - **Group mention filter** — silently drops group messages that do not `@` the bot.
+    /// - **Group mention filter** — silently drops group messages that do not `@` the bot, unless the
+    ///   sender addressed it within the last `sticky_secs`.
     /// - **Session claim** — tries to acquire a per-sender session lock. If the session is busy,
     ///   the message is enqueued as a steer event instead.
     /// - **Task tracking** — starts a runtime task (if available) and builds a [`QqChatTaskTrace`].
@@ -657,7 +745,8 @@ impl QqChatAgentServiceInner {
             target_id
         );
 
-        if let Err(err) = persist_message_event(event, ctx.rdb_pool, None) {
+        let history = ctx.message_history.map(|message_history| message_history.backend());
+        if let Err(err) = persist_message_event_with_history(event, ctx.rdb_pool, history, None) {
             warn!("{LOG_PREFIX} Message persistence failed: {err}");
         }
 
@@ -676,7 +765,19 @@ impl QqChatAgentServiceInner {
             let bot_id = get_bot_id(ctx.adapter);
             let msg_prop =
                 MessageProp::from_messages_with_bot_name(&event.message_list, Some(&bot_id), Some(ctx.bot_name));
-            if !msg_prop.is_at_me {
+            let addressee = if msg_prop.is_at_me {
+                Addressee::Bot
+            } else if event.mentions().is_empty() {
+                Addressee::Nobody
+            } else {
+                Addressee::Others
+            };
+            let sticky_window = Duration::from_secs(ctx.qq_chat_config.sticky_secs);
+            let group_id = event.group_id.unwrap_or_default();
+            if !ctx
+                .sticky_engagement
+                .admit(group_id, event.sender.user_id, addressee, sticky_window)
+            {
                 return Ok(());
             }
         }
@@ -1,15 +1,17 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-use storage_handler::ElasticsearchRef;
+use storage_handler::{ElasticsearchRef, MessageStore};
 use zihuan_agent::session_state::QqChatAgentServiceSessionState;
 use zihuan_core::agent_config::qq_chat::QqChatAgentServiceConfig;
 use zihuan_core::data_refs::RelationalDbConnection;
+use zihuan_core::ims_bot_adapter::sticky_engagement::StickyEngagementStore;
 use zihuan_core::llm::embedding_base::EmbeddingBase;
 use zihuan_core::llm::llm_base::LLMBase;
 use zihuan_core::rag::WebSearchEngineRef;
 use zihuan_core::steer::PendingSteerStore;
 use zihuan_core::task_context::AgentTaskRuntime;
+use zihuan_core::weather::WeatherRef;
 use zihuan_core::weaviate::WeaviateRef;
 use zihuan_graph_engine::brain_tool_spec::BrainToolDefinition;
 use zihuan_graph_engine::data_value::{LLMMessageSessionCacheRef, SessionStateRef};
@@ -31,13 +33,17 @@ pub(crate) struct QqChatAgentServiceContext<'a> {
     pub(crate) intent_classification_llm: &'a Arc<dyn LLMBase>,
     pub(crate) math_programming_llm: &'a Arc<dyn LLMBase>,
     pub(crate) natural_language_reply_llm: &'a Arc<dyn LLMBase>,
+    pub(crate) qq_chat_config: &'a QqChatAgentServiceConfig,
+    pub(crate) llm_profile_overrides: &'a HashMap<String, Arc<dyn LLMBase>>,
     pub(crate) natural_language_reply_system_prompt: Option<&'a str>,
     pub(crate) rdb_pool: Option<&'a RelationalDbConnection>,
+    pub(crate) message_history: Option<&'a Arc<MessageStore>>,
     pub(crate) weaviate_image_ref: Option<&'a Arc<WeaviateRef>>,
     pub(crate) weaviate_memory_ref: Option<&'a Arc<WeaviateRef>>,
     pub(crate) elasticsearch_memory_ref: Option<&'a Arc<ElasticsearchRef>>,
     pub(crate) embedding_model: Option<&'a Arc<dyn EmbeddingBase>>,
     pub(crate) web_search_engine: &'a Arc<WebSearchEngineRef>,
+    pub(crate) weather: Option<&'a Arc<WeatherRef>>,
     pub(crate) s3_ref: Option<&'a Arc<S3Ref>>,
     pub(crate) max_message_length: usize,
     pub(crate) compact_context_length: usize,
@@ -46,6 +52,7 @@ pub(crate) struct QqChatAgentServiceContext<'a> {
     pub(crate) shared_runtime_values: HashMap<String, DataValue>,
     pub(crate) session_state_store: &'a Arc<Mutex<QqChatAgentServiceSessionState>>,
     pub(crate) pending_steer: &'a Arc<PendingSteerStore>,
+    pub(crate) sticky_engagement: &'a Arc<StickyEngagementStore>,
     pub(crate) task_runtime: Option<Arc<dyn AgentTaskRuntime>>,
     pub(crate) task_db_connection_id: Option<String>,
     pub(crate) tool_quota: Option<QqChatToolQuotaContext>,
@@ -80,12 +87,17 @@ pub struct QqChatAgentServiceRuntimeConfig {
     pub intent_classification_llm: Arc<dyn LLMBase>,
     pub math_programming_llm: Arc<dyn LLMBase>,
     pub natural_language_reply_llm: Arc<dyn LLMBase>,
+    /// LLMs built for `qq_chat_config.llm_profile_overrides`, keyed by llm ref id.
+    pub llm_profile_overrides: HashMap<String, Arc<dyn LLMBase>>,
     pub rdb_pool: Option<RelationalDbConnection>,
+    /// The store on `qq_chat_config.message_history_connection_id`, if set.
+    pub message_history: Option<Arc<MessageStore>>,
     pub weaviate_image_ref: Option<Arc<WeaviateRef>>,
     pub weaviate_memory_ref: Option<Arc<WeaviateRef>>,
     pub elasticsearch_memory_ref: Option<Arc<ElasticsearchRef>>,
     pub embedding_model: Option<Arc<dyn EmbeddingBase>>,
     pub web_search_engine: Arc<WebSearchEngineRef>,
+    pub weather: Option<Arc<WeatherRef>>,
     pub s3_ref: Option<Arc<S3Ref>>,
     pub max_message_length: usize,
     pub compact_context_length: usize,
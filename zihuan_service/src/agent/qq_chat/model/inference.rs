@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use storage_handler::ElasticsearchRef;
+use storage_handler::{ElasticsearchRef, MessageStore};
 use zihuan_core::data_refs::RelationalDbConnection;
 use zihuan_core::llm::embedding_base::EmbeddingBase;
 use zihuan_core::llm::llm_base::LLMBase;
 use zihuan_core::rag::WebSearchEngineRef;
+use zihuan_core::weather::WeatherRef;
 use zihuan_core::weaviate::WeaviateRef;
 use zihuan_graph_engine::brain_tool_spec::BrainToolDefinition;
 use zihuan_graph_engine::object_storage::S3Ref;
@@ -16,7 +17,9 @@ pub(crate) struct QqLoadedInferenceResources {
     pub(crate) bot_name: String,
     pub(crate) default_tools_enabled: HashMap<String, bool>,
     pub(crate) web_search_engine_ref: Option<Arc<WebSearchEngineRef>>,
+    pub(crate) weather_ref: Option<Arc<WeatherRef>>,
     pub(crate) rdb_pool: Option<RelationalDbConnection>,
+    pub(crate) message_history: Option<Arc<MessageStore>>,
     pub(crate) s3_ref: Option<Arc<S3Ref>>,
     pub(crate) weaviate_image_ref: Option<Arc<WeaviateRef>>,
     pub(crate) elasticsearch_image_ref: Option<Arc<ElasticsearchRef>>,
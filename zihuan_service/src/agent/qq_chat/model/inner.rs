@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use zihuan_core::ims_bot_adapter::sticky_engagement::StickyEngagementStore;
 use zihuan_core::steer::PendingSteerStore;
 use zihuan_graph_engine::brain_tool_spec::BrainToolDefinition;
 use zihuan_graph_engine::function_graph::FunctionPortDef;
@@ -20,4 +21,5 @@ pub struct QqChatAgentService {
     pub(crate) inner: QqChatAgentServiceInner,
     pub(crate) config: QqChatAgentServiceRuntimeConfig,
     pub(crate) pending_steer: Arc<PendingSteerStore>,
+    pub(crate) sticky_engagement: Arc<StickyEngagementStore>,
 }
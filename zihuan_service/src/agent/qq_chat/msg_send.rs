@@ -498,23 +498,11 @@ fn resolve_bot_identity<'a>(ctx: &'a QqChatServiceSendContext<'_>) -> (Cow<'a, s
 }
 
 fn resolve_bot_name(adapter: &SharedBotAdapter, fallback: &str) -> String {
-    if let Ok(handle) = tokio::runtime::Handle::try_current() {
-        tokio::task::block_in_place(|| {
-            let guard = handle.block_on(adapter.lock());
-            guard
-                .get_bot_profile()
-                .map(|profile| profile.nickname.clone())
-                .filter(|name| !name.trim().is_empty())
-                .unwrap_or_else(|| fallback.to_string())
-        })
-    } else {
-        let guard = adapter.blocking_lock();
-        guard
-            .get_bot_profile()
-            .map(|profile| profile.nickname.clone())
-            .filter(|name| !name.trim().is_empty())
-            .unwrap_or_else(|| fallback.to_string())
-    }
+    adapter
+        .profile_snapshot()
+        .map(|profile| profile.nickname)
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| fallback.to_string())
 }
 
 fn ensure_space_after_at(batches: &mut [Vec<Message>]) {
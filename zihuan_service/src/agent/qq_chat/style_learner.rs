@@ -48,14 +48,9 @@ pub async fn learn_language_style(
 
     let messages = build_style_learning_messages(scope, &samples);
     let llm_clone = Arc::clone(llm);
-    let response = tokio::task::spawn_blocking(move || {
-        llm_clone.inference(&InferenceParam {
-            messages: &messages,
-            tools: None,
-        })
-    })
-    .await
-    .map_err(|e| Error::StringError(format!("style learning LLM task panicked: {e}")))?;
+    let response = tokio::task::spawn_blocking(move || llm_clone.inference(&InferenceParam::new(&messages)))
+        .await
+        .map_err(|e| Error::StringError(format!("style learning LLM task panicked: {e}")))?;
     let style_prompt = parse_style_learning_result(&response.content_text_owned().unwrap_or_default())?;
     let saved =
         upsert_language_style(connection, scope, &style_prompt, samples.len() as i32, learned_by_sender_id).await?;
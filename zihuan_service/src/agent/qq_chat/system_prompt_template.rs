@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use log::warn;
+use zihuan_core::utils::template::render_template;
+
+/// Directory, relative to the working directory, holding the optional
+/// system prompt templates. Each file is read once, on first use, so edits
+/// apply after a restart.
+const PROMPTS_DIR: &str = "prompts";
+pub(crate) const PRIVATE_SYSTEM_PROMPT_FILE: &str = "qq_chat_private_system.md";
+pub(crate) const GROUP_SYSTEM_PROMPT_FILE: &str = "qq_chat_group_system.md";
+pub(crate) const CAPABILITIES_TEMPLATE_FILE: &str = "capabilities.md";
+
+/// Values substituted into a system prompt template. Each field fills the
+/// placeholder of the same name, e.g. `${nickname}`. Templates can also use
+/// `${tool_rules}` for the generated guidance on the enabled default tools.
+///
+/// Nothing here comes from the user: who sent the message is stated in the
+/// user message, so a crafted nickname cannot speak with system authority.
+pub(crate) struct SystemPromptVars<'a> {
+    pub(crate) nickname: &'a str,
+    pub(crate) qq_id: &'a str,
+    pub(crate) group_name: Option<&'a str>,
+    pub(crate) persona: Option<&'a str>,
+}
+
+impl SystemPromptVars<'_> {
+    fn value(&self, name: &str, tool_rules: &str) -> Option<String> {
+        let value = match name {
+            "nickname" => self.nickname,
+            "qq_id" => self.qq_id,
+            "group_name" => self.group_name.unwrap_or(""),
+            "persona" => self.persona.map(str::trim).unwrap_or(""),
+            "tool_rules" => tool_rules,
+            _ => return None,
+        };
+        Some(value.to_string())
+    }
+}
+
+/// Render `prompts/<file_name>` if it exists; `None` means the caller should
+/// use its built-in prompt.
+pub(crate) fn render_system_prompt_file(
+    file_name: &'static str,
+    vars: &SystemPromptVars<'_>,
+    tool_rules: &str,
+) -> Option<String> {
+    cached_template(file_name).map(|template| render_system_prompt(&template, vars, tool_rules))
+}
+
+/// Render `prompts/<file_name>`, or `default_template` when that file does not
+/// exist, filling each `${name}` from `value`. Unknown placeholders render as
+/// empty text.
+pub(crate) fn render_prompt_template(
+    file_name: &'static str,
+    default_template: &str,
+    value: impl FnMut(&str) -> Option<String>,
+) -> String {
+    let template = cached_template(file_name);
+    render_template(template.as_deref().unwrap_or(default_template), false, value)
+        .expect("non-strict template rendering does not fail")
+}
+
+/// The template in `prompts/<file_name>`, read on the first call and reused
+/// afterwards, including when the file is missing or unreadable.
+fn cached_template(file_name: &'static str) -> Option<Arc<str>> {
+    static TEMPLATES: OnceLock<Mutex<HashMap<&'static str, Option<Arc<str>>>>> = OnceLock::new();
+    let mut templates = TEMPLATES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    templates.entry(file_name).or_insert_with(|| read_template(file_name)).clone()
+}
+
+fn read_template(file_name: &str) -> Option<Arc<str>> {
+    let path = Path::new(PROMPTS_DIR).join(file_name);
+    match std::fs::read_to_string(&path) {
+        Ok(template) => Some(template.into()),
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => {
+            warn!(
+                "[QqChatAgentService] failed to read prompt template {}: {err}; using built-in prompt",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
+/// Unknown `${...}` placeholders render as empty text.
+pub(crate) fn render_system_prompt(template: &str, vars: &SystemPromptVars<'_>, tool_rules: &str) -> String {
+    render_template(template, false, |name| vars.value(name, tool_rules))
+        .expect("non-strict template rendering does not fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_known_placeholders_and_blanks_the_rest() {
+        let vars = SystemPromptVars {
+            nickname: "紫幻",
+            qq_id: "10001",
+            group_name: None,
+            persona: Some("  温柔的猫娘\n"),
+        };
+
+        let rendered = render_system_prompt(
+            "你是${nickname}(${qq_id})，群:${group_name}，发言人:${sender}\n${persona}\n${tool_rules}\n{\"json\": {}}",
+            &vars,
+            "- rule ${qq_id}",
+        );
+
+        assert_eq!(
+            rendered,
+            "你是紫幻(10001)，群:，发言人:\n温柔的猫娘\n- rule ${qq_id}\n{\"json\": {}}"
+        );
+    }
+}
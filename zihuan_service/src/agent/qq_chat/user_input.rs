@@ -496,7 +496,7 @@ fn traverse_current_messages_for_image_references(
                     media_id: media_id.to_string(),
                 });
             }
-            Message::PlainText(_) | Message::At(_) | Message::Reply(_) | Message::Forward(_) => {}
+            Message::PlainText(_) | Message::At(_) | Message::Face(_) | Message::Reply(_) | Message::Forward(_) => {}
         }
     }
 }
@@ -527,7 +527,7 @@ fn traverse_reference_messages_for_image_references(messages: &[Message], refere
                     traverse_reference_messages_for_image_references(&node.content, references);
                 }
             }
-            Message::PlainText(_) | Message::At(_) | Message::Image(_) => {}
+            Message::PlainText(_) | Message::At(_) | Message::Face(_) | Message::Image(_) => {}
         }
     }
 }
@@ -273,7 +273,7 @@ fn split_memory_items(resources: &AgentMemoryToolResources, content: &str) -> Re
         ),
         LLMMessage::user(format!("请整理下面的内容为记忆 JSON：\n{content}")),
     ];
-    let response = resources.llm.inference(&InferenceParam { messages: &prompt, tools: None });
+    let response = resources.llm.inference(&InferenceParam::new(&prompt));
     if let Some(text) = response.content_text_owned() {
         if let Some(parsed) = parse_memory_json(&text) {
             let normalized = normalize_draft_items(parsed);
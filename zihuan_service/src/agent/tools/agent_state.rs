@@ -135,10 +135,7 @@ impl UpdateAgentStateBrainTool {
             )),
         ];
         self.llm
-            .inference(&InferenceParam {
-                messages: &messages,
-                tools: None,
-            })
+            .inference(&InferenceParam::new(&messages))
             .content_text_owned()
             .map(|value| value.trim().to_string())
             .filter(|value| !value.is_empty())
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use zihuan_agent::brain::BrainTool;
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::tooling::FunctionTool;
+use zihuan_core::utils::math_expr::{apply_binary_op, evaluate_expression};
+
+use super::common::{optional_string_argument, StaticFunctionToolSpec};
+
+/// Exact arithmetic for the model, either a whole expression (`expr`) or one
+/// named operation on two numbers (`a`, `b`, `op`).
+pub(crate) struct CalculateBrainTool;
+
+fn calculate(arguments: &Value) -> Result<f64> {
+    if let Some(expr) = optional_string_argument(arguments, "expr") {
+        return evaluate_expression(&expr);
+    }
+    let number = |key: &str| {
+        arguments
+            .get(key)
+            .and_then(Value::as_f64)
+            .ok_or_else(|| Error::ValidationError(format!("{key} must be a number when expr is not given")))
+    };
+    let op = optional_string_argument(arguments, "op")
+        .ok_or_else(|| Error::ValidationError("expr or a/b/op is required".to_string()))?;
+    apply_binary_op(&op, number("a")?, number("b")?)
+}
+
+impl BrainTool for CalculateBrainTool {
+    fn spec(&self) -> Arc<dyn FunctionTool> {
+        Arc::new(StaticFunctionToolSpec {
+            name: "calculate",
+            description: "精确计算算术表达式，支持 + - * / % ^ 与括号；涉及数值计算时使用，不要心算",
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "expr": { "type": "string", "description": "算术表达式，如 \"(3 + 4) * 2 ^ 3 % 5\"；给出时忽略 a/b/op" },
+                    "a": { "type": "number", "description": "不使用 expr 时的左操作数" },
+                    "b": { "type": "number", "description": "不使用 expr 时的右操作数" },
+                    "op": {
+                        "type": "string",
+                        "enum": ["add", "sub", "mul", "div", "pow", "mod"],
+                        "description": "不使用 expr 时对 a、b 执行的运算"
+                    }
+                },
+                "required": []
+            }),
+        })
+    }
+
+    fn execute(&self, _call_content: &str, arguments: &Value) -> String {
+        match calculate(arguments) {
+            Ok(result) => serde_json::json!({"ok": true, "result": result}).to_string(),
+            Err(e) => serde_json::json!({"ok": false, "error": e.to_string()}).to_string(),
+        }
+    }
+}
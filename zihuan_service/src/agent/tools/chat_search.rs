@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::task::block_in_place;
+
+use storage_handler::{MessageRecord, MessageStore};
+use zihuan_agent::brain::BrainTool;
+use zihuan_core::data_refs::RelationalDbConnection;
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::tooling::FunctionTool;
+
+use super::common::{
+    optional_string_argument, sanitize_positive_limit, StaticFunctionToolSpec, ToolNotificationTarget,
+};
+
+const DEFAULT_SEARCH_TOOL_LIMIT: i64 = 10;
+const MAX_SEARCH_TOOL_LIMIT: i64 = 50;
+const SEARCH_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Keyword search over persisted chat messages. In a group chat the search is
+/// pinned to the current group and the model's `group_id` is ignored, so one
+/// group cannot read another group's history.
+///
+/// Searches `message_history` when the agent has one, otherwise `rdb_pool`.
+pub(crate) struct ChatSearchBrainTool {
+    rdb_pool: Option<RelationalDbConnection>,
+    message_history: Option<Arc<MessageStore>>,
+    notification_target: ToolNotificationTarget,
+}
+
+impl ChatSearchBrainTool {
+    pub(crate) fn new(
+        rdb_pool: Option<RelationalDbConnection>,
+        message_history: Option<Arc<MessageStore>>,
+        notification_target: ToolNotificationTarget,
+    ) -> Self {
+        Self {
+            rdb_pool,
+            message_history,
+            notification_target,
+        }
+    }
+
+    fn store(&self) -> Result<Arc<MessageStore>> {
+        if let Some(message_history) = &self.message_history {
+            return Ok(Arc::clone(message_history));
+        }
+        let rdb_pool = self
+            .rdb_pool
+            .clone()
+            .ok_or_else(|| Error::ValidationError("rdb_pool is required for message search".to_string()))?;
+        Ok(Arc::new(MessageStore::new(rdb_pool)?))
+    }
+
+    fn search(&self, arguments: &Value) -> Result<Vec<MessageRecord>> {
+        let store = self.store()?;
+        let keyword = optional_string_argument(arguments, "keyword")
+            .ok_or_else(|| Error::ValidationError("keyword is required".to_string()))?;
+        let group_id = if self.notification_target.is_group() {
+            Some(self.notification_target.target_id().to_string())
+        } else {
+            optional_string_argument(arguments, "group_id")
+        };
+        let limit = sanitize_positive_limit(
+            arguments.get("limit").and_then(Value::as_i64),
+            DEFAULT_SEARCH_TOOL_LIMIT,
+            MAX_SEARCH_TOOL_LIMIT,
+        );
+
+        let search = store.search_messages(&keyword, group_id.as_deref(), limit);
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            block_in_place(|| handle.block_on(search))
+        } else {
+            tokio::runtime::Runtime::new()?.block_on(search)
+        }
+    }
+}
+
+fn record_json(record: &MessageRecord) -> Value {
+    serde_json::json!({
+        "message_id": record.message_id,
+        "sender_id": record.sender_id,
+        "sender_name": record.sender_name,
+        "send_time": record.send_time.format(SEARCH_TIME_FORMAT).to_string(),
+        "group_id": record.group_id,
+        "content": record.content,
+    })
+}
+
+impl BrainTool for ChatSearchBrainTool {
+    fn spec(&self) -> Arc<dyn FunctionTool> {
+        Arc::new(StaticFunctionToolSpec {
+            name: "search_chat_messages",
+            description: "按关键词搜索历史聊天消息，结果按时间从新到旧排列。群聊中只搜索当前群；需要查找以前聊过的某个话题时使用。",
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "keyword": { "type": "string", "description": "要搜索的关键词，按原文子串匹配" },
+                    "group_id": { "type": "string", "description": "可选：只搜索该群内的消息；群聊中忽略，始终只搜索当前群" },
+                    "limit": { "type": "integer", "description": "返回的消息数量，默认 10，最大 50" }
+                },
+                "required": ["keyword"]
+            }),
+        })
+    }
+
+    fn execute(&self, _call_content: &str, arguments: &Value) -> String {
+        match self.search(arguments) {
+            Ok(records) => serde_json::json!({
+                "ok": true,
+                "messages": records.iter().map(record_json).collect::<Vec<_>>(),
+            })
+            .to_string(),
+            Err(e) => serde_json::json!({"ok": false, "error": e.to_string()}).to_string(),
+        }
+    }
+}
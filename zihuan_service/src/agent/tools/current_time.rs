@@ -0,0 +1,128 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use serde_json::Value;
+
+use zihuan_agent::brain::BrainTool;
+use zihuan_core::llm::tooling::FunctionTool;
+
+use super::common::StaticFunctionToolSpec;
+
+const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+/// Largest offset in use anywhere (UTC+14).
+const MAX_UTC_OFFSET_HOURS: i32 = 14;
+
+pub(crate) struct GetCurrentTimeBrainTool;
+
+/// Accepts `UTC`, `GMT`, `Z`, `+08:00`, `-0530`, `UTC+8`, `GMT-05:30`.
+fn parse_utc_offset(timezone: &str) -> Option<FixedOffset> {
+    let upper = timezone.trim().to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    if rest.is_empty() || rest == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, digits) = if let Some(digits) = rest.strip_prefix('+') {
+        (1, digits)
+    } else if let Some(digits) = rest.strip_prefix('-') {
+        (-1, digits)
+    } else {
+        return None;
+    };
+    if digits.is_empty() || !digits.is_ascii() {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours.parse::<i32>().ok()?, minutes.parse::<i32>().ok()?),
+        None if digits.len() == 4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse::<i32>().ok()?),
+        None => (digits.parse::<i32>().ok()?, 0),
+    };
+    if hours > MAX_UTC_OFFSET_HOURS || !(0..60).contains(&minutes) {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn render<Tz: TimeZone>(now: DateTime<Tz>, format: &str) -> std::result::Result<Value, String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    // An invalid strftime specifier surfaces as a fmt error instead of a panic.
+    let mut time = String::new();
+    write!(time, "{}", now.format(format)).map_err(|_| format!("无效的时间格式 \"{format}\""))?;
+    Ok(serde_json::json!({
+        "time": time,
+        "utc_offset": now.format("%:z").to_string(),
+        "weekday": now.format("%A").to_string(),
+    }))
+}
+
+fn current_time(timezone: Option<&str>, format: Option<&str>) -> std::result::Result<Value, String> {
+    let format = format
+        .map(str::trim)
+        .filter(|format| !format.is_empty())
+        .unwrap_or(DEFAULT_TIME_FORMAT);
+    match timezone.map(str::trim).filter(|timezone| !timezone.is_empty()) {
+        None => render(Local::now(), format),
+        Some(timezone) => {
+            let offset = parse_utc_offset(timezone)
+                .ok_or_else(|| format!("不支持的时区 \"{timezone}\"，请使用 UTC 偏移，例如 UTC+8 或 -05:00"))?;
+            render(Utc::now().with_timezone(&offset), format)
+        }
+    }
+}
+
+impl BrainTool for GetCurrentTimeBrainTool {
+    fn spec(&self) -> Arc<dyn FunctionTool> {
+        Arc::new(StaticFunctionToolSpec {
+            name: "get_current_time",
+            description: "获取当前日期和时间。用户询问现在几点、今天几号、星期几，或需要根据当前时间推算时调用",
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "timezone": { "type": "string", "description": "UTC 偏移，例如 UTC+8、-05:00；不填时使用服务器本地时区" },
+                    "format": { "type": "string", "description": "strftime 格式，例如 %Y年%m月%d日 %H:%M；默认 %Y-%m-%d %H:%M:%S" }
+                },
+                "required": [],
+                "additionalProperties": false
+            }),
+        })
+    }
+
+    fn execute(&self, _call_content: &str, arguments: &Value) -> String {
+        let timezone = arguments.get("timezone").and_then(Value::as_str);
+        let format = arguments.get("format").and_then(Value::as_str);
+        match current_time(timezone, format) {
+            Ok(result) => result.to_string(),
+            Err(error) => serde_json::json!({ "error": error }).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offset_secs(timezone: &str) -> Option<i32> {
+        parse_utc_offset(timezone).map(|offset| offset.local_minus_utc())
+    }
+
+    #[test]
+    fn utc_offsets_parse_with_and_without_colon() {
+        assert_eq!(offset_secs("+08:00"), Some(8 * 3600));
+        assert_eq!(offset_secs("UTC+8"), Some(8 * 3600));
+        assert_eq!(offset_secs("-0530"), Some(-(5 * 3600 + 30 * 60)));
+        assert_eq!(offset_secs("gmt"), Some(0));
+    }
+
+    #[test]
+    fn invalid_utc_offsets_are_rejected() {
+        for timezone in ["Asia/Shanghai", "+", "+25:00", "-05:75", "+８"] {
+            assert_eq!(offset_secs(timezone), None, "{timezone}");
+        }
+    }
+}
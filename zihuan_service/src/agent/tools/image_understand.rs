@@ -185,10 +185,7 @@ fn analyze_persisted_media(media: &PersistedMedia, focus_text: Option<&str>, s3_
         ),
         LLMMessage::user_with_parts(vec![MessagePart::text(prompt), resolved.part]),
     ];
-    let response = llm.inference(&InferenceParam {
-        messages: &messages,
-        tools: None,
-    });
+    let response = llm.inference(&InferenceParam::new(&messages));
 
     let content = response.content_text_owned().unwrap_or_default();
     let trimmed = content.trim();
@@ -2,18 +2,22 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use storage_handler::AgentMemoryAccessContext;
-use storage_handler::ElasticsearchRef;
+use storage_handler::{ElasticsearchRef, MessageStore};
 use zihuan_agent::brain::BrainTool;
 use zihuan_core::data_refs::RelationalDbConnection;
 use zihuan_core::llm::embedding_base::EmbeddingBase;
 use zihuan_core::llm::llm_base::LLMBase;
 use zihuan_core::rag::WebSearchEngineRef;
+use zihuan_core::weather::WeatherRef;
 use zihuan_core::weaviate::WeaviateRef;
 use zihuan_graph_engine::object_storage::S3Ref;
 
 mod agent_memory;
 mod agent_state;
+mod calculator;
+mod chat_search;
 mod common;
+mod current_time;
 mod deep_research;
 mod editable_qq_agent_tool;
 mod image_save;
@@ -24,6 +28,7 @@ mod natural_language_reply;
 mod recent_messages;
 mod reply_message;
 mod research;
+mod weather;
 mod web_search;
 mod workspace_tools;
 
@@ -36,7 +41,10 @@ pub(crate) use agent_memory::{
     SearchMemoryContentBrainTool,
 };
 pub(crate) use agent_state::UpdateAgentStateBrainTool;
+pub(crate) use calculator::CalculateBrainTool;
+pub(crate) use chat_search::ChatSearchBrainTool;
 pub(crate) use common::{ToolNotificationTarget, QQ_CHAT_EMIT_TOOL_PROGRESS_NOTIFICATIONS};
+pub(crate) use current_time::GetCurrentTimeBrainTool;
 pub(crate) use deep_research::RunDeepResearchSubagentBrainTool;
 pub(crate) use editable_qq_agent_tool::EditableQqAgentTool;
 pub(crate) use image_save::SaveImageBrainTool;
@@ -49,6 +57,7 @@ pub(crate) use natural_language_reply::{
 pub(crate) use recent_messages::{GetRecentGroupMessagesBrainTool, GetRecentUserMessagesBrainTool};
 pub(crate) use reply_message::ReplyMessageBrainTool;
 pub(crate) use research::RunResearchSubagentBrainTool;
+pub(crate) use weather::WeatherBrainTool;
 pub(crate) use web_search::WebSearchBrainTool;
 pub(crate) use workspace_tools::{
     AskUserBrainTool, CreateFileBrainTool, DeleteFileBrainTool, EditFileBrainTool, ExecCmdBrainTool,
@@ -59,8 +68,12 @@ pub(crate) use workspace_tools::{
 pub(crate) const DEFAULT_TOOL_WEB_SEARCH: &str = "web_search";
 pub(crate) const DEFAULT_TOOL_GET_AGENT_PUBLIC_INFO: &str = "get_agent_public_info";
 pub(crate) const DEFAULT_TOOL_GET_FUNCTION_LIST: &str = "get_function_list";
+pub(crate) const DEFAULT_TOOL_GET_CURRENT_TIME: &str = "get_current_time";
+pub(crate) const DEFAULT_TOOL_GET_WEATHER: &str = "get_weather";
+pub(crate) const DEFAULT_TOOL_CALCULATE: &str = "calculate";
 pub(crate) const DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES: &str = "get_recent_group_messages";
 pub(crate) const DEFAULT_TOOL_GET_RECENT_USER_MESSAGES: &str = "get_recent_user_messages";
+pub(crate) const DEFAULT_TOOL_SEARCH_CHAT_MESSAGES: &str = "search_chat_messages";
 pub(crate) const DEFAULT_TOOL_SEARCH_SIMILAR_IMAGES: &str = "search_similar_images";
 pub(crate) const DEFAULT_TOOL_SAVE_IMAGE: &str = "save_image";
 pub(crate) const DEFAULT_TOOL_IMAGE_UNDERSTAND: &str = "image_understand";
@@ -74,7 +87,9 @@ const AGENT_GIT_COMMIT_ID: &str = build_metadata::ZIHUAN_GIT_COMMIT_ID;
 pub(crate) fn build_info_brain_tools(
     default_tools_enabled: &HashMap<String, bool>,
     web_search_engine_ref: Option<Arc<WebSearchEngineRef>>,
+    weather_ref: Option<Arc<WeatherRef>>,
     rdb_pool: Option<RelationalDbConnection>,
+    message_history: Option<Arc<MessageStore>>,
     s3_ref: Option<Arc<S3Ref>>,
     weaviate_image_ref: Option<Arc<WeaviateRef>>,
     elasticsearch_image_ref: Option<Arc<ElasticsearchRef>>,
@@ -106,6 +121,18 @@ pub(crate) fn build_info_brain_tools(
         tools.push(Box::new(GetFunctionListBrainTool));
     }
 
+    if is_enabled(default_tools_enabled, DEFAULT_TOOL_GET_CURRENT_TIME) {
+        tools.push(Box::new(GetCurrentTimeBrainTool));
+    }
+
+    if is_enabled(default_tools_enabled, DEFAULT_TOOL_GET_WEATHER) {
+        tools.push(Box::new(WeatherBrainTool::new(weather_ref)));
+    }
+
+    if is_enabled(default_tools_enabled, DEFAULT_TOOL_CALCULATE) {
+        tools.push(Box::new(CalculateBrainTool));
+    }
+
     if is_enabled(default_tools_enabled, DEFAULT_TOOL_GET_RECENT_GROUP_MESSAGES) {
         tools.push(Box::new(GetRecentGroupMessagesBrainTool::new(
             rdb_pool.clone(),
@@ -120,6 +147,14 @@ pub(crate) fn build_info_brain_tools(
         )));
     }
 
+    if is_enabled(default_tools_enabled, DEFAULT_TOOL_SEARCH_CHAT_MESSAGES) {
+        tools.push(Box::new(ChatSearchBrainTool::new(
+            rdb_pool.clone(),
+            message_history,
+            dashboard_target.clone(),
+        )));
+    }
+
     if is_enabled(default_tools_enabled, DEFAULT_TOOL_SEARCH_SIMILAR_IMAGES) {
         if let Some(engine) = web_search_engine_ref {
             tools.push(Box::new(SearchSimilarImagesBrainTool::new(
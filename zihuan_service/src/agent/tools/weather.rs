@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use zihuan_agent::brain::BrainTool;
+use zihuan_core::error::{Error, Result};
+use zihuan_core::llm::tooling::FunctionTool;
+use zihuan_core::weather::{WeatherLocation, WeatherRef, WeatherReport};
+
+use super::common::{optional_string_argument, StaticFunctionToolSpec};
+
+/// Current weather for a city or a coordinate pair, answered by the agent's
+/// weather API connection.
+pub(crate) struct WeatherBrainTool {
+    weather_ref: Arc<WeatherRef>,
+}
+
+impl WeatherBrainTool {
+    pub(crate) fn new(weather_ref: Arc<WeatherRef>) -> Self {
+        Self { weather_ref }
+    }
+
+    fn lookup(&self, arguments: &Value) -> Result<WeatherReport> {
+        let location = weather_location(arguments)?;
+        self.weather_ref.current_weather(&location)
+    }
+}
+
+fn weather_location(arguments: &Value) -> Result<WeatherLocation> {
+    let latitude = arguments.get("latitude").and_then(Value::as_f64);
+    let longitude = arguments.get("longitude").and_then(Value::as_f64);
+    match (latitude, longitude) {
+        (Some(latitude), Some(longitude)) => {
+            if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+                return Err(Error::ValidationError(format!(
+                    "coordinates out of range: latitude={latitude}, longitude={longitude}"
+                )));
+            }
+            Ok(WeatherLocation::Coordinates { latitude, longitude })
+        }
+        (Some(_), None) | (None, Some(_)) => Err(Error::ValidationError(
+            "latitude and longitude must be given together".to_string(),
+        )),
+        (None, None) => optional_string_argument(arguments, "city")
+            .map(WeatherLocation::City)
+            .ok_or_else(|| Error::ValidationError("city or latitude/longitude is required".to_string())),
+    }
+}
+
+impl BrainTool for WeatherBrainTool {
+    fn spec(&self) -> Arc<dyn FunctionTool> {
+        Arc::new(StaticFunctionToolSpec {
+            name: "get_weather",
+            description: "查询某个城市或坐标当前的天气，返回温度（摄氏度）、天气状况、湿度与风速",
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "city": { "type": "string", "description": "城市名，建议使用英文或拼音，如 \"Beijing\"、\"Shanghai,CN\"；未给出坐标时必填" },
+                    "latitude": { "type": "number", "description": "可选：纬度，与 longitude 同时给出时优先于 city" },
+                    "longitude": { "type": "number", "description": "可选：经度，与 latitude 同时给出时优先于 city" }
+                }
+            }),
+        })
+    }
+
+    fn execute(&self, _call_content: &str, arguments: &Value) -> String {
+        match self.lookup(arguments) {
+            Ok(report) => serde_json::json!({"ok": true, "weather": report}).to_string(),
+            Err(e) => serde_json::json!({"ok": false, "error": e.to_string()}).to_string(),
+        }
+    }
+}
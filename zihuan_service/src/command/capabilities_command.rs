@@ -0,0 +1,183 @@
+use std::sync::{Arc, Mutex};
+
+use model_inference::system_config::{load_agents, AgentConfig, AgentType};
+use zihuan_core::command::{CommandContext, CommandDefinition, CommandHandler, CommandRegistry, CommandResult};
+
+use crate::agent::qq_chat::enabled_default_tool_names;
+use crate::agent::qq_chat::system_prompt_template::{render_prompt_template, CAPABILITIES_TEMPLATE_FILE};
+
+/// Built-in layout of the reply; `prompts/capabilities.md` replaces it when
+/// present. `${agents}`, `${tools}` and `${commands}` are bullet lists, or
+/// [`EMPTY_LIST`] when there is nothing to list.
+const DEFAULT_CAPABILITIES_TEMPLATE: &str =
+    "已启用的 Agent：\n${agents}\n\n可用工具：\n${tools}\n\n支持的命令：\n${commands}";
+const EMPTY_LIST: &str = "暂无";
+
+/// CapabilitiesCommand — `/capabilities`, `/能力` handler.
+///
+/// Describes what the bot can currently do: enabled agents, the tools of the
+/// agent handling this conversation, and the registered slash-commands. Built
+/// from the live config and registry so it never drifts from what is deployed.
+pub struct CapabilitiesCommand {
+    pub registry: Arc<Mutex<Option<Arc<CommandRegistry>>>>,
+}
+
+impl CommandHandler for CapabilitiesCommand {
+    fn handle(&self, ctx: &CommandContext, _args: &[String]) -> CommandResult {
+        let agents = load_agents().unwrap_or_default();
+        let guard = self.registry.lock().unwrap();
+        let commands = guard.as_ref().map(|reg| reg.list_commands()).unwrap_or_default();
+
+        CommandResult {
+            reply: build_capabilities_text(&agents, &ctx.agent_id, &commands),
+            side_effects: vec![],
+            echo_message: None,
+            inject_to_llm: true,
+        }
+    }
+}
+
+fn agent_tool_names(agent: &AgentConfig) -> Vec<String> {
+    let mut names: Vec<String> = agent
+        .tools
+        .iter()
+        .filter(|tool| tool.enabled)
+        .map(|tool| {
+            if tool.description.trim().is_empty() {
+                tool.name.clone()
+            } else {
+                format!("{} — {}", tool.name, tool.description.trim())
+            }
+        })
+        .collect();
+    if let AgentType::QqChat(config) = &agent.agent_type {
+        let weather_available = config
+            .weather_api_connection_id
+            .as_deref()
+            .is_some_and(|id| !id.trim().is_empty());
+        names.extend(
+            enabled_default_tool_names(&config.default_tools_enabled, weather_available)
+                .into_iter()
+                .map(str::to_string),
+        );
+    }
+    names
+}
+
+fn bullet_list(items: impl IntoIterator<Item = String>) -> String {
+    let lines: Vec<String> = items.into_iter().map(|item| format!("- {item}")).collect();
+    if lines.is_empty() {
+        EMPTY_LIST.to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+pub(crate) fn build_capabilities_text(
+    agents: &[AgentConfig],
+    current_agent_id: &str,
+    commands: &[&CommandDefinition],
+) -> String {
+    render_prompt_template(
+        CAPABILITIES_TEMPLATE_FILE,
+        DEFAULT_CAPABILITIES_TEMPLATE,
+        capabilities_values(agents, current_agent_id, commands),
+    )
+}
+
+fn capabilities_values<'a>(
+    agents: &'a [AgentConfig],
+    current_agent_id: &'a str,
+    commands: &'a [&CommandDefinition],
+) -> impl FnMut(&str) -> Option<String> + 'a {
+    move |name| match name {
+        "agents" => Some(bullet_list(
+            agents.iter().filter(|agent| agent.enabled).map(|agent| agent.name.clone()),
+        )),
+        "tools" => Some(bullet_list(
+            agents
+                .iter()
+                .find(|agent| agent.id == current_agent_id)
+                .map(agent_tool_names)
+                .unwrap_or_default(),
+        )),
+        "commands" => {
+            let mut command_lines: Vec<String> = commands
+                .iter()
+                .map(|def| format!("/{} — {}", def.name, def.description))
+                .collect();
+            command_lines.sort();
+            Some(bullet_list(command_lines))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zihuan_core::utils::template::render_template;
+
+    use super::*;
+
+    fn agent(id: &str, name: &str, enabled: bool, tools: serde_json::Value) -> AgentConfig {
+        let mut agent: AgentConfig = serde_json::from_value(serde_json::json!({
+            "name": name,
+            "enabled": enabled,
+            "agent_type": {
+                "type": "qq_chat",
+                "ims_bot_adapter_connection_id": "bot",
+                "web_search_engine_connection_id": "search",
+                "default_tools_enabled": {}
+            },
+            "tools": tools
+        }))
+        .expect("deserialize agent");
+        agent.id = id.to_string();
+        agent
+    }
+
+    #[test]
+    fn announcement_reflects_registered_agents_tools_and_commands() {
+        let agents = vec![
+            agent(
+                "a1",
+                "紫幻",
+                true,
+                serde_json::json!([{
+                    "id": "t1",
+                    "name": "weather",
+                    "description": "查询天气",
+                    "enabled": true,
+                    "tool_type": { "type": "python_script", "script_path": "weather.py" }
+                }]),
+            ),
+            agent("a2", "停用的助手", false, serde_json::json!([])),
+        ];
+        let registry = crate::command::build_command_registry();
+        let commands = registry.list_commands();
+
+        let text = build_capabilities_text(&agents, "a1", &commands);
+
+        assert!(text.contains("- 紫幻"));
+        assert!(!text.contains("停用的助手"));
+        assert!(text.contains("- weather — 查询天气"));
+        assert!(text.contains("- /help — 列出可用命令"));
+        assert!(text.contains("- /capabilities"));
+        assert!(text.contains("- get_current_time"));
+        assert!(!text.contains("- get_weather"));
+    }
+
+    #[test]
+    fn custom_template_fills_each_list() {
+        let agents = vec![agent("a1", "紫幻", true, serde_json::json!([]))];
+
+        let text = render_template(
+            "Agents:\n${agents}\nCommands:\n${commands}",
+            false,
+            capabilities_values(&agents, "a1", &[]),
+        )
+        .unwrap();
+
+        assert_eq!(text, "Agents:\n- 紫幻\nCommands:\n暂无");
+    }
+}
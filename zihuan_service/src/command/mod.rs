@@ -1,4 +1,5 @@
 mod auth_command;
+mod capabilities_command;
 mod emotion_command;
 mod help_command;
 mod learn_style_command;
@@ -11,6 +12,7 @@ use zihuan_core::command::{CommandDefinition, CommandRegistry, CommandScope};
 use zihuan_core::task_context::AgentTaskRuntime;
 
 use auth_command::AuthCommand;
+use capabilities_command::CapabilitiesCommand;
 use emotion_command::EmotionCommand;
 use help_command::HelpCommand;
 use learn_style_command::LearnStyleCommand;
@@ -135,6 +137,18 @@ pub fn build_command_registry() -> Arc<CommandRegistry> {
         Arc::new(HelpCommand { registry: reg_ptr.clone() }),
     );
 
+    registry.register(
+        CommandDefinition {
+            name: "capabilities".to_string(),
+            aliases: vec!["能力".to_string()],
+            description: "介绍当前启用的 Agent、可用工具和命令".to_string(),
+            scope: CommandScope::All,
+            accepted_arg_count: 0,
+            allow_steer_bypass: false,
+        },
+        Arc::new(CapabilitiesCommand { registry: reg_ptr.clone() }),
+    );
+
     registry.register(
         CommandDefinition {
             name: "auth".to_string(),
@@ -237,6 +237,7 @@ impl Node for BrainNode {
         let shared_runtime_values = self.parse_shared_inputs_input(&inputs)?;
 
         let mut brain = Brain::new(model);
+        brain.apply_timeout_settings(zihuan_core::system_config::timeout_settings());
         for tool_def in &self.tool_definitions {
             brain.add_tool(SubgraphBrainTool {
                 runner: ToolSubgraphRunner {
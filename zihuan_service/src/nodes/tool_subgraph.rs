@@ -545,15 +545,15 @@ impl ToolSubgraphRunner {
                     let value = result_node_values.get(&port.name).ok_or_else(|| {
                         self.wrap_error(format!("Tool '{}' 输出 '{}' 未在子图中提供", tool.name, port.name))
                     })?;
-                    if !port.data_type.is_compatible_with(&value.data_type()) {
-                        return Err(self.wrap_error(format!(
+                    let value = value.coerce_to(&port.data_type).ok_or_else(|| {
+                        self.wrap_error(format!(
                             "Tool '{}' 输出 '{}' 类型不匹配：声明为 {}，实际为 {}",
                             tool.name,
                             port.name,
                             port.data_type,
                             value.data_type()
-                        )));
-                    }
+                        ))
+                    })?;
                     result_payload.insert(port.name.clone(), value.to_json());
                 }
                 let result = Value::Object(result_payload).to_string();
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use zihuan_graph_engine::load_graph_definition_from_json;
+use zihuan_graph_engine::read_graph_definition_from_json;
 
 fn workflow_path(name: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -11,7 +11,7 @@ fn workflow_path(name: &str) -> PathBuf {
 
 #[test]
 fn deep_search_workflow_uses_optional_group_id_node() {
-    let graph = load_graph_definition_from_json(workflow_path("deep_search_qq_message")).expect("workflow should load");
+    let graph = read_graph_definition_from_json(workflow_path("deep_search_qq_message")).expect("workflow should load");
     let prompt_node = graph
         .nodes
         .iter()
@@ -27,7 +27,7 @@ fn deep_search_workflow_uses_optional_group_id_node() {
 
 #[test]
 fn deep_search_workflow_prompt_mentions_private_empty_group_id() {
-    let graph = load_graph_definition_from_json(workflow_path("deep_search_qq_message")).expect("workflow should load");
+    let graph = read_graph_definition_from_json(workflow_path("deep_search_qq_message")).expect("workflow should load");
 
     let prompt_node = graph
         .nodes
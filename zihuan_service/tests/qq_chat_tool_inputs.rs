@@ -35,6 +35,7 @@ fn build_reply_image_event() -> MessageEvent {
         group_id: Some(3001),
         group_name: Some("test-group".to_string()),
         is_group_message: true,
+        time: None,
     }
 }
 
@@ -29,6 +29,7 @@ fn build_event(message_list: Vec<Message>) -> MessageEvent {
         group_id: Some(3001),
         group_name: Some("test-group".to_string()),
         is_group_message: true,
+        time: None,
     }
 }
 